@@ -1,44 +1,232 @@
+use std::io::Cursor;
+
 use anyhow::{Error, Result};
-use base64::Engine as _;
+use base64::{
+    Engine as _, alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+};
 use image::ImageReader;
+use serde::{Deserialize, Serialize};
+
+/// MIME 规定的每行字符数
+pub const MIME_LINE_WIDTH: usize = 76;
 
-pub fn encode_text(data: &str) -> Result<String> {
-    Ok(base64::engine::general_purpose::STANDARD_NO_PAD.encode(data))
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Base64Options {
+    /// 使用 URL 安全的字母表（`-` 和 `_` 代替 `+` 和 `/`）
+    pub url_safe: bool,
+    /// 在末尾补 `=` 填充到 4 的倍数
+    pub padding: bool,
+    /// 每行的字符数，0 表示不换行；MIME 为 76
+    pub line_width: usize,
 }
 
-pub fn decode_text(data: &str) -> Result<String> {
-    String::from_utf8(
-        base64::engine::general_purpose::STANDARD_NO_PAD
-            .decode(data)
-            .map_err(|e| Error::msg(e.to_string()))?,
-    )
-    .map_err(|e| Error::msg(e.to_string()))
-}
-
-pub fn encode_img(path: &str) -> Result<String> {
-    let image = ImageReader::open(path)
-        .map_err(|e| Error::msg(e.to_string()))?
-        .decode()
-        .map_err(|e| Error::msg(e.to_string()))?;
-    let hasher = image_hasher::HasherConfig::new().to_hasher();
-    let hash = hasher.hash_image(&image);
-    Ok(hash.to_base64())
-}
-
-pub fn decode_img(data: &str) -> Result<Vec<u8>> {
-    Ok(image_hasher::ImageHash::<Box<[u8]>>::from_base64(data)
-        .map_err(|e| Error::msg(format!("{e:?}")))?
+/// 图片的 MIME 类型、扩展名与尺寸，无法读取尺寸的格式（如 SVG）宽高为 `None`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageInfo {
+    pub mime: String,
+    pub extension: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// 图片的字节数
+    pub size: usize,
+}
+
+pub fn encode(data: &[u8], options: &Base64Options) -> String {
+    let alphabet = if options.url_safe {
+        &alphabet::URL_SAFE
+    } else {
+        &alphabet::STANDARD
+    };
+    let config = GeneralPurposeConfig::new().with_encode_padding(options.padding);
+    let encoded = GeneralPurpose::new(alphabet, config).encode(data);
+    if options.line_width == 0 {
+        return encoded;
+    }
+    // Base64 只包含 ASCII 字符，可以按字节切分
+    encoded
         .as_bytes()
-        .to_vec())
+        .chunks(options.line_width)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 解码时忽略空白与换行，自动识别标准和 URL 安全字母表，填充可有可无
+pub fn decode(data: &str) -> Result<Vec<u8>> {
+    let data = data
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect::<String>();
+    let alphabet = if data.contains(['-', '_']) {
+        &alphabet::URL_SAFE
+    } else {
+        &alphabet::STANDARD
+    };
+    let config =
+        GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    GeneralPurpose::new(alphabet, config)
+        .decode(data)
+        .map_err(|e| Error::msg(format!("Base64 解码失败: {e}")))
+}
+
+pub fn encode_text(data: &str, options: &Base64Options) -> Result<String> {
+    Ok(encode(data.as_bytes(), options))
+}
+
+pub fn decode_text(data: &str) -> Result<String> {
+    String::from_utf8(decode(data)?).map_err(|e| Error::msg(e.to_string()))
+}
+
+/// 把图片编码为 Base64，`data_uri` 为 true 时输出 `data:image/...;base64,` 形式，
+/// 此时总是带填充且不换行，以兼容各浏览器
+pub fn encode_img(data: &[u8], options: &Base64Options, data_uri: bool) -> Result<String> {
+    let info = sniff_image(data)?;
+    if data_uri {
+        let options = Base64Options {
+            padding: true,
+            line_width: 0,
+            ..*options
+        };
+        return Ok(format!(
+            "data:{};base64,{}",
+            info.mime,
+            encode(data, &options)
+        ));
+    }
+    Ok(encode(data, options))
+}
+
+/// 解码 Base64 或 data URI 形式的图片，返回图片信息与原始字节
+pub fn decode_img(data: &str) -> Result<(ImageInfo, Vec<u8>)> {
+    let data = data.trim();
+    let (declared, bytes) = match data.strip_prefix("data:") {
+        Some(uri) => {
+            let (meta, payload) = uri
+                .split_once(',')
+                .ok_or_else(|| Error::msg("data URI 缺少逗号分隔的数据部分"))?;
+            let mut params = meta.split(';');
+            let mime = params
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            let bytes = if params.any(|param| param.trim().eq_ignore_ascii_case("base64")) {
+                decode(payload)?
+            } else {
+                // 未声明 base64 的 data URI 使用百分号编码，常见于 SVG
+                urlencoding::decode_binary(payload.as_bytes()).into_owned()
+            };
+            (Some(mime), bytes)
+        }
+        None => (None, decode(data)?),
+    };
+
+    let info = match sniff_image(&bytes) {
+        Ok(info) => info,
+        // 识别不出时相信 data URI 声明的图片类型
+        Err(err) => match declared {
+            Some(mime) if mime.starts_with("image/") => ImageInfo {
+                extension: mime_extension(&mime),
+                mime,
+                width: None,
+                height: None,
+                size: bytes.len(),
+            },
+            _ => return Err(err),
+        },
+    };
+    Ok((info, bytes))
+}
+
+/// 按文件头识别图片格式并读取尺寸
+pub fn sniff_image(data: &[u8]) -> Result<ImageInfo> {
+    if is_svg(data) {
+        return Ok(ImageInfo {
+            mime: "image/svg+xml".to_string(),
+            extension: "svg".to_string(),
+            width: None,
+            height: None,
+            size: data.len(),
+        });
+    }
+    let format = image::guess_format(data).map_err(|_| Error::msg("无法识别的图片格式"))?;
+    // 只读取文件头中的尺寸，不解码整张图片
+    let dimensions = ImageReader::with_format(Cursor::new(data), format)
+        .into_dimensions()
+        .ok();
+    Ok(ImageInfo {
+        mime: format.to_mime_type().to_string(),
+        extension: format.extensions_str()[0].to_string(),
+        width: dimensions.map(|(width, _)| width),
+        height: dimensions.map(|(_, height)| height),
+        size: data.len(),
+    })
+}
+
+fn is_svg(data: &[u8]) -> bool {
+    let head = &data[..data.len().min(1024)];
+    let Ok(text) = std::str::from_utf8(head) else {
+        return false;
+    };
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    text.starts_with("<svg") || (text.starts_with("<?xml") && text.contains("<svg"))
+}
+
+fn mime_extension(mime: &str) -> String {
+    let subtype = mime.split('/').nth(1).unwrap_or("bin");
+    match subtype {
+        "jpeg" => "jpg",
+        "svg+xml" => "svg",
+        "x-icon" | "vnd.microsoft.icon" => "ico",
+        _ => subtype,
+    }
+    .to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// 1×1 像素的 PNG
+    const PNG: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8z8BQDwAEhQGAhKmMIQAAAABJRU5ErkJggg==";
+
     #[test]
     fn test_encode_decode_text() {
         let str = "hello rust";
-        assert_eq!(str, decode_text(&encode_text(str).unwrap()).unwrap());
+        let options = Base64Options {
+            padding: true,
+            ..Default::default()
+        };
+        assert_eq!("aGVsbG8gcnVzdA==", encode_text(str, &options).unwrap());
+        assert_eq!(str, decode_text("aGVsbG8gcnVzdA").unwrap());
+
+        let options = Base64Options {
+            url_safe: true,
+            padding: false,
+            line_width: 4,
+        };
+        let encoded = encode(&[0xfb, 0xff, 0xbf, 0xfe], &options);
+        assert_eq!("-_-_\n_g", encoded);
+        assert_eq!(vec![0xfb, 0xff, 0xbf, 0xfe], decode(&encoded).unwrap());
+    }
+
+    #[test]
+    fn test_encode_decode_img() {
+        let png = decode(PNG).unwrap();
+        let uri = encode_img(&png, &Base64Options::default(), true).unwrap();
+        assert_eq!(format!("data:image/png;base64,{PNG}"), uri);
+
+        let (info, bytes) = decode_img(&uri).unwrap();
+        assert_eq!(png, bytes);
+        assert_eq!("image/png", info.mime);
+        assert_eq!("png", info.extension);
+        assert_eq!((Some(1), Some(1)), (info.width, info.height));
+
+        let (info, _) = decode_img("data:image/svg+xml,%3Csvg%20xmlns%3D%22%22%2F%3E").unwrap();
+        assert_eq!("image/svg+xml", info.mime);
+        assert!(decode_img("aGVsbG8gcnVzdA==").is_err());
     }
 }
@@ -0,0 +1,1099 @@
+//! X.509 证书：解析 PEM/DER 证书、证书签名请求（CSR）和 PKCS#12，把证书整理成链并离线校验每一级签名，
+//! 生成本地测试用的自签名证书
+//!
+//! PEM 可以包含多个块，私钥等其他类型的块会被忽略；不是 PEM 的文本按 Base64 或 Hex 编码的 DER 处理。
+
+use std::{
+    net::IpAddr,
+    str::FromStr as _,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Error, Result};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use p12_keystore::{KeyStore, KeyStoreEntry};
+use password_hash::rand_core::{OsRng, RngCore as _};
+use rsa::{Pkcs1v15Sign, Pss, pkcs1::RsaPssParams, signature::Verifier as _};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest as _, Sha224, Sha256, Sha384, Sha512};
+use x509_cert::{
+    Certificate, TbsCertificate, Version,
+    der::{
+        Any, Decode as _, Encode as _, Header, Reader as _, SliceReader,
+        asn1::{BitString, GeneralizedTime, Ia5String, OctetString, UtcTime, Utf8StringRef},
+        oid::{
+            AssociatedOid, ObjectIdentifier,
+            db::{
+                DB,
+                rfc4519::COMMON_NAME,
+                rfc5280::{ID_KP_CLIENT_AUTH, ID_KP_SERVER_AUTH},
+                rfc5912::{
+                    ECDSA_WITH_SHA_224, ECDSA_WITH_SHA_256, ECDSA_WITH_SHA_384, ECDSA_WITH_SHA_512,
+                    ID_RSASSA_PSS, ID_SHA_1, ID_SHA_224, ID_SHA_256, ID_SHA_384, ID_SHA_512,
+                    SHA_1_WITH_RSA_ENCRYPTION, SHA_224_WITH_RSA_ENCRYPTION,
+                    SHA_256_WITH_RSA_ENCRYPTION, SHA_384_WITH_RSA_ENCRYPTION,
+                    SHA_512_WITH_RSA_ENCRYPTION,
+                },
+                rfc8410::ID_ED_25519,
+            },
+        },
+        pem::{self, LineEnding},
+    },
+    ext::{
+        Extension,
+        pkix::{
+            AuthorityInfoAccessSyntax, AuthorityKeyIdentifier, BasicConstraints,
+            CertificatePolicies, CrlDistributionPoints, ExtendedKeyUsage, IssuerAltName, KeyUsage,
+            KeyUsages, SubjectAltName, SubjectKeyIdentifier,
+            name::{DistributionPointName, GeneralName},
+        },
+    },
+    name::Name,
+    request::{CertReq, ExtensionReq},
+    serial_number::SerialNumber,
+    spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned},
+    time::{Time, Validity},
+};
+
+use crate::{
+    charset, datetime,
+    keypair::{self, Key, KeyAlgorithm, KeyFormat, PrivateKey, PublicKey},
+};
+
+/// const-oid 没有收录 ecdsa-with-SHA1
+const ECDSA_WITH_SHA_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.1");
+
+/// 证书、CSR 与 PKCS#12 的解析结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertBundle {
+    /// 按证书链排序：叶子证书在前、根证书在后，不在同一条链上的证书依次排在后面
+    pub certificates: Vec<CertInfo>,
+    pub requests: Vec<CsrInfo>,
+    /// PKCS#12 中的私钥，PKCS#8 PEM 格式
+    pub private_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertInfo {
+    pub version: u8,
+    /// 冒号分隔的十六进制
+    pub serial_number: String,
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+    pub validity: ValidityStatus,
+    /// 距离过期的整天数，已过期时为负数
+    pub days_remaining: i64,
+    pub signature_algorithm: String,
+    /// 公钥算法与长度，例如 `RSA 2048 位`
+    pub public_key: String,
+    pub subject_alt_names: Vec<String>,
+    pub key_usage: Vec<String>,
+    pub extended_key_usage: Vec<String>,
+    /// Basic Constraints 中的 CA 标记
+    pub ca: bool,
+    pub extensions: Vec<CertExtension>,
+    /// 证书的 SHA-1、SHA-256 指纹，以及公钥（SPKI）SHA-256 的 Base64，即常说的 pin
+    pub fingerprints: Vec<(String, String)>,
+    pub self_signed: bool,
+    /// 签发者在 `certificates` 中的下标，自签名或找不到签发者时为 `None`
+    pub issuer_index: Option<usize>,
+    /// 用签发者公钥校验本证书签名的结果，自签名证书用自身公钥校验
+    pub link: LinkStatus,
+    pub pem: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValidityStatus {
+    Valid,
+    Expired,
+    NotYetValid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LinkStatus {
+    Valid,
+    Invalid,
+    /// 输入中没有签发者证书
+    IssuerMissing,
+    /// 不支持的签名算法或公钥
+    Unsupported,
+}
+
+impl LinkStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Valid => "签名有效",
+            Self::Invalid => "签名无效",
+            Self::IssuerMissing => "缺少签发者证书",
+            Self::Unsupported => "不支持的签名算法",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertExtension {
+    pub oid: String,
+    pub name: String,
+    pub critical: bool,
+    /// 常见扩展解码为可读文本，其余为冒号分隔的十六进制
+    pub value: String,
+}
+
+/// 证书签名请求（PKCS#10）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsrInfo {
+    pub subject: String,
+    pub signature_algorithm: String,
+    pub public_key: String,
+    pub subject_alt_names: Vec<String>,
+    pub extensions: Vec<CertExtension>,
+    /// 用请求自带的公钥校验签名，不支持的算法为 `None`
+    pub signature_valid: Option<bool>,
+    pub fingerprints: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SelfSignedOptions {
+    /// `CN=example.com,O=Example` 形式的主题，只写域名时视为 CN
+    pub subject: String,
+    /// 可带 `DNS:`、`IP:`、`email:`、`URI:` 前缀，省略时按内容识别；为空时取 CN
+    pub subject_alt_names: Vec<String>,
+    pub days: u32,
+    pub algorithm: KeyAlgorithm,
+    pub rsa_bits: usize,
+    /// 生成 CA 证书，可用于签发其他证书
+    pub ca: bool,
+    /// 已有的私钥，为空时按 `algorithm` 生成新密钥
+    pub key: Option<String>,
+}
+
+impl Default for SelfSignedOptions {
+    fn default() -> Self {
+        Self {
+            subject: "CN=localhost".to_string(),
+            subject_alt_names: Vec::new(),
+            days: 365,
+            algorithm: KeyAlgorithm::P256,
+            rsa_bits: 2048,
+            ca: false,
+            key: None,
+        }
+    }
+}
+
+/// 自签名证书与对应的 PKCS#8 私钥，均为 PEM
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfSignedCertificate {
+    pub certificate: String,
+    pub private_key: String,
+}
+
+/// 解析证书、CSR 或 PKCS#12，`password` 只用于 PKCS#12
+pub fn decode(input: &[u8], password: &str) -> Result<CertBundle> {
+    let mut bundle = CertBundle::default();
+    let mut certificates = Vec::new();
+    match std::str::from_utf8(input).map(str::trim) {
+        Ok(text) if text.contains("-----BEGIN ") => {
+            for (label, der) in pem_blocks(text)? {
+                match label {
+                    "CERTIFICATE" | "X509 CERTIFICATE" => certificates.push(der),
+                    "CERTIFICATE REQUEST" | "NEW CERTIFICATE REQUEST" => {
+                        bundle.requests.push(csr_info(&der)?)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(text) => {
+            // 只含十六进制字符时视为 Hex，否则视为 Base64
+            let is_hex = text
+                .chars()
+                .all(|c| c.is_ascii_hexdigit() || c.is_whitespace() || c == ':');
+            let der = charset::parse_bytes_from_string(text, if is_hex { "hex" } else { "base64" })
+                .map_err(|_| Error::msg("无法识别的输入，请使用 PEM，或 Base64/Hex 编码的 DER"))?;
+            decode_der(&der, password, &mut certificates, &mut bundle)?;
+        }
+        Err(_) => decode_der(input, password, &mut certificates, &mut bundle)?,
+    }
+    if certificates.is_empty() && bundle.requests.is_empty() {
+        return Err(Error::msg("没有找到证书或证书签名请求"));
+    }
+    bundle.certificates = chain(certificates)?;
+    Ok(bundle)
+}
+
+/// 生成自签名证书，签名算法与 [`KeyAlgorithm::signature_scheme`] 一致
+pub fn self_signed(options: &SelfSignedOptions) -> Result<SelfSignedCertificate> {
+    if options.days == 0 {
+        return Err(Error::msg("有效期至少为 1 天"));
+    }
+    let key = match options.key.as_deref().map(str::trim) {
+        Some(key) if !key.is_empty() => match keypair::parse(key)?.0 {
+            Key::Private(key) => key,
+            Key::Public(_) => return Err(Error::msg("签发证书需要私钥")),
+        },
+        _ => keypair::generate_key(options.algorithm, options.rsa_bits)?,
+    };
+    let (oid, parameters) = match &key {
+        PrivateKey::Rsa(_) => (SHA_256_WITH_RSA_ENCRYPTION, Some(Any::null())),
+        PrivateKey::P256(_) => (ECDSA_WITH_SHA_256, None),
+        PrivateKey::P384(_) => (ECDSA_WITH_SHA_384, None),
+        PrivateKey::Ed25519(_) => (ID_ED_25519, None),
+        PrivateKey::X25519(_) => return Err(keypair::x25519_cannot_sign()),
+    };
+    let algorithm = AlgorithmIdentifierOwned { oid, parameters };
+
+    let subject = parse_subject(&options.subject)?;
+    let mut names = options
+        .subject_alt_names
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(parse_general_name)
+        .collect::<Result<Vec<_>>>()?;
+    // 浏览器只认 SAN，没有填写时把不含空格的 CN 当作域名或 IP
+    if names.is_empty()
+        && let Some(cn) = common_name(&subject).filter(|cn| !cn.contains(' '))
+    {
+        names.push(parse_general_name(&cn)?);
+    }
+
+    let spki = SubjectPublicKeyInfoOwned::from_der(&keypair::spki_der(&key.public_key())?)?;
+    let key_id = Sha1::digest(spki.subject_public_key.raw_bytes()).to_vec();
+    let key_usage = if options.ca {
+        KeyUsages::KeyCertSign | KeyUsages::CRLSign | KeyUsages::DigitalSignature
+    } else if matches!(key, PrivateKey::Rsa(_)) {
+        KeyUsages::DigitalSignature | KeyUsages::KeyEncipherment
+    } else {
+        KeyUsages::DigitalSignature.into()
+    };
+    let mut extensions = vec![
+        extension(
+            &BasicConstraints {
+                ca: options.ca,
+                path_len_constraint: None,
+            },
+            true,
+        )?,
+        extension(&KeyUsage(key_usage), true)?,
+        extension(
+            &SubjectKeyIdentifier(OctetString::new(key_id.clone())?),
+            false,
+        )?,
+        extension(
+            &AuthorityKeyIdentifier {
+                key_identifier: Some(OctetString::new(key_id)?),
+                ..Default::default()
+            },
+            false,
+        )?,
+    ];
+    if !options.ca {
+        extensions.push(extension(
+            &ExtendedKeyUsage(vec![ID_KP_SERVER_AUTH, ID_KP_CLIENT_AUTH]),
+            false,
+        )?);
+    }
+    if !names.is_empty() {
+        extensions.push(extension(&SubjectAltName(names), false)?);
+    }
+
+    // 最高位清零保证序列号为正数
+    let mut serial = [0u8; 16];
+    OsRng.fill_bytes(&mut serial);
+    serial[0] = serial[0] & 0x7f | 0x40;
+    let now = SystemTime::now();
+    let tbs = TbsCertificate {
+        version: Version::V3,
+        serial_number: SerialNumber::new(&serial)?,
+        signature: algorithm.clone(),
+        issuer: subject.clone(),
+        validity: Validity {
+            not_before: x509_time(now)?,
+            not_after: x509_time(now + Duration::from_secs(u64::from(options.days) * 86400))?,
+        },
+        subject,
+        subject_public_key_info: spki,
+        issuer_unique_id: None,
+        subject_unique_id: None,
+        extensions: Some(extensions),
+    };
+    let signature = keypair::sign_bytes(&key, &tbs.to_der()?)?;
+    let certificate = Certificate {
+        tbs_certificate: tbs,
+        signature_algorithm: algorithm,
+        signature: BitString::from_bytes(&signature)?,
+    };
+    Ok(SelfSignedCertificate {
+        certificate: pem_string("CERTIFICATE", &certificate.to_der()?)?,
+        private_key: keypair::encode_private(&key, KeyFormat::Pkcs8, false)?,
+    })
+}
+
+/// 依次取出所有 PEM 块的类型和内容。不要求每行 64 个字符，以兼容各种工具导出的 PEM
+fn pem_blocks(text: &str) -> Result<Vec<(&str, Vec<u8>)>> {
+    let mut blocks = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("-----BEGIN ") {
+        let Some((label, block)) = rest[start + 11..].split_once("-----") else {
+            break;
+        };
+        let end_line = format!("-----END {label}-----");
+        let end = block
+            .find(&end_line)
+            .ok_or_else(|| Error::msg(format!("PEM 缺少结束行: {end_line}")))?;
+        // 跳过 `Proc-Type:` 之类的头部
+        let body = block[..end]
+            .lines()
+            .filter(|line| !line.contains(':'))
+            .flat_map(str::split_whitespace)
+            .collect::<String>();
+        let der = STANDARD
+            .decode(body)
+            .map_err(|e| Error::msg(format!("PEM 解析失败: {e}")))?;
+        blocks.push((label, der));
+        rest = &block[end + end_line.len()..];
+    }
+    Ok(blocks)
+}
+
+fn decode_der(
+    der: &[u8],
+    password: &str,
+    certificates: &mut Vec<Vec<u8>>,
+    bundle: &mut CertBundle,
+) -> Result<()> {
+    if Certificate::from_der(der).is_ok() {
+        certificates.push(der.to_vec());
+        return Ok(());
+    }
+    if CertReq::from_der(der).is_ok() {
+        bundle.requests.push(csr_info(der)?);
+        return Ok(());
+    }
+    let keystore = KeyStore::from_pkcs12(der, password).map_err(|e| match e {
+        p12_keystore::error::Error::MacError(_) => Error::msg("PKCS#12 密码错误"),
+        e => Error::msg(format!("无法识别为证书、CSR 或 PKCS#12: {e}")),
+    })?;
+    for (_, entry) in keystore.entries() {
+        let chain = match entry {
+            KeyStoreEntry::PrivateKeyChain(chain) => {
+                bundle
+                    .private_keys
+                    .push(pem_string("PRIVATE KEY", chain.key())?);
+                chain.chain()
+            }
+            KeyStoreEntry::Certificate(certificate) => std::slice::from_ref(certificate),
+        };
+        certificates.extend(chain.iter().map(|c| c.as_der().to_vec()));
+    }
+    Ok(())
+}
+
+/// 去重后按签发关系排序：从叶子证书出发沿签发者向上，直到根证书或找不到签发者
+fn chain(ders: Vec<Vec<u8>>) -> Result<Vec<CertInfo>> {
+    let mut unique = Vec::with_capacity(ders.len());
+    for der in ders {
+        if !unique.contains(&der) {
+            unique.push(der);
+        }
+    }
+    let certificates = unique
+        .iter()
+        .map(|der| Certificate::from_der(der).map_err(|e| Error::msg(format!("证书解析失败: {e}"))))
+        .collect::<Result<Vec<_>>>()?;
+
+    // 主题与签发者相同即可；双方都有密钥标识时还要求 AKI 与 SKI 一致，以区分同名的交叉证书
+    let issuers = certificates
+        .iter()
+        .enumerate()
+        .map(|(i, cert)| {
+            let tbs = &cert.tbs_certificate;
+            if tbs.subject == tbs.issuer {
+                return None;
+            }
+            let authority_key_id = tbs
+                .get::<AuthorityKeyIdentifier>()
+                .ok()
+                .flatten()
+                .and_then(|(_, aki)| aki.key_identifier);
+            certificates.iter().enumerate().position(|(j, issuer)| {
+                j != i
+                    && issuer.tbs_certificate.subject == tbs.issuer
+                    && match (&authority_key_id, subject_key_id(issuer)) {
+                        (Some(aki), Some(ski)) => *aki == ski,
+                        _ => true,
+                    }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut order = Vec::with_capacity(certificates.len());
+    let leaves = (0..certificates.len()).filter(|i| !issuers.contains(&Some(*i)));
+    for start in leaves.chain(0..certificates.len()) {
+        let mut next = Some(start);
+        while let Some(i) = next.filter(|i| !order.contains(i)) {
+            order.push(i);
+            next = issuers[i];
+        }
+    }
+
+    order
+        .iter()
+        .map(|&i| {
+            let cert = &certificates[i];
+            let self_signed = cert.tbs_certificate.subject == cert.tbs_certificate.issuer;
+            let link = match (self_signed, issuers[i]) {
+                (true, _) => link_status(&unique[i], cert, cert),
+                (false, Some(j)) => link_status(&unique[i], cert, &certificates[j]),
+                (false, None) => LinkStatus::IssuerMissing,
+            };
+            let issuer_index = issuers[i].and_then(|j| order.iter().position(|&k| k == j));
+            cert_info(&unique[i], cert, self_signed, issuer_index, link)
+        })
+        .collect()
+}
+
+fn subject_key_id(cert: &Certificate) -> Option<OctetString> {
+    cert.tbs_certificate
+        .get::<SubjectKeyIdentifier>()
+        .ok()
+        .flatten()
+        .map(|(_, ski)| ski.0)
+}
+
+fn link_status(der: &[u8], cert: &Certificate, issuer: &Certificate) -> LinkStatus {
+    let valid = signed_part(der).ok().and_then(|message| {
+        verify_signature(
+            &issuer.tbs_certificate.subject_public_key_info,
+            &cert.signature_algorithm,
+            message,
+            cert.signature.raw_bytes(),
+        )
+    });
+    match valid {
+        Some(true) => LinkStatus::Valid,
+        Some(false) => LinkStatus::Invalid,
+        None => LinkStatus::Unsupported,
+    }
+}
+
+fn cert_info(
+    der: &[u8],
+    cert: &Certificate,
+    self_signed: bool,
+    issuer_index: Option<usize>,
+    link: LinkStatus,
+) -> Result<CertInfo> {
+    let tbs = &cert.tbs_certificate;
+    let not_before = tbs.validity.not_before.to_unix_duration().as_secs() as i64;
+    let not_after = tbs.validity.not_after.to_unix_duration().as_secs() as i64;
+    let now = datetime::now().unix_timestamp();
+    let extensions = tbs.extensions.as_deref().unwrap_or_default();
+    Ok(CertInfo {
+        version: match tbs.version {
+            Version::V1 => 1,
+            Version::V2 => 2,
+            Version::V3 => 3,
+        },
+        serial_number: keypair::colon_hex(tbs.serial_number.as_bytes()),
+        subject: tbs.subject.to_string(),
+        issuer: tbs.issuer.to_string(),
+        not_before: datetime::timestamp_to_str(not_before)?,
+        not_after: datetime::timestamp_to_str(not_after)?,
+        validity: if now < not_before {
+            ValidityStatus::NotYetValid
+        } else if now > not_after {
+            ValidityStatus::Expired
+        } else {
+            ValidityStatus::Valid
+        },
+        days_remaining: (not_after - now) / 86400,
+        signature_algorithm: oid_name(&cert.signature_algorithm.oid),
+        public_key: public_key_name(&tbs.subject_public_key_info),
+        subject_alt_names: tbs
+            .get::<SubjectAltName>()
+            .ok()
+            .flatten()
+            .map(|(_, san)| san.0.iter().map(general_name).collect())
+            .unwrap_or_default(),
+        key_usage: tbs
+            .get::<KeyUsage>()
+            .ok()
+            .flatten()
+            .map(|(_, usage)| key_usages(&usage))
+            .unwrap_or_default(),
+        extended_key_usage: tbs
+            .get::<ExtendedKeyUsage>()
+            .ok()
+            .flatten()
+            .map(|(_, usage)| usage.0.iter().map(oid_name).collect())
+            .unwrap_or_default(),
+        ca: tbs
+            .get::<BasicConstraints>()
+            .ok()
+            .flatten()
+            .is_some_and(|(_, constraints)| constraints.ca),
+        extensions: extensions.iter().map(cert_extension).collect(),
+        fingerprints: vec![
+            (
+                "SHA-256".to_string(),
+                keypair::colon_hex(&Sha256::digest(der)).to_uppercase(),
+            ),
+            (
+                "SHA-1".to_string(),
+                keypair::colon_hex(&Sha1::digest(der)).to_uppercase(),
+            ),
+            (
+                "SPKI SHA-256".to_string(),
+                STANDARD.encode(Sha256::digest(tbs.subject_public_key_info.to_der()?)),
+            ),
+        ],
+        self_signed,
+        issuer_index,
+        link,
+        pem: pem_string("CERTIFICATE", der)?,
+    })
+}
+
+fn csr_info(der: &[u8]) -> Result<CsrInfo> {
+    let csr = CertReq::from_der(der).map_err(|e| Error::msg(format!("CSR 解析失败: {e}")))?;
+    let extensions = csr
+        .info
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.oid == ExtensionReq::OID)
+        .flat_map(|attribute| attribute.values.iter())
+        .filter_map(|value| ExtensionReq::from_der(&value.to_der().ok()?).ok())
+        .flat_map(|request| request.0)
+        .collect::<Vec<_>>();
+    let subject_alt_names = extensions
+        .iter()
+        .filter(|ext| ext.extn_id == SubjectAltName::OID)
+        .filter_map(|ext| SubjectAltName::from_der(ext.extn_value.as_bytes()).ok())
+        .flat_map(|san| san.0)
+        .map(|name| general_name(&name))
+        .collect();
+    Ok(CsrInfo {
+        subject: csr.info.subject.to_string(),
+        signature_algorithm: oid_name(&csr.algorithm.oid),
+        public_key: public_key_name(&csr.info.public_key),
+        subject_alt_names,
+        extensions: extensions.iter().map(cert_extension).collect(),
+        signature_valid: verify_signature(
+            &csr.info.public_key,
+            &csr.algorithm,
+            signed_part(der)?,
+            csr.signature.raw_bytes(),
+        ),
+        fingerprints: vec![(
+            "SPKI SHA-256".to_string(),
+            STANDARD.encode(Sha256::digest(csr.info.public_key.to_der()?)),
+        )],
+    })
+}
+
+/// 签名覆盖的原始字节，即外层 SEQUENCE 的第一个元素。直接截取而不是重新编码，
+/// 避免不规范的编码在重新编码后与签名时的内容不一致
+fn signed_part(der: &[u8]) -> Result<&[u8]> {
+    let mut reader = SliceReader::new(der)?;
+    Header::decode(&mut reader)?;
+    let start = reader.position();
+    let header = Header::decode(&mut reader)?;
+    let end = (reader.position() + header.length)?;
+    der.get(usize::try_from(start)?..usize::try_from(end)?)
+        .ok_or_else(|| Error::msg("签名内容越界"))
+}
+
+#[derive(Clone, Copy)]
+enum Hash {
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl Hash {
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha1 => Sha1::digest(data).to_vec(),
+            Self::Sha224 => Sha224::digest(data).to_vec(),
+            Self::Sha256 => Sha256::digest(data).to_vec(),
+            Self::Sha384 => Sha384::digest(data).to_vec(),
+            Self::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+
+    fn pkcs1v15(self) -> Pkcs1v15Sign {
+        match self {
+            Self::Sha1 => Pkcs1v15Sign::new::<Sha1>(),
+            Self::Sha224 => Pkcs1v15Sign::new::<Sha224>(),
+            Self::Sha256 => Pkcs1v15Sign::new::<Sha256>(),
+            Self::Sha384 => Pkcs1v15Sign::new::<Sha384>(),
+            Self::Sha512 => Pkcs1v15Sign::new::<Sha512>(),
+        }
+    }
+
+    fn pss(self, salt_len: usize) -> Pss {
+        match self {
+            Self::Sha1 => Pss::new_with_salt::<Sha1>(salt_len),
+            Self::Sha224 => Pss::new_with_salt::<Sha224>(salt_len),
+            Self::Sha256 => Pss::new_with_salt::<Sha256>(salt_len),
+            Self::Sha384 => Pss::new_with_salt::<Sha384>(salt_len),
+            Self::Sha512 => Pss::new_with_salt::<Sha512>(salt_len),
+        }
+    }
+}
+
+/// 校验签名，公钥与签名算法不匹配或不支持时返回 `None`
+fn verify_signature(
+    public_key: &SubjectPublicKeyInfoOwned,
+    algorithm: &AlgorithmIdentifierOwned,
+    message: &[u8],
+    signature: &[u8],
+) -> Option<bool> {
+    use p256::ecdsa::signature::hazmat::PrehashVerifier as _;
+
+    let key = keypair::from_spki_der(&public_key.to_der().ok()?).ok()?;
+    let rsa_hash = match algorithm.oid {
+        SHA_1_WITH_RSA_ENCRYPTION => Some(Hash::Sha1),
+        SHA_224_WITH_RSA_ENCRYPTION => Some(Hash::Sha224),
+        SHA_256_WITH_RSA_ENCRYPTION => Some(Hash::Sha256),
+        SHA_384_WITH_RSA_ENCRYPTION => Some(Hash::Sha384),
+        SHA_512_WITH_RSA_ENCRYPTION => Some(Hash::Sha512),
+        _ => None,
+    };
+    let ecdsa_hash = match algorithm.oid {
+        ECDSA_WITH_SHA_1 => Some(Hash::Sha1),
+        ECDSA_WITH_SHA_224 => Some(Hash::Sha224),
+        ECDSA_WITH_SHA_256 => Some(Hash::Sha256),
+        ECDSA_WITH_SHA_384 => Some(Hash::Sha384),
+        ECDSA_WITH_SHA_512 => Some(Hash::Sha512),
+        _ => None,
+    };
+    Some(match key {
+        PublicKey::Rsa(key) if algorithm.oid == ID_RSASSA_PSS => {
+            let params = algorithm.parameters.as_ref()?.to_der().ok()?;
+            let params = RsaPssParams::from_der(&params).ok()?;
+            let hash = match params.hash.oid {
+                ID_SHA_1 => Hash::Sha1,
+                ID_SHA_224 => Hash::Sha224,
+                ID_SHA_256 => Hash::Sha256,
+                ID_SHA_384 => Hash::Sha384,
+                ID_SHA_512 => Hash::Sha512,
+                _ => return None,
+            };
+            key.verify(
+                hash.pss(params.salt_len.into()),
+                &hash.digest(message),
+                signature,
+            )
+            .is_ok()
+        }
+        PublicKey::Rsa(key) => {
+            let hash = rsa_hash?;
+            key.verify(hash.pkcs1v15(), &hash.digest(message), signature)
+                .is_ok()
+        }
+        PublicKey::P256(key) => {
+            let digest = ecdsa_hash?.digest(message);
+            p256::ecdsa::Signature::from_der(signature).is_ok_and(|signature| {
+                p256::ecdsa::VerifyingKey::from(&key)
+                    .verify_prehash(&digest, &signature)
+                    .is_ok()
+            })
+        }
+        PublicKey::P384(key) => {
+            let digest = ecdsa_hash?.digest(message);
+            p384::ecdsa::Signature::from_der(signature).is_ok_and(|signature| {
+                p384::ecdsa::VerifyingKey::from(&key)
+                    .verify_prehash(&digest, &signature)
+                    .is_ok()
+            })
+        }
+        PublicKey::Ed25519(key) if algorithm.oid == ID_ED_25519 => {
+            ed25519_dalek::Signature::from_slice(signature)
+                .is_ok_and(|signature| key.verify(message, &signature).is_ok())
+        }
+        _ => return None,
+    })
+}
+
+fn public_key_name(spki: &SubjectPublicKeyInfoOwned) -> String {
+    spki.to_der()
+        .ok()
+        .and_then(|der| keypair::from_spki_der(&der).ok())
+        .map(|key| format!("{} {} 位", key.algorithm().name(), key.bits()))
+        .unwrap_or_else(|| oid_name(&spki.algorithm.oid))
+}
+
+/// 取 const-oid 收录的名称并去掉 `id-ce-` 之类的前缀，没有收录时为点分形式
+fn oid_name(oid: &ObjectIdentifier) -> String {
+    match DB.by_oid(oid) {
+        Some(name) => ["id-ce-", "id-pe-", "id-kp-", "id-ad-", "id-"]
+            .iter()
+            .find_map(|prefix| name.strip_prefix(prefix))
+            .unwrap_or(name)
+            .to_string(),
+        None => oid.to_string(),
+    }
+}
+
+fn cert_extension(ext: &Extension) -> CertExtension {
+    CertExtension {
+        oid: ext.extn_id.to_string(),
+        name: oid_name(&ext.extn_id),
+        critical: ext.critical,
+        value: extension_value(ext)
+            .unwrap_or_else(|_| keypair::colon_hex(ext.extn_value.as_bytes())),
+    }
+}
+
+fn extension_value(ext: &Extension) -> Result<String> {
+    let der = ext.extn_value.as_bytes();
+    let value = match ext.extn_id {
+        SubjectAltName::OID => general_names(&SubjectAltName::from_der(der)?.0),
+        IssuerAltName::OID => general_names(&IssuerAltName::from_der(der)?.0),
+        KeyUsage::OID => key_usages(&KeyUsage::from_der(der)?).join(", "),
+        ExtendedKeyUsage::OID => ExtendedKeyUsage::from_der(der)?
+            .0
+            .iter()
+            .map(oid_name)
+            .collect::<Vec<_>>()
+            .join(", "),
+        BasicConstraints::OID => {
+            let constraints = BasicConstraints::from_der(der)?;
+            match (constraints.ca, constraints.path_len_constraint) {
+                (true, Some(len)) => format!("CA:TRUE, pathlen:{len}"),
+                (true, None) => "CA:TRUE".to_string(),
+                (false, _) => "CA:FALSE".to_string(),
+            }
+        }
+        SubjectKeyIdentifier::OID => {
+            keypair::colon_hex(SubjectKeyIdentifier::from_der(der)?.0.as_bytes())
+        }
+        AuthorityKeyIdentifier::OID => AuthorityKeyIdentifier::from_der(der)?
+            .key_identifier
+            .map(|id| keypair::colon_hex(id.as_bytes()))
+            .unwrap_or_default(),
+        CrlDistributionPoints::OID => CrlDistributionPoints::from_der(der)?
+            .0
+            .iter()
+            .filter_map(|point| match &point.distribution_point {
+                Some(DistributionPointName::FullName(names)) => Some(general_names(names)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        AuthorityInfoAccessSyntax::OID => AuthorityInfoAccessSyntax::from_der(der)?
+            .0
+            .iter()
+            .map(|access| {
+                format!(
+                    "{} - {}",
+                    oid_name(&access.access_method),
+                    general_name(&access.access_location)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        CertificatePolicies::OID => CertificatePolicies::from_der(der)?
+            .0
+            .iter()
+            .map(|policy| oid_name(&policy.policy_identifier))
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => keypair::colon_hex(der),
+    };
+    Ok(value)
+}
+
+fn key_usages(usage: &KeyUsage) -> Vec<String> {
+    usage
+        .0
+        .into_iter()
+        .map(|flag| {
+            match flag {
+                KeyUsages::DigitalSignature => "digitalSignature",
+                KeyUsages::NonRepudiation => "nonRepudiation",
+                KeyUsages::KeyEncipherment => "keyEncipherment",
+                KeyUsages::DataEncipherment => "dataEncipherment",
+                KeyUsages::KeyAgreement => "keyAgreement",
+                KeyUsages::KeyCertSign => "keyCertSign",
+                KeyUsages::CRLSign => "cRLSign",
+                KeyUsages::EncipherOnly => "encipherOnly",
+                KeyUsages::DecipherOnly => "decipherOnly",
+            }
+            .to_string()
+        })
+        .collect()
+}
+
+fn general_names(names: &[GeneralName]) -> String {
+    names
+        .iter()
+        .map(general_name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 与 OpenSSL 的写法一致，例如 `DNS:example.com`、`IP:127.0.0.1`
+fn general_name(name: &GeneralName) -> String {
+    match name {
+        GeneralName::DnsName(name) => format!("DNS:{name}"),
+        GeneralName::Rfc822Name(name) => format!("email:{name}"),
+        GeneralName::UniformResourceIdentifier(uri) => format!("URI:{uri}"),
+        GeneralName::IpAddress(ip) => {
+            let bytes = ip.as_bytes();
+            let ip = <[u8; 4]>::try_from(bytes)
+                .map(IpAddr::from)
+                .or_else(|_| <[u8; 16]>::try_from(bytes).map(IpAddr::from));
+            match ip {
+                Ok(ip) => format!("IP:{ip}"),
+                Err(_) => format!("IP:{}", keypair::colon_hex(bytes)),
+            }
+        }
+        GeneralName::DirectoryName(name) => format!("DirName:{name}"),
+        GeneralName::RegisteredId(oid) => format!("RID:{oid}"),
+        GeneralName::OtherName(other) => format!("othername:{}", other.type_id),
+        GeneralName::EdiPartyName(_) => "EdiPartyName".to_string(),
+    }
+}
+
+fn parse_general_name(name: &str) -> Result<GeneralName> {
+    let (kind, value) = match name.split_once(':') {
+        Some((kind, value))
+            if ["dns", "ip", "email", "uri"].contains(&kind.to_ascii_lowercase().as_str()) =>
+        {
+            (kind.to_ascii_lowercase(), value.trim())
+        }
+        _ => {
+            let kind = if name.parse::<IpAddr>().is_ok() {
+                "ip"
+            } else if name.contains("://") {
+                "uri"
+            } else if name.contains('@') {
+                "email"
+            } else {
+                "dns"
+            };
+            (kind.to_string(), name)
+        }
+    };
+    Ok(match kind.as_str() {
+        "ip" => {
+            let ip = value
+                .parse::<IpAddr>()
+                .map_err(|_| Error::msg(format!("IP 地址无效: {value}")))?;
+            GeneralName::IpAddress(OctetString::new(match ip {
+                IpAddr::V4(ip) => ip.octets().to_vec(),
+                IpAddr::V6(ip) => ip.octets().to_vec(),
+            })?)
+        }
+        "email" => GeneralName::Rfc822Name(Ia5String::new(value)?),
+        "uri" => GeneralName::UniformResourceIdentifier(Ia5String::new(value)?),
+        _ => GeneralName::DnsName(Ia5String::new(value)?),
+    })
+}
+
+fn parse_subject(subject: &str) -> Result<Name> {
+    let subject = subject.trim();
+    if subject.is_empty() {
+        return Err(Error::msg("主题不能为空"));
+    }
+    let subject = if subject.contains('=') {
+        subject.to_string()
+    } else {
+        format!("CN={subject}")
+    };
+    Name::from_str(&subject).map_err(|e| Error::msg(format!("主题格式错误: {e}")))
+}
+
+fn common_name(name: &Name) -> Option<String> {
+    name.0
+        .iter()
+        .flat_map(|rdn| rdn.0.iter())
+        .find(|atv| atv.oid == COMMON_NAME)
+        .and_then(|atv| Utf8StringRef::try_from(&atv.value).ok())
+        .map(|cn| cn.to_string())
+}
+
+fn extension<T: AssociatedOid + x509_cert::der::Encode>(
+    value: &T,
+    critical: bool,
+) -> Result<Extension> {
+    Ok(Extension {
+        extn_id: T::OID,
+        critical,
+        extn_value: OctetString::new(value.to_der()?)?,
+    })
+}
+
+/// RFC 5280 要求 2050 年以前的时间使用 UTCTime
+fn x509_time(time: SystemTime) -> Result<Time> {
+    Ok(match UtcTime::from_system_time(time) {
+        Ok(time) => Time::UtcTime(time),
+        Err(_) => Time::GeneralTime(GeneralizedTime::from_system_time(time)?),
+    })
+}
+
+fn pem_string(label: &str, der: &[u8]) -> Result<String> {
+    pem::encode_string(label, LineEnding::LF, der).map_err(|e| Error::msg(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // openssl 生成的证书链：RSA 根 CA 用 RSASSA-PSS 签发 P-384 中间 CA，
+    // 中间 CA 用 ecdsa-with-SHA384 签发 P-256 叶子证书
+    const ROOT: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIICFjCCAX+gAwIBAgIUVEycISshpIIJP4bzmYUveoEPm48wDQYJKoZIhvcNAQEL\n\
+BQAwFDESMBAGA1UEAwwJVGVzdCBSb290MCAXDTI2MTAxODEzNDMzOVoYDzIxMjYw\n\
+OTI0MTM0MzM5WjAUMRIwEAYDVQQDDAlUZXN0IFJvb3QwgZ8wDQYJKoZIhvcNAQEB\n\
+BQADgY0AMIGJAoGBAKP7B0Z/uDrtlVIQ83VdaJGcz3a60+cmD0ynKQ5U6yk6xRk3\n\
+8ugo0jzhj+m5ivy0DHf6RbYOQCBT+J1DlbRdva/l/39HX8bp+KH7LgMeIJ47owwc\n\
+20HhSuGtKNrPBQlPOZf4aWGai8UsM2f7OAp6ABcdfy27RHwI/h8vlrLixVXhAgMB\n\
+AAGjYzBhMB0GA1UdDgQWBBTGpZyAj62g/FJFs7m94AEAAzhP/DAfBgNVHSMEGDAW\n\
+gBTGpZyAj62g/FJFs7m94AEAAzhP/DAPBgNVHRMBAf8EBTADAQH/MA4GA1UdDwEB\n\
+/wQEAwIBBjANBgkqhkiG9w0BAQsFAAOBgQABSRlTKbSImzkht8GQ+kn6A/WC1gY+\n\
+bY+cdCPnrrb4LUhvknBGs3v1K75tosEgvsQiHPMSNRCydz3L3F+hzHNPL5PmMVcL\n\
+WeGnM5V24zRuRmk7j98q8OJouFCZAHT3GQ212tq8c/BYnTxZiefeTrss0iCqYIy4\n\
+/TLUiB6daYJGyQ==\n\
+-----END CERTIFICATE-----\n";
+
+    const INTERMEDIATE: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIICXzCCAZSgAwIBAgIUFCB26VJcXjxXc2t/9kssqb6UClswQQYJKoZIhvcNAQEK\n\
+MDSgDzANBglghkgBZQMEAgEFAKEcMBoGCSqGSIb3DQEBCDANBglghkgBZQMEAgEF\n\
+AKIDAgEgMBQxEjAQBgNVBAMMCVRlc3QgUm9vdDAgFw0yNjEwMTgxMzQzMzlaGA8y\n\
+MTI2MDkyNDEzNDMzOVowHDEaMBgGA1UEAwwRVGVzdCBJbnRlcm1lZGlhdGUwdjAQ\n\
+BgcqhkjOPQIBBgUrgQQAIgNiAAT2ghMHrBFs2OsDg4F/v2xYo2xJGETMTouqPIHJ\n\
+7bfly2ytzzb9RA9RQNv9uUaDF5y+c8CJ9TucSS7dwVoeUxGKcES285prmDP/P0q4\n\
+vgXTPdcyUTPiW7f94E/9ztghPmejZjBkMBIGA1UdEwEB/wQIMAYBAf8CAQAwDgYD\n\
+VR0PAQH/BAQDAgIEMB0GA1UdDgQWBBRZp2ONiIPKVZMo65JTdBSt7/I0GDAfBgNV\n\
+HSMEGDAWgBTGpZyAj62g/FJFs7m94AEAAzhP/DBBBgkqhkiG9w0BAQowNKAPMA0G\n\
+CWCGSAFlAwQCAQUAoRwwGgYJKoZIhvcNAQEIMA0GCWCGSAFlAwQCAQUAogMCASAD\n\
+gYEADotb0bpxSw0oqvcgMrNcaCfnE+B/dMNMZqw8OS0rAEJ27Q30BmNN7bxgb8if\n\
+WjHB3MTF+RUgbmXycClHv7tlBbsEXcZfleqMeMkxbGpWkfbGPWOy5qtVIb8vrq3a\n\
+dQnb4+pBmzGyipDRTQeIXV346kENh0++soBt/hio9KBSrKA=\n\
+-----END CERTIFICATE-----\n";
+
+    const LEAF: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIIByDCCAU2gAwIBAgIUQhA0IEbhiLYnCA5c+W0jZK3TtbgwCgYIKoZIzj0EAwMw\n\
+HDEaMBgGA1UEAwwRVGVzdCBJbnRlcm1lZGlhdGUwIBcNMjYxMDE4MTM0MzM5WhgP\n\
+MjEyNjA5MjQxMzQzMzlaMBQxEjAQBgNVBAMMCWxlYWYudGVzdDBZMBMGByqGSM49\n\
+AgEGCCqGSM49AwEHA0IABDCKbrHIWaTyWJdoNwbVweJBBlUfjU6DT2IuFdIENNu/\n\
+/7obBmcy89p3kkZhNVcr3jCcEkFYT83K2Xf4ezyNgsijczBxMBoGA1UdEQQTMBGC\n\
+CWxlYWYudGVzdIcECgAAATATBgNVHSUEDDAKBggrBgEFBQcDATAfBgNVHSMEGDAW\n\
+gBRZp2ONiIPKVZMo65JTdBSt7/I0GDAdBgNVHQ4EFgQUKEvJamKdZkU5t6u77YYW\n\
+cqbh4OAwCgYIKoZIzj0EAwMDaQAwZgIxAJyA+6iQd65c1tT/1q+CvW51q0EOFC6K\n\
+OZKzT8y9vbb3rih5iCqWCozWd5lzThJH1AIxANlKi3HchHK0+XYJjLiFQaR+Z1D4\n\
+UGtoLfHmvygH4SaiXzBH9Txr9vCro/cvzw78Kw==\n\
+-----END CERTIFICATE-----\n";
+
+    const LEAF_CSR: &str = "\
+-----BEGIN CERTIFICATE REQUEST-----\n\
+MIH2MIGdAgEAMBQxEjAQBgNVBAMMCWxlYWYudGVzdDBZMBMGByqGSM49AgEGCCqG\n\
+SM49AwEHA0IABDCKbrHIWaTyWJdoNwbVweJBBlUfjU6DT2IuFdIENNu//7obBmcy\n\
+89p3kkZhNVcr3jCcEkFYT83K2Xf4ezyNgsigJzAlBgkqhkiG9w0BCQ4xGDAWMBQG\n\
+A1UdEQQNMAuCCWxlYWYudGVzdDAKBggqhkjOPQQDAgNIADBFAiEAthxVtBYOGgSW\n\
+1O4YVcplhRM08YwStVfvY0h4Cn2OBdsCIAIVRNb5XkdNPTPLWiu2QtflZZZy9fqu\n\
+xEtnvpGuQJYo\n\
+-----END CERTIFICATE REQUEST-----\n";
+
+    #[test]
+    fn test_self_signed_chain() {
+        let ca = self_signed(&SelfSignedOptions {
+            subject: "CN=Toolbox Test CA,O=Toolbox".to_string(),
+            ca: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let leaf = self_signed(&SelfSignedOptions {
+            subject: "localhost".to_string(),
+            subject_alt_names: vec!["127.0.0.1".to_string(), "DNS:example.test".to_string()],
+            algorithm: KeyAlgorithm::Ed25519,
+            days: 30,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let bundle = decode(
+            format!("{}{}", ca.certificate, leaf.certificate).as_bytes(),
+            "",
+        )
+        .unwrap();
+        assert_eq!(bundle.certificates.len(), 2);
+        let ca = bundle.certificates.iter().find(|cert| cert.ca).unwrap();
+        assert_eq!(ca.subject, "CN=Toolbox Test CA,O=Toolbox");
+        assert!(ca.self_signed);
+        assert_eq!(ca.link, LinkStatus::Valid);
+        assert_eq!(ca.key_usage, ["digitalSignature", "keyCertSign", "cRLSign"]);
+
+        let leaf = bundle.certificates.iter().find(|cert| !cert.ca).unwrap();
+        assert_eq!(leaf.subject, "CN=localhost");
+        assert_eq!(leaf.subject_alt_names, ["IP:127.0.0.1", "DNS:example.test"]);
+        assert_eq!(leaf.extended_key_usage, ["serverAuth", "clientAuth"]);
+        assert_eq!(leaf.public_key, "Ed25519 256 位");
+        assert_eq!(leaf.validity, ValidityStatus::Valid);
+        assert!((29..=30).contains(&leaf.days_remaining));
+    }
+
+    #[test]
+    fn test_chain_order() {
+        // 乱序粘贴，并夹带一个 CSR
+        let bundle = decode(
+            format!("{ROOT}{LEAF}{LEAF_CSR}{INTERMEDIATE}{LEAF}").as_bytes(),
+            "",
+        )
+        .unwrap();
+        let subjects = bundle
+            .certificates
+            .iter()
+            .map(|cert| cert.subject.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            subjects,
+            ["CN=leaf.test", "CN=Test Intermediate", "CN=Test Root"]
+        );
+        assert_eq!(
+            bundle
+                .certificates
+                .iter()
+                .map(|cert| (cert.issuer_index, cert.link))
+                .collect::<Vec<_>>(),
+            [
+                (Some(1), LinkStatus::Valid),
+                (Some(2), LinkStatus::Valid),
+                (None, LinkStatus::Valid),
+            ]
+        );
+
+        assert_eq!(
+            bundle.certificates[0].subject_alt_names,
+            ["DNS:leaf.test", "IP:10.0.0.1"]
+        );
+        assert_eq!(bundle.requests.len(), 1);
+        assert_eq!(bundle.requests[0].subject, "CN=leaf.test");
+        assert_eq!(bundle.requests[0].public_key, "P-256 256 位");
+        assert_eq!(bundle.requests[0].signature_valid, Some(true));
+
+        let bundle = decode(LEAF.as_bytes(), "").unwrap();
+        assert_eq!(bundle.certificates[0].link, LinkStatus::IssuerMissing);
+    }
+}
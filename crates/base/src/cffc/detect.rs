@@ -0,0 +1,162 @@
+//! 源格式自动识别
+//!
+//! 依次尝试 JSON、JSONC、JSON5、XML、TOML、YAML，取第一个解析成功的格式。YAML 几乎能把
+//! 任意文本解析为字符串，所以放在最后，并按解析结果的结构给出可信度。
+//! HJSON、INI、.env、properties、CSV 与上述格式难以区分，不参与识别。
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{Ft, dialect, xml};
+
+/// 识别结果的可信度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Confidence {
+    /// 没有任何格式能解析
+    Unknown,
+    /// 只解析出了单个标量，例如纯文本被当作 YAML 字符串
+    Low,
+    /// 解析出了结构，但该格式容易误判，例如 YAML
+    Medium,
+    /// 语法严格的格式解析出了对象或数组
+    High,
+}
+
+impl std::fmt::Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Confidence::Unknown => write!(f, "未知"),
+            Confidence::Low => write!(f, "低"),
+            Confidence::Medium => write!(f, "中"),
+            Confidence::High => write!(f, "高"),
+        }
+    }
+}
+
+/// 被排除的候选格式
+#[derive(Debug, Clone, Serialize)]
+pub struct Rejected {
+    pub format: Ft,
+    /// 按该格式解析时的错误
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Detection {
+    /// 识别出的格式，所有候选都解析失败时为 `None`
+    pub format: Option<Ft>,
+    pub confidence: Confidence,
+    /// 在识别出的格式之前尝试过、但解析失败的格式
+    pub rejected: Vec<Rejected>,
+}
+
+impl Detection {
+    /// 未识别出格式时，列出每个候选的解析错误
+    pub fn error_message(&self) -> String {
+        let mut message = String::from("无法识别输入格式");
+        for r in &self.rejected {
+            message.push_str(&format!("\n{}: {}", r.format, r.error));
+        }
+        message
+    }
+}
+
+/// 候选格式，按尝试顺序排列
+const CANDIDATES: [Ft; 6] = [Ft::Json, Ft::Jsonc, Ft::Json5, Ft::Xml, Ft::Toml, Ft::Yaml];
+
+pub fn detect(text: &str) -> Detection {
+    let mut rejected = Vec::new();
+    for format in CANDIDATES {
+        match try_parse(format, text) {
+            Ok(confidence) => {
+                return Detection {
+                    format: Some(format),
+                    confidence,
+                    rejected,
+                };
+            }
+            Err(error) => rejected.push(Rejected { format, error }),
+        }
+    }
+    Detection {
+        format: None,
+        confidence: Confidence::Unknown,
+        rejected,
+    }
+}
+
+fn try_parse(format: Ft, text: &str) -> Result<Confidence, String> {
+    match format {
+        Ft::Json => serde_json::from_str::<Value>(text)
+            .map(|v| structured(&v, Confidence::High))
+            .map_err(|e| e.to_string()),
+        Ft::Jsonc => dialect::from_jsonc(text)
+            .map(|v| structured(&v, Confidence::High))
+            .map_err(|e| e.to_string()),
+        Ft::Json5 => dialect::from_json5(text)
+            .map(|v| structured(&v, Confidence::High))
+            .map_err(|e| e.to_string()),
+        Ft::Xml => {
+            if !text.trim_start().starts_with('<') {
+                return Err("不是以 < 开头".into());
+            }
+            xml::from_xml(text, &Default::default())
+                .map(|_| Confidence::High)
+                .map_err(|e| e.to_string())
+        }
+        Ft::Toml => match toml::from_str::<Value>(text) {
+            Ok(v) if v.as_object().is_some_and(|m| !m.is_empty()) => Ok(Confidence::High),
+            Ok(_) => Err("内容为空".into()),
+            Err(e) => Err(e.message().to_string()),
+        },
+        Ft::Yaml => serde_yaml::from_str::<Value>(text)
+            .map(|v| structured(&v, Confidence::Medium))
+            .map_err(|e| e.to_string()),
+        _ => Err("不参与自动识别".into()),
+    }
+}
+
+/// 解析出对象或数组时取 `confidence`，否则为低可信度
+fn structured(value: &Value, confidence: Confidence) -> Confidence {
+    match value {
+        Value::Object(_) | Value::Array(_) => confidence,
+        _ => Confidence::Low,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let cases = [
+            (r#"{"a": [1, 2]}"#, Ft::Json, Confidence::High, 0),
+            ("{\n  // c\n  \"a\": 1,\n}", Ft::Jsonc, Confidence::High, 1),
+            ("{a: 'x'}", Ft::Json5, Confidence::High, 2),
+            ("<a><b>1</b></a>", Ft::Xml, Confidence::High, 3),
+            (
+                "[package]\nname = \"cffc\"\n",
+                Ft::Toml,
+                Confidence::High,
+                4,
+            ),
+            ("a:\n  - 1\n  - 2\n", Ft::Yaml, Confidence::Medium, 5),
+            ("just some text", Ft::Yaml, Confidence::Low, 5),
+            ("42", Ft::Json, Confidence::Low, 0),
+        ];
+        for (text, format, confidence, rejected) in cases {
+            let d = detect(text);
+            assert_eq!(Some(format), d.format, "{text}");
+            assert_eq!(confidence, d.confidence, "{text}");
+            assert_eq!(rejected, d.rejected.len(), "{text}");
+        }
+
+        let d = detect("a: [1\n");
+        assert_eq!(None, d.format);
+        assert_eq!(Confidence::Unknown, d.confidence);
+        assert_eq!(6, d.rejected.len());
+        assert!(d.error_message().starts_with("无法识别输入格式\nJson: "));
+    }
+}
@@ -0,0 +1,197 @@
+//! JSON 方言：JSONC、JSON5
+//!
+//! JSONC 即带注释的 JSON（允许尾逗号），去掉注释后交给 serde_json 解析，写出时与 JSON 相同。
+//! JSON5 由 json5 解析；写出时键名是合法标识符的不加引号，字符串仍用双引号。
+
+use anyhow::{Error, Result};
+use serde_json::Value;
+
+use super::FormatOptions;
+
+pub fn from_jsonc(text: &str) -> Result<Value> {
+    serde_json::from_str(&strip_jsonc(text)).map_err(|e| Error::msg(e.to_string()))
+}
+
+/// 把注释和尾逗号替换为空白，保留换行，使错误信息中的行列号不变
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    // 最近一个逗号在 out 中的位置，遇到其他非空白字符时清除
+    let mut comma = None;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                comma = None;
+                out.push(c);
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    out.push(c);
+                    match c {
+                        '\\' if !escaped => escaped = true,
+                        '"' if !escaped => break,
+                        _ => escaped = false,
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                out.push_str("  ");
+                chars.next();
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    out.push(if c == '\t' { '\t' } else { ' ' });
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                out.push_str("  ");
+                chars.next();
+                let mut star = false;
+                for c in chars.by_ref() {
+                    out.push(if c == '\n' { '\n' } else { ' ' });
+                    if star && c == '/' {
+                        break;
+                    }
+                    star = c == '*';
+                }
+            }
+            ',' => {
+                comma = Some(out.len());
+                out.push(c);
+            }
+            '}' | ']' => {
+                if let Some(i) = comma.take() {
+                    out.replace_range(i..i + 1, " ");
+                }
+                out.push(c);
+            }
+            c if c.is_whitespace() => out.push(c),
+            c => {
+                comma = None;
+                out.push(c);
+            }
+        }
+    }
+    out
+}
+
+pub fn from_json5(text: &str) -> Result<Value> {
+    json5::from_str(text).map_err(|e| Error::msg(e.to_string()))
+}
+
+pub fn to_json5(value: &Value, opts: &FormatOptions) -> Result<String> {
+    let unit = opts.unit();
+    let mut out = String::new();
+    write_json5(&mut out, value, &unit, "")?;
+    Ok(out)
+}
+
+fn write_json5(out: &mut String, value: &Value, unit: &str, pad: &str) -> Result<()> {
+    let pretty = !unit.is_empty();
+    let child = format!("{pad}{unit}");
+    let (open, close, len) = match value {
+        Value::Object(map) => ('{', '}', map.len()),
+        Value::Array(items) => ('[', ']', items.len()),
+        _ => {
+            out.push_str(&serde_json::to_string(value)?);
+            return Ok(());
+        }
+    };
+    if len == 0 {
+        out.push(open);
+        out.push(close);
+        return Ok(());
+    }
+
+    out.push(open);
+    let mut separator = |out: &mut String, i: usize| {
+        if i > 0 {
+            out.push(',');
+        }
+        if pretty {
+            out.push('\n');
+            out.push_str(&child);
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (i, (k, v)) in map.iter().enumerate() {
+                separator(out, i);
+                if is_identifier(k) {
+                    out.push_str(k);
+                } else {
+                    out.push_str(&serde_json::to_string(k)?);
+                }
+                out.push_str(if pretty { ": " } else { ":" });
+                write_json5(out, v, unit, &child)?;
+            }
+        }
+        Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                separator(out, i);
+                write_json5(out, v, unit, &child)?;
+            }
+        }
+        _ => unreachable!(),
+    }
+    if pretty {
+        out.push('\n');
+        out.push_str(pad);
+    }
+    out.push(close);
+    Ok(())
+}
+
+/// 可以不加引号的键名（仅考虑 ASCII 标识符）
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_jsonc() -> Result<()> {
+        let text = r#"{
+    // 注释里的 "引号" 和逗号,
+    "url": "http://example.com", /* 块注释 */
+    "list": [1, 2,],
+}"#;
+        assert_eq!(
+            json!({ "url": "http://example.com", "list": [1, 2] }),
+            from_jsonc(text)?
+        );
+        let err = from_jsonc("{\n// c\n\"a\": }").unwrap_err();
+        assert!(err.to_string().contains("line 3"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_json5() -> Result<()> {
+        let value = from_json5("{a: 1, 'b-c': [0x10, .5,], /* c */ d: 'x'}")?;
+        assert_eq!(json!({ "a": 1, "b-c": [16, 0.5], "d": "x" }), value);
+
+        let opts = FormatOptions::default();
+        let text = to_json5(&value, &opts)?;
+        assert_eq!(
+            "{\n  a: 1,\n  \"b-c\": [\n    16,\n    0.5\n  ],\n  d: \"x\"\n}",
+            text
+        );
+        assert_eq!(value, from_json5(&text)?);
+
+        let opts = FormatOptions {
+            indent: 0,
+            ..Default::default()
+        };
+        assert_eq!(r#"{a:1,"b-c":[16,0.5],d:"x"}"#, to_json5(&value, &opts)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,418 @@
+//! 扁平格式：.env、properties、INI
+//!
+//! 嵌套结构按点号路径展开，如 `server.port`；数组下标也作为路径的一段，如 `tags.0`。
+//! 读回时再按点号还原，键全部是从 0 开始的连续下标的对象还原为数组。
+//! 扁平格式中的值一律按字符串处理。
+
+use anyhow::{Error, Result};
+use ini::Ini;
+use serde_json::{Map, Value};
+
+/// 把对象展开为 `(点号路径, 字符串值)`，空对象、空数组和 null 写成空字符串
+pub fn flatten(value: &Value) -> Result<Vec<(String, String)>> {
+    let Value::Object(map) = value else {
+        return Err(Error::msg("扁平格式的顶层必须是对象"));
+    };
+    let mut out = Vec::new();
+    for (k, v) in map {
+        flatten_into(k.clone(), v, &mut out);
+    }
+    Ok(out)
+}
+
+fn flatten_into(prefix: String, value: &Value, out: &mut Vec<(String, String)>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        }
+    };
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (k, v) in map {
+                flatten_into(join(k), v, out);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (i, v) in items.iter().enumerate() {
+                flatten_into(join(&i.to_string()), v, out);
+            }
+        }
+        _ => out.push((prefix, scalar_string(value))),
+    }
+}
+
+fn scalar_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null | Value::Object(_) | Value::Array(_) => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// 按点号路径还原嵌套结构
+pub fn unflatten<I>(pairs: I) -> Result<Value>
+where
+    I: IntoIterator<Item = (String, Value)>,
+{
+    let mut root = Map::new();
+    for (key, value) in pairs {
+        let mut map = &mut root;
+        let mut segments = key.split('.').peekable();
+        while let Some(segment) = segments.next() {
+            if segments.peek().is_none() {
+                if map.get(segment).is_some_and(Value::is_object) {
+                    return Err(Error::msg(format!("键 {key} 与其子键冲突")));
+                }
+                map.insert(segment.to_string(), value);
+                break;
+            }
+            let entry = map
+                .entry(segment.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            let Value::Object(child) = entry else {
+                return Err(Error::msg(format!("键 {key} 与已有的值冲突")));
+            };
+            map = child;
+        }
+    }
+    let mut value = Value::Object(root);
+    restore_arrays(&mut value);
+    Ok(value)
+}
+
+/// 键为 0..n 的对象还原为数组
+fn restore_arrays(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.values_mut().for_each(restore_arrays);
+            let is_array =
+                !map.is_empty() && map.keys().enumerate().all(|(i, k)| *k == i.to_string());
+            if is_array {
+                let items = std::mem::take(map).into_iter().map(|(_, v)| v).collect();
+                *value = Value::Array(items);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(restore_arrays),
+        _ => {}
+    }
+}
+
+fn strings(pairs: Vec<(String, String)>) -> impl Iterator<Item = (String, Value)> {
+    pairs.into_iter().map(|(k, v)| (k, Value::String(v)))
+}
+
+pub fn from_env(text: &str) -> Result<Value> {
+    let mut pairs = Vec::new();
+    let mut rest = text;
+    let mut line_no = 0;
+    while !rest.is_empty() {
+        line_no += 1;
+        let (line, next) = rest.split_once('\n').unwrap_or((rest, ""));
+        rest = next;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(Error::msg(format!("第 {line_no} 行缺少 =")));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(Error::msg(format!("第 {line_no} 行缺少键名")));
+        }
+        let value = value.trim_start();
+
+        let parsed = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                // 引号内的值可以跨行
+                let mut body = value[1..].to_string();
+                let end = loop {
+                    if let Some(end) = find_closing(&body, quote) {
+                        break end;
+                    }
+                    if rest.is_empty() {
+                        return Err(Error::msg(format!("第 {line_no} 行的引号未闭合")));
+                    }
+                    let (line, next) = rest.split_once('\n').unwrap_or((rest, ""));
+                    rest = next;
+                    line_no += 1;
+                    body.push('\n');
+                    body.push_str(line);
+                };
+                body.truncate(end);
+                if quote == '"' { unescape(&body) } else { body }
+            }
+            _ => {
+                // 未加引号的值中，空白后的 # 开始注释
+                let end = value
+                    .char_indices()
+                    .find(|&(i, c)| c == '#' && value[..i].ends_with([' ', '\t']))
+                    .map_or(value.len(), |(i, _)| i);
+                value[..end].trim_end().to_string()
+            }
+        };
+        pairs.push((key.to_string(), parsed));
+    }
+    unflatten(strings(pairs))
+}
+
+fn find_closing(s: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\\' if quote == '"' && !escaped => escaped = true,
+            c if c == quote && !escaped => return Some(i),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(c) => out.push(c),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+pub fn to_env(value: &Value) -> Result<String> {
+    let mut out = String::new();
+    for (key, value) in flatten(value)? {
+        let plain = !value.is_empty()
+            && !value.starts_with([' ', '\t'])
+            && !value.ends_with([' ', '\t'])
+            && !value.contains(['#', '"', '\'', '\\', '\n', '\r', '$', ' ']);
+        out.push_str(&key);
+        out.push('=');
+        if plain || value.is_empty() {
+            out.push_str(&value);
+        } else if !value.contains(['\'', '\n', '\r']) {
+            out.push_str(&format!("'{value}'"));
+        } else {
+            let escaped = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r");
+            out.push_str(&format!("\"{escaped}\""));
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+pub fn from_properties(text: &str) -> Result<Value> {
+    let mut pairs = Vec::new();
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with(['#', '!']) {
+            continue;
+        }
+        // 以奇数个反斜杠结尾的行与下一行相连
+        let mut logical = line.to_string();
+        while logical.chars().rev().take_while(|&c| c == '\\').count() % 2 == 1 {
+            logical.pop();
+            match lines.next() {
+                Some(next) => logical.push_str(next.trim_start()),
+                None => break,
+            }
+        }
+
+        let mut key_end = logical.len();
+        let mut escaped = false;
+        for (i, c) in logical.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if matches!(c, '=' | ':' | ' ' | '\t' | '\x0c') {
+                key_end = i;
+                break;
+            }
+        }
+        let (key, rest) = logical.split_at(key_end);
+        let rest = rest.trim_start_matches([' ', '\t', '\x0c']);
+        let rest = rest
+            .strip_prefix(['=', ':'])
+            .unwrap_or(rest)
+            .trim_start_matches([' ', '\t', '\x0c']);
+        pairs.push((unescape_properties(key)?, unescape_properties(rest)?));
+    }
+    unflatten(strings(pairs))
+}
+
+fn unescape_properties(s: &str) -> Result<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('f') => out.push('\x0c'),
+            Some('u') => {
+                let hex = chars.by_ref().take(4).collect::<String>();
+                let code = u32::from_str_radix(&hex, 16)
+                    .map_err(|_| Error::msg(format!("无效的转义: \\u{hex}")))?;
+                out.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            Some(c) => out.push(c),
+            None => {}
+        }
+    }
+    Ok(out)
+}
+
+pub fn to_properties(value: &Value) -> Result<String> {
+    let mut out = String::new();
+    for (key, value) in flatten(value)? {
+        out.push_str(&escape_properties(&key, true));
+        out.push('=');
+        out.push_str(&escape_properties(&value, false));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn escape_properties(s: &str, is_key: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\x0c' => out.push_str("\\f"),
+            '=' | ':' | '#' | '!' if is_key => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' ' if is_key || i == 0 => out.push_str("\\ "),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn from_ini(text: &str) -> Result<Value> {
+    let ini = Ini::load_from_str(text).map_err(|e| Error::msg(e.to_string()))?;
+    let mut pairs = Vec::new();
+    for (section, props) in ini.iter() {
+        for (key, value) in props.iter() {
+            let key = match section {
+                Some(section) => format!("{section}.{key}"),
+                None => key.to_string(),
+            };
+            pairs.push((key, value.to_string()));
+        }
+    }
+    unflatten(strings(pairs))
+}
+
+/// 顶层的对象写成节，其余的值写在节之前
+pub fn to_ini(value: &Value) -> Result<String> {
+    let Value::Object(map) = value else {
+        return Err(Error::msg("INI 的顶层必须是对象"));
+    };
+    let mut ini = Ini::new();
+    let (sections, globals): (Vec<_>, Vec<_>) = map
+        .iter()
+        .partition(|(_, v)| matches!(v, Value::Object(m) if !m.is_empty()));
+
+    let globals = Value::Object(
+        globals
+            .into_iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    );
+    for (key, value) in flatten(&globals)? {
+        ini.with_general_section().set(key, value);
+    }
+    for (name, value) in sections {
+        for (key, value) in flatten(value)? {
+            ini.with_section(Some(name.as_str())).set(key, value);
+        }
+    }
+
+    let mut buf = Vec::new();
+    ini.write_to(&mut buf)?;
+    String::from_utf8(buf).map_err(|e| Error::msg(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn value() -> Value {
+        json!({
+            "name": "toolbox",
+            "server": { "host": "0.0.0.0", "port": "8080" },
+            "tags": ["a b", "c#d"],
+        })
+    }
+
+    #[test]
+    fn test_env() -> Result<()> {
+        let env = to_env(&value())?;
+        assert_eq!(
+            "name=toolbox\nserver.host=0.0.0.0\nserver.port=8080\ntags.0='a b'\ntags.1='c#d'\n",
+            env
+        );
+        assert_eq!(value(), from_env(&env)?);
+
+        let text = "# comment\nexport A=1 # tail\nB=\"x\\ny\"\nC='multi\nline'\n";
+        assert_eq!(
+            json!({ "A": "1", "B": "x\ny", "C": "multi\nline" }),
+            from_env(text)?
+        );
+        assert!(from_env("A='x").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_properties() -> Result<()> {
+        let text = to_properties(&value())?;
+        assert_eq!(value(), from_properties(&text)?);
+
+        let text = "! comment\nkey with\\ space = v\nlong: a\\\n    b\nunicode=\\u4e2d\nempty\n";
+        assert_eq!(
+            json!({ "key": "with space = v", "long": "ab", "unicode": "中", "empty": "" }),
+            from_properties(text)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ini() -> Result<()> {
+        let ini = to_ini(&value())?;
+        assert!(ini.contains("[server]"));
+        assert_eq!(value(), from_ini(&ini)?);
+
+        let nested = json!({ "a": { "b": { "c": "1" } } });
+        assert_eq!(nested, from_ini(&to_ini(&nested)?)?);
+        assert!(unflatten([("a".into(), json!("1")), ("a.b".into(), json!("2"))]).is_err());
+        Ok(())
+    }
+}
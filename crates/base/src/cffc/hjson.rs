@@ -0,0 +1,375 @@
+//! HJSON
+//!
+//! 支持的语法：`#`、`//`、`/* */` 注释，无引号的键和字符串，可省略的逗号和根对象的花括号，
+//! 以及 `'''` 多行字符串。写出时能无歧义读回的字符串不加引号，其余用 JSON 字符串。
+
+use anyhow::{Error, Result};
+use serde_json::{Map, Number, Value};
+
+use super::FormatOptions;
+
+pub fn from_hjson(text: &str) -> Result<Value> {
+    let mut parser = Parser { text, pos: 0 };
+    parser.skip_blank();
+    let value = match parser.peek() {
+        Some('{') | Some('[') => parser.value()?,
+        _ => parser.members(None)?,
+    };
+    parser.skip_blank();
+    if parser.pos < text.len() {
+        return Err(parser.error("多余的内容"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, message: &str) -> Error {
+        let line = self.text[..self.pos].matches('\n').count() + 1;
+        Error::msg(format!("HJSON 解析失败（第 {line} 行）: {message}"))
+    }
+
+    /// 跳过空白和注释
+    fn skip_blank(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            let rest = self.rest();
+            if rest.starts_with('#') || rest.starts_with("//") {
+                self.pos += rest.find('\n').unwrap_or(rest.len());
+            } else if rest.starts_with("/*") {
+                self.pos += rest.find("*/").map_or(rest.len(), |i| i + 2);
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// 跳过空白、注释和一个可选的逗号
+    fn skip_separator(&mut self) {
+        self.skip_blank();
+        if self.peek() == Some(',') {
+            self.pos += 1;
+            self.skip_blank();
+        }
+    }
+
+    /// 解析对象成员，`close` 为 `None` 时表示省略了花括号的根对象
+    fn members(&mut self, close: Option<char>) -> Result<Value> {
+        let mut map = Map::new();
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                None if close.is_none() => break,
+                None => return Err(self.error("对象未闭合")),
+                Some(c) if Some(c) == close => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => {}
+            }
+            let key = self.key()?;
+            self.skip_blank();
+            if self.bump() != Some(':') {
+                return Err(self.error(&format!("键 {key} 后缺少冒号")));
+            }
+            self.skip_blank();
+            let value = self.value()?;
+            map.insert(key, value);
+            self.skip_separator();
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn key(&mut self) -> Result<String> {
+        match self.peek() {
+            Some('"') | Some('\'') => self.quoted(),
+            _ => {
+                let rest = self.rest();
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || ",:[]{}".contains(c))
+                    .unwrap_or(rest.len());
+                if end == 0 {
+                    return Err(self.error("缺少键名"));
+                }
+                let key = rest[..end].to_string();
+                self.pos += end;
+                Ok(key)
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        match self.peek() {
+            Some('{') => {
+                self.pos += 1;
+                self.members(Some('}'))
+            }
+            Some('[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_blank();
+                    match self.peek() {
+                        Some(']') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        None => return Err(self.error("数组未闭合")),
+                        _ => {}
+                    }
+                    items.push(self.value()?);
+                    self.skip_separator();
+                }
+                Ok(Value::Array(items))
+            }
+            Some('\'') if self.rest().starts_with("'''") => self.multiline(),
+            Some('"') | Some('\'') => self.quoted().map(Value::String),
+            Some(c) if ",:]}".contains(c) => Err(self.error(&format!("意外的字符 {c}"))),
+            None => Err(self.error("缺少值")),
+            _ => Ok(self.quoteless()),
+        }
+    }
+
+    /// 无引号的值：整行是数字或关键字时按对应类型解析，否则到行尾都是字符串
+    fn quoteless(&mut self) -> Value {
+        let rest = self.rest();
+        let line = &rest[..rest.find(['\n', '\r']).unwrap_or(rest.len())];
+        let token_end = line
+            .find(|c: char| c.is_whitespace() || ",]}#/".contains(c))
+            .unwrap_or(line.len());
+        let token = &line[..token_end];
+        let after = line[token_end..].trim_start();
+        let ends = after.is_empty()
+            || after.starts_with([',', ']', '}', '#'])
+            || after.starts_with("//")
+            || after.starts_with("/*");
+        if ends && let Some(value) = keyword_or_number(token) {
+            self.pos += token_end;
+            return value;
+        }
+        self.pos += line.len();
+        Value::String(line.trim_end().to_string())
+    }
+
+    fn quoted(&mut self) -> Result<String> {
+        let quote = self.bump().unwrap_or('"');
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(self.error("字符串未闭合")),
+                Some(c) if c == quote => return Ok(out),
+                Some('\\') => match self.bump() {
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('b') => out.push('\x08'),
+                    Some('f') => out.push('\x0c'),
+                    Some('u') => {
+                        let hex = self.rest().get(..4).unwrap_or_default().to_string();
+                        let code = u32::from_str_radix(&hex, 16)
+                            .map_err(|_| self.error(&format!("无效的转义 \\u{hex}")))?;
+                        self.pos += 4;
+                        out.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    Some(c) => out.push(c),
+                    None => return Err(self.error("字符串未闭合")),
+                },
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    /// `'''` 多行字符串，按开头 `'''` 所在列去掉每行的缩进
+    fn multiline(&mut self) -> Result<Value> {
+        let line_start = self.text[..self.pos].rfind('\n').map_or(0, |i| i + 1);
+        let indent = self.text[line_start..self.pos].chars().count();
+        self.pos += 3;
+        let rest = self.rest();
+        let Some(end) = rest.find("'''") else {
+            return Err(self.error("多行字符串未闭合"));
+        };
+        let body = &rest[..end];
+        self.pos += end + 3;
+
+        // 开头 ''' 之后同一行的空白不计入内容
+        let trimmed = body.trim_start_matches([' ', '\t']);
+        let body = trimmed
+            .strip_prefix('\n')
+            .or_else(|| trimmed.strip_prefix("\r\n"))
+            .unwrap_or(trimmed);
+        let mut lines = body
+            .split('\n')
+            .map(|line| {
+                let skip = line
+                    .chars()
+                    .take(indent)
+                    .take_while(|c| c.is_whitespace())
+                    .map(char::len_utf8)
+                    .sum::<usize>();
+                line[skip..].trim_end_matches('\r')
+            })
+            .collect::<Vec<_>>();
+        // 结尾 ''' 所在行只有缩进时去掉
+        if lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        Ok(Value::String(lines.join("\n")))
+    }
+}
+
+fn keyword_or_number(token: &str) -> Option<Value> {
+    match token {
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        "null" => Some(Value::Null),
+        _ => {
+            // 只接受 JSON 的数字写法
+            let number = serde_json::from_str::<Number>(token).ok()?;
+            Some(Value::Number(number))
+        }
+    }
+}
+
+pub fn to_hjson(value: &Value, opts: &FormatOptions) -> Result<String> {
+    let mut out = String::new();
+    write_value(&mut out, value, &opts.unit(), "")?;
+    out.push('\n');
+    Ok(out)
+}
+
+fn write_value(out: &mut String, value: &Value, unit: &str, pad: &str) -> Result<()> {
+    let child = format!("{pad}{unit}");
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            out.push_str("{\n");
+            for (k, v) in map {
+                out.push_str(&child);
+                out.push_str(&key(k)?);
+                out.push_str(": ");
+                write_value(out, v, unit, &child)?;
+                out.push('\n');
+            }
+            out.push_str(pad);
+            out.push('}');
+        }
+        Value::Array(items) if !items.is_empty() => {
+            out.push_str("[\n");
+            for v in items {
+                out.push_str(&child);
+                write_value(out, v, unit, &child)?;
+                out.push('\n');
+            }
+            out.push_str(pad);
+            out.push(']');
+        }
+        Value::String(s) => out.push_str(&string(s)?),
+        _ => out.push_str(&serde_json::to_string(value)?),
+    }
+    Ok(())
+}
+
+fn key(k: &str) -> Result<String> {
+    let quoteless = !k.is_empty()
+        && !k.contains(|c: char| c.is_whitespace() || ",:[]{}\"'#".contains(c))
+        && !k.starts_with("//")
+        && !k.starts_with("/*");
+    if quoteless {
+        Ok(k.to_string())
+    } else {
+        Ok(serde_json::to_string(k)?)
+    }
+}
+
+/// 不加引号时能原样读回的字符串才写成无引号形式
+fn string(s: &str) -> Result<String> {
+    let quoteless = !s.is_empty()
+        && s.trim() == s
+        && !s.contains(['\n', '\r'])
+        && !s.starts_with(|c: char| "{}[],:\"'#".contains(c))
+        && !s.starts_with("//")
+        && !s.starts_with("/*")
+        && (Parser { text: s, pos: 0 }).quoteless() == Value::String(s.to_string());
+    if quoteless {
+        Ok(s.to_string())
+    } else {
+        Ok(serde_json::to_string(s)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_from_hjson() -> Result<()> {
+        let text = r#"
+# 注释
+name: toolbox  // 行尾注释在无引号字符串中保留
+port: 8080
+enabled: true, ratio: 0.5
+list: [
+  1
+  two words
+  "quoted"
+]
+nested: { a: null }
+text:
+  '''
+  第一行
+    第二行
+  '''
+"#;
+        assert_eq!(
+            json!({
+                "name": "toolbox  // 行尾注释在无引号字符串中保留",
+                "port": 8080,
+                "enabled": true,
+                "ratio": 0.5,
+                "list": [1, "two words", "quoted"],
+                "nested": { "a": null },
+                "text": "第一行\n  第二行",
+            }),
+            from_hjson(text)?
+        );
+        assert!(from_hjson("{a: 1").is_err());
+        assert!(from_hjson("a 1").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let value = json!({
+            "name": "toolbox",
+            "version": "1.0",
+            "count": 3,
+            "a key": ["x", "", " pad", "1, 2", "true", "#hash", "line\nbreak", {}],
+            "empty": [],
+        });
+        let text = to_hjson(&value, &FormatOptions::default())?;
+        assert!(text.starts_with("{\n  name: toolbox\n  version: \"1.0\"\n  count: 3\n"));
+        assert_eq!(value, from_hjson(&text)?);
+        Ok(())
+    }
+}
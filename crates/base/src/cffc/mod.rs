@@ -0,0 +1,264 @@
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod detect;
+mod dialect;
+mod flat;
+mod hjson;
+mod stream;
+mod style;
+mod tabular;
+mod xml;
+
+pub use detect::{Confidence, Detection, Rejected, detect};
+pub use stream::{StreamFt, transcode, transcode_file};
+pub use style::{FormatOptions, TomlTables, YamlStyle};
+pub use xml::XmlOptions;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Ft {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+    Xml,
+    /// 带注释的 JSON
+    Jsonc,
+    Json5,
+    Hjson,
+    Ini,
+    /// `.env` 环境变量文件
+    Env,
+    /// Java `.properties`
+    Properties,
+    Csv,
+    /// 仅用于源格式，转换前自动识别
+    Auto,
+}
+
+impl From<&str> for Ft {
+    fn from(value: &str) -> Self {
+        match value.to_lowercase().as_ref() {
+            "json" => Self::Json,
+            "yaml" => Self::Yaml,
+            "toml" => Self::Toml,
+            "xml" => Self::Xml,
+            "jsonc" => Self::Jsonc,
+            "json5" => Self::Json5,
+            "hjson" => Self::Hjson,
+            "ini" => Self::Ini,
+            "env" | ".env" | "dotenv" => Self::Env,
+            "properties" => Self::Properties,
+            "csv" => Self::Csv,
+            "auto" => Self::Auto,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl std::fmt::Display for Ft {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ft::Json => write!(f, "Json"),
+            Ft::Yaml => write!(f, "Yaml"),
+            Ft::Toml => write!(f, "Toml"),
+            Ft::Xml => write!(f, "Xml"),
+            Ft::Jsonc => write!(f, "Jsonc"),
+            Ft::Json5 => write!(f, "Json5"),
+            Ft::Hjson => write!(f, "Hjson"),
+            Ft::Ini => write!(f, "Ini"),
+            Ft::Env => write!(f, "Env"),
+            Ft::Properties => write!(f, "Properties"),
+            Ft::Csv => write!(f, "Csv"),
+            Ft::Auto => write!(f, "Auto"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Data {
+    pub from: Ft,
+    pub to: Ft,
+    pub input: String,
+    #[serde(default)]
+    pub style: FormatOptions,
+    #[serde(default)]
+    pub xml: XmlOptions,
+}
+
+impl Data {
+    pub fn new(from: Ft, to: Ft, input: &str, indent: u8) -> Self {
+        Self {
+            from,
+            to,
+            input: input.into(),
+            style: FormatOptions {
+                indent,
+                ..Default::default()
+            },
+            xml: XmlOptions::default(),
+        }
+    }
+
+    /// 设置输出格式选项，会覆盖 `new` 中传入的缩进
+    pub fn style(mut self, style: FormatOptions) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// 设置 XML 的属性、文本节点和根元素约定
+    pub fn xml(mut self, xml: XmlOptions) -> Self {
+        self.xml = xml;
+        self
+    }
+
+    /// 按源格式把输入解析为 `Value`，源格式为 Auto 时先识别
+    pub fn parse(&self) -> Result<Value> {
+        let from = match self.from {
+            Ft::Auto => {
+                let detection = detect(&self.input);
+                detection
+                    .format
+                    .ok_or_else(|| Error::msg(detection.error_message()))?
+            }
+            ft => ft,
+        };
+        match from {
+            Ft::Json => Self::from_json(&self.input),
+            Ft::Yaml => Self::from_yaml(&self.input),
+            Ft::Toml => Self::from_toml(&self.input),
+            Ft::Xml => self.parse_xml(),
+            Ft::Jsonc => dialect::from_jsonc(&self.input),
+            Ft::Json5 => dialect::from_json5(&self.input),
+            Ft::Hjson => hjson::from_hjson(&self.input),
+            Ft::Ini => flat::from_ini(&self.input),
+            Ft::Env => flat::from_env(&self.input),
+            Ft::Properties => flat::from_properties(&self.input),
+            Ft::Csv => tabular::from_csv(&self.input),
+            Ft::Auto => unreachable!("detect 不会返回 Auto"),
+        }
+    }
+
+    /// 解析输入后按格式选项写出目标格式；源格式与目标格式相同时即为格式化
+    pub fn transform(&self) -> Result<String> {
+        if self.input.is_empty() {
+            return Ok(String::new());
+        }
+
+        let mut value = self.parse()?;
+        if self.style.sort_keys {
+            style::sort_keys(&mut value);
+        }
+        match self.to {
+            Ft::Json => style::to_json(&value, &self.style),
+            Ft::Yaml => style::to_yaml(&value, &self.style),
+            Ft::Toml => style::to_toml(&value, &self.style),
+            Ft::Xml => xml::to_xml(&value, &self.xml, &self.style),
+            Ft::Jsonc => style::to_json(&value, &self.style),
+            Ft::Json5 => dialect::to_json5(&value, &self.style),
+            Ft::Hjson => hjson::to_hjson(&value, &self.style),
+            Ft::Ini => flat::to_ini(&value),
+            Ft::Env => flat::to_env(&value),
+            Ft::Properties => flat::to_properties(&value),
+            Ft::Csv => tabular::to_csv(&value),
+            Ft::Auto => Err(Error::msg("目标格式不能为自动识别")),
+        }
+    }
+}
+
+impl Data {
+    fn from_json(text: &str) -> Result<Value> {
+        serde_json::from_str(text).map_err(|e| Error::msg(e.to_string()))
+    }
+
+    /// 先解析为 `serde_yaml::Value` 以展开 `<<` 合并键，非字符串的键转为字符串
+    fn from_yaml(text: &str) -> Result<Value> {
+        let mut value = serde_yaml::from_str::<serde_yaml::Value>(text)
+            .map_err(|e| Error::msg(e.to_string()))?;
+        value.apply_merge().map_err(|e| Error::msg(e.to_string()))?;
+        serde_json::to_value(value).map_err(|e| Error::msg(e.to_string()))
+    }
+
+    fn from_toml(text: &str) -> Result<Value> {
+        toml::from_str(text).map_err(|e| Error::msg(e.to_string()))
+    }
+
+    fn parse_xml(&self) -> Result<Value> {
+        xml::from_xml(&self.input, &self.xml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+[package]
+name = "cffc"
+version = "0.1.0"
+authors = ["gopher9527 <gopher9527@gmail.com>"]
+edition = "2018"
+
+[dependencies]
+serde = "1.0.125"
+actix-web = "3.3.2"
+"#;
+
+    #[test]
+    fn test_transform() -> Result<()> {
+        let yaml = Data::new(Ft::Toml, Ft::Yaml, TOML, 2).transform()?;
+        assert_eq!(
+            "package:
+  name: cffc
+  version: 0.1.0
+  authors:
+    - gopher9527 <gopher9527@gmail.com>
+  edition: '2018'
+dependencies:
+  serde: 1.0.125
+  actix-web: 3.3.2
+",
+            yaml
+        );
+        assert_eq!(
+            TOML.trim_start(),
+            Data::new(Ft::Yaml, Ft::Toml, &yaml, 2).transform()?
+        );
+
+        let json = Data::new(Ft::Yaml, Ft::Json, "b: 1\na: [1, 2]\n", 4).transform()?;
+        assert_eq!(
+            "{\n    \"b\": 1,\n    \"a\": [\n        1,\n        2\n    ]\n}",
+            json
+        );
+
+        let style = FormatOptions {
+            indent: 0,
+            sort_keys: true,
+            ..Default::default()
+        };
+        let json = Data::new(Ft::Json, Ft::Json, &json, 0)
+            .style(style)
+            .transform()?;
+        assert_eq!(r#"{"a":[1,2],"b":1}"#, json);
+
+        let merged = Data::new(
+            Ft::Yaml,
+            Ft::Json,
+            "base: &b {x: 1}\nc:\n  <<: *b\n  1: y\n",
+            0,
+        )
+        .transform()?;
+        assert_eq!(r#"{"base":{"x":1},"c":{"1":"y","x":1}}"#, merged);
+
+        let auto = Data::new(Ft::Auto, Ft::Json, TOML, 0).transform()?;
+        assert!(auto.starts_with(r#"{"package":{"name":"cffc""#));
+        assert!(
+            Data::new(Ft::Auto, Ft::Json, "{a: [", 0)
+                .transform()
+                .is_err()
+        );
+        assert!(Data::new(Ft::Json, Ft::Auto, "{}", 0).transform().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+//! 大文件格式转换
+//!
+//! 借助 serde-transcode 把反序列化事件直接转给序列化器，边读边写，不构建完整的 `Value` 树。
+//! 注意 YAML 解析器会先把原文整体读入内存，但同样不会生成 `Value` 树。
+
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Lines, Read, Write},
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use anyhow::{Error, Result};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, DeserializeSeed, SeqAccess, Visitor},
+    ser::{self, SerializeSeq},
+};
+use serde_transcode::Transcoder;
+
+/// 支持流式转换的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamFt {
+    Json,
+    Yaml,
+    /// 每行一个 JSON 值
+    JsonLines,
+}
+
+impl TryFrom<&str> for StreamFt {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_ref() {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "jsonl" | "ndjson" | "json lines" | "jsonlines" => Ok(Self::JsonLines),
+            _ => Err(Error::msg(format!("大文件模式不支持的格式: {value}"))),
+        }
+    }
+}
+
+/// 统计已读取字节数的 reader，供界面显示进度
+struct ProgressReader<'a, R> {
+    inner: R,
+    read: &'a AtomicU64,
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// 把 `reader` 中的内容从 `from` 格式转码为 `to` 格式写入 `writer`，
+/// 每读取一段数据就累加到 `read`
+pub fn transcode<R, W>(
+    from: StreamFt,
+    to: StreamFt,
+    reader: R,
+    writer: W,
+    indent: u8,
+    read: &AtomicU64,
+) -> Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    let reader = BufReader::new(ProgressReader {
+        inner: reader,
+        read,
+    });
+    let mut writer = BufWriter::new(writer);
+
+    match from {
+        StreamFt::Json => {
+            let mut de = serde_json::Deserializer::from_reader(reader);
+            match to {
+                StreamFt::Json => write_json(&mut writer, &Transcoder::new(&mut de), indent)?,
+                StreamFt::Yaml => {
+                    let mut ser = serde_yaml::Serializer::new(&mut writer);
+                    serde_transcode::transcode(&mut de, &mut ser)?;
+                }
+                StreamFt::JsonLines => {
+                    (&mut de).deserialize_seq(SplitArray {
+                        writer: &mut writer,
+                    })?;
+                }
+            }
+            de.end()?;
+        }
+        StreamFt::Yaml => {
+            let mut docs = serde_yaml::Deserializer::from_reader(reader);
+            match to {
+                StreamFt::Json => {
+                    let doc = docs.next().ok_or_else(|| Error::msg("YAML 内容为空"))?;
+                    write_json(&mut writer, &Transcoder::new(doc), indent)?;
+                    if docs.next().is_some() {
+                        return Err(Error::msg(
+                            "YAML 包含多个文档，无法转为单个 JSON，请改用 JSON Lines",
+                        ));
+                    }
+                }
+                StreamFt::Yaml => {
+                    let mut ser = serde_yaml::Serializer::new(&mut writer);
+                    for doc in docs {
+                        serde_transcode::transcode(doc, &mut ser)?;
+                    }
+                }
+                StreamFt::JsonLines => {
+                    for doc in docs {
+                        write_json(&mut writer, &Transcoder::new(doc), 0)?;
+                        writer.write_all(b"\n")?;
+                    }
+                }
+            }
+        }
+        StreamFt::JsonLines => match to {
+            StreamFt::Json => write_json(&mut writer, &JsonLinesSeq::new(reader), indent)?,
+            StreamFt::Yaml => {
+                let mut ser = serde_yaml::Serializer::new(&mut writer);
+                for_each_line(reader, |de| {
+                    serde_transcode::transcode(de, &mut ser)?;
+                    Ok(())
+                })?;
+            }
+            StreamFt::JsonLines => for_each_line(reader, |de| {
+                write_json(&mut writer, &Transcoder::new(de), 0)?;
+                writer.write_all(b"\n")?;
+                Ok(())
+            })?,
+        },
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// 转换文件。先写入输出目录中的临时文件，成功后再替换 `output`，
+/// 中途失败不会留下半截文件；输出与源文件是同一个文件时报错，避免先被清空
+pub fn transcode_file(
+    from: StreamFt,
+    to: StreamFt,
+    input: &Path,
+    output: &Path,
+    indent: u8,
+    read: &AtomicU64,
+) -> Result<()> {
+    let reader = File::open(input)?;
+    if output
+        .canonicalize()
+        .is_ok_and(|output| input.canonicalize().is_ok_and(|input| input == output))
+    {
+        return Err(Error::msg("输出文件不能与源文件相同"));
+    }
+    let dir = output
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+    transcode(from, to, reader, temp.as_file_mut(), indent, read)?;
+    temp.persist(output)
+        .map_err(|e| Error::msg(e.to_string()))?;
+    Ok(())
+}
+
+fn write_json<W, T>(writer: W, value: &T, indent: u8) -> Result<()>
+where
+    W: Write,
+    T: Serialize + ?Sized,
+{
+    if indent == 0 {
+        value.serialize(&mut serde_json::Serializer::new(writer))?;
+    } else {
+        let indent = vec![b' '; indent as usize];
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(&indent);
+        value.serialize(&mut serde_json::Serializer::with_formatter(
+            writer, formatter,
+        ))?;
+    }
+    Ok(())
+}
+
+/// 逐行解析 JSON Lines，跳过空行，错误信息带上行号
+fn for_each_line<R, F>(reader: R, mut f: F) -> Result<()>
+where
+    R: BufRead,
+    F: FnMut(&mut serde_json::Deserializer<serde_json::de::StrRead>) -> Result<()>,
+{
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut de = serde_json::Deserializer::from_str(&line);
+        f(&mut de)
+            .and_then(|_| de.end().map_err(Error::from))
+            .map_err(|e| Error::msg(format!("第 {} 行: {e}", i + 1)))?;
+    }
+    Ok(())
+}
+
+/// 把 JSON Lines 作为一个序列序列化，用于输出 JSON 数组
+struct JsonLinesSeq<R> {
+    lines: RefCell<Lines<R>>,
+}
+
+impl<R: BufRead> JsonLinesSeq<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            lines: RefCell::new(reader.lines()),
+        }
+    }
+}
+
+impl<R: BufRead> Serialize for JsonLinesSeq<R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for (i, line) in self.lines.borrow_mut().by_ref().enumerate() {
+            let line = line.map_err(ser::Error::custom)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut de = serde_json::Deserializer::from_str(&line);
+            seq.serialize_element(&Transcoder::new(&mut de))?;
+            de.end()
+                .map_err(|e| ser::Error::custom(format!("第 {} 行: {e}", i + 1)))?;
+        }
+        seq.end()
+    }
+}
+
+/// 逐个元素转码 JSON 顶层数组，每个元素写成一行
+struct SplitArray<'a, W> {
+    writer: &'a mut W,
+}
+
+impl<'de, W: Write> Visitor<'de> for SplitArray<'_, W> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("顶层为数组的 JSON")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while seq
+            .next_element_seed(LineSeed {
+                writer: &mut *self.writer,
+            })?
+            .is_some()
+        {}
+        Ok(())
+    }
+}
+
+struct LineSeed<'a, W> {
+    writer: &'a mut W,
+}
+
+impl<'de, W: Write> DeserializeSeed<'de> for LineSeed<'_, W> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        serde_transcode::transcode(
+            deserializer,
+            &mut serde_json::Serializer::new(&mut *self.writer),
+        )
+        .map_err(de::Error::custom)?;
+        self.writer.write_all(b"\n").map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(from: StreamFt, to: StreamFt, input: &str, indent: u8) -> Result<String> {
+        let read = AtomicU64::new(0);
+        let mut out = Vec::new();
+        transcode(from, to, input.as_bytes(), &mut out, indent, &read)?;
+        assert_eq!(input.len() as u64, read.load(Ordering::Relaxed));
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn test_transcode_file() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let input = dir.path().join("in.json");
+        std::fs::write(&input, r#"{"a":1}"#)?;
+        let read = AtomicU64::new(0);
+        // 输出选成源文件时不能先把源文件清空
+        let same = dir.path().join(".").join("in.json");
+        assert!(transcode_file(StreamFt::Json, StreamFt::Yaml, &input, &same, 0, &read).is_err());
+        assert_eq!(r#"{"a":1}"#, std::fs::read_to_string(&input)?);
+
+        let output = dir.path().join("out.yml");
+        transcode_file(StreamFt::Json, StreamFt::Yaml, &input, &output, 0, &read)?;
+        assert_eq!("a: 1\n", std::fs::read_to_string(&output)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_yaml() -> Result<()> {
+        let yaml = run(
+            StreamFt::Json,
+            StreamFt::Yaml,
+            r#"{"name":"cffc","tags":["a","b"]}"#,
+            2,
+        )?;
+        assert_eq!("name: cffc\ntags:\n- a\n- b\n", yaml);
+        assert_eq!(
+            r#"{"name":"cffc","tags":["a","b"]}"#,
+            run(StreamFt::Yaml, StreamFt::Json, &yaml, 0)?
+        );
+        assert!(run(StreamFt::Yaml, StreamFt::Json, "a: 1\n---\na: 2\n", 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_lines() -> Result<()> {
+        let lines = run(
+            StreamFt::Json,
+            StreamFt::JsonLines,
+            r#"[{"a":1}, 2, "x"]"#,
+            2,
+        )?;
+        assert_eq!("{\"a\":1}\n2\n\"x\"\n", lines);
+        assert_eq!(
+            "[\n  {\n    \"a\": 1\n  },\n  2,\n  \"x\"\n]",
+            run(StreamFt::JsonLines, StreamFt::Json, &lines, 2)?
+        );
+        assert_eq!(
+            "a: 1\n---\na: 2\n",
+            run(
+                StreamFt::JsonLines,
+                StreamFt::Yaml,
+                "{\"a\":1}\n\n{\"a\":2}\n",
+                2
+            )?
+        );
+        assert_eq!(
+            "{\"a\":1}\n{\"a\":2}\n",
+            run(StreamFt::Yaml, StreamFt::JsonLines, "a: 1\n---\na: 2\n", 2)?
+        );
+        assert!(run(StreamFt::Json, StreamFt::JsonLines, r#"{"a":1}"#, 0).is_err());
+        let err = run(StreamFt::JsonLines, StreamFt::JsonLines, "1\n{oops\n", 0).unwrap_err();
+        assert!(err.to_string().starts_with("第 2 行"));
+        Ok(())
+    }
+}
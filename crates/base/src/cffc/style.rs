@@ -0,0 +1,548 @@
+//! 输出格式选项
+//!
+//! 所有格式都先解析为 `serde_json::Value`（启用 `preserve_order`，底层为 `IndexMap`，
+//! 保留源文件中的键顺序），再按 [`FormatOptions`] 写出目标格式。
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// TOML 中 `toml` 解析出的日期时间在 `Value` 里的表示
+const TOML_DATETIME: &str = "$__toml_private_datetime";
+
+/// TOML 中嵌套表的写法
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TomlTables {
+    /// `[a.b]`、`[[a.b]]` 形式的表头
+    #[default]
+    Table,
+    /// `a = { b = 1 }` 形式的内联表
+    Inline,
+}
+
+/// YAML 的集合写法
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum YamlStyle {
+    /// 按缩进分行书写
+    #[default]
+    Block,
+    /// `{a: 1, b: [x, y]}` 形式写在一行
+    Flow,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FormatOptions {
+    /// 缩进宽度；JSON 为 0 时输出紧凑格式
+    pub indent: u8,
+    /// 使用制表符缩进；YAML 不允许制表符缩进，仍按 `indent` 个空格处理
+    pub use_tabs: bool,
+    /// 按键名排序；否则保留源文件中的键顺序
+    pub sort_keys: bool,
+    pub toml_tables: TomlTables,
+    pub yaml_style: YamlStyle,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            use_tabs: false,
+            sort_keys: false,
+            toml_tables: TomlTables::default(),
+            yaml_style: YamlStyle::default(),
+        }
+    }
+}
+
+impl FormatOptions {
+    /// 一级缩进对应的字符串
+    pub(super) fn unit(&self) -> String {
+        if self.use_tabs {
+            "\t".into()
+        } else {
+            " ".repeat(self.indent as usize)
+        }
+    }
+}
+
+/// 递归地按键名排序
+pub fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let mut entries = std::mem::take(map).into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (k, mut v) in entries {
+                sort_keys(&mut v);
+                map.insert(k, v);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+pub fn to_json(value: &Value, opts: &FormatOptions) -> Result<String> {
+    if !opts.use_tabs && opts.indent == 0 {
+        return serde_json::to_string(value).map_err(|e| Error::msg(e.to_string()));
+    }
+    let unit = opts.unit();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(unit.as_bytes());
+    let mut buf = Vec::new();
+    value
+        .serialize(&mut serde_json::Serializer::with_formatter(
+            &mut buf, formatter,
+        ))
+        .map_err(|e| Error::msg(e.to_string()))?;
+    String::from_utf8(buf).map_err(|e| Error::msg(e.to_string()))
+}
+
+pub fn to_yaml(value: &Value, opts: &FormatOptions) -> Result<String> {
+    let mut out = String::new();
+    match opts.yaml_style {
+        YamlStyle::Flow => {
+            yaml_flow(&mut out, value)?;
+            out.push('\n');
+        }
+        YamlStyle::Block => {
+            // 块结构中 "- " 后的内容要与下一行对齐，缩进至少为 2
+            let unit = " ".repeat(opts.indent.max(2) as usize);
+            match value {
+                Value::Object(map) if !map.is_empty() => yaml_map(&mut out, map, "", &unit)?,
+                Value::Array(items) if !items.is_empty() => yaml_seq(&mut out, items, "", &unit)?,
+                _ => {
+                    out.push_str(&yaml_scalar(value, false)?);
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn yaml_map(out: &mut String, map: &Map<String, Value>, pad: &str, unit: &str) -> Result<()> {
+    let child = format!("{pad}{unit}");
+    for (k, v) in map {
+        out.push_str(pad);
+        out.push_str(&yaml_string(k, false)?);
+        out.push(':');
+        match v {
+            Value::Object(map) if !map.is_empty() => {
+                out.push('\n');
+                yaml_map(out, map, &child, unit)?;
+            }
+            Value::Array(items) if !items.is_empty() => {
+                out.push('\n');
+                yaml_seq(out, items, &child, unit)?;
+            }
+            Value::String(s) if is_literal_block(s) => yaml_literal(out, s, &child),
+            _ => {
+                out.push(' ');
+                out.push_str(&yaml_scalar(v, false)?);
+                out.push('\n');
+            }
+        }
+    }
+    Ok(())
+}
+
+fn yaml_seq(out: &mut String, items: &[Value], pad: &str, unit: &str) -> Result<()> {
+    let child = format!("{pad}{unit}");
+    // 集合元素先按子级缩进写出，再把首行的缩进替换为 "-" 加空格
+    let dash = format!("{pad}{:<width$}", "-", width = unit.len());
+    for item in items {
+        match item {
+            Value::Object(map) if !map.is_empty() => {
+                let mut buf = String::new();
+                yaml_map(&mut buf, map, &child, unit)?;
+                out.push_str(&dash);
+                out.push_str(&buf[child.len()..]);
+            }
+            Value::Array(items) if !items.is_empty() => {
+                let mut buf = String::new();
+                yaml_seq(&mut buf, items, &child, unit)?;
+                out.push_str(&dash);
+                out.push_str(&buf[child.len()..]);
+            }
+            Value::String(s) if is_literal_block(s) => {
+                out.push_str(pad);
+                out.push('-');
+                yaml_literal(out, s, &child);
+            }
+            _ => {
+                out.push_str(pad);
+                out.push_str("- ");
+                out.push_str(&yaml_scalar(item, false)?);
+                out.push('\n');
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 多行字符串是否可以写成 `|` 字面块
+fn is_literal_block(s: &str) -> bool {
+    s.contains('\n')
+        && !s.ends_with("\n\n")
+        && !s.starts_with([' ', '\t', '\n'])
+        && !s.chars().any(|c| c.is_control() && c != '\n' && c != '\t')
+}
+
+fn yaml_literal(out: &mut String, s: &str, pad: &str) {
+    out.push_str(if s.ends_with('\n') { " |\n" } else { " |-\n" });
+    for line in s.strip_suffix('\n').unwrap_or(s).split('\n') {
+        if !line.is_empty() {
+            out.push_str(pad);
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+}
+
+fn yaml_flow(out: &mut String, value: &Value) -> Result<()> {
+    match value {
+        Value::Object(map) => {
+            out.push('{');
+            for (i, (k, v)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&yaml_string(k, true)?);
+                out.push_str(": ");
+                yaml_flow(out, v)?;
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                yaml_flow(out, item)?;
+            }
+            out.push(']');
+        }
+        _ => out.push_str(&yaml_scalar(value, true)?),
+    }
+    Ok(())
+}
+
+fn yaml_scalar(value: &Value, flow: bool) -> Result<String> {
+    match value {
+        Value::Null => Ok("null".into()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::String(s) => yaml_string(s, flow),
+        Value::Object(_) => Ok("{}".into()),
+        Value::Array(_) => Ok("[]".into()),
+    }
+}
+
+/// 单行字符串交给 serde_yaml 决定是否加引号；多行或流式中含有分隔符时用双引号
+fn yaml_string(s: &str, flow: bool) -> Result<String> {
+    if !s.contains(['\n', '\r']) {
+        let text = serde_yaml::to_string(s).map_err(|e| Error::msg(e.to_string()))?;
+        let text = text.trim_end_matches('\n');
+        let quoted = text.starts_with(['\'', '"']);
+        if quoted || !flow || !text.contains([',', '[', ']', '{', '}']) {
+            return Ok(text.to_string());
+        }
+    }
+    // JSON 字符串同时也是合法的 YAML 双引号字符串
+    serde_json::to_string(s).map_err(|e| Error::msg(e.to_string()))
+}
+
+pub fn to_toml(value: &Value, opts: &FormatOptions) -> Result<String> {
+    let Value::Object(map) = value else {
+        return Err(Error::msg("TOML 的顶层必须是表（对象）"));
+    };
+    let mut out = String::new();
+    toml_table(&mut out, &mut Vec::new(), map, opts, false)?;
+    Ok(out)
+}
+
+/// 写出一个表：先写普通键值，再写子表和表数组
+fn toml_table(
+    out: &mut String,
+    path: &mut Vec<String>,
+    map: &Map<String, Value>,
+    opts: &FormatOptions,
+    array_item: bool,
+) -> Result<()> {
+    let (sections, values): (Vec<_>, Vec<_>) = map
+        .iter()
+        .filter(|(_, v)| !v.is_null())
+        .partition(|(_, v)| opts.toml_tables == TomlTables::Table && is_toml_section(v));
+
+    // 只有子表的表可以省略表头
+    if !path.is_empty() && (array_item || !values.is_empty() || sections.is_empty()) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let header = path.join(".");
+        if array_item {
+            out.push_str(&format!("[[{header}]]\n"));
+        } else {
+            out.push_str(&format!("[{header}]\n"));
+        }
+    }
+    for (k, v) in values {
+        out.push_str(&toml_key(k));
+        out.push_str(" = ");
+        toml_value(out, v, opts, "")?;
+        out.push('\n');
+    }
+    for (k, v) in sections {
+        path.push(toml_key(k));
+        match v {
+            Value::Object(map) => toml_table(out, path, map, opts, false)?,
+            Value::Array(items) => {
+                for item in items {
+                    if let Value::Object(map) = item {
+                        toml_table(out, path, map, opts, true)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+/// 是否写成 `[table]` 或 `[[array]]`
+fn is_toml_section(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => toml_datetime(map).is_none(),
+        Value::Array(items) => {
+            !items.is_empty()
+                && items
+                    .iter()
+                    .all(|v| matches!(v, Value::Object(map) if toml_datetime(map).is_none()))
+        }
+        _ => false,
+    }
+}
+
+fn toml_datetime(map: &Map<String, Value>) -> Option<&str> {
+    match map.get(TOML_DATETIME) {
+        Some(Value::String(s)) if map.len() == 1 => Some(s),
+        _ => None,
+    }
+}
+
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        toml_string(key)
+    }
+}
+
+fn toml_string(s: &str) -> String {
+    if s.contains('\n') {
+        // 避免 toml 写出多行字符串，内联表中不允许换行
+        serde_json::to_string(s).unwrap_or_default()
+    } else {
+        toml::Value::String(s.to_string()).to_string()
+    }
+}
+
+fn toml_value(out: &mut String, value: &Value, opts: &FormatOptions, pad: &str) -> Result<()> {
+    match value {
+        Value::Null => return Err(Error::msg("TOML 不支持 null 值")),
+        Value::Bool(b) => out.push_str(&b.to_string()),
+        Value::Number(n) => {
+            let v = if let Some(i) = n.as_i64() {
+                toml::Value::Integer(i)
+            } else if let Some(f) = n.as_f64().filter(|_| !n.is_u64()) {
+                toml::Value::Float(f)
+            } else {
+                return Err(Error::msg(format!("TOML 整数超出范围: {n}")));
+            };
+            out.push_str(&v.to_string());
+        }
+        Value::String(s) => out.push_str(&toml_string(s)),
+        Value::Object(map) => {
+            if let Some(dt) = toml_datetime(map) {
+                out.push_str(dt);
+                return Ok(());
+            }
+            if map.is_empty() {
+                out.push_str("{}");
+                return Ok(());
+            }
+            // 内联表必须写在一行内，其中的数组不再换行
+            let flat = FormatOptions {
+                indent: 0,
+                use_tabs: false,
+                ..opts.clone()
+            };
+            out.push_str("{ ");
+            let mut first = true;
+            for (k, v) in map.iter().filter(|(_, v)| !v.is_null()) {
+                if !first {
+                    out.push_str(", ");
+                }
+                first = false;
+                out.push_str(&toml_key(k));
+                out.push_str(" = ");
+                toml_value(out, v, &flat, pad)?;
+            }
+            out.push_str(" }");
+        }
+        Value::Array(items) => {
+            // 含有表或数组的数组在有缩进时逐项换行
+            let multiline = (opts.use_tabs || opts.indent > 0)
+                && items.len() > 1
+                && items
+                    .iter()
+                    .any(|v| matches!(v, Value::Object(_) | Value::Array(_)));
+            if !multiline {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    toml_value(out, item, opts, pad)?;
+                }
+                out.push(']');
+                return Ok(());
+            }
+            let child = format!("{pad}{}", opts.unit());
+            out.push_str("[\n");
+            for item in items {
+                out.push_str(&child);
+                toml_value(out, item, opts, &child)?;
+                out.push_str(",\n");
+            }
+            out.push_str(pad);
+            out.push(']');
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn value() -> Value {
+        json!({
+            "name": "cffc",
+            "authors": ["gopher9527", "LittleGuest"],
+            "package": { "version": "0.1.0", "edition": 2018 },
+            "bin": [{ "name": "a" }, { "name": "b", "path": "src/b.rs" }],
+        })
+    }
+
+    #[test]
+    fn test_json() -> Result<()> {
+        let mut opts = FormatOptions {
+            indent: 0,
+            ..Default::default()
+        };
+        let value = json!({ "b": [1], "a": { "d": 1, "c": 2 } });
+        assert_eq!(r#"{"b":[1],"a":{"d":1,"c":2}}"#, to_json(&value, &opts)?);
+
+        opts.use_tabs = true;
+        assert_eq!(
+            "{\n\t\"b\": [\n\t\t1\n\t],\n\t\"a\": {\n\t\t\"d\": 1,\n\t\t\"c\": 2\n\t}\n}",
+            to_json(&value, &opts)?
+        );
+
+        let mut sorted = value.clone();
+        sort_keys(&mut sorted);
+        opts.use_tabs = false;
+        assert_eq!(r#"{"a":{"c":2,"d":1},"b":[1]}"#, to_json(&sorted, &opts)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_yaml() -> Result<()> {
+        let mut opts = FormatOptions {
+            indent: 4,
+            ..Default::default()
+        };
+        let yaml = to_yaml(&value(), &opts)?;
+        assert_eq!(
+            "name: cffc
+authors:
+    - gopher9527
+    - LittleGuest
+package:
+    version: 0.1.0
+    edition: 2018
+bin:
+    -   name: a
+    -   name: b
+        path: src/b.rs
+",
+            yaml
+        );
+        assert_eq!(value(), serde_yaml::from_str::<Value>(&yaml)?);
+
+        let text = json!({ "script": "echo 1\necho 2\n", "list": [[1, "2"], "a, b"] });
+        let yaml = to_yaml(&text, &opts)?;
+        assert!(yaml.starts_with("script: |\n    echo 1\n    echo 2\n"));
+        assert_eq!(text, serde_yaml::from_str::<Value>(&yaml)?);
+
+        opts.yaml_style = YamlStyle::Flow;
+        let yaml = to_yaml(&text, &opts)?;
+        assert_eq!(
+            "{script: \"echo 1\\necho 2\\n\", list: [[1, '2'], \"a, b\"]}\n",
+            yaml
+        );
+        assert_eq!(text, serde_yaml::from_str::<Value>(&yaml)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_toml() -> Result<()> {
+        let mut opts = FormatOptions::default();
+        let text = to_toml(&value(), &opts)?;
+        assert_eq!(
+            r#"name = "cffc"
+authors = ["gopher9527", "LittleGuest"]
+
+[package]
+version = "0.1.0"
+edition = 2018
+
+[[bin]]
+name = "a"
+
+[[bin]]
+name = "b"
+path = "src/b.rs"
+"#,
+            text
+        );
+        assert_eq!(value(), toml::from_str::<Value>(&text)?);
+
+        opts.toml_tables = TomlTables::Inline;
+        let text = to_toml(&value(), &opts)?;
+        assert!(text.contains(r#"package = { version = "0.1.0", edition = 2018 }"#));
+        assert!(text.contains("bin = [\n  { name = \"a\" },\n"));
+        assert_eq!(value(), toml::from_str::<Value>(&text)?);
+
+        let nested = json!({ "a": { "b": { "c": 1.0 } }, "t": toml::from_str::<Value>("t = 1979-05-27")?["t"] });
+        opts.toml_tables = TomlTables::Table;
+        assert_eq!(
+            "t = 1979-05-27\n\n[a.b]\nc = 1.0\n",
+            to_toml(&nested, &opts)?
+        );
+        assert!(to_toml(&json!([1]), &opts).is_err());
+        assert!(to_toml(&json!({ "a": [null] }), &opts).is_err());
+        Ok(())
+    }
+}
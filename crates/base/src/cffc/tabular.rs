@@ -0,0 +1,142 @@
+//! CSV 与对象数组互转
+//!
+//! 读取时根据首行推断是否为表头：首行各列非空、互不重复且都不是数字时作为表头，
+//! 否则按 `column1`、`column2`… 命名。单元格中的数字和布尔值转为对应类型，空单元格为 null。
+//! 表头中的点号路径还原为嵌套结构，写出时嵌套结构同样展开为点号路径。
+
+use anyhow::{Error, Result};
+use indexmap::IndexSet;
+use serde_json::{Number, Value};
+
+use super::flat;
+
+pub fn from_csv(text: &str) -> Result<Value> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| Error::msg(e.to_string()))?;
+        rows.push(record.iter().map(str::to_string).collect::<Vec<_>>());
+    }
+    let Some(first) = rows.first() else {
+        return Ok(Value::Array(Vec::new()));
+    };
+
+    let (mut headers, data) = if is_header(first) {
+        (first.clone(), &rows[1..])
+    } else {
+        (Vec::new(), &rows[..])
+    };
+    let mut items = Vec::with_capacity(data.len());
+    for row in data {
+        // 数据列多于表头时补上列名
+        while headers.len() < row.len() {
+            headers.push(format!("column{}", headers.len() + 1));
+        }
+        let pairs = headers
+            .iter()
+            .zip(row)
+            .map(|(h, cell)| (h.clone(), infer(cell)));
+        items.push(flat::unflatten(pairs)?);
+    }
+    Ok(Value::Array(items))
+}
+
+fn is_header(row: &[String]) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    row.iter().all(|cell| {
+        let cell = cell.trim();
+        !cell.is_empty() && cell.parse::<f64>().is_err() && seen.insert(cell)
+    })
+}
+
+/// 推断单元格的类型
+fn infer(cell: &str) -> Value {
+    match cell {
+        "" => Value::Null,
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => serde_json::from_str::<Number>(cell)
+            .ok()
+            // 带前导零的编号等保持为字符串
+            .filter(|n| n.to_string() == cell)
+            .map_or_else(|| Value::String(cell.to_string()), Value::Number),
+    }
+}
+
+/// 顶层须为对象数组（单个对象视为一行），表头取所有对象键的并集
+pub fn to_csv(value: &Value) -> Result<String> {
+    let items = match value {
+        Value::Array(items) => items.as_slice(),
+        Value::Object(_) => std::slice::from_ref(value),
+        _ => return Err(Error::msg("CSV 的顶层必须是对象数组")),
+    };
+
+    let mut rows = Vec::with_capacity(items.len());
+    let mut headers = IndexSet::new();
+    for item in items {
+        if !item.is_object() {
+            return Err(Error::msg("CSV 的每一行必须是对象"));
+        }
+        let row = flat::flatten(item)?;
+        headers.extend(row.iter().map(|(key, _)| key.clone()));
+        rows.push(row);
+    }
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&headers)?;
+    for row in rows {
+        let row = row.into_iter().collect::<std::collections::HashMap<_, _>>();
+        writer.write_record(
+            headers
+                .iter()
+                .map(|k| row.get(k).map(String::as_str).unwrap_or_default()),
+        )?;
+    }
+    let buf = writer.into_inner().map_err(|e| Error::msg(e.to_string()))?;
+    String::from_utf8(buf).map_err(|e| Error::msg(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_csv() -> Result<()> {
+        let value = json!([
+            { "id": 1, "name": "Alice", "tags": ["a", "b"], "addr": { "city": "Paris" } },
+            { "id": 2, "name": "Bob, Jr.", "active": true },
+        ]);
+        let text = to_csv(&value)?;
+        assert_eq!(
+            "id,name,tags.0,tags.1,addr.city,active\n1,Alice,a,b,Paris,\n2,\"Bob, Jr.\",,,,true\n",
+            text
+        );
+        assert_eq!(
+            json!([
+                { "id": 1, "name": "Alice", "tags": ["a", "b"], "addr": { "city": "Paris" }, "active": null },
+                { "id": 2, "name": "Bob, Jr.", "tags": [null, null], "addr": { "city": null }, "active": true },
+            ]),
+            from_csv(&text)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_inference() -> Result<()> {
+        assert_eq!(
+            json!([
+                { "column1": 1, "column2": "007", "column3": 1.5 },
+                { "column1": 2, "column2": "x", "column3": null, "column4": "extra" },
+            ]),
+            from_csv("1,007,1.5\n2,x,,extra\n")?
+        );
+        assert_eq!(json!([]), from_csv("")?);
+        assert!(to_csv(&json!([1, 2])).is_err());
+        Ok(())
+    }
+}
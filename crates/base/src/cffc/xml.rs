@@ -0,0 +1,394 @@
+//! XML 与 `serde_json::Value` 互转
+//!
+//! 约定（与 xmltodict 类似）：
+//! - 属性以 `attr_prefix + 属性名` 作为键，如 `@id`
+//! - 同时存在属性或子元素时，文本放在 `text_key` 下，如 `#text`
+//! - 同名的兄弟元素合并为数组
+//! - 只有文本的元素直接转为字符串，空元素转为空字符串
+//! - XML 中的值一律按字符串处理
+
+use anyhow::{Error, Result};
+use quick_xml::{
+    Reader, Writer,
+    events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::FormatOptions;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XmlOptions {
+    /// 属性键前缀；为空时写出 XML 不产生属性
+    pub attr_prefix: String,
+    /// 文本节点键
+    pub text_key: String,
+    /// 根元素名；为空时顶层须是只有一个键的对象，键名即根元素名
+    pub root: String,
+}
+
+impl Default for XmlOptions {
+    fn default() -> Self {
+        Self {
+            attr_prefix: "@".into(),
+            text_key: "#text".into(),
+            root: "root".into(),
+        }
+    }
+}
+
+/// 数组中嵌套数组时，内层元素使用的名称
+const ITEM: &str = "item";
+
+/// 正在解析的元素
+struct Node {
+    name: String,
+    map: Map<String, Value>,
+    text: String,
+}
+
+impl Node {
+    fn new(e: &BytesStart, opts: &XmlOptions) -> Result<Self> {
+        let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+        let mut map = Map::new();
+        for attr in e.attributes() {
+            let attr = attr.map_err(|e| Error::msg(e.to_string()))?;
+            let key = format!(
+                "{}{}",
+                opts.attr_prefix,
+                String::from_utf8_lossy(attr.key.as_ref())
+            );
+            let value = attr
+                .unescape_value()
+                .map_err(|e| Error::msg(e.to_string()))?;
+            map.insert(key, Value::String(value.into_owned()));
+        }
+        Ok(Self {
+            name,
+            map,
+            text: String::new(),
+        })
+    }
+
+    fn into_value(self, opts: &XmlOptions) -> Value {
+        let text = self.text.trim();
+        if self.map.is_empty() {
+            return Value::String(text.to_string());
+        }
+        let mut map = self.map;
+        if !text.is_empty() {
+            map.insert(opts.text_key.clone(), Value::String(text.to_string()));
+        }
+        Value::Object(map)
+    }
+
+    /// 加入子元素，同名元素合并为数组
+    fn push_child(&mut self, name: String, value: Value) {
+        match self.map.get_mut(&name) {
+            Some(Value::Array(items)) => items.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                self.map.insert(name, value);
+            }
+        }
+    }
+}
+
+pub fn from_xml(text: &str, opts: &XmlOptions) -> Result<Value> {
+    let mut reader = Reader::from_str(text);
+    let mut stack: Vec<Node> = Vec::new();
+    let mut root: Option<(String, Value)> = None;
+
+    loop {
+        let event = reader.read_event().map_err(|e| {
+            Error::msg(format!(
+                "XML 解析失败（位置 {}）: {e}",
+                reader.error_position()
+            ))
+        })?;
+        match event {
+            Event::Start(e) => {
+                if root.is_some() {
+                    return Err(Error::msg("XML 只能有一个根元素"));
+                }
+                stack.push(Node::new(&e, opts)?);
+            }
+            Event::Empty(e) => {
+                if root.is_some() {
+                    return Err(Error::msg("XML 只能有一个根元素"));
+                }
+                let node = Node::new(&e, opts)?;
+                let name = node.name.clone();
+                let value = node.into_value(opts);
+                match stack.last_mut() {
+                    Some(parent) => parent.push_child(name, value),
+                    None => root = Some((name, value)),
+                }
+            }
+            Event::End(_) => {
+                let Some(node) = stack.pop() else {
+                    return Err(Error::msg("XML 结束标签不匹配"));
+                };
+                let name = node.name.clone();
+                let value = node.into_value(opts);
+                match stack.last_mut() {
+                    Some(parent) => parent.push_child(name, value),
+                    None => root = Some((name, value)),
+                }
+            }
+            Event::Text(e) => {
+                let text = e.xml_content().map_err(|e| Error::msg(e.to_string()))?;
+                match stack.last_mut() {
+                    Some(node) => node.text.push_str(&text),
+                    None if text.trim().is_empty() => {}
+                    None => return Err(Error::msg("根元素之外存在文本内容")),
+                }
+            }
+            Event::CData(e) => {
+                if let Some(node) = stack.last_mut() {
+                    node.text.push_str(&String::from_utf8_lossy(&e));
+                }
+            }
+            Event::GeneralRef(e) => {
+                let resolved = if let Some(ch) = e
+                    .resolve_char_ref()
+                    .map_err(|e| Error::msg(e.to_string()))?
+                {
+                    ch.to_string()
+                } else {
+                    let name = e.decode().map_err(|e| Error::msg(e.to_string()))?;
+                    quick_xml::escape::resolve_predefined_entity(&name)
+                        .ok_or_else(|| Error::msg(format!("未知的实体引用: &{name};")))?
+                        .to_string()
+                };
+                if let Some(node) = stack.last_mut() {
+                    node.text.push_str(&resolved);
+                }
+            }
+            Event::Eof => break,
+            Event::Decl(_) | Event::Comment(_) | Event::PI(_) | Event::DocType(_) => {}
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(Error::msg("XML 元素未闭合"));
+    }
+    let Some((name, value)) = root else {
+        return Err(Error::msg("XML 缺少根元素"));
+    };
+    if opts.root.is_empty() {
+        let mut map = Map::new();
+        map.insert(name, value);
+        Ok(Value::Object(map))
+    } else {
+        Ok(value)
+    }
+}
+
+pub fn to_xml(value: &Value, opts: &XmlOptions, style: &FormatOptions) -> Result<String> {
+    let (root, value) = if opts.root.is_empty() {
+        match value {
+            Value::Object(map) if map.len() == 1 => {
+                let (k, v) = map.iter().next().expect("map has one entry");
+                (k.as_str(), v)
+            }
+            _ => {
+                return Err(Error::msg("未指定根元素名时，顶层必须是只有一个键的对象"));
+            }
+        }
+    } else {
+        (opts.root.as_str(), value)
+    };
+
+    let mut writer = if style.use_tabs {
+        Writer::new_with_indent(Vec::new(), b'\t', 1)
+    } else if style.indent == 0 {
+        Writer::new(Vec::new())
+    } else {
+        Writer::new_with_indent(Vec::new(), b' ', style.indent as usize)
+    };
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    if let Value::Array(_) = value {
+        // 数组不能直接作为根元素，逐项包成 item
+        check_name(root)?;
+        writer.write_event(Event::Start(BytesStart::new(root)))?;
+        write_element(&mut writer, ITEM, value, opts)?;
+        writer.write_event(Event::End(BytesEnd::new(root)))?;
+    } else {
+        write_element(&mut writer, root, value, opts)?;
+    }
+    String::from_utf8(writer.into_inner()).map_err(|e| Error::msg(e.to_string()))
+}
+
+fn write_element(
+    writer: &mut Writer<Vec<u8>>,
+    name: &str,
+    value: &Value,
+    opts: &XmlOptions,
+) -> Result<()> {
+    check_name(name)?;
+    match value {
+        Value::Array(items) => {
+            // 顶层或嵌套数组：逐项写出同名元素，内层数组包一层 item
+            for item in items {
+                if let Value::Array(_) = item {
+                    writer.write_event(Event::Start(BytesStart::new(name)))?;
+                    write_element(writer, ITEM, item, opts)?;
+                    writer.write_event(Event::End(BytesEnd::new(name)))?;
+                } else {
+                    write_element(writer, name, item, opts)?;
+                }
+            }
+        }
+        Value::Object(map) => {
+            let mut start = BytesStart::new(name);
+            let mut text = None;
+            let mut children = Vec::new();
+            for (k, v) in map {
+                if *k == opts.text_key {
+                    text = Some(scalar_text(v)?);
+                } else if let Some(attr) = k
+                    .strip_prefix(&opts.attr_prefix)
+                    .filter(|_| !opts.attr_prefix.is_empty())
+                {
+                    check_name(attr)?;
+                    start.push_attribute((attr, scalar_text(v)?.as_str()));
+                } else {
+                    children.push((k, v));
+                }
+            }
+
+            if text.is_none() && children.is_empty() {
+                writer.write_event(Event::Empty(start))?;
+                return Ok(());
+            }
+            writer.write_event(Event::Start(start))?;
+            if let Some(text) = text {
+                writer.write_event(Event::Text(BytesText::new(&text)))?;
+            }
+            for (k, v) in children {
+                write_element(writer, k, v, opts)?;
+            }
+            writer.write_event(Event::End(BytesEnd::new(name)))?;
+        }
+        Value::Null => writer.write_event(Event::Empty(BytesStart::new(name)))?,
+        scalar => {
+            writer
+                .create_element(name)
+                .write_text_content(BytesText::new(&scalar_text(scalar)?))?;
+        }
+    }
+    Ok(())
+}
+
+fn scalar_text(value: &Value) -> Result<String> {
+    match value {
+        Value::Null => Ok(String::new()),
+        Value::String(s) => Ok(s.clone()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Array(_) | Value::Object(_) => {
+            Err(Error::msg("属性和文本节点只能是标量值".to_string()))
+        }
+    }
+}
+
+/// 粗略校验 XML 名称，避免生成无法解析的文档
+fn check_name(name: &str) -> Result<()> {
+    let valid = name.chars().enumerate().all(|(i, c)| {
+        c.is_alphabetic()
+            || c == '_'
+            || c == ':'
+            || (i > 0 && (c.is_ascii_digit() || c == '-' || c == '.'))
+    });
+    if name.is_empty() || !valid {
+        return Err(Error::msg(format!("无效的 XML 元素名: {name:?}")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package id="cffc">
+    <name>cffc</name>
+    <authors>gopher9527 &lt;gopher9527@gmail.com&gt;</authors>
+    <authors>LittleGuest</authors>
+    <edition lang="rust">2018</edition>
+    <empty/>
+</package>"#;
+
+    #[test]
+    fn test_from_xml() -> Result<()> {
+        let value = from_xml(XML, &XmlOptions::default())?;
+        assert_eq!(
+            json!({
+                "@id": "cffc",
+                "name": "cffc",
+                "authors": ["gopher9527 <gopher9527@gmail.com>", "LittleGuest"],
+                "edition": { "@lang": "rust", "#text": "2018" },
+                "empty": "",
+            }),
+            value
+        );
+
+        let opts = XmlOptions {
+            root: String::new(),
+            ..Default::default()
+        };
+        let value = from_xml(XML, &opts)?;
+        assert_eq!("cffc", value["package"]["@id"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let opts = XmlOptions {
+            attr_prefix: "_".into(),
+            text_key: "$".into(),
+            root: "config".into(),
+        };
+        let value = json!({
+            "_version": "1",
+            "server": { "host": "localhost", "port": "8080" },
+            "tags": ["a", "b"],
+            "note": { "_lang": "en", "$": "x < y" },
+        });
+        let xml = to_xml(&value, &opts, &FormatOptions::default())?;
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains(r#"<config version="1">"#));
+        assert!(xml.contains("x &lt; y"));
+        assert_eq!(value, from_xml(&xml, &opts)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_cases() -> Result<()> {
+        let opts = XmlOptions::default();
+        let style = FormatOptions {
+            indent: 0,
+            ..Default::default()
+        };
+        assert!(from_xml("<a><b></a>", &opts).is_err());
+        assert!(from_xml("<a/><b/>", &opts).is_err());
+        assert!(to_xml(&json!({ "1a": "x" }), &opts, &style).is_err());
+        assert_eq!(
+            json!({ "item": ["1", "2"] }),
+            from_xml(&to_xml(&json!([1, 2]), &opts, &style)?, &opts)?
+        );
+        let opts = XmlOptions {
+            root: String::new(),
+            ..Default::default()
+        };
+        assert!(to_xml(&json!({ "a": 1, "b": 2 }), &opts, &style).is_err());
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{Error, Result};
+use base64::Engine as _;
 use encoding_rs::{Encoding, GBK, UTF_8, UTF_16BE, UTF_16LE};
 use serde::{Deserialize, Serialize};
 
@@ -44,6 +45,16 @@ pub fn decode_bytes(data: &[u8], charset: &str) -> Result<String> {
 
 // 从不同进制字符串解析为字节向量
 pub fn parse_bytes_from_string(input: &str, input_type: &str) -> Result<Vec<u8>> {
+    if input_type == "base64" {
+        // 兼容有无填充以及 URL 安全字母表
+        let normalized_input = input.replace(char::is_whitespace, "");
+        let normalized_input = normalized_input.trim_end_matches('=');
+        return base64::engine::general_purpose::STANDARD_NO_PAD
+            .decode(normalized_input)
+            .or_else(|_| base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(normalized_input))
+            .map_err(|e| Error::msg(e.to_string()));
+    }
+
     let normalized_input = input.replace(|c: char| !c.is_ascii_alphanumeric(), "");
 
     match input_type {
@@ -163,6 +174,7 @@ pub fn format_bytes_to_string(
                 .join(delimiter);
             Ok(formatted)
         }
+        "base64" => Ok(base64::engine::general_purpose::STANDARD.encode(bytes)),
         _ => Err(Error::msg("Unsupported output type".to_string())),
     }
 }
@@ -1,74 +1,596 @@
-use std::{collections::HashMap, fs::File, io, sync::LazyLock};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::{Error, Result};
-use dashmap::DashMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::hash;
 
 #[derive(Debug, Serialize)]
 pub struct Checksum {}
 
 impl Checksum {
+    /// 计算文件的校验和，`type` 为哈希注册表中的算法名称或别名，如 `sha256`、`sha2_256sum`
     pub async fn sum(r#type: &str, file_path: &str) -> Result<String> {
-        let mut file = File::open(file_path)?;
-        let checksum = match r#type {
-            "md5sum" => {
-                use md5::Digest;
-                let mut md5_hasher = md5::Md5::new();
-                io::copy(&mut file, &mut md5_hasher)?;
-                format!("{:x}", md5_hasher.finalize())
-            }
-            "sha1sum" => {
-                use sha1::Digest;
-                let mut sha1_hasher = sha1::Sha1::new();
-                io::copy(&mut file, &mut sha1_hasher)?;
-                format!("{:x}", sha1_hasher.finalize())
-            }
-            "sha2_224sum" => {
-                use sha2::Digest;
-                let mut sha2_224_hasher = sha2::Sha224::new();
-                io::copy(&mut file, &mut sha2_224_hasher)?;
-                format!("{:x}", sha2_224_hasher.finalize())
-            }
-            "sha2_256sum" => {
-                use sha2::Digest;
-                let mut sha256_hasher = sha2::Sha256::new();
-                io::copy(&mut file, &mut sha256_hasher)?;
-                format!("{:x}", sha256_hasher.finalize())
-            }
-            "sha2_384sum" => {
-                use sha2::Digest;
-                let mut sha2_384_hasher = sha2::Sha384::new();
-                io::copy(&mut file, &mut sha2_384_hasher)?;
-                format!("{:x}", sha2_384_hasher.finalize())
-            }
-            "sha2_512sum" => {
-                use sha2::Digest;
-                let mut sha512_hasher = sha2::Sha512::new();
-                io::copy(&mut file, &mut sha512_hasher)?;
-                format!("{:x}", sha512_hasher.finalize())
-            }
-            "sha3_256sum" => {
-                use sha3::Digest;
-                let mut sha3_256_hasher = sha3::Sha3_256::new();
-                io::copy(&mut file, &mut sha3_256_hasher)?;
-                format!("{:x}", sha3_256_hasher.finalize())
-            }
-            "sha3_384sum" => {
-                use sha3::Digest;
-                let mut sha3_384_hasher = sha3::Sha3_384::new();
-                io::copy(&mut file, &mut sha3_384_hasher)?;
-                format!("{:x}", sha3_384_hasher.finalize())
+        let algorithm = hash::algorithm(r#type)?;
+        let file_path = file_path.to_string();
+        tokio::task::spawn_blocking(move || {
+            let file = File::open(file_path)?;
+            let digest = hash::digest_reader(BufReader::new(file), &[algorithm])?;
+            Ok(hex::encode(digest.concat()))
+        })
+        .await?
+    }
+
+    /// 并行计算 `dir` 下所有文件的校验和，每算完一个文件调用一次 `progress`
+    ///
+    /// 路径相对于 `dir`，以 `/` 分隔并按字典序排列；根目录下与 `manifest_name` 同名的清单文件会被跳过
+    pub async fn manifest(
+        dir: &Path,
+        algorithm: &str,
+        mut progress: impl FnMut(&ManifestProgress),
+    ) -> Result<Vec<ManifestEntry>> {
+        let algorithm = hash::algorithm(algorithm)?;
+        let skip = manifest_name(algorithm.name);
+        let files = list_files(dir)?
+            .into_iter()
+            .filter(|path| *path != skip)
+            .collect::<Vec<_>>();
+
+        let sums = sum_files(dir, algorithm.name, files, &mut progress).await?;
+        sums.into_iter()
+            .map(|(path, checksum)| {
+                checksum.map(|checksum| ManifestEntry {
+                    path,
+                    checksum,
+                    algorithm: Some(algorithm.name.to_string()),
+                })
+            })
+            .collect()
+    }
+
+    /// 按清单校验 `dir` 下的文件，`dir` 为空时使用清单所在目录
+    ///
+    /// GNU 格式的清单不带算法，依次按 `algorithm`、清单文件名和校验和长度推断
+    pub async fn verify(
+        manifest_path: &Path,
+        dir: Option<&Path>,
+        algorithm: Option<&str>,
+        mut progress: impl FnMut(&ManifestProgress),
+    ) -> Result<VerifyReport> {
+        let content = std::fs::read_to_string(manifest_path)?;
+        let dir = match dir {
+            Some(dir) => dir.to_path_buf(),
+            // 只有文件名时 parent 为空路径，使用当前目录
+            None => manifest_path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .unwrap_or(Path::new("."))
+                .to_path_buf(),
+        };
+        let entries = parse_manifest(&content)?;
+
+        // 为每条记录确定算法
+        let fallback = match algorithm {
+            Some(name) => Some(hash::algorithm(name)?.name),
+            None => manifest_path
+                .file_name()
+                .and_then(|name| algorithm_from_file_name(&name.to_string_lossy())),
+        };
+        let mut expected = BTreeMap::new();
+        for entry in entries {
+            let algorithm = match &entry.algorithm {
+                Some(name) => hash::algorithm(name)?.name,
+                None => fallback
+                    .or_else(|| algorithm_from_len(entry.checksum.len()))
+                    .ok_or_else(|| {
+                        Error::msg(format!("无法确定 {} 使用的算法，请指定算法", entry.path))
+                    })?,
+            };
+            expected.insert(
+                normalize_path(&entry.path),
+                (algorithm, entry.checksum.to_lowercase()),
+            );
+        }
+
+        // 清单外的文件，跳过清单本身
+        let manifest_path = manifest_path.canonicalize()?;
+        let extra = list_files(&dir)?
+            .into_iter()
+            .filter(|path| !expected.contains_key(path))
+            .filter(|path| {
+                dir.join(path)
+                    .canonicalize()
+                    .is_ok_and(|path| path != manifest_path)
+            })
+            .collect::<Vec<_>>();
+
+        let mut report = VerifyReport::default();
+        let mut present = Vec::new();
+        for (path, (algorithm, checksum)) in &expected {
+            if dir.join(path).is_file() {
+                present.push((path.clone(), *algorithm));
+            } else {
+                report.entries.push(VerifyEntry {
+                    path: path.clone(),
+                    status: VerifyStatus::Missing,
+                    expected: Some(checksum.clone()),
+                    actual: None,
+                    message: None,
+                });
             }
-            "sha3_512sum" => {
-                use sha3::Digest;
-                let mut sha3_512_hasher = sha3::Sha3_512::new();
-                io::copy(&mut file, &mut sha3_512_hasher)?;
-                format!("{:x}", sha3_512_hasher.finalize())
+        }
+
+        // 清单中可能混用多种算法，按算法分组计算
+        let total = present.len();
+        let mut done = 0;
+        let mut by_algorithm = BTreeMap::<&str, Vec<String>>::new();
+        for (path, algorithm) in present {
+            by_algorithm.entry(algorithm).or_default().push(path);
+        }
+        for (algorithm, files) in by_algorithm {
+            let sums = sum_files(&dir, algorithm, files, &mut |p: &ManifestProgress| {
+                progress(&ManifestProgress {
+                    done: done + p.done,
+                    total,
+                    path: p.path.clone(),
+                })
+            })
+            .await?;
+            done += sums.len();
+            for (path, actual) in sums {
+                let checksum = expected[&path].1.clone();
+                report.entries.push(match actual {
+                    Ok(actual) => VerifyEntry {
+                        status: if actual == checksum {
+                            VerifyStatus::Ok
+                        } else {
+                            VerifyStatus::Modified
+                        },
+                        path,
+                        expected: Some(checksum),
+                        actual: Some(actual),
+                        message: None,
+                    },
+                    Err(e) => VerifyEntry {
+                        path,
+                        status: VerifyStatus::Error,
+                        expected: Some(checksum),
+                        actual: None,
+                        message: Some(e.to_string()),
+                    },
+                });
             }
-            _ => {
-                return Err(Error::msg("不支持的算法".to_string()));
+        }
+
+        report
+            .entries
+            .extend(extra.into_iter().map(|path| VerifyEntry {
+                path,
+                status: VerifyStatus::Extra,
+                expected: None,
+                actual: None,
+                message: None,
+            }));
+        report.entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(report)
+    }
+}
+
+/// 校验清单的格式
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestStyle {
+    /// GNU coreutils 格式：`<校验和>  <文件>`，与 sha256sum 的输出一致
+    #[default]
+    Gnu,
+    /// BSD 格式：`SHA256 (<文件>) = <校验和>`，即 `sha256sum --tag` 的输出
+    Bsd,
+}
+
+impl TryFrom<&str> for ManifestStyle {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_ref() {
+            "gnu" => Ok(Self::Gnu),
+            "bsd" | "tag" => Ok(Self::Bsd),
+            _ => Err(Error::msg(format!("不支持的清单格式: {value}"))),
+        }
+    }
+}
+
+/// 清单中的一条记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// 相对路径，以 `/` 分隔
+    pub path: String,
+    pub checksum: String,
+    /// 算法名称，GNU 格式的清单解析后为空
+    pub algorithm: Option<String>,
+}
+
+/// 每算完一个文件上报一次的进度
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestProgress {
+    pub done: usize,
+    pub total: usize,
+    /// 刚算完的文件
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerifyStatus {
+    /// 校验和一致
+    Ok,
+    /// 校验和不一致
+    Modified,
+    /// 清单中有、目录中没有
+    Missing,
+    /// 目录中有、清单中没有
+    Extra,
+    /// 读取失败
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyEntry {
+    pub path: String,
+    pub status: VerifyStatus,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub message: Option<String>,
+}
+
+/// 清单校验结果，按路径排列
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReport {
+    pub entries: Vec<VerifyEntry>,
+}
+
+impl VerifyReport {
+    pub fn count(&self, status: VerifyStatus) -> usize {
+        self.entries.iter().filter(|e| e.status == status).count()
+    }
+
+    /// 所有文件都一致，且没有多出的文件
+    pub fn is_ok(&self) -> bool {
+        self.entries.iter().all(|e| e.status == VerifyStatus::Ok)
+    }
+}
+
+/// 算法对应的清单文件名，如 `SHA256SUMS`、`MD5SUMS`
+pub fn manifest_name(algorithm: &str) -> String {
+    let tag = hash::algorithm(algorithm)
+        .map(bsd_tag)
+        .unwrap_or_else(|_| algorithm.to_string());
+    format!("{}SUMS", tag.replace(['-', '/'], "").to_uppercase())
+}
+
+/// BSD 格式中的算法标记，与 coreutils `--tag` 一致
+fn bsd_tag(algorithm: hash::Algorithm) -> String {
+    match algorithm.name {
+        "blake2b" => "BLAKE2b".to_string(),
+        name if name.starts_with("sha") && !name.starts_with("sha3") => {
+            algorithm.label.replacen('-', "", 1)
+        }
+        _ => algorithm.label.to_string(),
+    }
+}
+
+/// 把记录写成清单文本，每行以换行结尾。BSD 格式的算法标记取自每条记录的 `algorithm`
+///
+/// 路径中含有 `\` 或换行时按 coreutils 的规则转义，并在行首加 `\`
+pub fn format_manifest(entries: &[ManifestEntry], style: ManifestStyle) -> Result<String> {
+    let mut out = String::new();
+    for entry in entries {
+        let escaped = entry.path.contains(['\\', '\n', '\r']);
+        let path = entry
+            .path
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+        if escaped {
+            out.push('\\');
+        }
+        match style {
+            ManifestStyle::Gnu => out.push_str(&format!("{}  {path}\n", entry.checksum)),
+            ManifestStyle::Bsd => {
+                let algorithm = entry
+                    .algorithm
+                    .as_deref()
+                    .ok_or_else(|| Error::msg(format!("BSD 格式需要算法: {}", entry.path)))?;
+                let tag = bsd_tag(hash::algorithm(algorithm)?);
+                out.push_str(&format!("{tag} ({path}) = {}\n", entry.checksum));
             }
+        }
+    }
+    Ok(out)
+}
+
+/// 解析 GNU 或 BSD 格式的清单，忽略空行和 `#` 开头的注释
+pub fn parse_manifest(content: &str) -> Result<Vec<ManifestEntry>> {
+    let mut entries = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (escaped, line) = match line.strip_prefix('\\') {
+            Some(line) => (true, line),
+            None => (false, line),
         };
-        Ok(checksum)
+        let entry = parse_bsd_line(line)
+            .or_else(|| parse_gnu_line(line))
+            .ok_or_else(|| Error::msg(format!("第 {} 行格式不正确: {line}", i + 1)))?;
+        entries.push(ManifestEntry {
+            path: if escaped {
+                unescape(&entry.path)
+            } else {
+                entry.path
+            },
+            ..entry
+        });
+    }
+    Ok(entries)
+}
+
+/// `<校验和>  <文件>` 或 `<校验和> *<文件>`（二进制模式）
+fn parse_gnu_line(line: &str) -> Option<ManifestEntry> {
+    let (checksum, rest) = line.split_once(' ')?;
+    let path = rest.strip_prefix([' ', '*'])?;
+    if checksum.is_empty() || path.is_empty() || !checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(ManifestEntry {
+        path: path.to_string(),
+        checksum: checksum.to_string(),
+        algorithm: None,
+    })
+}
+
+/// `<算法> (<文件>) = <校验和>`
+fn parse_bsd_line(line: &str) -> Option<ManifestEntry> {
+    let (tag, rest) = line.split_once(" (")?;
+    let (path, checksum) = rest.rsplit_once(") = ")?;
+    if tag.is_empty() || tag.contains(' ') || !checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(ManifestEntry {
+        path: path.to_string(),
+        checksum: checksum.to_string(),
+        algorithm: Some(tag.to_string()),
+    })
+}
+
+fn unescape(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(c) => out.push(c),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// 清单中的路径与 [`list_files`] 的结果对齐：去掉开头的 `./`，分隔符统一为 `/`，
+/// 以兼容 `find . -type f -exec sha256sum {} +` 生成的清单
+fn normalize_path(path: &str) -> String {
+    let path = path.replace(std::path::MAIN_SEPARATOR, "/");
+    let mut path = path.as_str();
+    while let Some(rest) = path.strip_prefix("./") {
+        path = rest.trim_start_matches('/');
+    }
+    path.to_string()
+}
+
+/// 由 `SHA256SUMS`、`md5sums.txt` 这样的文件名推断算法
+fn algorithm_from_file_name(name: &str) -> Option<&'static str> {
+    let stem = name.split('.').next()?.to_lowercase();
+    let tag = stem
+        .strip_suffix("sums")
+        .or_else(|| stem.strip_suffix("sum"))?;
+    match tag {
+        "b2" => Some("blake2b"),
+        "b3" => Some("blake3"),
+        tag => hash::algorithm(tag).ok().map(|algorithm| algorithm.name),
+    }
+}
+
+/// 按十六进制校验和的长度推断常见的 SHA 系列算法
+fn algorithm_from_len(len: usize) -> Option<&'static str> {
+    match len {
+        32 => Some("md5"),
+        40 => Some("sha1"),
+        56 => Some("sha224"),
+        64 => Some("sha256"),
+        96 => Some("sha384"),
+        128 => Some("sha512"),
+        _ => None,
+    }
+}
+
+/// 递归列出 `dir` 下的普通文件，返回以 `/` 分隔的相对路径
+fn list_files(dir: &Path) -> Result<Vec<String>> {
+    if !dir.is_dir() {
+        return Err(Error::msg(format!("目录不存在: {}", dir.display())));
+    }
+    let mut files = BTreeSet::new();
+    let mut stack = vec![PathBuf::new()];
+    while let Some(relative) = stack.pop() {
+        for entry in std::fs::read_dir(dir.join(&relative))? {
+            let entry = entry?;
+            let path = relative.join(entry.file_name());
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                stack.push(path);
+            } else if file_type.is_file() || dir.join(&path).is_file() {
+                let path = path
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.insert(path);
+            }
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// 用 `Checksum::sum` 并行计算一组文件，结果与 `files` 的顺序一致
+async fn sum_files(
+    dir: &Path,
+    algorithm: &str,
+    files: Vec<String>,
+    progress: &mut impl FnMut(&ManifestProgress),
+) -> Result<Vec<(String, Result<String>)>> {
+    let total = files.len();
+    let limit = std::thread::available_parallelism().map_or(4, |n| n.get());
+    let semaphore = Arc::new(Semaphore::new(limit));
+    let mut tasks = JoinSet::new();
+    for (i, path) in files.iter().enumerate() {
+        let semaphore = semaphore.clone();
+        let algorithm = algorithm.to_string();
+        let full_path = dir.join(path).to_string_lossy().to_string();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await?;
+            Ok::<_, Error>((i, Checksum::sum(&algorithm, &full_path).await))
+        });
+    }
+
+    let mut sums = files
+        .into_iter()
+        .map(|path| (path, None))
+        .collect::<Vec<_>>();
+    let mut done = 0;
+    while let Some(result) = tasks.join_next().await {
+        let (i, sum) = result??;
+        done += 1;
+        progress(&ManifestProgress {
+            done,
+            total,
+            path: sums[i].0.clone(),
+        });
+        sums[i].1 = Some(sum);
+    }
+    Ok(sums
+        .into_iter()
+        .map(|(path, sum)| (path, sum.unwrap_or_else(|| Err(Error::msg("未完成")))))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试结束时随 `TempDir` 一起删除
+    fn fixture() -> Result<tempfile::TempDir> {
+        let temp = tempfile::Builder::new()
+            .prefix("toolbox checksum")
+            .tempdir()?;
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("sub"))?;
+        std::fs::write(dir.join("a.txt"), "hello world")?;
+        std::fs::write(dir.join("sub/b.txt"), "abc")?;
+        Ok(temp)
+    }
+
+    #[tokio::test]
+    async fn test_manifest() -> Result<()> {
+        let temp = fixture()?;
+        let mut calls = 0;
+        let mut entries = Checksum::manifest(temp.path(), "sha256", |p| {
+            calls += 1;
+            assert_eq!(2, p.total);
+        })
+        .await?;
+        assert_eq!(2, calls);
+
+        let gnu = format_manifest(&entries, ManifestStyle::Gnu)?;
+        assert_eq!(
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9  a.txt\n\
+             ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  sub/b.txt\n",
+            gnu
+        );
+        let bsd = format_manifest(&entries, ManifestStyle::Bsd)?;
+        assert!(bsd.starts_with("SHA256 (a.txt) = b94d"));
+        assert_eq!("SHA256SUMS", manifest_name("sha256"));
+
+        let parsed = parse_manifest(&bsd)?;
+        assert_eq!(Some("SHA256".to_string()), parsed[0].algorithm);
+        assert_eq!("sub/b.txt", parsed[1].path);
+        assert_eq!(
+            "a b",
+            parse_manifest("5eb63bbbe01eeed093cb22bb8f5acdc3 *a b")?[0].path
+        );
+        assert_eq!(
+            "a\nb",
+            parse_manifest("\\5eb63bbbe01eeed093cb22bb8f5acdc3  a\\nb")?[0].path
+        );
+        assert!(parse_manifest("not a manifest").is_err());
+
+        // BSD 格式的标记只能来自记录本身
+        entries[0].algorithm = None;
+        assert!(format_manifest(&entries, ManifestStyle::Bsd).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify() -> Result<()> {
+        let temp = fixture()?;
+        let dir = temp.path();
+        let entries = Checksum::manifest(dir, "sha256", |_| {}).await?;
+        let manifest = dir.join("SHA256SUMS");
+        let text = format_manifest(&entries, ManifestStyle::Gnu)?;
+        std::fs::write(&manifest, &text)?;
+
+        let report = Checksum::verify(&manifest, None, None, |_| {}).await?;
+        assert!(report.is_ok());
+        assert_eq!(2, report.count(VerifyStatus::Ok));
+
+        // find . -type f -exec sha256sum {} + 生成的路径带 `./`
+        std::fs::write(&manifest, text.replace("  ", "  ./"))?;
+        let report = Checksum::verify(&manifest, None, None, |_| {}).await?;
+        assert!(report.is_ok());
+        assert_eq!("a.txt", report.entries[0].path);
+
+        std::fs::write(dir.join("a.txt"), "changed")?;
+        std::fs::remove_file(dir.join("sub/b.txt"))?;
+        std::fs::write(dir.join("c.txt"), "new")?;
+        let report = Checksum::verify(&manifest, None, None, |_| {}).await?;
+        let statuses = report
+            .entries
+            .iter()
+            .map(|e| (e.path.as_str(), e.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("a.txt", VerifyStatus::Modified),
+                ("c.txt", VerifyStatus::Extra),
+                ("sub/b.txt", VerifyStatus::Missing),
+            ],
+            statuses
+        );
+        assert!(!report.is_ok());
+        Ok(())
     }
 }
@@ -0,0 +1,1031 @@
+//! Cron 表达式解析、说明与触发时间计算
+//!
+//! 支持三种写法：
+//! - Unix：5 段 `分 时 日 月 周`，以及 `@daily` 等别名，日和周都有限制时满足其一即触发
+//! - Quartz：6 段或 7 段 `秒 分 时 日 月 周 [年]`，周 1-7 对应周日到周六，支持 `?`、`L`、`W`、`#`
+//! - systemd `OnCalendar`：`[周] [年-月-日] [时:分[:秒]] [时区]`，以及 `daily` 等别名
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, Weekday};
+use time_tz::{OffsetDateTimeExt, OffsetResult, PrimitiveDateTimeExt, TimeZone, Tz};
+
+use crate::datetime::{self, ZonedTime};
+
+/// 查找触发时间的上限年份，超过后认为不会再触发
+const MAX_YEAR: i32 = 2199;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CronDialect {
+    Unix,
+    Quartz,
+    Systemd,
+}
+
+impl CronDialect {
+    /// 按别名和段数识别
+    pub fn detect(expr: &str) -> Self {
+        let expr = expr.trim();
+        let fields = expr.split_whitespace().count();
+        if expr.starts_with('@') {
+            Self::Unix
+        } else if expr.contains(':') || expr.contains('~') || fields < 5 {
+            Self::Systemd
+        } else if fields == 5 {
+            Self::Unix
+        } else {
+            Self::Quartz
+        }
+    }
+}
+
+impl TryFrom<&str> for CronDialect {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_ref() {
+            "unix" | "crontab" => Ok(Self::Unix),
+            "quartz" => Ok(Self::Quartz),
+            "systemd" | "oncalendar" => Ok(Self::Systemd),
+            _ => Err(Error::msg(format!("不支持的 Cron 格式: {value}"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    /// 0-6 为周日到周六，Unix 的 7 也是周日
+    Weekday,
+    Year,
+}
+
+impl Unit {
+    fn range(self) -> (u32, u32) {
+        match self {
+            Self::Second | Self::Minute => (0, 59),
+            Self::Hour => (0, 23),
+            Self::Day => (1, 31),
+            Self::Month => (1, 12),
+            Self::Weekday => (0, 7),
+            Self::Year => (1970, MAX_YEAR as u32),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Second => "秒",
+            Self::Minute => "分",
+            Self::Hour => "时",
+            Self::Day => "日",
+            Self::Month => "月",
+            Self::Weekday => "周",
+            Self::Year => "年",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Item {
+    Value(u32),
+    Range(u32, u32),
+    /// 起点、终点、步长
+    Step(u32, u32, u32),
+    /// 日：`L` 为 0，`L-n` 为 n，即倒数第 n+1 天
+    Last(u32),
+    /// 日：`nW`，离 n 日最近的工作日
+    NearestWeekday(u32),
+    /// 日：`LW`，最后一个工作日
+    LastWeekday,
+    /// 周：`nL`，当月最后一个周 n
+    LastOf(u32),
+    /// 周：`n#k`，当月第 k 个周 n
+    Nth(u32, u32),
+}
+
+/// 一段表达式，`items` 为空表示不限制（`*` 或 `?`）
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    unit: Unit,
+    items: Vec<Item>,
+}
+
+impl Field {
+    fn any(unit: Unit) -> Self {
+        Self {
+            unit,
+            items: Vec::new(),
+        }
+    }
+
+    fn value(unit: Unit, value: u32) -> Self {
+        Self {
+            unit,
+            items: vec![Item::Value(value)],
+        }
+    }
+
+    fn is_any(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn is_step(&self) -> bool {
+        !self.is_any() && self.items.iter().all(|item| matches!(item, Item::Step(..)))
+    }
+
+    fn single(&self) -> Option<u32> {
+        match self.items[..] {
+            [Item::Value(v)] => Some(v),
+            _ => None,
+        }
+    }
+
+    fn contains(&self, value: u32) -> bool {
+        self.is_any()
+            || self.items.iter().any(|item| match *item {
+                Item::Value(v) => v == value,
+                Item::Range(a, b) => (a..=b).contains(&value),
+                Item::Step(a, b, n) => (a..=b).contains(&value) && (value - a).is_multiple_of(n),
+                _ => false,
+            })
+    }
+
+    fn matches_day(&self, date: Date) -> bool {
+        let last = date.month().length(date.year()) as u32;
+        let day = date.day() as u32;
+        self.contains(day)
+            || self.items.iter().any(|item| match *item {
+                Item::Last(n) => last.checked_sub(n) == Some(day),
+                Item::NearestWeekday(n) => n <= last && nearest_weekday(date, n) == day,
+                Item::LastWeekday => nearest_weekday(date, last) == day,
+                _ => false,
+            })
+    }
+
+    fn matches_weekday(&self, date: Date) -> bool {
+        let weekday = date.weekday().number_days_from_sunday() as u32;
+        let last = date.month().length(date.year());
+        let day = date.day();
+        (self.contains(weekday) || weekday == 0 && self.contains(7))
+            || self.items.iter().any(|item| match *item {
+                Item::LastOf(w) => w % 7 == weekday && day + 7 > last,
+                Item::Nth(w, k) => w % 7 == weekday && (day as u32 - 1) / 7 + 1 == k,
+                _ => false,
+            })
+    }
+}
+
+/// 同一月中离 `n` 日最近的工作日，不跨月
+fn nearest_weekday(date: Date, n: u32) -> u32 {
+    let last = date.month().length(date.year()) as u32;
+    let Ok(target) = date.replace_day(n as u8) else {
+        return 0;
+    };
+    match target.weekday() {
+        Weekday::Saturday if n == 1 => 3,
+        Weekday::Saturday => n - 1,
+        Weekday::Sunday if n == last => n - 2,
+        Weekday::Sunday => n + 1,
+        _ => n,
+    }
+}
+
+/// 解析后的表达式
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cron {
+    pub dialect: CronDialect,
+    /// systemd 表达式末尾指定的时区
+    pub zone: Option<String>,
+    second: Field,
+    minute: Field,
+    hour: Field,
+    day: Field,
+    month: Field,
+    weekday: Field,
+    year: Field,
+}
+
+impl Cron {
+    /// 自动识别写法后解析
+    pub fn parse(expr: &str) -> Result<Self> {
+        Self::parse_as(expr, CronDialect::detect(expr))
+    }
+
+    pub fn parse_as(expr: &str, dialect: CronDialect) -> Result<Self> {
+        let expr = expr.trim();
+        if expr.is_empty() {
+            return Err(Error::msg("表达式为空"));
+        }
+        match dialect {
+            CronDialect::Unix => parse_unix(expr),
+            CronDialect::Quartz => parse_quartz(expr),
+            CronDialect::Systemd => parse_systemd(expr),
+        }
+    }
+
+    /// `after` 之后的 `count` 个触发时间，按 `tz` 的本地时间计算。
+    /// 夏令时跳过的时间不触发，重复的时间只触发较早的一次
+    pub fn next_times(&self, after: OffsetDateTime, tz: &Tz, count: usize) -> Vec<OffsetDateTime> {
+        let local = after.to_timezone(tz);
+        let Ok(start) = PrimitiveDateTime::new(local.date(), local.time()).replace_nanosecond(0)
+        else {
+            return Vec::new();
+        };
+        self.search(start + Duration::SECOND, tz, count)
+    }
+
+    fn search(&self, mut t: PrimitiveDateTime, tz: &Tz, count: usize) -> Vec<OffsetDateTime> {
+        let mut times = Vec::with_capacity(count);
+        while times.len() < count && t.year() <= MAX_YEAR {
+            let date = t.date();
+            if !self.year.contains(date.year() as u32) {
+                t = start_of_year(date.year() + 1);
+            } else if !self.month.contains(date.month() as u32) {
+                t = start_of_month(date);
+            } else if !self.matches_date(date) {
+                t = PrimitiveDateTime::new(date.next_day().unwrap_or(date), Time::MIDNIGHT);
+            } else if !self.hour.contains(t.hour() as u32) {
+                t = t.replace_time(Time::from_hms(t.hour(), 0, 0).unwrap_or(Time::MIDNIGHT))
+                    + Duration::HOUR;
+            } else if !self.minute.contains(t.minute() as u32) {
+                t = t.replace_time(
+                    Time::from_hms(t.hour(), t.minute(), 0).unwrap_or(Time::MIDNIGHT),
+                ) + Duration::MINUTE;
+            } else if !self.second.contains(t.second() as u32) {
+                t += Duration::SECOND;
+            } else {
+                match t.assume_timezone(tz) {
+                    OffsetResult::Some(time) | OffsetResult::Ambiguous(time, _) => times.push(time),
+                    OffsetResult::None => {}
+                }
+                t += Duration::SECOND;
+            }
+        }
+        times
+    }
+
+    fn matches_date(&self, date: Date) -> bool {
+        let day = self.day.matches_day(date);
+        let weekday = self.weekday.matches_weekday(date);
+        // Unix cron 的日和周都有限制时满足其一即可
+        if self.dialect == CronDialect::Unix && !self.day.is_any() && !self.weekday.is_any() {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+
+    /// 中文说明
+    pub fn describe_zh(&self) -> String {
+        Describer {
+            cron: self,
+            en: false,
+        }
+        .describe()
+    }
+
+    /// 英文说明
+    pub fn describe_en(&self) -> String {
+        Describer {
+            cron: self,
+            en: true,
+        }
+        .describe()
+    }
+
+    /// 秒是否需要出现在说明中
+    fn shows_second(&self) -> bool {
+        self.dialect != CronDialect::Unix && self.second.single() != Some(0)
+    }
+}
+
+/// 表达式的说明和接下来的触发时间
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CronSchedule {
+    pub dialect: CronDialect,
+    pub description_zh: String,
+    pub description_en: String,
+    /// 计算触发时间使用的时区，systemd 表达式指定了时区时以表达式为准
+    pub zone: String,
+    pub next: Vec<ZonedTime>,
+}
+
+/// `dialect` 为 `None` 时自动识别写法，表达式永远不会触发时（如 2 月 31 日）报错
+pub fn schedule(
+    expr: &str,
+    dialect: Option<CronDialect>,
+    zone: &str,
+    count: usize,
+) -> Result<CronSchedule> {
+    let cron = match dialect {
+        Some(dialect) => Cron::parse_as(expr, dialect)?,
+        None => Cron::parse(expr)?,
+    };
+    let zone = cron.zone.as_deref().unwrap_or(zone);
+    let tz = datetime::zone(zone)?;
+    let next = cron
+        .next_times(datetime::now(), tz, count)
+        .into_iter()
+        .map(|t| datetime::zoned(t, zone))
+        .collect::<Result<Vec<_>>>()?;
+    if count > 0 && next.is_empty() {
+        return Err(Error::msg(format!(
+            "表达式永远不会触发：到 {MAX_YEAR} 年都没有匹配的时间"
+        )));
+    }
+    Ok(CronSchedule {
+        dialect: cron.dialect,
+        description_zh: cron.describe_zh(),
+        description_en: cron.describe_en(),
+        zone: tz.name().to_string(),
+        next,
+    })
+}
+
+fn start_of_year(year: i32) -> PrimitiveDateTime {
+    Date::from_calendar_date(year, Month::January, 1)
+        .map(|date| date.midnight())
+        .unwrap_or(PrimitiveDateTime::MAX)
+}
+
+fn start_of_month(date: Date) -> PrimitiveDateTime {
+    match date.month() {
+        Month::December => start_of_year(date.year() + 1),
+        month => Date::from_calendar_date(date.year(), month.next(), 1)
+            .map(|date| date.midnight())
+            .unwrap_or(PrimitiveDateTime::MAX),
+    }
+}
+
+fn parse_unix(expr: &str) -> Result<Cron> {
+    let expr = match expr.to_lowercase().as_ref() {
+        "@yearly" | "@annually" => "0 0 1 1 *",
+        "@monthly" => "0 0 1 * *",
+        "@weekly" => "0 0 * * 0",
+        "@daily" | "@midnight" => "0 0 * * *",
+        "@hourly" => "0 * * * *",
+        alias if alias.starts_with('@') => {
+            return Err(Error::msg(format!("不支持的别名: {expr}")));
+        }
+        _ => expr,
+    };
+    let parts = expr.split_whitespace().collect::<Vec<_>>();
+    let [minute, hour, day, month, weekday] = parts[..] else {
+        return Err(Error::msg(format!(
+            "Unix cron 应为 5 段，实际为 {} 段",
+            parts.len()
+        )));
+    };
+    Ok(Cron {
+        dialect: CronDialect::Unix,
+        zone: None,
+        second: Field::value(Unit::Second, 0),
+        minute: parse_field(minute, Unit::Minute, Syntax::Unix)?,
+        hour: parse_field(hour, Unit::Hour, Syntax::Unix)?,
+        day: parse_field(day, Unit::Day, Syntax::Unix)?,
+        month: parse_field(month, Unit::Month, Syntax::Unix)?,
+        weekday: parse_field(weekday, Unit::Weekday, Syntax::Unix)?,
+        year: Field::any(Unit::Year),
+    })
+}
+
+fn parse_quartz(expr: &str) -> Result<Cron> {
+    let parts = expr.split_whitespace().collect::<Vec<_>>();
+    let (second, minute, hour, day, month, weekday, year) = match parts[..] {
+        [s, m, h, d, mo, w] => (s, m, h, d, mo, w, "*"),
+        [s, m, h, d, mo, w, y] => (s, m, h, d, mo, w, y),
+        _ => {
+            return Err(Error::msg(format!(
+                "Quartz 表达式应为 6 或 7 段，实际为 {} 段",
+                parts.len()
+            )));
+        }
+    };
+    if day != "?" && weekday != "?" && day != "*" && weekday != "*" {
+        return Err(Error::msg("Quartz 表达式中日和周必须有一个为 ?"));
+    }
+    Ok(Cron {
+        dialect: CronDialect::Quartz,
+        zone: None,
+        second: parse_field(second, Unit::Second, Syntax::Quartz)?,
+        minute: parse_field(minute, Unit::Minute, Syntax::Quartz)?,
+        hour: parse_field(hour, Unit::Hour, Syntax::Quartz)?,
+        day: parse_field(day, Unit::Day, Syntax::Quartz)?,
+        month: parse_field(month, Unit::Month, Syntax::Quartz)?,
+        weekday: parse_field(weekday, Unit::Weekday, Syntax::Quartz)?,
+        year: parse_field(year, Unit::Year, Syntax::Quartz)?,
+    })
+}
+
+fn parse_systemd(expr: &str) -> Result<Cron> {
+    let expr = match expr.to_lowercase().as_ref() {
+        "minutely" => "*-*-* *:*:00",
+        "hourly" => "*-*-* *:00:00",
+        "daily" => "*-*-* 00:00:00",
+        "weekly" => "Mon *-*-* 00:00:00",
+        "monthly" => "*-*-01 00:00:00",
+        "quarterly" => "*-01,04,07,10-01 00:00:00",
+        "semiannually" | "semi-annually" => "*-01,07-01 00:00:00",
+        "yearly" | "annually" => "*-01-01 00:00:00",
+        _ => expr,
+    };
+    let mut cron = Cron {
+        dialect: CronDialect::Systemd,
+        zone: None,
+        second: Field::value(Unit::Second, 0),
+        minute: Field::value(Unit::Minute, 0),
+        hour: Field::value(Unit::Hour, 0),
+        day: Field::any(Unit::Day),
+        month: Field::any(Unit::Month),
+        weekday: Field::any(Unit::Weekday),
+        year: Field::any(Unit::Year),
+    };
+    let mut tokens = expr.split_whitespace().peekable();
+    if let Some(token) = tokens.next_if(|t| !t.contains([':', '-', '~', '*']) && !is_zone(t)) {
+        cron.weekday = parse_field(token, Unit::Weekday, Syntax::Systemd)?;
+    }
+    if let Some(token) = tokens.next_if(|t| !t.contains(':') && t.contains(['-', '~', '*'])) {
+        let (rest, day) = match token.split_once('~') {
+            Some((rest, last)) => {
+                let n = parse_number(last, Unit::Day, Syntax::Systemd)?;
+                (rest, vec![Item::Last(n.saturating_sub(1))])
+            }
+            None => {
+                let (rest, day) = token.rsplit_once('-').unwrap_or(("", token));
+                (rest, parse_field(day, Unit::Day, Syntax::Systemd)?.items)
+            }
+        };
+        cron.day.items = day;
+        let (year, month) = rest.rsplit_once('-').unwrap_or(("*", rest));
+        cron.month = parse_field(month, Unit::Month, Syntax::Systemd)?;
+        cron.year = parse_field(year, Unit::Year, Syntax::Systemd)?;
+    }
+    if let Some(token) = tokens.next_if(|t| t.contains(':')) {
+        let parts = token.split(':').collect::<Vec<_>>();
+        let (hour, minute, second) = match parts[..] {
+            [h, m] => (h, m, "00"),
+            [h, m, s] => (h, m, s),
+            _ => return Err(Error::msg(format!("时间格式无效: {token}"))),
+        };
+        cron.hour = parse_field(hour, Unit::Hour, Syntax::Systemd)?;
+        cron.minute = parse_field(minute, Unit::Minute, Syntax::Systemd)?;
+        cron.second = parse_field(second, Unit::Second, Syntax::Systemd)?;
+    }
+    if let Some(token) = tokens.next() {
+        if !is_zone(token) {
+            return Err(Error::msg(format!("无法识别的部分: {token}")));
+        }
+        cron.zone = Some(token.to_string());
+    }
+    if let Some(token) = tokens.next() {
+        return Err(Error::msg(format!("无法识别的部分: {token}")));
+    }
+    Ok(cron)
+}
+
+fn is_zone(token: &str) -> bool {
+    datetime::zone(token).is_ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Syntax {
+    Unix,
+    Quartz,
+    Systemd,
+}
+
+fn parse_field(text: &str, unit: Unit, syntax: Syntax) -> Result<Field> {
+    let mut items = Vec::new();
+    for part in text.split(',') {
+        match part {
+            "*" => return Ok(Field::any(unit)),
+            "?" if syntax == Syntax::Quartz && matches!(unit, Unit::Day | Unit::Weekday) => {
+                return Ok(Field::any(unit));
+            }
+            _ => items.push(parse_item(part, unit, syntax)?),
+        }
+    }
+    Ok(Field { unit, items })
+}
+
+fn parse_item(part: &str, unit: Unit, syntax: Syntax) -> Result<Item> {
+    let (min, max) = unit.range();
+    let upper = part.to_uppercase();
+    if syntax == Syntax::Quartz {
+        match (unit, upper.as_str()) {
+            (Unit::Day, "L") => return Ok(Item::Last(0)),
+            (Unit::Day, "LW") => return Ok(Item::LastWeekday),
+            (Unit::Day, offset) if offset.starts_with("L-") => {
+                return Ok(Item::Last(parse_number(&offset[2..], Unit::Day, syntax)?));
+            }
+            (Unit::Day, day) if day.ends_with('W') => {
+                let day = parse_number(&day[..day.len() - 1], Unit::Day, syntax)?;
+                return Ok(Item::NearestWeekday(day));
+            }
+            (Unit::Weekday, "L") => return Ok(Item::Value(6)),
+            (Unit::Weekday, weekday) if weekday.ends_with('L') => {
+                let weekday = parse_number(&weekday[..weekday.len() - 1], unit, syntax)?;
+                return Ok(Item::LastOf(weekday));
+            }
+            (Unit::Weekday, nth) if nth.contains('#') => {
+                let (weekday, k) = nth.split_once('#').unwrap_or_default();
+                let k = k
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|k| (1..=5).contains(k))
+                    .ok_or_else(|| Error::msg(format!("# 后应为 1-5: {part}")))?;
+                return Ok(Item::Nth(parse_number(weekday, unit, syntax)?, k));
+            }
+            _ => {}
+        }
+    }
+
+    let range_sep = if syntax == Syntax::Systemd { ".." } else { "-" };
+    let (range, step) = match part.split_once('/') {
+        Some((range, step)) => {
+            let step = step
+                .parse::<u32>()
+                .ok()
+                .filter(|s| *s > 0)
+                .ok_or_else(|| Error::msg(format!("步长无效: {part}")))?;
+            (range, Some(step))
+        }
+        None => (part, None),
+    };
+    let (start, end) = match range {
+        "*" => (min, max),
+        range => match range.split_once(range_sep) {
+            Some((a, b)) => (
+                parse_number(a, unit, syntax)?,
+                parse_number(b, unit, syntax)?,
+            ),
+            None => {
+                let v = parse_number(range, unit, syntax)?;
+                (v, if step.is_some() { max } else { v })
+            }
+        },
+    };
+    if start > end {
+        return Err(Error::msg(format!("范围起点大于终点: {part}")));
+    }
+    Ok(match step {
+        Some(step) => Item::Step(start, end, step),
+        None if start == end => Item::Value(start),
+        None => Item::Range(start, end),
+    })
+}
+
+fn parse_name(text: &str, unit: Unit) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ];
+    const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+    let text = text.to_uppercase();
+    let prefix = text.get(..3)?;
+    match unit {
+        Unit::Month => MONTHS
+            .iter()
+            .position(|m| *m == prefix)
+            .map(|i| i as u32 + 1),
+        Unit::Weekday => WEEKDAYS.iter().position(|w| *w == prefix).map(|i| i as u32),
+        _ => None,
+    }
+}
+
+fn parse_number(text: &str, unit: Unit, syntax: Syntax) -> Result<u32> {
+    let (min, max) = unit.range();
+    if let Some(value) = parse_name(text, unit) {
+        return Ok(value);
+    }
+    let value = text.parse::<u32>().map_err(|_| {
+        if syntax != Syntax::Quartz && text.to_uppercase().contains(['L', 'W', '#']) {
+            Error::msg(format!("L、W、# 仅 Quartz 表达式支持: {text}"))
+        } else {
+            Error::msg(format!("{}字段的值无效: {text}", unit.label()))
+        }
+    })?;
+    // Quartz 的周 1-7 对应周日到周六
+    let (value, min, max) = match (unit, syntax) {
+        (Unit::Weekday, Syntax::Quartz) if (1..=7).contains(&value) => (value - 1, 0, 6),
+        (Unit::Weekday, Syntax::Quartz) => (value, 1, 7),
+        (Unit::Weekday, Syntax::Systemd) => (value, 0, 6),
+        _ => (value, min, max),
+    };
+    if !(min..=max).contains(&value) {
+        return Err(Error::msg(format!(
+            "{}字段的值 {text} 超出范围 {min}-{max}",
+            unit.label()
+        )));
+    }
+    Ok(value)
+}
+
+struct Describer<'a> {
+    cron: &'a Cron,
+    en: bool,
+}
+
+impl Describer<'_> {
+    fn describe(&self) -> String {
+        let cron = self.cron;
+        let time = self.time();
+        let mut dates = Vec::new();
+        if !cron.year.is_any() {
+            dates.push(self.field(&cron.year));
+        }
+        if !cron.month.is_any() {
+            dates.push(self.field(&cron.month));
+        }
+        let day = (!cron.day.is_any()).then(|| self.field(&cron.day));
+        let weekday = (!cron.weekday.is_any()).then(|| self.field(&cron.weekday));
+        let either = cron.dialect == CronDialect::Unix;
+        match (day, weekday) {
+            (Some(day), Some(weekday)) if either => {
+                dates.push(if self.en {
+                    format!("{day} or {weekday}")
+                } else {
+                    format!("{day}或{weekday}")
+                });
+            }
+            (day, weekday) => dates.extend(day.into_iter().chain(weekday)),
+        }
+
+        if self.en {
+            // 英文从小到大：At 09:30, Monday through Friday
+            dates.reverse();
+            let mut parts = vec![time];
+            parts.extend(dates);
+            let text = parts.join(", ");
+            let mut chars = text.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        } else {
+            if dates.is_empty() && self.clock().is_some() {
+                dates.push("每天".to_string());
+            }
+            let separator = if self.clock().is_some() { " " } else { "，" };
+            if dates.is_empty() {
+                time
+            } else {
+                format!("{}{separator}{time}", dates.join("，"))
+            }
+        }
+    }
+
+    /// 时、分、秒都是单个值时的 `HH:MM[:SS]`
+    fn clock(&self) -> Option<String> {
+        let cron = self.cron;
+        let hour = cron.hour.single()?;
+        let minute = cron.minute.single()?;
+        if cron.shows_second() {
+            let second = cron.second.single()?;
+            Some(format!("{hour:02}:{minute:02}:{second:02}"))
+        } else {
+            Some(format!("{hour:02}:{minute:02}"))
+        }
+    }
+
+    fn time(&self) -> String {
+        if let Some(clock) = self.clock() {
+            return if self.en {
+                format!("at {clock}")
+            } else {
+                clock
+            };
+        }
+        let cron = self.cron;
+        let mut fields = Vec::new();
+        if cron.shows_second() {
+            fields.push(&cron.second);
+        }
+        fields.push(&cron.minute);
+        fields.push(&cron.hour);
+
+        // 最小的段不限制时为每秒或每分钟，更大的段只在比它小的段有限制时说明，
+        // 例如 `0 * * * *` 为每小时的第 0 分钟，`*/5 * * * *` 为每 5 分钟
+        let mut parts = Vec::new();
+        for (i, field) in fields.iter().enumerate() {
+            if !field.is_any() {
+                parts.push(self.field(field));
+            } else if i == 0 || !fields[i - 1].is_any() && !fields[i - 1].is_step() {
+                parts.push(self.every(field.unit));
+            }
+        }
+        if !self.en {
+            parts.reverse();
+        }
+        parts.join(if self.en { ", " } else { "，" })
+    }
+
+    fn every(&self, unit: Unit) -> String {
+        let (zh, en) = match unit {
+            Unit::Second => ("每秒", "every second"),
+            Unit::Minute => ("每分钟", "every minute"),
+            Unit::Hour => ("每小时", "every hour"),
+            Unit::Day => ("每天", "every day"),
+            Unit::Month => ("每月", "every month"),
+            Unit::Weekday => ("每天", "every day"),
+            Unit::Year => ("每年", "every year"),
+        };
+        if self.en { en } else { zh }.to_string()
+    }
+
+    fn field(&self, field: &Field) -> String {
+        let items = field
+            .items
+            .iter()
+            .map(|item| self.item(field.unit, *item))
+            .collect::<Vec<_>>();
+        let list = items.join(if self.en { ", " } else { "、" });
+        // 只有步长时已经是完整的说明，例如每 15 分钟
+        if field
+            .items
+            .iter()
+            .all(|item| matches!(item, Item::Step(..)))
+        {
+            return list;
+        }
+        let month_any = self.cron.month.is_any();
+        if self.en {
+            match field.unit {
+                Unit::Second => format!("at second {list}"),
+                Unit::Minute => format!("at minute {list}"),
+                Unit::Hour => format!("past hour {list}"),
+                Unit::Day => format!("on {list} of the month"),
+                Unit::Month | Unit::Year => format!("in {list}"),
+                Unit::Weekday => format!("on {list}"),
+            }
+        } else {
+            let monthly = field
+                .items
+                .iter()
+                .any(|item| matches!(item, Item::LastOf(..) | Item::Nth(..)));
+            match field.unit {
+                Unit::Second => format!("第 {list} 秒"),
+                Unit::Minute => format!("第 {list} 分钟"),
+                Unit::Hour => format!("{list} 点"),
+                // 数字前加空格：每月 1 日、每月最后一天
+                Unit::Day if month_any && list.starts_with(|c: char| c.is_ascii_digit()) => {
+                    format!("每月 {list}")
+                }
+                Unit::Day if month_any => format!("每月{list}"),
+                Unit::Weekday if month_any && monthly => format!("每月{list}"),
+                Unit::Weekday if !monthly => format!("每{list}"),
+                _ => list,
+            }
+        }
+    }
+
+    fn item(&self, unit: Unit, item: Item) -> String {
+        let value = |v: u32| self.value(unit, v);
+        let en = self.en;
+        match item {
+            Item::Value(v) => value(v),
+            Item::Range(a, b) if en => format!("{} through {}", value(a), value(b)),
+            Item::Range(a, b) => match unit {
+                Unit::Weekday => format!("{}到{}", value(a), value(b)),
+                _ => format!("{} 到 {}", value(a), value(b)),
+            },
+            Item::Step(a, b, n) => {
+                let (min, max) = unit.range();
+                let max = if unit == Unit::Weekday { 6 } else { max };
+                let (unit_zh, unit_en) = match unit {
+                    Unit::Second => ("秒", "seconds"),
+                    Unit::Minute => ("分钟", "minutes"),
+                    Unit::Hour => ("小时", "hours"),
+                    Unit::Day => ("天", "days"),
+                    Unit::Month => ("个月", "months"),
+                    Unit::Weekday => ("天", "days"),
+                    Unit::Year => ("年", "years"),
+                };
+                let every = if en {
+                    format!("every {n} {unit_en}")
+                } else {
+                    format!("每 {n} {unit_zh}")
+                };
+                if a == min && b >= max {
+                    every
+                } else if b >= max && en {
+                    format!("{every} starting at {}", value(a))
+                } else if b >= max {
+                    format!("从{}起{every}", value(a))
+                } else if en {
+                    format!("{every} from {} through {}", value(a), value(b))
+                } else {
+                    format!("{} 到 {} {every}", value(a), value(b))
+                }
+            }
+            Item::Last(0) if en => "the last day".to_string(),
+            Item::Last(0) => "最后一天".to_string(),
+            Item::Last(n) if en => format!("{n} days before the last day"),
+            Item::Last(n) => format!("倒数第 {} 天", n + 1),
+            Item::NearestWeekday(n) if en => format!("the weekday nearest day {n}"),
+            Item::NearestWeekday(n) => format!("离 {n} 日最近的工作日"),
+            Item::LastWeekday if en => "the last weekday".to_string(),
+            Item::LastWeekday => "最后一个工作日".to_string(),
+            Item::LastOf(w) if en => format!("the last {}", value(w)),
+            Item::LastOf(w) => format!("最后一个{}", value(w)),
+            Item::Nth(w, k) if en => {
+                let ordinal = ["first", "second", "third", "fourth", "fifth"];
+                format!("the {} {}", ordinal[k as usize - 1], value(w))
+            }
+            Item::Nth(w, k) => format!("第 {k} 个{}", value(w)),
+        }
+    }
+
+    fn value(&self, unit: Unit, v: u32) -> String {
+        const MONTHS: [&str; 12] = [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ];
+        const WEEKDAYS_EN: [&str; 7] = [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ];
+        const WEEKDAYS_ZH: [&str; 7] = ["周日", "周一", "周二", "周三", "周四", "周五", "周六"];
+        match (unit, self.en) {
+            (Unit::Month, true) => MONTHS[v as usize - 1].to_string(),
+            (Unit::Weekday, true) => WEEKDAYS_EN[v as usize % 7].to_string(),
+            (Unit::Weekday, false) => WEEKDAYS_ZH[v as usize % 7].to_string(),
+            (_, true) if unit == Unit::Day => format!("day {v}"),
+            (_, true) => v.to_string(),
+            (Unit::Second | Unit::Minute | Unit::Hour, false) => v.to_string(),
+            (Unit::Day, false) => format!("{v} 日"),
+            (Unit::Month, false) => format!("{v} 月"),
+            (Unit::Year, false) => format!("{v} 年"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn next(expr: &str, after: OffsetDateTime, zone: &str, count: usize) -> Result<Vec<String>> {
+        let tz = datetime::zone(zone)?;
+        Cron::parse(expr)?
+            .next_times(after, tz, count)
+            .into_iter()
+            .map(|t| {
+                let zoned = datetime::zoned(t, zone)?;
+                Ok(format!("{} {}", zoned.datetime, zoned.offset))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unix() -> Result<()> {
+        let after = datetime!(2024-01-31 10:00 UTC);
+        assert_eq!(
+            vec!["2024-01-31 10:15:00 +00:00", "2024-01-31 10:30:00 +00:00",],
+            next("*/15 * * * *", after, "UTC", 2)?
+        );
+        // 日和周都有限制时满足其一：1 日或周五
+        assert_eq!(
+            vec![
+                "2024-02-01 09:00:00 +08:00",
+                "2024-02-02 09:00:00 +08:00",
+                "2024-02-09 09:00:00 +08:00",
+            ],
+            next("0 9 1 * FRI", after, "Asia/Shanghai", 3)?
+        );
+        assert_eq!(
+            vec!["2024-02-04 00:00:00 +00:00"],
+            next("@weekly", after, "UTC", 1)?
+        );
+        // 2 月 30 日不存在
+        assert!(next("0 0 30 2 *", after, "UTC", 1)?.is_empty());
+        assert!(schedule("0 0 31 2 *", None, "UTC", 1).is_err());
+
+        assert!(Cron::parse("60 * * * *").is_err());
+        assert!(Cron::parse("0 0 L * *").is_err());
+        assert!(Cron::parse("5-1 * * * *").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_quartz() -> Result<()> {
+        let after = datetime!(2024-01-01 00:00 UTC);
+        assert_eq!(
+            vec!["2024-01-31 18:00:00 +00:00", "2024-02-29 18:00:00 +00:00",],
+            next("0 0 18 L * ?", after, "UTC", 2)?
+        );
+        // 每月第二个周一，1 月 8 日、2 月 12 日
+        assert_eq!(
+            vec!["2024-01-08 12:00:30 +00:00", "2024-02-12 12:00:30 +00:00",],
+            next("30 0 12 ? * MON#2", after, "UTC", 2)?
+        );
+        // 6L 为最后一个周五，2024-03-29；15W 为 3 月 15 日（周五）
+        assert_eq!(
+            vec!["2024-03-29 00:00:00 +00:00"],
+            next("0 0 0 ? 3 6L 2024", after, "UTC", 1)?
+        );
+        assert_eq!(
+            vec!["2024-06-14 00:00:00 +00:00"],
+            next("0 0 0 15W 6 ?", after, "UTC", 1)?
+        );
+        assert!(Cron::parse("0 0 12 1 * MON").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_systemd() -> Result<()> {
+        let after = datetime!(2024-03-09 12:00 UTC);
+        assert_eq!(
+            vec!["2024-03-11 09:30:00 -04:00", "2024-03-12 09:30:00 -04:00",],
+            next("Mon..Fri *-*-* 09:30", after, "America/New_York", 2)?
+        );
+        assert_eq!(
+            vec!["2024-04-01 00:00:00 +00:00"],
+            next("quarterly", after, "UTC", 1)?
+        );
+        assert_eq!(
+            vec!["2025-02-26 00:00:00 +00:00"],
+            next("*-02~03", after, "UTC", 1)?
+        );
+        let cron = Cron::parse("*-*-* 02:30:00 Asia/Tokyo")?;
+        assert_eq!(Some("Asia/Tokyo".to_string()), cron.zone);
+        // 纽约 2024-03-10 02:30 因夏令时不存在，跳过
+        assert_eq!(
+            vec!["2024-03-11 02:30:00 -04:00",],
+            next(
+                "*-*-* 02:30",
+                datetime!(2024-03-10 00:00 UTC),
+                "America/New_York",
+                1
+            )?
+        );
+        assert!(Cron::parse("Mon *-*-* 25:00").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe() -> Result<()> {
+        for (expr, zh, en) in [
+            ("* * * * *", "每分钟", "Every minute"),
+            ("*/5 * * * *", "每 5 分钟", "Every 5 minutes"),
+            ("0 * * * *", "每小时，第 0 分钟", "At minute 0, every hour"),
+            (
+                "30 9 * * 1-5",
+                "每周一到周五 09:30",
+                "At 09:30, on Monday through Friday",
+            ),
+            (
+                "*/15 9-17 * * *",
+                "9 到 17 点，每 15 分钟",
+                "Every 15 minutes, past hour 9 through 17",
+            ),
+            (
+                "0 0 1,15 * *",
+                "每月 1 日、15 日 00:00",
+                "At 00:00, on day 1, day 15 of the month",
+            ),
+            (
+                "0 0 12 ? * 2#1",
+                "每月第 1 个周一 12:00",
+                "At 12:00, on the first Monday",
+            ),
+            (
+                "0 0 0 L 2 ?",
+                "2 月，最后一天 00:00",
+                "At 00:00, on the last day of the month, in February",
+            ),
+            (
+                "0 0 0 L * ?",
+                "每月最后一天 00:00",
+                "At 00:00, on the last day of the month",
+            ),
+            ("daily", "每天 00:00", "At 00:00"),
+        ] {
+            let cron = Cron::parse(expr)?;
+            assert_eq!(zh, cron.describe_zh(), "{expr}");
+            assert_eq!(en, cron.describe_en(), "{expr}");
+        }
+        Ok(())
+    }
+}
@@ -1,25 +1,394 @@
+//! 时间戳与时间字符串互转
+//!
+//! 时间戳按位数识别单位：不超过 11 位为秒，14 位为毫秒，17 位为微秒，更长为纳秒。
+//! 时间字符串依次尝试 RFC 3339、RFC 2822、ISO 8601、HTTP-date，
+//! 最后按 `yyyy-MM-dd HH:mm:ss` 等不带时区的格式解析，视为输入时区的本地时间。
+
 use anyhow::{Error, Result};
-use time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
+use time::{
+    Date, OffsetDateTime, PrimitiveDateTime, UtcOffset,
+    format_description::{
+        BorrowedFormatItem,
+        well_known::{Iso8601, Rfc2822, Rfc3339},
+    },
+    macros::format_description,
+};
+use time_tz::{
+    Offset, OffsetDateTimeExt, OffsetResult, PrimitiveDateTimeExt, TimeZone, Tz, timezones,
+};
 
 pub fn now() -> OffsetDateTime {
     time::OffsetDateTime::now_utc()
 }
 
-/// 时间字符串转时间戳（毫秒）
+/// 时间字符串转时间戳（秒），不带时区的时间视为 UTC
 pub fn str_to_timestamp(t: &str) -> Result<i64> {
-    use time::format_description::well_known::Iso8601;
-    let offset =
-        OffsetDateTime::parse(t, &Iso8601::DEFAULT).map_err(|e| Error::msg(e.to_string()))?;
-    Ok(offset.unix_timestamp())
+    Ok(parse_datetime(t, timezones::db::UTC)?.unix_timestamp())
 }
 
-/// 时间戳（毫秒）转时间字符串
+/// 时间戳（秒）转 UTC 时间字符串
 pub fn timestamp_to_str(t: i64) -> Result<String> {
     let datetime = OffsetDateTime::from_unix_timestamp(t).map_err(|e| Error::msg(e.to_string()))?;
-    let format = time::format_description::parse("[year]-[month]-[day] [hour]:[minute]:[second]")
-        .map_err(|e| Error::msg(e.to_string()))?;
-    let s = datetime
-        .format(&format)
+    format_local(datetime)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl TimeUnit {
+    /// 按整数部分的位数推断单位
+    pub fn detect(timestamp: &str) -> Self {
+        match timestamp.trim_start_matches('-').len() {
+            0..=11 => Self::Seconds,
+            12..=14 => Self::Milliseconds,
+            15..=17 => Self::Microseconds,
+            _ => Self::Nanoseconds,
+        }
+    }
+
+    fn nanos(self) -> i128 {
+        match self {
+            Self::Seconds => 1_000_000_000,
+            Self::Milliseconds => 1_000_000,
+            Self::Microseconds => 1_000,
+            Self::Nanoseconds => 1,
+        }
+    }
+}
+
+impl TryFrom<&str> for TimeUnit {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_ref() {
+            "s" | "sec" | "seconds" => Ok(Self::Seconds),
+            "ms" | "millis" | "milliseconds" => Ok(Self::Milliseconds),
+            "us" | "µs" | "micros" | "microseconds" => Ok(Self::Microseconds),
+            "ns" | "nanos" | "nanoseconds" => Ok(Self::Nanoseconds),
+            _ => Err(Error::msg(format!("不支持的时间戳单位: {value}"))),
+        }
+    }
+}
+
+/// 同一时刻在某个时区的表示
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZonedTime {
+    pub zone: String,
+    /// `yyyy-MM-dd HH:mm:ss`，有小数秒时保留到有效的毫秒、微秒或纳秒
+    pub datetime: String,
+    /// 例如 `+08:00`
+    pub offset: String,
+    /// 时区缩写，例如 `CST`、`EDT`
+    pub abbreviation: String,
+    pub rfc3339: String,
+    /// 年份超出 RFC 2822 范围时为空
+    pub rfc2822: String,
+}
+
+/// 转换结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeConversion {
+    /// 输入为时间戳时采用的单位，输入为时间字符串时为 `None`
+    pub unit: Option<TimeUnit>,
+    pub seconds: i64,
+    pub milliseconds: i64,
+    pub microseconds: i64,
+    /// 纳秒时间戳可能超出 i64，以字符串给出
+    pub nanoseconds: String,
+    pub zones: Vec<ZonedTime>,
+}
+
+pub fn zone(name: &str) -> Result<&'static Tz> {
+    let name = name.trim();
+    if name.eq_ignore_ascii_case("utc") || name == "Z" {
+        return Ok(timezones::db::UTC);
+    }
+    timezones::get_by_name(name).ok_or_else(|| Error::msg(format!("未知的时区: {name}")))
+}
+
+/// 所有 IANA 时区名，按名称排序
+pub fn zone_names() -> Vec<&'static str> {
+    let mut names = timezones::iter().map(|tz| tz.name()).collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+/// 解析时间戳或时间字符串并在各个时区中展示。
+/// `unit` 为 `None` 时按位数识别，其中 14 位且是合法日期的数字按 `20231114221320` 形式的时间解析，
+/// 需要当作时间戳时须指定单位；`input_zone` 为不带时区的时间字符串所在的时区
+pub fn convert(
+    input: &str,
+    unit: Option<TimeUnit>,
+    input_zone: &str,
+    zones: &[&str],
+) -> Result<TimeConversion> {
+    let input = input.trim();
+    let compact = if unit.is_none() && input.len() == 14 && is_integer(input) {
+        parse_datetime(input, zone(input_zone)?).ok()
+    } else {
+        None
+    };
+    let (datetime, unit) = if let Some(datetime) = compact {
+        (datetime, None)
+    } else if is_integer(input) {
+        let unit = unit.unwrap_or_else(|| TimeUnit::detect(input));
+        let nanos = input.parse::<i128>()? * unit.nanos();
+        let datetime = OffsetDateTime::from_unix_timestamp_nanos(nanos)
+            .map_err(|e| Error::msg(format!("时间戳超出范围: {e}")))?;
+        (datetime, Some(unit))
+    } else {
+        (parse_datetime(input, zone(input_zone)?)?, None)
+    };
+
+    let nanos = datetime.unix_timestamp_nanos();
+    Ok(TimeConversion {
+        unit,
+        seconds: datetime.unix_timestamp(),
+        milliseconds: nanos.div_euclid(1_000_000) as i64,
+        microseconds: nanos.div_euclid(1_000) as i64,
+        nanoseconds: nanos.to_string(),
+        zones: zones
+            .iter()
+            .map(|name| zoned(datetime, name))
+            .collect::<Result<_>>()?,
+    })
+}
+
+fn is_integer(input: &str) -> bool {
+    let digits = input.strip_prefix('-').unwrap_or(input);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+pub(crate) fn zoned(datetime: OffsetDateTime, name: &str) -> Result<ZonedTime> {
+    let tz = zone(name)?;
+    let local = datetime.to_timezone(tz);
+    let (hours, minutes, _) = local.offset().as_hms();
+    Ok(ZonedTime {
+        zone: tz.name().to_string(),
+        datetime: format_local(local)?,
+        offset: format!(
+            "{}{:02}:{:02}",
+            if local.offset().is_negative() {
+                '-'
+            } else {
+                '+'
+            },
+            hours.unsigned_abs(),
+            minutes.unsigned_abs()
+        ),
+        abbreviation: tz.get_offset_utc(&datetime).name().to_string(),
+        rfc3339: local.format(&Rfc3339).unwrap_or_default(),
+        rfc2822: local.format(&Rfc2822).unwrap_or_default(),
+    })
+}
+
+fn format_local(datetime: OffsetDateTime) -> Result<String> {
+    let mut s = datetime
+        .format(format_description!(
+            "[year]-[month]-[day] [hour]:[minute]:[second]"
+        ))
         .map_err(|e| Error::msg(e.to_string()))?;
+    let nanos = datetime.nanosecond();
+    if nanos != 0 {
+        let fraction = format!("{nanos:09}");
+        let len = if nanos.is_multiple_of(1_000_000) {
+            3
+        } else if nanos.is_multiple_of(1_000) {
+            6
+        } else {
+            9
+        };
+        s.push('.');
+        s.push_str(&fraction[..len]);
+    }
     Ok(s)
 }
+
+/// 不带时区的格式，按顺序尝试
+const LOCAL_FORMATS: [&[BorrowedFormatItem<'static>]; 6] = [
+    format_description!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"),
+    format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"),
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]"),
+    format_description!("[year]/[month]/[day] [hour]:[minute]:[second]"),
+    format_description!("[year]-[month]-[day] [hour]:[minute]"),
+    format_description!("[year][month][day][hour][minute][second]"),
+];
+
+/// HTTP-date 中已废弃的 RFC 850 和 asctime 格式，时间均为 GMT。RFC 850 的两位年份先补全为四位
+const HTTP_FORMATS: [&[BorrowedFormatItem<'static>]; 2] = [
+    format_description!("[weekday], [day]-[month repr:short]-[year] [hour]:[minute]:[second] GMT"),
+    format_description!(
+        "[weekday repr:short] [month repr:short] [day padding:space] [hour]:[minute]:[second] [year]"
+    ),
+];
+
+/// 解析时间字符串，不带时区的时间视为 `tz` 的本地时间
+pub fn parse_datetime(input: &str, tz: &Tz) -> Result<OffsetDateTime> {
+    let input = input.trim();
+    if let Ok(datetime) = OffsetDateTime::parse(input, &Rfc3339)
+        .or_else(|_| OffsetDateTime::parse(input, &Rfc2822))
+        .or_else(|_| OffsetDateTime::parse(input, &Iso8601::DEFAULT))
+    {
+        return Ok(datetime);
+    }
+    if let Some(datetime) = parse_http_date(input) {
+        return Ok(datetime);
+    }
+
+    let local = LOCAL_FORMATS
+        .iter()
+        .find_map(|format| PrimitiveDateTime::parse(input, format).ok())
+        .or_else(|| {
+            Date::parse(input, format_description!("[year]-[month]-[day]"))
+                .ok()
+                .map(|date| date.midnight())
+        })
+        .ok_or_else(|| Error::msg(format!("无法识别的时间格式: {input}")))?;
+    match local.assume_timezone(tz) {
+        OffsetResult::Some(datetime) => Ok(datetime),
+        // 夏令时结束时重复的一小时，取较早的时刻
+        OffsetResult::Ambiguous(datetime, _) => Ok(datetime),
+        OffsetResult::None => Err(Error::msg(format!(
+            "{input} 在 {} 中不存在（夏令时跳过的时间）",
+            tz.name()
+        ))),
+    }
+}
+
+fn parse_http_date(input: &str) -> Option<OffsetDateTime> {
+    // IMF-fixdate 以 GMT 结尾，替换为 RFC 2822 的数字时区
+    if let Some(datetime) = input
+        .strip_suffix(" GMT")
+        .and_then(|s| OffsetDateTime::parse(&format!("{s} +0000"), &Rfc2822).ok())
+    {
+        return Some(datetime);
+    }
+    let input = expand_rfc850_year(input).unwrap_or_else(|| input.to_string());
+    HTTP_FORMATS.iter().find_map(|format| {
+        PrimitiveDateTime::parse(&input, format)
+            .ok()
+            .map(|datetime| datetime.assume_offset(UtcOffset::UTC))
+    })
+}
+
+/// `Sunday, 06-Nov-94 08:49:37 GMT` 中的两位年份，70 以下视为 20xx
+fn expand_rfc850_year(input: &str) -> Option<String> {
+    let (weekday, rest) = input.split_once(", ")?;
+    let (date, time) = rest.split_once(' ')?;
+    let (day_month, year) = date.rsplit_once('-')?;
+    if year.len() != 2 {
+        return None;
+    }
+    let year = year.parse::<u16>().ok()?;
+    let year = if year < 70 { 2000 + year } else { 1900 + year };
+    Some(format!("{weekday}, {day_month}-{year} {time}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_units() -> Result<()> {
+        let zones = ["UTC", "Asia/Shanghai", "America/New_York"];
+        for (input, unit) in [
+            ("1700000000", TimeUnit::Seconds),
+            ("1700000000123", TimeUnit::Milliseconds),
+            ("1700000000123456", TimeUnit::Microseconds),
+            ("1700000000123456789", TimeUnit::Nanoseconds),
+        ] {
+            let conversion = convert(input, None, "UTC", &zones)?;
+            assert_eq!(Some(unit), conversion.unit);
+            assert_eq!(1_700_000_000, conversion.seconds);
+            assert!(
+                conversion.zones[0]
+                    .datetime
+                    .starts_with("2023-11-14 22:13:20")
+            );
+        }
+
+        let conversion = convert("1700000000123456789", None, "UTC", &zones)?;
+        assert_eq!(
+            "2023-11-14 22:13:20.123456789",
+            conversion.zones[0].datetime
+        );
+        assert_eq!(1_700_000_000_123, conversion.milliseconds);
+
+        let shanghai = &conversion.zones[1];
+        assert_eq!("2023-11-15 06:13:20.123456789", shanghai.datetime);
+        assert_eq!("+08:00", shanghai.offset);
+        assert_eq!("CST", shanghai.abbreviation);
+        let new_york = &conversion.zones[2];
+        assert_eq!("-05:00", new_york.offset);
+        assert_eq!("EST", new_york.abbreviation);
+        assert_eq!("Tue, 14 Nov 2023 17:13:20 -0500", new_york.rfc2822);
+
+        let conversion = convert("1700000000123", Some(TimeUnit::Seconds), "UTC", &[]);
+        assert!(conversion.is_err());
+        assert_eq!(
+            -1,
+            convert("-1000", Some(TimeUnit::Milliseconds), "UTC", &[])?.seconds
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let utc = zone("UTC")?;
+        for input in [
+            "2023-11-14T22:13:20Z",
+            "2023-11-15T06:13:20+08:00",
+            "Tue, 14 Nov 2023 22:13:20 +0000",
+            "Tue, 14 Nov 2023 22:13:20 GMT",
+            "Tuesday, 14-Nov-23 22:13:20 GMT",
+            "Tue Nov 14 22:13:20 2023",
+            "2023-11-14 22:13:20",
+            "2023/11/14 22:13:20",
+            "20231114221320",
+        ] {
+            assert_eq!(
+                1_700_000_000,
+                parse_datetime(input, utc)?.unix_timestamp(),
+                "{input}"
+            );
+        }
+
+        let shanghai = zone("Asia/Shanghai")?;
+        let datetime = parse_datetime("2023-11-15 06:13:20.5", shanghai)?;
+        assert_eq!(
+            1_700_000_000_500,
+            (datetime.unix_timestamp_nanos() / 1_000_000) as i64
+        );
+        assert_eq!(
+            1_699_977_600,
+            parse_datetime("2023-11-15", shanghai)?.unix_timestamp()
+        );
+
+        // 纽约 2024-03-10 02:30 因夏令时不存在
+        assert!(parse_datetime("2024-03-10 02:30", zone("America/New_York")?).is_err());
+        assert!(parse_datetime("yesterday", utc).is_err());
+
+        // 14 位数字优先按紧凑格式的时间解析，指定单位时才是时间戳
+        let conversion = convert("20231114221320", None, "UTC", &[])?;
+        assert_eq!((None, 1_700_000_000), (conversion.unit, conversion.seconds));
+        let conversion = convert("20231114221320", Some(TimeUnit::Milliseconds), "UTC", &[])?;
+        assert_eq!(Some(TimeUnit::Milliseconds), conversion.unit);
+        assert_eq!(20_231_114_221, conversion.seconds);
+        // 不是合法日期时仍按位数识别为时间戳
+        assert_eq!(
+            Some(TimeUnit::Milliseconds),
+            convert("17000000001234", None, "UTC", &[])?.unit
+        );
+        assert!(zone("Mars/Olympus").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,564 @@
+//! Base64 之外的二进制转文本编码：Base32 家族、Base36/58/62、Ascii85/Z85、Base45 与 Bech32
+
+use std::sync::LazyLock;
+
+use data_encoding::{BASE32_NOPAD, BASE32HEX_NOPAD, Specification, Translate};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{Error, Result};
+
+/// 编码 Bech32 时默认的人类可读前缀（HRP）
+pub const DEFAULT_HRP: &str = "data";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Encoding {
+    /// RFC 4648 Base32
+    Base32,
+    /// RFC 4648 扩展十六进制字母表
+    Base32Hex,
+    /// Crockford Base32，解码时不区分大小写并把 O/I/L 视为 0/1/1
+    Base32Crockford,
+    Base36,
+    /// 比特币字母表
+    Base58,
+    Base58Flickr,
+    /// 比特币字母表，末尾附加双 SHA-256 的前 4 字节作为校验和
+    Base58Check,
+    Base62,
+    /// Adobe/btoa 风格，全零的 4 字节组写作 `z`，解码时可带 `<~ ~>` 定界符
+    Ascii85,
+    /// ZeroMQ 字母表（ZMQ RFC 32），数据长度必须是 4 的倍数，编码结果长度是 5 的倍数
+    Z85,
+    /// RFC 9285，用于二维码
+    Base45,
+    /// BIP-173
+    Bech32,
+    /// BIP-350
+    Bech32m,
+}
+
+impl Encoding {
+    pub const ALL: [Self; 13] = [
+        Self::Base32,
+        Self::Base32Hex,
+        Self::Base32Crockford,
+        Self::Base36,
+        Self::Base58,
+        Self::Base58Flickr,
+        Self::Base58Check,
+        Self::Base62,
+        Self::Ascii85,
+        Self::Z85,
+        Self::Base45,
+        Self::Bech32,
+        Self::Bech32m,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Base32 => "Base32",
+            Self::Base32Hex => "Base32hex",
+            Self::Base32Crockford => "Crockford Base32",
+            Self::Base36 => "Base36",
+            Self::Base58 => "Base58",
+            Self::Base58Flickr => "Base58 Flickr",
+            Self::Base58Check => "Base58Check",
+            Self::Base62 => "Base62",
+            Self::Ascii85 => "Ascii85",
+            Self::Z85 => "Z85",
+            Self::Base45 => "Base45",
+            Self::Bech32 => "Bech32",
+            Self::Bech32m => "Bech32m",
+        }
+    }
+}
+
+impl TryFrom<&str> for Encoding {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let normalized = value.to_lowercase().replace(['-', '_', ' '], "");
+        Self::ALL
+            .into_iter()
+            .find(|encoding| encoding.name().to_lowercase().replace(' ', "") == normalized)
+            .or(match normalized.as_str() {
+                "crockford" | "base32crockford" => Some(Self::Base32Crockford),
+                "flickr" | "base58flickr" => Some(Self::Base58Flickr),
+                "base85" => Some(Self::Ascii85),
+                _ => None,
+            })
+            .ok_or_else(|| Error::msg(format!("不支持的编码: {value}")))
+    }
+}
+
+/// 一种编码下的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Encoded {
+    pub encoding: Encoding,
+    pub name: &'static str,
+    pub value: String,
+}
+
+static BASE32_CROCKFORD: LazyLock<data_encoding::Encoding> = LazyLock::new(|| {
+    let mut spec = Specification::new();
+    spec.symbols.push_str("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+    spec.ignore.push('-');
+    spec.translate = Translate {
+        from: "abcdefghjkmnpqrstvwxyzOoIiLl".to_string(),
+        to: "ABCDEFGHJKMNPQRSTVWXYZ001111".to_string(),
+    };
+    spec.encoding().unwrap()
+});
+
+const BASE36: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE58_FLICKR: &[u8] = b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ";
+const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const Z85: &[u8] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+const BASE45: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+const BECH32: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// `hrp` 只用于 Bech32/Bech32m
+pub fn encode(encoding: Encoding, data: &[u8], hrp: &str) -> Result<String> {
+    Ok(match encoding {
+        Encoding::Base32 => data_encoding::BASE32.encode(data),
+        Encoding::Base32Hex => data_encoding::BASE32HEX.encode(data),
+        Encoding::Base32Crockford => BASE32_CROCKFORD.encode(data),
+        Encoding::Base36 => encode_radix(data, BASE36),
+        Encoding::Base58 => encode_radix(data, BASE58),
+        Encoding::Base58Flickr => encode_radix(data, BASE58_FLICKR),
+        Encoding::Base58Check => {
+            let mut payload = data.to_vec();
+            payload.extend_from_slice(&checksum(data));
+            encode_radix(&payload, BASE58)
+        }
+        Encoding::Base62 => encode_radix(data, BASE62),
+        Encoding::Ascii85 => encode_base85(data, None, true),
+        Encoding::Z85 => {
+            if !data.len().is_multiple_of(4) {
+                return Err(Error::msg(format!(
+                    "Z85 要求数据长度是 4 的倍数，当前为 {} 字节",
+                    data.len()
+                )));
+            }
+            encode_base85(data, Some(Z85), false)
+        }
+        Encoding::Base45 => encode_base45(data),
+        Encoding::Bech32 => encode_bech32(hrp, data, BECH32_CONST)?,
+        Encoding::Bech32m => encode_bech32(hrp, data, BECH32M_CONST)?,
+    })
+}
+
+/// 忽略输入中的空白
+pub fn decode(encoding: Encoding, input: &str) -> Result<Vec<u8>> {
+    let input = input
+        .chars()
+        .filter(|c| !c.is_whitespace() || (encoding == Encoding::Base45 && *c == ' '))
+        .collect::<String>();
+    // Base45 的字母表包含空格，只去掉首尾空白
+    let input = if encoding == Encoding::Base45 {
+        input.trim_matches(|c: char| c.is_whitespace())
+    } else {
+        &input
+    };
+    let name = encoding.name();
+    let invalid = |e: data_encoding::DecodeError| Error::msg(format!("无效的 {name}: {e}"));
+    match encoding {
+        Encoding::Base32 => BASE32_NOPAD
+            .decode(input.trim_end_matches('=').to_ascii_uppercase().as_bytes())
+            .map_err(invalid),
+        Encoding::Base32Hex => BASE32HEX_NOPAD
+            .decode(input.trim_end_matches('=').to_ascii_uppercase().as_bytes())
+            .map_err(invalid),
+        Encoding::Base32Crockford => BASE32_CROCKFORD.decode(input.as_bytes()).map_err(invalid),
+        Encoding::Base36 => decode_radix(&input.to_ascii_lowercase(), BASE36, name),
+        Encoding::Base58 => decode_radix(input, BASE58, name),
+        Encoding::Base58Flickr => decode_radix(input, BASE58_FLICKR, name),
+        Encoding::Base58Check => {
+            let mut payload = decode_radix(input, BASE58, name)?;
+            if payload.len() < 4 {
+                return Err(Error::msg("Base58Check 数据过短"));
+            }
+            let sum = payload.split_off(payload.len() - 4);
+            if sum != checksum(&payload) {
+                return Err(Error::msg("Base58Check 校验和不匹配"));
+            }
+            Ok(payload)
+        }
+        Encoding::Base62 => decode_radix(input, BASE62, name),
+        Encoding::Ascii85 => {
+            let input = input.strip_prefix("<~").unwrap_or(input);
+            let input = input.strip_suffix("~>").unwrap_or(input);
+            decode_base85(input, None, name)
+        }
+        Encoding::Z85 => {
+            let len = input.chars().count();
+            if !len.is_multiple_of(5) {
+                return Err(Error::msg(format!(
+                    "无效的 Z85: 长度必须是 5 的倍数，当前为 {len}"
+                )));
+            }
+            decode_base85(input, Some(Z85), name)
+        }
+        Encoding::Base45 => decode_base45(input),
+        Encoding::Bech32 => decode_bech32(input, BECH32_CONST, name).map(|(_, data)| data),
+        Encoding::Bech32m => decode_bech32(input, BECH32M_CONST, name).map(|(_, data)| data),
+    }
+}
+
+/// 同一份数据的全部编码结果，按 `Encoding::ALL` 的顺序排列，
+/// 数据长度不是 4 的倍数时不含 Z85
+pub fn encode_all(data: &[u8], hrp: &str) -> Result<Vec<Encoded>> {
+    Encoding::ALL
+        .into_iter()
+        .filter(|encoding| *encoding != Encoding::Z85 || data.len().is_multiple_of(4))
+        .map(|encoding| {
+            Ok(Encoded {
+                encoding,
+                name: encoding.name(),
+                value: encode(encoding, data, hrp)?,
+            })
+        })
+        .collect()
+}
+
+fn checksum(data: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(Sha256::digest(data));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// 把整个字节串当作大端整数转换进制，开头的每个零字节对应一个字母表首字符
+fn encode_radix(data: &[u8], alphabet: &[u8]) -> String {
+    let base = alphabet.len() as u32;
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+    // 低位在前的各位数字
+    let mut digits: Vec<u32> = Vec::with_capacity(data.len() * 2);
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += *digit << 8;
+            *digit = carry % base;
+            carry /= base;
+        }
+        while carry > 0 {
+            digits.push(carry % base);
+            carry /= base;
+        }
+    }
+    std::iter::repeat_n(alphabet[0], zeros)
+        .chain(digits.iter().rev().map(|&digit| alphabet[digit as usize]))
+        .map(char::from)
+        .collect()
+}
+
+fn decode_radix(input: &str, alphabet: &[u8], name: &str) -> Result<Vec<u8>> {
+    let base = alphabet.len() as u32;
+    let zeros = input.bytes().take_while(|&c| c == alphabet[0]).count();
+    // 低位在前的各字节
+    let mut bytes: Vec<u32> = Vec::with_capacity(input.len());
+    for c in input[zeros..].chars() {
+        let mut carry = alphabet
+            .iter()
+            .position(|&symbol| char::from(symbol) == c)
+            .ok_or_else(|| Error::msg(format!("无效的 {name} 字符: {c}")))?
+            as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte * base;
+            *byte = carry & 0xff;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry & 0xff);
+            carry >>= 8;
+        }
+    }
+    Ok(std::iter::repeat_n(0, zeros)
+        .chain(bytes.iter().rev().map(|&byte| byte as u8))
+        .collect())
+}
+
+/// `alphabet` 为 `None` 时使用 Ascii85 的 `!`~`u`
+fn encode_base85(data: &[u8], alphabet: Option<&[u8]>, zero_group: bool) -> String {
+    let symbol = |digit: u32| match alphabet {
+        Some(alphabet) => char::from(alphabet[digit as usize]),
+        None => char::from(b'!' + digit as u8),
+    };
+    let mut output = String::with_capacity(data.len().div_ceil(4) * 5);
+    for chunk in data.chunks(4) {
+        if zero_group && chunk == [0; 4] {
+            output.push('z');
+            continue;
+        }
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(group);
+        let mut digits = [0u32; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = value % 85;
+            value /= 85;
+        }
+        // 不足 4 字节的末组只输出 n + 1 个字符
+        output.extend(digits[..chunk.len() + 1].iter().map(|&digit| symbol(digit)));
+    }
+    output
+}
+
+fn decode_base85(input: &str, alphabet: Option<&[u8]>, name: &str) -> Result<Vec<u8>> {
+    let digit = |c: char| -> Result<u32> {
+        let value = match alphabet {
+            Some(alphabet) => alphabet.iter().position(|&symbol| char::from(symbol) == c),
+            None => ('!'..='u').contains(&c).then(|| c as usize - '!' as usize),
+        };
+        value
+            .map(|value| value as u32)
+            .ok_or_else(|| Error::msg(format!("无效的 {name} 字符: {c}")))
+    };
+    let mut output = Vec::with_capacity(input.len() / 5 * 4);
+    let mut group = Vec::with_capacity(5);
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if alphabet.is_none() && c == 'z' {
+            if !group.is_empty() {
+                return Err(Error::msg(format!("无效的 {name}: z 只能出现在组的开头")));
+            }
+            output.extend_from_slice(&[0; 4]);
+            continue;
+        }
+        group.push(digit(c)?);
+        if group.len() == 5 || chars.peek().is_none() {
+            if group.len() == 1 {
+                return Err(Error::msg(format!("无效的 {name}: 末组只有 1 个字符")));
+            }
+            let len = group.len();
+            // 截短的末组用最大的数字补齐，解码后丢掉多出的字节
+            group.resize(5, 84);
+            let value = group
+                .iter()
+                .try_fold(0u32, |value, &digit| {
+                    value.checked_mul(85)?.checked_add(digit)
+                })
+                .ok_or_else(|| Error::msg(format!("无效的 {name}: 数值超出 32 位")))?;
+            output.extend_from_slice(&value.to_be_bytes()[..len - 1]);
+            group.clear();
+        }
+    }
+    Ok(output)
+}
+
+fn encode_base45(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len().div_ceil(2) * 3);
+    for chunk in data.chunks(2) {
+        let (mut value, len) = match chunk {
+            [a, b] => ((*a as u32) << 8 | *b as u32, 3),
+            [a] => (*a as u32, 2),
+            _ => unreachable!(),
+        };
+        for _ in 0..len {
+            output.push(char::from(BASE45[(value % 45) as usize]));
+            value /= 45;
+        }
+    }
+    output
+}
+
+fn decode_base45(input: &str) -> Result<Vec<u8>> {
+    let digits = input
+        .chars()
+        .map(|c| {
+            BASE45
+                .iter()
+                .position(|&symbol| char::from(symbol) == c)
+                .map(|value| value as u32)
+                .ok_or_else(|| Error::msg(format!("无效的 Base45 字符: {c}")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut output = Vec::with_capacity(digits.len() / 3 * 2);
+    for chunk in digits.chunks(3) {
+        let value = chunk
+            .iter()
+            .rev()
+            .fold(0, |value, &digit| value * 45 + digit);
+        match chunk.len() {
+            3 if value <= 0xffff => output.extend_from_slice(&(value as u16).to_be_bytes()),
+            2 if value <= 0xff => output.push(value as u8),
+            1 => return Err(Error::msg("无效的 Base45: 末组只有 1 个字符")),
+            _ => return Err(Error::msg("无效的 Base45: 数值超出范围")),
+        }
+    }
+    Ok(output)
+}
+
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    values.into_iter().fold(1, |chk, value| {
+        let top = chk >> 25;
+        let chk = (chk & 0x1ffffff) << 5 ^ value as u32;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(chk, |chk, (_, generator)| chk ^ generator)
+    })
+}
+
+fn hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes()
+        .map(|b| b >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|b| b & 31))
+}
+
+/// 在 8 位和 5 位分组之间转换，`pad` 为 false 时要求多余的位全为 0 且不足一组
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0;
+    let mut output = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    let max = (1 << to) - 1;
+    for &value in data {
+        acc = acc << from | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            output.push((acc >> bits & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            output.push((acc << (to - bits) & max) as u8);
+        }
+    } else if bits >= from || acc << (to - bits) & max != 0 {
+        return None;
+    }
+    Some(output)
+}
+
+fn encode_bech32(hrp: &str, data: &[u8], constant: u32) -> Result<String> {
+    if hrp.is_empty() || !hrp.bytes().all(|b| (33..=126).contains(&b)) {
+        return Err(Error::msg(format!("无效的 Bech32 前缀: {hrp}")));
+    }
+    let hrp = hrp.to_ascii_lowercase();
+    let values = convert_bits(data, 8, 5, true).unwrap();
+    let chk = polymod(hrp_expand(&hrp).chain(values.iter().copied()).chain([0; 6])) ^ constant;
+    let checksum = (0..6).map(|i| (chk >> (5 * (5 - i)) & 31) as u8);
+    Ok(format!(
+        "{hrp}1{}",
+        values
+            .iter()
+            .copied()
+            .chain(checksum)
+            .map(|value| char::from(BECH32[value as usize]))
+            .collect::<String>()
+    ))
+}
+
+/// 不限制 BIP-173 的 90 字符上限，以便处理闪电网络发票等较长的数据
+fn decode_bech32(input: &str, constant: u32, name: &str) -> Result<(String, Vec<u8>)> {
+    if input.chars().any(|c| c.is_ascii_lowercase())
+        && input.chars().any(|c| c.is_ascii_uppercase())
+    {
+        return Err(Error::msg(format!("无效的 {name}: 不能混用大小写")));
+    }
+    let input = input.to_ascii_lowercase();
+    let (hrp, data) = input
+        .rsplit_once('1')
+        .ok_or_else(|| Error::msg(format!("无效的 {name}: 缺少分隔符 1")))?;
+    if hrp.is_empty() || data.len() < 6 || !hrp.bytes().all(|b| (33..=126).contains(&b)) {
+        return Err(Error::msg(format!("无效的 {name}: 前缀或数据长度不正确")));
+    }
+    let values = data
+        .chars()
+        .map(|c| {
+            BECH32
+                .iter()
+                .position(|&symbol| char::from(symbol) == c)
+                .map(|value| value as u8)
+                .ok_or_else(|| Error::msg(format!("无效的 {name} 字符: {c}")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if polymod(hrp_expand(hrp).chain(values.iter().copied())) != constant {
+        return Err(Error::msg(format!("{name} 校验和不匹配")));
+    }
+    let data = convert_bits(&values[..values.len() - 6], 5, 8, false)
+        .ok_or_else(|| Error::msg(format!("无效的 {name}: 填充位不正确")))?;
+    Ok((hrp.to_string(), data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_vectors() {
+        let cases = [
+            (Encoding::Base32, "foobar".as_bytes(), "MZXW6YTBOI======"),
+            (Encoding::Base32Hex, b"foobar", "CPNMUOJ1E8======"),
+            (Encoding::Base32Crockford, b"foobar", "CSQPYRK1E8"),
+            (Encoding::Base36, b"Hello World!", "2678lx5gvmsv1dro9b5"),
+            (Encoding::Base58, b"\0\0hello world", "11StV1DL6CwTryKyV"),
+            (Encoding::Base58Flickr, b"hello world", "rTu1dk6cWsRYjYu"),
+            (Encoding::Base58Check, b"\0hello", "12L5B5yqsf7vwb"),
+            (Encoding::Base62, b"hello world", "AAwf93rvy4aWQVw"),
+            (Encoding::Ascii85, b"\0\0\0\0hello", "zBOu!rDZ"),
+            (
+                Encoding::Z85,
+                &[0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b],
+                "HelloWorld",
+            ),
+            (Encoding::Base45, b"ietf!", "QED8WEX0"),
+            (Encoding::Bech32, b"", "data1tu4da0"),
+        ];
+        for (encoding, data, expected) in cases {
+            assert_eq!(
+                expected,
+                encode(encoding, data, DEFAULT_HRP).unwrap(),
+                "{encoding:?}"
+            );
+            assert_eq!(data, decode(encoding, expected).unwrap(), "{encoding:?}");
+        }
+        // BIP-173 与 BIP-350 的有效校验和示例
+        assert!(decode(Encoding::Bech32, "A12UEL5L").is_ok());
+        assert!(decode(Encoding::Bech32m, "a1lqfn3a").is_ok());
+        assert!(decode(Encoding::Bech32, "a1lqfn3a").is_err());
+        assert!(decode(Encoding::Base58Check, "12L5B5yqsf7vwc").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = (0..=255).chain([0, 0, 255]).collect::<Vec<u8>>();
+        for encoded in encode_all(&data, "bc").unwrap() {
+            assert_eq!(
+                data,
+                decode(encoded.encoding, &encoded.value).unwrap(),
+                "{}",
+                encoded.name
+            );
+        }
+        for len in 0..9 {
+            for encoding in [Encoding::Ascii85, Encoding::Z85, Encoding::Base45] {
+                if encoding == Encoding::Z85 && len % 4 != 0 {
+                    continue;
+                }
+                let value = encode(encoding, &data[..len], "").unwrap();
+                assert_eq!(&data[..len], decode(encoding, &value).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_z85_length() {
+        // ZMQ RFC 32 不允许截短的末组
+        assert!(encode(Encoding::Z85, b"hello", "").is_err());
+        assert!(decode(Encoding::Z85, "HelloWorl").is_err());
+        assert!(decode(Encoding::Z85, "Hello World").is_ok());
+        let all = encode_all(b"hello", DEFAULT_HRP).unwrap();
+        assert!(all.iter().all(|encoded| encoded.encoding != Encoding::Z85));
+        assert_eq!(Encoding::ALL.len() - 1, all.len());
+    }
+}
@@ -1,115 +1,468 @@
+//! 哈希与校验和算法的注册表，文本摘要和文件校验共用
+
+use std::{
+    io::Read,
+    sync::{LazyLock, RwLock},
+};
+
+use ::base64::Engine as _;
+use hmac::{
+    Mac, SimpleHmac,
+    digest::{Digest, core_api::BlockSizeUser},
+};
+use indexmap::IndexMap;
+use serde::Serialize;
+
 use crate::{Error, Result};
 
+/// 计算文本摘要时默认使用的算法
+pub const DEFAULT_ALGORITHMS: [&str; 6] =
+    ["md5", "sha1", "sha256", "sha512", "sha3_256", "sha3_512"];
+
+/// 可增量输入的哈希或校验和
+pub trait Hasher: Send {
+    fn update(&mut self, data: &[u8]);
+
+    /// 结果的字节串，整数类的校验和按大端排列
+    fn finalize(self: Box<Self>) -> Vec<u8>;
+}
+
+/// HMAC 函数，参数为密钥和数据
+pub type HmacFn = fn(&[u8], &[u8]) -> Vec<u8>;
+
+/// 算法的类别
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Category {
+    /// 加密哈希
+    Crypto,
+    /// 校验和，如 CRC、Adler-32
+    Checksum,
+    /// 非加密哈希，如 xxHash、MurmurHash3
+    NonCrypto,
+}
+
+/// 注册表中的一个算法
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Algorithm {
+    /// 唯一名称，如 `sha256`
+    pub name: &'static str,
+    /// 显示名称，如 `SHA-256`
+    pub label: &'static str,
+    pub category: Category,
+    /// 其它可识别的名称，如文件校验以前使用的 `sha2_256sum`
+    pub aliases: &'static [&'static str],
+    #[serde(skip)]
+    new: fn() -> Box<dyn Hasher>,
+    /// 以该算法为摘要的 HMAC
+    #[serde(skip)]
+    hmac: Option<HmacFn>,
+}
+
+impl Algorithm {
+    pub const fn new(
+        name: &'static str,
+        label: &'static str,
+        category: Category,
+        new: fn() -> Box<dyn Hasher>,
+    ) -> Self {
+        Self {
+            name,
+            label,
+            category,
+            aliases: &[],
+            new,
+            hmac: None,
+        }
+    }
+
+    pub const fn aliases(mut self, aliases: &'static [&'static str]) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    pub const fn with_hmac(mut self, hmac: HmacFn) -> Self {
+        self.hmac = Some(hmac);
+        self
+    }
+
+    pub fn supports_hmac(&self) -> bool {
+        self.hmac.is_some()
+    }
+
+    pub fn hasher(&self) -> Box<dyn Hasher> {
+        (self.new)()
+    }
+
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// RFC 2104 HMAC，只支持注册了 HMAC 的加密哈希
+    pub fn hmac(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+        let hmac = self
+            .hmac
+            .ok_or_else(|| Error::msg(format!("{} 不支持 HMAC", self.label)))?;
+        Ok(hmac(key, data))
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        let normalize = |s: &str| s.replace(['-', '_', '/'], "").to_lowercase();
+        let name = normalize(name);
+        normalize(self.name) == name
+            || normalize(self.label) == name
+            || self.aliases.iter().any(|alias| normalize(alias) == name)
+    }
+}
+
+static REGISTRY: LazyLock<RwLock<IndexMap<&'static str, Algorithm>>> = LazyLock::new(|| {
+    RwLock::new(
+        builtin()
+            .into_iter()
+            .map(|algorithm| (algorithm.name, algorithm))
+            .collect(),
+    )
+});
+
+/// 注册算法，与已有算法同名时替换
+pub fn register(algorithm: Algorithm) {
+    if let Ok(mut registry) = REGISTRY.write() {
+        registry.insert(algorithm.name, algorithm);
+    }
+}
+
+/// 已注册的全部算法，按注册顺序排列
+pub fn algorithms() -> Vec<Algorithm> {
+    REGISTRY
+        .read()
+        .map(|registry| registry.values().copied().collect())
+        .unwrap_or_default()
+}
+
+/// 按名称、显示名称或别名查找算法，忽略大小写和 `-`、`_`、`/`
+pub fn algorithm(name: &str) -> Result<Algorithm> {
+    algorithms()
+        .into_iter()
+        .find(|algorithm| algorithm.matches(name))
+        .ok_or_else(|| Error::msg(format!("不支持的算法: {name}")))
+}
+
+/// 按 `input_type` 解码输入：`text`（默认，UTF-8）、`hex` 或 `base64`，忽略空白
+pub fn decode_input(input: &str, input_type: Option<&str>) -> Result<Vec<u8>> {
+    match input_type.unwrap_or("text") {
+        "text" => Ok(input.as_bytes().to_vec()),
+        "hex" => {
+            let hex = input
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>();
+            let hex = hex.strip_prefix("0x").unwrap_or(&hex);
+            hex::decode(hex).map_err(|e| Error::msg(format!("无效的十六进制输入: {e}")))
+        }
+        "base64" => {
+            let data = input
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>();
+            ::base64::engine::general_purpose::STANDARD
+                .decode(&data)
+                .or_else(|_| ::base64::engine::general_purpose::URL_SAFE.decode(&data))
+                .or_else(|_| ::base64::engine::general_purpose::STANDARD_NO_PAD.decode(&data))
+                .or_else(|_| ::base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(&data))
+                .map_err(|e| Error::msg(format!("无效的 Base64 输入: {e}")))
+        }
+        other => Err(Error::msg(format!("不支持的输入类型: {other}"))),
+    }
+}
+
+/// 按 `output_type` 编码结果：`hex`（默认）或 `base64`，大写只作用于十六进制
+pub fn encode_output(bytes: &[u8], output_type: Option<&str>, uppercase: bool) -> String {
+    match output_type {
+        Some("base64") => ::base64::engine::general_purpose::STANDARD.encode(bytes),
+        _ if uppercase => hex::encode_upper(bytes),
+        _ => hex::encode(bytes),
+    }
+}
+
+/// 读取一遍数据，同时计算多个算法，结果与 `algorithms` 一一对应
+pub fn digest_reader(mut reader: impl Read, algorithms: &[Algorithm]) -> Result<Vec<Vec<u8>>> {
+    let mut hashers = algorithms.iter().map(Algorithm::hasher).collect::<Vec<_>>();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        for hasher in hashers.iter_mut() {
+            hasher.update(&buf[..n]);
+        }
+    }
+    Ok(hashers
+        .into_iter()
+        .map(|hasher| hasher.finalize())
+        .collect())
+}
+
+fn hex_digest(name: &str, data: &[u8]) -> Result<String> {
+    Ok(hex::encode(algorithm(name)?.digest(data)))
+}
+
+fn hex_hmac(name: &str, secret: &[u8], data: &[u8]) -> Result<String> {
+    Ok(hex::encode(algorithm(name)?.hmac(secret, data)?))
+}
+
+/// 实现了 `digest::Digest` 的算法
+struct DigestHasher<D>(D);
+
+impl<D: Digest + Send> Hasher for DigestHasher<D> {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        self.0.finalize().to_vec()
+    }
+}
+
+fn digest<D: Digest + Send + 'static>() -> Box<dyn Hasher> {
+    Box::new(DigestHasher(D::new()))
+}
+
+fn digest_hmac<D: Digest + BlockSizeUser>(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(key).expect("HMAC 的密钥可以是任意长度");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// 用一对函数适配状态式的校验和
+struct StateHasher<S> {
+    state: S,
+    update: fn(&mut S, &[u8]),
+    finalize: fn(S) -> Vec<u8>,
+}
+
+impl<S: Send> Hasher for StateHasher<S> {
+    fn update(&mut self, data: &[u8]) {
+        (self.update)(&mut self.state, data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        (self.finalize)(self.state)
+    }
+}
+
+fn state<S: Send + 'static>(
+    state: S,
+    update: fn(&mut S, &[u8]),
+    finalize: fn(S) -> Vec<u8>,
+) -> Box<dyn Hasher> {
+    Box::new(StateHasher {
+        state,
+        update,
+        finalize,
+    })
+}
+
+static CRC32: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+static CRC32C: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI);
+static CRC64: crc::Crc<u64> = crc::Crc::<u64>::new(&crc::CRC_64_XZ);
+
+fn builtin() -> Vec<Algorithm> {
+    use Category::*;
+    vec![
+        Algorithm::new("md5", "MD5", Crypto, digest::<md5::Md5>)
+            .aliases(&["md5sum"])
+            .with_hmac(digest_hmac::<md5::Md5>),
+        Algorithm::new("sha1", "SHA-1", Crypto, digest::<sha1::Sha1>)
+            .aliases(&["sha1sum"])
+            .with_hmac(digest_hmac::<sha1::Sha1>),
+        Algorithm::new("sha224", "SHA-224", Crypto, digest::<sha2::Sha224>)
+            .aliases(&["sha2_224", "sha2_224sum"])
+            .with_hmac(digest_hmac::<sha2::Sha224>),
+        Algorithm::new("sha256", "SHA-256", Crypto, digest::<sha2::Sha256>)
+            .aliases(&["sha2_256", "sha2_256sum"])
+            .with_hmac(digest_hmac::<sha2::Sha256>),
+        Algorithm::new("sha384", "SHA-384", Crypto, digest::<sha2::Sha384>)
+            .aliases(&["sha2_384", "sha2_384sum"])
+            .with_hmac(digest_hmac::<sha2::Sha384>),
+        Algorithm::new("sha512", "SHA-512", Crypto, digest::<sha2::Sha512>)
+            .aliases(&["sha2_512", "sha2_512sum"])
+            .with_hmac(digest_hmac::<sha2::Sha512>),
+        Algorithm::new(
+            "sha512_256",
+            "SHA-512/256",
+            Crypto,
+            digest::<sha2::Sha512_256>,
+        )
+        .with_hmac(digest_hmac::<sha2::Sha512_256>),
+        Algorithm::new("sha3_256", "SHA3-256", Crypto, digest::<sha3::Sha3_256>)
+            .aliases(&["sha3_256sum"])
+            .with_hmac(digest_hmac::<sha3::Sha3_256>),
+        Algorithm::new("sha3_384", "SHA3-384", Crypto, digest::<sha3::Sha3_384>)
+            .aliases(&["sha3_384sum"])
+            .with_hmac(digest_hmac::<sha3::Sha3_384>),
+        Algorithm::new("sha3_512", "SHA3-512", Crypto, digest::<sha3::Sha3_512>)
+            .aliases(&["sha3_512sum"])
+            .with_hmac(digest_hmac::<sha3::Sha3_512>),
+        Algorithm::new(
+            "blake2b",
+            "BLAKE2b-512",
+            Crypto,
+            digest::<blake2::Blake2b512>,
+        )
+        .aliases(&["b2sum", "blake2b512"])
+        .with_hmac(digest_hmac::<blake2::Blake2b512>),
+        Algorithm::new(
+            "blake2s",
+            "BLAKE2s-256",
+            Crypto,
+            digest::<blake2::Blake2s256>,
+        )
+        .aliases(&["blake2s256"])
+        .with_hmac(digest_hmac::<blake2::Blake2s256>),
+        Algorithm::new("blake3", "BLAKE3", Crypto, || {
+            state(
+                blake3::Hasher::new(),
+                |hasher, data| {
+                    hasher.update(data);
+                },
+                |hasher| hasher.finalize().as_bytes().to_vec(),
+            )
+        })
+        .aliases(&["b3sum"]),
+        Algorithm::new(
+            "ripemd160",
+            "RIPEMD-160",
+            Crypto,
+            digest::<ripemd::Ripemd160>,
+        )
+        .with_hmac(digest_hmac::<ripemd::Ripemd160>),
+        Algorithm::new("sm3", "SM3", Crypto, digest::<sm3::Sm3>).with_hmac(digest_hmac::<sm3::Sm3>),
+        Algorithm::new("crc32", "CRC-32", Checksum, || {
+            state(
+                CRC32.digest(),
+                |crc, data| crc.update(data),
+                |crc| crc.finalize().to_be_bytes().to_vec(),
+            )
+        }),
+        Algorithm::new("crc32c", "CRC-32C", Checksum, || {
+            state(
+                CRC32C.digest(),
+                |crc, data| crc.update(data),
+                |crc| crc.finalize().to_be_bytes().to_vec(),
+            )
+        }),
+        Algorithm::new("crc64", "CRC-64/XZ", Checksum, || {
+            state(
+                CRC64.digest(),
+                |crc, data| crc.update(data),
+                |crc| crc.finalize().to_be_bytes().to_vec(),
+            )
+        }),
+        Algorithm::new("adler32", "Adler-32", Checksum, || {
+            state(
+                adler2::Adler32::new(),
+                |adler, data| adler.write_slice(data),
+                |adler| adler.checksum().to_be_bytes().to_vec(),
+            )
+        }),
+        Algorithm::new("xxh64", "xxHash64", NonCrypto, || {
+            state(
+                xxhash_rust::xxh64::Xxh64::new(0),
+                |xxh, data| xxh.update(data),
+                |xxh| xxh.digest().to_be_bytes().to_vec(),
+            )
+        }),
+        Algorithm::new("xxh3", "XXH3-64", NonCrypto, || {
+            state(
+                xxhash_rust::xxh3::Xxh3::new(),
+                |xxh, data| xxh.update(data),
+                |xxh| xxh.digest().to_be_bytes().to_vec(),
+            )
+        }),
+        Algorithm::new("murmur3_32", "MurmurHash3 x86 32", NonCrypto, || {
+            state(
+                mur3::Hasher32::with_seed(0),
+                std::hash::Hasher::write,
+                |murmur| murmur.finish32().to_be_bytes().to_vec(),
+            )
+        }),
+        Algorithm::new("murmur3_128", "MurmurHash3 x64 128", NonCrypto, || {
+            state(
+                mur3::Hasher128::with_seed(0),
+                std::hash::Hasher::write,
+                |murmur| {
+                    // 与常见实现一致，按 (h2 << 64) | h1 组成的 128 位整数输出
+                    let (h1, h2) = murmur.finish128();
+                    [h2.to_be_bytes(), h1.to_be_bytes()].concat()
+                },
+            )
+        }),
+    ]
+}
+
 pub async fn md5(data: impl AsRef<[u8]>) -> Result<String> {
-    use md5::Digest;
-    let mut hasher = md5::Md5::new();
-    hasher.update(data);
-    Ok(format!("{:x}", hasher.finalize()))
+    hex_digest("md5", data.as_ref())
 }
 
 pub async fn sha1(data: impl AsRef<[u8]>) -> Result<String> {
-    use sha1::Digest;
-    let mut hasher = sha1::Sha1::new();
-    hasher.update(data);
-    Ok(format!("{:x}", hasher.finalize()))
+    hex_digest("sha1", data.as_ref())
 }
 
 pub async fn sha256(data: impl AsRef<[u8]>) -> Result<String> {
-    use sha2::Digest;
-    let mut hasher = sha2::Sha256::new();
-    hasher.update(data);
-    Ok(format!("{:x}", hasher.finalize()))
+    hex_digest("sha256", data.as_ref())
 }
 
 pub async fn sha512(data: impl AsRef<[u8]>) -> Result<String> {
-    use sha2::Digest;
-    let mut hasher = sha2::Sha512::new();
-    hasher.update(data);
-    Ok(format!("{:x}", hasher.finalize()))
+    hex_digest("sha512", data.as_ref())
 }
 
 pub async fn sha2_224(data: impl AsRef<[u8]>) -> Result<String> {
-    use sha2::Digest;
-    let mut hasher = sha2::Sha224::new();
-    hasher.update(data);
-    Ok(format!("{:x}", hasher.finalize()))
+    hex_digest("sha224", data.as_ref())
 }
 
 pub async fn sha2_384(data: impl AsRef<[u8]>) -> Result<String> {
-    use sha2::Digest;
-    let mut hasher = sha2::Sha384::new();
-    hasher.update(data);
-    Ok(format!("{:x}", hasher.finalize()))
+    hex_digest("sha384", data.as_ref())
 }
 
 pub async fn sha3_256(data: impl AsRef<[u8]>) -> Result<String> {
-    use sha3::Digest;
-    let mut hasher = sha3::Sha3_256::new();
-    hasher.update(data);
-    Ok(format!("{:x}", hasher.finalize()))
+    hex_digest("sha3_256", data.as_ref())
 }
 
 pub async fn sha3_384(data: impl AsRef<[u8]>) -> Result<String> {
-    use sha3::Digest;
-    let mut hasher = sha3::Sha3_384::new();
-    hasher.update(data);
-    Ok(format!("{:x}", hasher.finalize()))
+    hex_digest("sha3_384", data.as_ref())
 }
 
 pub async fn sha3_512(data: impl AsRef<[u8]>) -> Result<String> {
-    use sha3::Digest;
-    let mut hasher = sha3::Sha3_512::new();
-    hasher.update(data);
-    Ok(format!("{:x}", hasher.finalize()))
+    hex_digest("sha3_512", data.as_ref())
 }
 
 // HMAC variants —— 使用 secret 对输入做 HMAC，返回十六进制字符串
 pub async fn hmac_md5(secret: impl AsRef<[u8]>, data: impl AsRef<[u8]>) -> Result<String> {
-    use hmac::Mac;
-    type HmacMd5 = hmac::Hmac<md5::Md5>;
-    let mut mac = HmacMd5::new_from_slice(secret.as_ref()).map_err(Error::from)?;
-    mac.update(data.as_ref());
-    Ok(format!("{:x}", mac.finalize().into_bytes()))
+    hex_hmac("md5", secret.as_ref(), data.as_ref())
 }
 
 pub async fn hmac_sha1(secret: impl AsRef<[u8]>, data: impl AsRef<[u8]>) -> Result<String> {
-    use hmac::Mac;
-    type HmacSha1 = hmac::Hmac<sha1::Sha1>;
-    let mut mac = HmacSha1::new_from_slice(secret.as_ref()).map_err(Error::from)?;
-    mac.update(data.as_ref());
-    Ok(format!("{:x}", mac.finalize().into_bytes()))
+    hex_hmac("sha1", secret.as_ref(), data.as_ref())
 }
 
 pub async fn hmac_sha256(secret: impl AsRef<[u8]>, data: impl AsRef<[u8]>) -> Result<String> {
-    use hmac::Mac;
-    type HmacSha256 = hmac::Hmac<sha2::Sha256>;
-    let mut mac = HmacSha256::new_from_slice(secret.as_ref()).map_err(Error::from)?;
-    mac.update(data.as_ref());
-    Ok(format!("{:x}", mac.finalize().into_bytes()))
+    hex_hmac("sha256", secret.as_ref(), data.as_ref())
 }
 
 pub async fn hmac_sha512(secret: impl AsRef<[u8]>, data: impl AsRef<[u8]>) -> Result<String> {
-    use hmac::Mac;
-    type HmacSha512 = hmac::Hmac<sha2::Sha512>;
-    let mut mac = HmacSha512::new_from_slice(secret.as_ref()).map_err(Error::from)?;
-    mac.update(data.as_ref());
-    Ok(format!("{:x}", mac.finalize().into_bytes()))
+    hex_hmac("sha512", secret.as_ref(), data.as_ref())
 }
 
 pub async fn hmac_sha3_256(secret: impl AsRef<[u8]>, data: impl AsRef<[u8]>) -> Result<String> {
-    use hmac::Mac;
-    type HmacSha3_256 = hmac::Hmac<sha3::Sha3_256>;
-    let mut mac = HmacSha3_256::new_from_slice(secret.as_ref()).map_err(Error::from)?;
-    mac.update(data.as_ref());
-    Ok(format!("{:x}", mac.finalize().into_bytes()))
+    hex_hmac("sha3_256", secret.as_ref(), data.as_ref())
 }
 
 pub async fn hmac_sha3_512(secret: impl AsRef<[u8]>, data: impl AsRef<[u8]>) -> Result<String> {
-    use hmac::Mac;
-    type HmacSha3_512 = hmac::Hmac<sha3::Sha3_512>;
-    let mut mac = HmacSha3_512::new_from_slice(secret.as_ref()).map_err(Error::from)?;
-    mac.update(data.as_ref());
-    Ok(format!("{:x}", mac.finalize().into_bytes()))
+    hex_hmac("sha3_512", secret.as_ref(), data.as_ref())
 }
 
 #[cfg(test)]
@@ -196,4 +549,159 @@ mod tests {
         );
         Ok(())
     }
+
+    fn hex_of(name: &str, data: &[u8]) -> String {
+        hex::encode(algorithm(name).unwrap().digest(data))
+    }
+
+    #[test]
+    fn test_extended_algorithms() {
+        let cases = [
+            (
+                "sha512_256",
+                "abc",
+                "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23",
+            ),
+            (
+                "blake2b",
+                "abc",
+                "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+            ),
+            (
+                "blake2s",
+                "abc",
+                "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+            ),
+            (
+                "blake3",
+                "",
+                "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            ),
+            (
+                "ripemd160",
+                "abc",
+                "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc",
+            ),
+            (
+                "sm3",
+                "abc",
+                "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0",
+            ),
+            ("crc32", "123456789", "cbf43926"),
+            ("crc32c", "123456789", "e3069283"),
+            ("crc64", "123456789", "995dc9bbdf1939fa"),
+            ("adler32", "Wikipedia", "11e60398"),
+            ("xxh64", "", "ef46db3751d8e999"),
+            ("xxh3", "", "2d06800538d394c2"),
+            ("murmur3_32", "hello world", "5e928f0f"),
+            (
+                "murmur3_128",
+                "hello world",
+                "ab97467d60eb63b1533f6046eb7f610e",
+            ),
+            (
+                "murmur3_128",
+                "The quick brown fox jumps over the lazy dog",
+                "7a433ca9c49a9347e34bbc7bbc071b6c",
+            ),
+        ];
+        for (name, input, expected) in cases {
+            assert_eq!(expected, hex_of(name, input.as_bytes()), "{name}");
+        }
+        // SM3 两个分组的标准示例
+        assert_eq!(
+            "debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732",
+            hex_of("sm3", "abcd".repeat(16).as_bytes())
+        );
+    }
+
+    #[test]
+    fn test_streaming() -> Result<()> {
+        let data = (0..100_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let algorithms = algorithms();
+        let digests = digest_reader(std::io::Cursor::new(&data), &algorithms)?;
+        for (algorithm, digest) in algorithms.iter().zip(digests) {
+            let mut hasher = algorithm.hasher();
+            for chunk in data.chunks(7) {
+                hasher.update(chunk);
+            }
+            assert_eq!(digest, hasher.finalize(), "{}", algorithm.name);
+            assert_eq!(digest, algorithm.digest(&data), "{}", algorithm.name);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_lookup() -> Result<()> {
+        assert_eq!("sha256", algorithm("SHA-256")?.name);
+        assert_eq!("sha256", algorithm("sha2_256sum")?.name);
+        assert_eq!("sha512_256", algorithm("SHA-512/256")?.name);
+        assert!(algorithm("sha0").is_err());
+        assert!(algorithm("crc32")?.hmac(b"key", b"data").is_err());
+        // RFC 4231 测试用例 2
+        assert_eq!(
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            hex::encode(algorithm("sha256")?.hmac(b"Jefe", b"what do ya want for nothing?")?)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_hmac() -> Result<()> {
+        // RFC 4231 测试用例 2 和 6（密钥长于分组，需要先做摘要）
+        let long_key = [0xaa; 131];
+        let long_data = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        for (name, case2, case6) in [
+            (
+                "sha224",
+                "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+                "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+            ),
+            (
+                "sha256",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            ),
+            (
+                "sha384",
+                "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+                "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952",
+            ),
+            (
+                "sha512",
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+            ),
+        ] {
+            let algorithm = algorithm(name)?;
+            let hmac = algorithm.hmac(b"Jefe", b"what do ya want for nothing?")?;
+            assert_eq!(case2, hex::encode(hmac), "{name}");
+            assert_eq!(
+                case6,
+                hex::encode(algorithm.hmac(&long_key, long_data)?),
+                "{name}"
+            );
+        }
+        assert!(!algorithm("blake3")?.supports_hmac());
+        Ok(())
+    }
+
+    #[test]
+    fn test_input_output() -> Result<()> {
+        assert_eq!(
+            b"hello".to_vec(),
+            decode_input("68 65 6c 6c 6f", Some("hex"))?
+        );
+        assert_eq!(b"hello".to_vec(), decode_input("aGVsbG8=", Some("base64"))?);
+        assert!(decode_input("zz", Some("hex")).is_err());
+        assert_eq!(
+            "DEADBEEF",
+            encode_output(&[0xde, 0xad, 0xbe, 0xef], None, true)
+        );
+        assert_eq!(
+            "3q2+7w==",
+            encode_output(&[0xde, 0xad, 0xbe, 0xef], Some("base64"), true)
+        );
+        Ok(())
+    }
 }
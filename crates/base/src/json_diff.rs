@@ -0,0 +1,362 @@
+//! JSON 结构对比
+//!
+//! 对象忽略键的顺序；数组默认按下标逐个对比，忽略顺序时按值配对，
+//! 配不上的元素记为删除和新增。差异可导出为 JSON Patch（RFC 6902）和 JSON Merge Patch（RFC 7386）。
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::cffc::{Data, Ft};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffOptions {
+    /// 忽略数组元素的顺序
+    pub ignore_array_order: bool,
+    /// 忽略的节点，JSON Pointer 形式，`*` 匹配任意一段，例如 `/items/*/updatedAt`
+    pub ignore_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// 一处差异
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// 删除和修改为左侧的 JSON Pointer，新增为右侧的
+    pub pointer: String,
+    /// 新增时没有左侧值，序列化时省略该字段，以便与值为 `null` 区分
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<Value>,
+    /// 删除时没有右侧值，序列化时省略该字段
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<Value>,
+}
+
+/// 对比结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiff {
+    pub left: Value,
+    pub right: Value,
+    pub changes: Vec<Change>,
+    /// 把左侧变为右侧的 JSON Patch，忽略数组顺序时新增的元素追加到数组末尾
+    pub patch: Value,
+    /// 把左侧变为右侧的 JSON Merge Patch，没有差异时为 `{}`
+    pub merge_patch: Value,
+}
+
+impl JsonDiff {
+    /// 按 `ft` 解析两侧输入后对比，`Auto` 时各自识别格式
+    pub fn new(ft: Ft, left: &str, right: &str, options: &DiffOptions) -> Result<Self> {
+        let left = Data::new(ft, Ft::Json, left, 0)
+            .parse()
+            .map_err(|e| Error::msg(format!("左侧解析失败: {e}")))?;
+        let right = Data::new(ft, Ft::Json, right, 0)
+            .parse()
+            .map_err(|e| Error::msg(format!("右侧解析失败: {e}")))?;
+        Ok(Self::from_values(left, right, options))
+    }
+
+    pub fn from_values(left: Value, right: Value, options: &DiffOptions) -> Self {
+        let differ = Differ { options };
+        let mut changes = Vec::new();
+        let mut patch = Vec::new();
+        differ.diff(&left, &right, "", "", &mut changes, &mut patch);
+        let merge_patch = differ
+            .merge_patch(&left, &right, "")
+            .unwrap_or_else(|| json!({}));
+        Self {
+            left,
+            right,
+            changes,
+            patch: Value::Array(patch),
+            merge_patch,
+        }
+    }
+}
+
+struct Differ<'a> {
+    options: &'a DiffOptions,
+}
+
+impl Differ<'_> {
+    fn ignored(&self, pointer: &str) -> bool {
+        self.options.ignore_paths.iter().any(|pattern| {
+            let pattern = pattern.trim().trim_end_matches('/');
+            let mut expected = pattern.split('/');
+            let mut actual = pointer.split('/');
+            loop {
+                match (expected.next(), actual.next()) {
+                    (None, None) => return true,
+                    (Some(e), Some(a)) if e == "*" || e == a => {}
+                    _ => return false,
+                }
+            }
+        })
+    }
+
+    fn equal(&self, left: &Value, right: &Value, pointer: &str) -> bool {
+        let mut changes = Vec::new();
+        self.diff(left, right, pointer, pointer, &mut changes, &mut Vec::new());
+        changes.is_empty()
+    }
+
+    /// `pointer` 为节点在左侧的位置，`right_pointer` 为在右侧的位置
+    fn diff(
+        &self,
+        left: &Value,
+        right: &Value,
+        pointer: &str,
+        right_pointer: &str,
+        changes: &mut Vec<Change>,
+        patch: &mut Vec<Value>,
+    ) {
+        if self.ignored(pointer) {
+            return;
+        }
+        match (left, right) {
+            (Value::Object(l), Value::Object(r)) => {
+                for (key, lv) in l {
+                    let child = format!("{pointer}/{}", escape(key));
+                    match r.get(key) {
+                        Some(rv) => {
+                            let right_child = format!("{right_pointer}/{}", escape(key));
+                            self.diff(lv, rv, &child, &right_child, changes, patch);
+                        }
+                        None if self.ignored(&child) => {}
+                        None => {
+                            changes.push(removed(&child, lv));
+                            patch.push(json!({ "op": "remove", "path": child }));
+                        }
+                    }
+                }
+                for (key, rv) in r.iter().filter(|(key, _)| !l.contains_key(*key)) {
+                    let child = format!("{pointer}/{}", escape(key));
+                    if !self.ignored(&child) {
+                        let right_child = format!("{right_pointer}/{}", escape(key));
+                        changes.push(added(&right_child, rv));
+                        patch.push(json!({ "op": "add", "path": child, "value": rv }));
+                    }
+                }
+            }
+            (Value::Array(l), Value::Array(r)) if self.options.ignore_array_order => {
+                self.diff_unordered(l, r, pointer, right_pointer, changes, patch);
+            }
+            (Value::Array(l), Value::Array(r)) => {
+                for (i, (lv, rv)) in l.iter().zip(r).enumerate() {
+                    let child = format!("{pointer}/{i}");
+                    let right_child = format!("{right_pointer}/{i}");
+                    self.diff(lv, rv, &child, &right_child, changes, patch);
+                }
+                // 从后往前删除，保证 patch 中的下标依次有效
+                for (i, lv) in l.iter().enumerate().skip(r.len()).rev() {
+                    let child = format!("{pointer}/{i}");
+                    if !self.ignored(&child) {
+                        changes.push(removed(&child, lv));
+                        patch.push(json!({ "op": "remove", "path": child }));
+                    }
+                }
+                for (i, rv) in r.iter().enumerate().skip(l.len()) {
+                    let child = format!("{pointer}/{i}");
+                    if !self.ignored(&child) {
+                        changes.push(added(&format!("{right_pointer}/{i}"), rv));
+                        patch.push(json!({ "op": "add", "path": child, "value": rv }));
+                    }
+                }
+            }
+            (l, r) if l == r => {}
+            (l, r) => {
+                changes.push(Change {
+                    kind: ChangeKind::Changed,
+                    pointer: pointer.to_string(),
+                    left: Some(l.clone()),
+                    right: Some(r.clone()),
+                });
+                patch.push(json!({ "op": "replace", "path": pointer, "value": r }));
+            }
+        }
+    }
+
+    /// 忽略顺序对比数组：每个左侧元素与第一个相等且未配对的右侧元素配对
+    fn diff_unordered(
+        &self,
+        l: &[Value],
+        r: &[Value],
+        pointer: &str,
+        right_pointer: &str,
+        changes: &mut Vec<Change>,
+        patch: &mut Vec<Value>,
+    ) {
+        let mut matched = vec![false; r.len()];
+        let mut unmatched = Vec::new();
+        for (i, lv) in l.iter().enumerate() {
+            let child = format!("{pointer}/{i}");
+            if self.ignored(&child) {
+                continue;
+            }
+            let found = r
+                .iter()
+                .enumerate()
+                .position(|(j, rv)| !matched[j] && self.equal(lv, rv, &child));
+            match found {
+                Some(j) => matched[j] = true,
+                None => unmatched.push((i, lv)),
+            }
+        }
+        for (i, lv) in unmatched.iter() {
+            changes.push(removed(&format!("{pointer}/{i}"), lv));
+        }
+        for (i, _) in unmatched.iter().rev() {
+            patch.push(json!({ "op": "remove", "path": format!("{pointer}/{i}") }));
+        }
+        for (j, rv) in r.iter().enumerate().filter(|(j, _)| !matched[*j]) {
+            if !self.ignored(&format!("{pointer}/{j}")) {
+                changes.push(added(&format!("{right_pointer}/{j}"), rv));
+                patch.push(json!({ "op": "add", "path": format!("{pointer}/-"), "value": rv }));
+            }
+        }
+    }
+
+    /// 没有差异时返回 `None`。Merge Patch 中 null 表示删除，所以右侧新增或改为 null 的字段无法表达
+    fn merge_patch(&self, left: &Value, right: &Value, pointer: &str) -> Option<Value> {
+        if self.ignored(pointer) {
+            return None;
+        }
+        match (left, right) {
+            (Value::Object(l), Value::Object(r)) => {
+                let mut patch = Map::new();
+                for (key, lv) in l {
+                    let child = format!("{pointer}/{}", escape(key));
+                    let value = match r.get(key) {
+                        Some(rv) => self.merge_patch(lv, rv, &child),
+                        None if self.ignored(&child) => None,
+                        None => Some(Value::Null),
+                    };
+                    if let Some(value) = value {
+                        patch.insert(key.clone(), value);
+                    }
+                }
+                for (key, rv) in r.iter().filter(|(key, _)| !l.contains_key(*key)) {
+                    if !self.ignored(&format!("{pointer}/{}", escape(key))) {
+                        patch.insert(key.clone(), rv.clone());
+                    }
+                }
+                (!patch.is_empty()).then_some(Value::Object(patch))
+            }
+            // 数组只能整体替换
+            (l, r) => (!self.equal(l, r, pointer)).then(|| r.clone()),
+        }
+    }
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn added(pointer: &str, value: &Value) -> Change {
+    Change {
+        kind: ChangeKind::Added,
+        pointer: pointer.to_string(),
+        left: None,
+        right: Some(value.clone()),
+    }
+}
+
+fn removed(pointer: &str, value: &Value) -> Change {
+    Change {
+        kind: ChangeKind::Removed,
+        pointer: pointer.to_string(),
+        left: Some(value.clone()),
+        right: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let left = json!({ "a": 1, "b": [1, 2, 3], "c": { "d": "x", "e/f": true } });
+        let right = json!({ "c": { "e/f": false, "d": "x" }, "a": 1, "b": [1, 5], "g": null });
+        let diff = JsonDiff::from_values(left, right, &DiffOptions::default());
+
+        let summary = diff
+            .changes
+            .iter()
+            .map(|c| (c.kind, c.pointer.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (ChangeKind::Changed, "/b/1"),
+                (ChangeKind::Removed, "/b/2"),
+                (ChangeKind::Changed, "/c/e~1f"),
+                (ChangeKind::Added, "/g"),
+            ],
+            summary
+        );
+        assert_eq!(
+            json!([
+                { "op": "replace", "path": "/b/1", "value": 5 },
+                { "op": "remove", "path": "/b/2" },
+                { "op": "replace", "path": "/c/e~1f", "value": false },
+                { "op": "add", "path": "/g", "value": null },
+            ]),
+            diff.patch
+        );
+        assert_eq!(
+            json!({ "b": [1, 5], "c": { "e/f": false }, "g": null }),
+            diff.merge_patch
+        );
+        // 新增的 null 值保留 right，缺失的 left 不输出
+        assert_eq!(
+            json!({ "kind": "added", "pointer": "/g", "right": null }),
+            serde_json::to_value(&diff.changes[3]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_diff_options() {
+        let left = json!({ "items": [{ "id": 1, "at": 1 }, { "id": 2, "at": 1 }, { "id": 3 }] });
+        let right = json!({ "items": [{ "id": 2, "at": 2 }, { "id": 4 }, { "id": 1, "at": 2 }] });
+
+        let options = DiffOptions {
+            ignore_array_order: true,
+            ignore_paths: vec!["/items/*/at".into()],
+        };
+        let diff = JsonDiff::from_values(left.clone(), right.clone(), &options);
+        assert_eq!(
+            vec![
+                removed("/items/2", &json!({ "id": 3 })),
+                added("/items/1", &json!({ "id": 4 })),
+            ],
+            diff.changes
+        );
+        assert_eq!(
+            json!([
+                { "op": "remove", "path": "/items/2" },
+                { "op": "add", "path": "/items/-", "value": { "id": 4 } },
+            ]),
+            diff.patch
+        );
+        assert_eq!(json!({ "items": right["items"] }), diff.merge_patch);
+
+        let options = DiffOptions {
+            ignore_array_order: false,
+            ignore_paths: vec!["/items".into()],
+        };
+        let diff = JsonDiff::from_values(left, right, &options);
+        assert!(diff.changes.is_empty());
+        assert_eq!(json!([]), diff.patch);
+        assert_eq!(json!({}), diff.merge_patch);
+    }
+}
@@ -0,0 +1,141 @@
+//! JSON Schema 校验
+//!
+//! 草案版本按 `$schema` 识别，未声明时使用 2020-12。`$ref` 只解析本地文件：
+//! 相对路径基于 `base_dir`，引用的文件须为 JSON。
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Error, Result};
+use jsonschema::{Retrieve, Uri};
+use serde::Serialize;
+use serde_json::Value;
+
+/// 一条校验错误
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Violation {
+    /// 出错节点的 JSON Pointer，根节点为空字符串
+    pub pointer: String,
+    /// 未通过的 Schema 关键字位置
+    pub schema_path: String,
+    pub message: String,
+}
+
+pub fn validate(
+    instance: &Value,
+    schema: &Value,
+    base_dir: Option<&Path>,
+) -> Result<Vec<Violation>> {
+    let mut options = jsonschema::options().with_retriever(FileRetriever);
+    if let Some(dir) = base_dir {
+        options = options.with_base_uri(dir_uri(dir)?);
+    }
+    let validator = options
+        .build(schema)
+        .map_err(|e| Error::msg(format!("Schema 无效: {e}")))?;
+    Ok(validator
+        .iter_errors(instance)
+        .map(|e| Violation {
+            pointer: e.instance_path().as_str().to_string(),
+            schema_path: e.schema_path().as_str().to_string(),
+            message: e.to_string(),
+        })
+        .collect())
+}
+
+/// 目录转为以 `/` 结尾的 `file://` URI，路径各段做百分号编码
+fn dir_uri(dir: &Path) -> Result<String> {
+    let dir = if dir.is_absolute() {
+        dir.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(dir)
+    };
+    let path = dir.to_string_lossy().replace('\\', "/");
+    let encoded = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| urlencoding::encode(s).into_owned())
+        .collect::<Vec<_>>();
+    Ok(format!("file:///{}/", encoded.join("/")))
+}
+
+/// 读取 `file://` 引用的 Schema
+struct FileRetriever;
+
+impl Retrieve for FileRetriever {
+    fn retrieve(
+        &self,
+        uri: &Uri<String>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        if uri.scheme().as_str() != "file" {
+            return Err(format!("只支持引用本地文件: {}", uri.as_str()).into());
+        }
+        let path = urlencoding::decode(uri.path().as_str())?.into_owned();
+        // Windows 下路径形如 /C:/dir/a.json
+        let path = match path.strip_prefix('/') {
+            Some(p) if p.get(1..2) == Some(":") => PathBuf::from(p),
+            _ => PathBuf::from(path),
+        };
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("读取 {} 失败: {e}", path.display()))?;
+        Ok(serde_json::from_str(&text)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_validate() -> Result<()> {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+            },
+            "required": ["name"],
+        });
+        assert!(validate(&json!({ "name": "a", "tags": ["x"] }), &schema, None)?.is_empty());
+
+        let violations = validate(&json!({ "tags": ["x", 1] }), &schema, None)?;
+        let pointers = violations
+            .iter()
+            .map(|v| v.pointer.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["", "/tags/1"], pointers);
+        assert_eq!("/required", violations[0].schema_path);
+        assert!(violations[1].message.contains("is not of type"));
+
+        assert!(validate(&json!(1), &json!({ "type": 1 }), None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_ref() -> Result<()> {
+        // 目录名带空格，测试结束时自动删除
+        let temp = tempfile::Builder::new()
+            .prefix("toolbox schema ref")
+            .tempdir()?;
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("defs"))?;
+        std::fs::write(
+            dir.join("defs/port.json"),
+            r#"{ "type": "integer", "maximum": 65535 }"#,
+        )?;
+        let schema = json!({
+            "properties": { "port": { "$ref": "defs/port.json" } },
+        });
+
+        let violations = validate(&json!({ "port": 70000 }), &schema, Some(dir))?;
+        assert_eq!(1, violations.len());
+        assert_eq!("/port", violations[0].pointer);
+        assert!(validate(&json!({ "port": 80 }), &schema, Some(dir))?.is_empty());
+
+        let missing = json!({ "$ref": "missing.json" });
+        assert!(validate(&json!(1), &missing, Some(dir)).is_err());
+        Ok(())
+    }
+}
@@ -2,130 +2,236 @@
 use std::{
     collections::HashMap,
     net::{Ipv4Addr, Ipv6Addr},
+    path::Path,
     str::FromStr,
+    sync::atomic::AtomicU64,
 };
 
 use anyhow::{Error, Result};
+pub use cert::{
+    CertBundle, CertExtension, CertInfo, CsrInfo, LinkStatus, SelfSignedCertificate,
+    SelfSignedOptions, ValidityStatus,
+};
+pub use cffc::{
+    Confidence, Detection, FormatOptions, Ft, Rejected, StreamFt, TomlTables, XmlOptions, YamlStyle,
+};
 pub use charset::RecoverGarbledCode;
+pub use cipher::{CipherAlgorithm, CipherMode, CipherOptions, Padding};
+pub use cron::{CronDialect, CronSchedule};
+pub use datetime::{TimeConversion, TimeUnit, ZonedTime};
+pub use encoding::{Encoded, Encoding};
+use indexmap::IndexMap;
+pub use json_diff::{Change, ChangeKind, DiffOptions, JsonDiff};
+pub use json_schema::Violation;
+pub use jwt::{ClaimStatus, TimeClaim, Verification};
+pub use keypair::{KeyAlgorithm, KeyConvertOptions, KeyFormat, KeyInfo, KeyPair};
+pub use password::{PasswordAlgorithm, PasswordOptions, PasswordVerification};
+pub use query::{Match, QueryLang};
 use serde::{Deserialize, Serialize};
+pub use url::{QueryParam, UrlParts};
+pub use uuid::{IdKind, IdOptions, SnowflakeConfig};
 
+pub use crate::base64::{Base64Options, ImageInfo, MIME_LINE_WIDTH};
 use crate::checksum::Checksum;
+pub use crate::checksum::{
+    ManifestEntry, ManifestProgress, ManifestStyle, VerifyEntry, VerifyReport, VerifyStatus,
+    format_manifest, parse_manifest,
+};
 
 mod base64;
+mod cert;
 mod cffc;
 mod charset;
 mod checksum;
+mod cipher;
+mod cron;
 mod datetime;
-mod hash;
+pub mod encoding;
+pub mod hash;
 mod hex;
 mod ip;
+mod json_diff;
+mod json_schema;
+mod jwe;
 mod jwt;
+mod keypair;
+mod password;
 mod qrcode;
+mod query;
 mod string;
+mod typegen;
 mod url;
 mod url_params;
 pub mod uuid;
 
+/// 计算输入的摘要，`algorithms` 为空时使用 `hash::DEFAULT_ALGORITHMS`
+///
+/// `input_type` 为 `text`（默认）、`hex` 或 `base64`；HMAC 模式下跳过不支持 HMAC 的算法
+#[allow(clippy::too_many_arguments)]
 pub async fn hash(
     uppercase: bool,
     output_type: Option<&str>,
     hmac_mode: bool,
     secret: Option<&str>,
     input: Option<&str>,
+    input_type: Option<&str>,
+    algorithms: &[&str],
 ) -> Result<HashMap<String, String>> {
-    let mut map = HashMap::with_capacity(6);
+    let mut map = HashMap::new();
 
     if let Some(input) = input {
-        let (md5, sha1, sha256, sha512, sha3_256, sha3_512) = if hmac_mode {
-            let secret = secret.unwrap_or("");
-            let (md5, sha1, sha256, sha512, sha3_256, sha3_512) = tokio::join!(
-                hash::hmac_md5(secret, input),
-                hash::hmac_sha1(secret, input),
-                hash::hmac_sha256(secret, input),
-                hash::hmac_sha512(secret, input),
-                hash::hmac_sha3_256(secret, input),
-                hash::hmac_sha3_512(secret, input),
-            );
-            (md5?, sha1?, sha256?, sha512?, sha3_256?, sha3_512?)
+        let data = hash::decode_input(input, input_type)?;
+        let names = if algorithms.is_empty() {
+            &hash::DEFAULT_ALGORITHMS[..]
         } else {
-            let (md5, sha1, sha256, sha512, sha3_256, sha3_512) = tokio::join!(
-                hash::md5(input),
-                hash::sha1(input),
-                hash::sha256(input),
-                hash::sha512(input),
-                hash::sha3_256(input),
-                hash::sha3_512(input),
-            );
-            (md5?, sha1?, sha256?, sha512?, sha3_256?, sha3_512?)
+            algorithms
         };
-
-        let mut values = [
-            ("md5", md5),
-            ("sha1", sha1),
-            ("sha256", sha256),
-            ("sha512", sha512),
-            ("sha3_256", sha3_256),
-            ("sha3_512", sha3_512),
-        ];
-
-        // 输出编码：base64 时把十六进制解码为字节再 base64 编码
-        if output_type == Some("base64") {
-            use ::base64::Engine as _;
-            for (_, val) in values.iter_mut() {
-                if let Ok(bytes) = hex::decode(val.as_str()) {
-                    *val = ::base64::engine::general_purpose::STANDARD.encode(&bytes);
+        for name in names {
+            let algorithm = hash::algorithm(name)?;
+            let bytes = if hmac_mode {
+                if !algorithm.supports_hmac() {
+                    continue;
                 }
-            }
-        }
-
-        if uppercase {
-            for (_, val) in values.iter_mut() {
-                *val = val.to_uppercase();
-            }
-        }
-
-        for (key, val) in values {
-            map.insert(key.into(), val);
+                algorithm.hmac(secret.unwrap_or("").as_bytes(), &data)?
+            } else {
+                algorithm.digest(&data)
+            };
+            map.insert(
+                algorithm.name.to_string(),
+                hash::encode_output(&bytes, output_type, uppercase),
+            );
         }
     }
     Ok(map)
 }
 
+/// 读取一遍文件，同时计算多个算法的摘要
+pub async fn hash_file(
+    file_path: &str,
+    algorithms: &[&str],
+    output_type: Option<&str>,
+    uppercase: bool,
+) -> Result<IndexMap<String, String>> {
+    let algorithms = algorithms
+        .iter()
+        .map(|name| hash::algorithm(name))
+        .collect::<Result<Vec<_>>>()?;
+    let file = std::fs::File::open(file_path)?;
+    let digests = hash::digest_reader(std::io::BufReader::new(file), &algorithms)?;
+    Ok(algorithms
+        .iter()
+        .zip(digests)
+        .map(|(algorithm, bytes)| {
+            (
+                algorithm.name.to_string(),
+                hash::encode_output(&bytes, output_type, uppercase),
+            )
+        })
+        .collect())
+}
+
 pub async fn checksum(r#type: &str, file_path: &str) -> Result<String> {
     Checksum::sum(r#type, file_path).await
 }
 
+/// 为目录下的所有文件生成校验清单，`style` 为 `gnu`（默认）或 `bsd`
+pub async fn checksum_manifest(
+    dir: &str,
+    algorithm: &str,
+    style: Option<&str>,
+    progress: impl FnMut(&ManifestProgress),
+) -> Result<String> {
+    let style = style
+        .map(ManifestStyle::try_from)
+        .transpose()?
+        .unwrap_or_default();
+    let entries = Checksum::manifest(std::path::Path::new(dir), algorithm, progress).await?;
+    checksum::format_manifest(&entries, style)
+}
+
+/// 算法对应的默认清单文件名，如 `SHA256SUMS`
+pub fn checksum_manifest_name(algorithm: &str) -> String {
+    checksum::manifest_name(algorithm)
+}
+
+/// 按清单校验文件，`dir` 为空时使用清单所在目录
+pub async fn checksum_verify(
+    manifest_path: &str,
+    dir: Option<&str>,
+    algorithm: Option<&str>,
+    progress: impl FnMut(&ManifestProgress),
+) -> Result<VerifyReport> {
+    Checksum::verify(
+        std::path::Path::new(manifest_path),
+        dir.map(std::path::Path::new),
+        algorithm,
+        progress,
+    )
+    .await
+}
+
+/// 用随机盐计算密码哈希，bcrypt 输出 `$2b$` 格式，其余为 PHC 字符串
+pub fn hash_password(password: &str, options: &PasswordOptions) -> Result<String> {
+    password::hash(password, options)
+}
+
+/// 校验密码与 bcrypt 或 PHC 格式的哈希是否匹配
+pub fn verify_password(password: &str, hash: &str) -> Result<PasswordVerification> {
+    password::verify(password, hash)
+}
+
+/// 对称加密，输入输出的格式由 `options` 中的 `input_type`、`output_type` 指定
+pub fn cipher_encrypt(input: &str, options: &CipherOptions) -> Result<String> {
+    cipher::encrypt(input, options)
+}
+
+/// 对称解密，输出为 text 时要求结果是合法的 UTF-8
+pub fn cipher_decrypt(input: &str, options: &CipherOptions) -> Result<String> {
+    cipher::decrypt(input, options)
+}
+
+/// 生成指定字节数的随机密钥或 IV
+pub fn cipher_random(len: usize, output_type: &str) -> Result<String> {
+    charset::format_bytes_to_string(&cipher::random_bytes(len), output_type, "", "")
+}
+
+/// 生成非对称密钥对，私钥为 PKCS#8 PEM，公钥为 SPKI PEM
+pub fn key_generate(algorithm: KeyAlgorithm, rsa_bits: usize) -> Result<KeyPair> {
+    keypair::generate(algorithm, rsa_bits)
+}
+
+/// 识别 PEM、DER、JWK 或 OpenSSH 格式的密钥，给出算法、长度与指纹
+pub fn key_inspect(input: &str) -> Result<KeyInfo> {
+    keypair::inspect(input)
+}
+
+/// 在 PKCS#1、PKCS#8、SEC1、SPKI、JWK 与 OpenSSH 之间转换密钥
+pub fn key_convert(input: &str, options: &KeyConvertOptions) -> Result<String> {
+    keypair::convert(input, options)
+}
+
+/// 用私钥对文本签名，返回 Base64 编码的签名
+pub fn key_sign(key: &str, message: &str) -> Result<String> {
+    keypair::sign(key, message)
+}
+
+/// 校验文本的签名，密钥可以是公钥或私钥
+pub fn key_verify(key: &str, message: &str, signature: &str) -> Result<bool> {
+    keypair::verify(key, message, signature)
+}
+
+/// 生成 UUID、ULID、KSUID、Snowflake、NanoID 或 CUID2，种类和参数见 `IdOptions`
 pub fn uuid(
     hyphens: Option<&str>,
     uppercase: bool,
     remove_connector: bool,
-    version: u8,
     number: u16,
+    options: &IdOptions,
 ) -> Result<Vec<String>> {
-    let mut uuids = match version {
-        1 => (0..number)
-            .map(|_| uuid::uuid_v1())
-            .collect::<Result<Vec<_>>>(),
-        3 => (0..number)
-            .map(|_| uuid::uuid_v3("", ""))
-            .collect::<Result<Vec<_>>>(),
-        4 => (0..number)
-            .map(|_| uuid::uuid_v4())
-            .collect::<Result<Vec<_>>>(),
-        5 => (0..number)
-            .map(|_| uuid::uuid_v5("", ""))
-            .collect::<Result<Vec<_>>>(),
-        6 => (0..number)
-            .map(|_| uuid::uuid_v6())
-            .collect::<Result<Vec<_>>>(),
-        7 => (0..number)
-            .map(|_| uuid::uuid_v7())
-            .collect::<Result<Vec<_>>>(),
-        8 => (0..number)
-            .map(|_| uuid::uuid_v8())
-            .collect::<Result<Vec<_>>>(),
-        _ => return Err(Error::msg("unsupport version".to_string())),
-    }?;
+    let mut generator = uuid::IdGenerator::new(options.clone())?;
+    let mut uuids = (0..number)
+        .map(|_| generator.next_id())
+        .collect::<Result<Vec<_>>>()?;
 
     if uppercase {
         uuids = uuids.iter().map(|u| u.to_uppercase()).collect::<Vec<_>>();
@@ -136,14 +242,50 @@ pub fn uuid(
     Ok(uuids)
 }
 
-pub fn encode_base64_text(data: &str) -> Result<String> {
-    base64::encode_text(data)
+/// 解析 UUID、ULID、KSUID、Snowflake、NanoID 或 CUID2，给出版本、内嵌时间等信息。
+/// `kind` 为空时自动识别
+pub fn inspect_id(
+    id: &str,
+    kind: Option<&str>,
+    snowflake: &SnowflakeConfig,
+) -> Result<uuid::IdInfo> {
+    let kind = kind.map(IdKind::try_from).transpose()?;
+    uuid::inspect(id, kind, snowflake)
 }
 
+pub fn encode_base64(data: &[u8], options: &Base64Options) -> String {
+    base64::encode(data, options)
+}
+
+pub fn encode_base64_text(data: &str, options: &Base64Options) -> Result<String> {
+    base64::encode_text(data, options)
+}
+
+/// 自动识别标准与 URL 安全字母表，忽略换行和填充
 pub fn decode_base64_text(data: &str) -> Result<String> {
     base64::decode_text(data)
 }
 
+/// 按 `input_type`（`text`、`hex` 或 `base64`）读取输入，给出 Base32、Base58、Bech32 等全部编码
+pub fn encode_binary(input: &str, input_type: Option<&str>, hrp: &str) -> Result<Vec<Encoded>> {
+    encoding::encode_all(&hash::decode_input(input, input_type)?, hrp)
+}
+
+/// 按指定编码解码，Bech32 的前缀会被丢弃
+pub fn decode_binary(encoding: Encoding, input: &str) -> Result<Vec<u8>> {
+    encoding::decode(encoding, input)
+}
+
+/// 把图片编码为 Base64 或 `data:image/...;base64,` 形式的 data URI
+pub fn encode_base64_img(data: &[u8], options: &Base64Options, data_uri: bool) -> Result<String> {
+    base64::encode_img(data, options, data_uri)
+}
+
+/// 解码 Base64 或 data URI 形式的图片，返回识别出的图片信息与图片字节
+pub fn decode_base64_img(data: &str) -> Result<(ImageInfo, Vec<u8>)> {
+    base64::decode_img(data)
+}
+
 pub fn encode_url(data: &str) -> Result<String> {
     url::encode(data)
 }
@@ -152,31 +294,200 @@ pub fn decode_url(data: &str) -> Result<String> {
     url::decode(data)
 }
 
+/// 按 RFC 3986 拆分 URL：协议、用户信息、主机（含国际化域名）、端口、路径、查询参数与片段
+pub fn parse_url(data: &str) -> Result<UrlParts> {
+    url::parse(data)
+}
+
+/// 由编辑后的各部分重新拼出 URL
+pub fn build_url(parts: &UrlParts) -> Result<String> {
+    url::build(parts)
+}
+
+/// 查询字符串转 JSON，重复的键和 `a[]=` 转为数组，`a[b]=` 转为嵌套对象
+pub fn url_params_to_json(data: &str) -> Result<String> {
+    url_params::parse(data)
+}
+
+/// JSON 对象转查询字符串，是 [`url_params_to_json`] 的逆操作
+pub fn json_to_url_params(data: &str) -> Result<String> {
+    url_params::stringify(data)
+}
+
 pub fn decode_jwt(data: &str) -> Result<String> {
     jwt::decode(data)
 }
 
+/// 解码 JWS 或 JWE，用 `key` 解密 JWE 并递归解码嵌套的 Token
+pub fn decrypt_jwt(data: &str, key: &str) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&jwt::decode_value(
+        data,
+        Some(key),
+    )?)?)
+}
+
+/// 验证 JWT 签名并检查 `exp`、`nbf`、`iat`，`key` 为 HMAC 密钥、PEM 公钥、JWK 或 JWKS。
+/// 允许的算法由密钥的形式决定，`algorithm` 指定时 Header 的 `alg` 必须与之相同
+pub fn verify_jwt(token: &str, key: &str, algorithm: Option<&str>) -> Result<Verification> {
+    jwt::verify(token, key, algorithm)
+}
+
+/// JWT 中 `exp`、`nbf`、`iat` 相对当前时间的有效性
+pub fn jwt_time_claims(token: &str) -> Result<Vec<TimeClaim>> {
+    jwt::time_claims(token)
+}
+
+/// 由 Header 和 Payload 的 JSON 签发 JWT，`key` 为 HMAC 密钥或 PEM 私钥
+pub fn sign_jwt(header: &str, payload: &str, key: &str) -> Result<String> {
+    jwt::sign(header, payload, key)
+}
+
+/// 解析 PEM/DER 证书、CSR 或 PKCS#12，把证书整理成链并离线校验每一级签名，`password` 只用于 PKCS#12
+pub fn cert_decode(input: &[u8], password: &str) -> Result<CertBundle> {
+    cert::decode(input, password)
+}
+
+/// 生成本地测试用的自签名证书，未提供私钥时一并生成
+pub fn cert_self_signed(options: &SelfSignedOptions) -> Result<SelfSignedCertificate> {
+    cert::self_signed(options)
+}
+
+/// 用 JSON Schema 校验 JSON，`base_dir` 为解析 `$ref` 相对路径的目录
+pub fn validate_json_schema(
+    input: &str,
+    schema: &str,
+    base_dir: Option<&str>,
+) -> Result<Vec<Violation>> {
+    let instance =
+        serde_json::from_str(input).map_err(|e| Error::msg(format!("JSON 无效: {e}")))?;
+    let schema = serde_json::from_str(schema)
+        .map_err(|e| Error::msg(format!("Schema 不是合法的 JSON: {e}")))?;
+    json_schema::validate(&instance, &schema, base_dir.map(std::path::Path::new))
+}
+
+/// 结构化对比两份 JSON/YAML/TOML，忽略键的顺序，并生成 JSON Patch 和 JSON Merge Patch
+pub fn json_diff(ft: &str, left: &str, right: &str, options: &DiffOptions) -> Result<JsonDiff> {
+    JsonDiff::new(cffc::Ft::from(ft), left, right, options)
+}
+
+/// 用 JSONPath、JMESPath 或 jq 查询 JSON，jq 过滤器运行超过 5 秒时报错
+pub fn query_json(lang: &str, input: &str, expr: &str) -> Result<Vec<Match>> {
+    let value = serde_json::from_str(input).map_err(|e| Error::msg(format!("JSON 无效: {e}")))?;
+    query::query(QueryLang::from(lang), &value, expr)
+}
+
+/// JSON Pointer 指向的节点在 JSONPath、JMESPath 或 jq 中的路径
+pub fn json_node_path(lang: &str, value: &serde_json::Value, pointer: &str) -> Option<String> {
+    query::node_path(QueryLang::from(lang), value, pointer)
+}
+
 pub fn cffc(indent: u8, ft: &str, tt: &str, input: &str) -> Result<String> {
     cffc::Data::new(cffc::Ft::from(ft), cffc::Ft::from(tt), input, indent).transform()
 }
 
+/// 识别输入的文件格式，附带可信度和被排除的候选格式的解析错误
+pub fn cffc_detect(input: &str) -> Detection {
+    cffc::detect(input)
+}
+
+/// 文件格式转换，自定义输出格式选项以及 XML 的属性前缀、文本节点键和根元素名
+pub fn cffc_with_options(
+    ft: &str,
+    tt: &str,
+    input: &str,
+    style: FormatOptions,
+    xml: XmlOptions,
+) -> Result<String> {
+    cffc::Data::new(cffc::Ft::from(ft), cffc::Ft::from(tt), input, style.indent)
+        .style(style)
+        .xml(xml)
+        .transform()
+}
+
+/// 大文件格式转换：边读边写，不把整个文件载入为 `Value`，`read` 累计已读取的字节数。
+/// 输出与源文件相同时报错
+pub fn cffc_stream(
+    indent: u8,
+    ft: &str,
+    tt: &str,
+    input_path: &str,
+    output_path: &str,
+    read: &AtomicU64,
+) -> Result<()> {
+    let from = StreamFt::try_from(ft)?;
+    let to = StreamFt::try_from(tt)?;
+    cffc::transcode_file(
+        from,
+        to,
+        Path::new(input_path),
+        Path::new(output_path),
+        indent,
+        read,
+    )
+}
+
+/// 根据样本数据生成类型定义，多个样本之间用单独一行的 `---` 分隔，合并推断可选字段和联合类型
+pub fn typegen(language: &str, ft: &str, root_name: &str, input: &str) -> Result<String> {
+    typegen::TypeGen {
+        language: typegen::Language::from(language),
+        root_name: root_name.into(),
+        from: cffc::Ft::from(ft),
+        samples: typegen::split_samples(input),
+    }
+    .run()
+}
+
 pub fn timestamp(time: Option<&str>) -> Result<HashMap<String, String>> {
     let mut map = HashMap::with_capacity(5);
     let Some(time) = time else {
         return Ok(map);
     };
 
-    if let Ok(time) = time.parse::<i64>() {
-        map.insert("format".to_string(), datetime::timestamp_to_str(time)?);
-    } else {
-        map.insert(
-            "format".to_string(),
-            datetime::str_to_timestamp(time)?.to_string(),
-        );
+    let conversion = datetime::convert(time, None, "UTC", &["UTC"])?;
+    match conversion.unit {
+        // 时间戳转为 UTC 时间，并给出识别出的单位
+        Some(unit) => {
+            let unit = serde_json::to_value(unit)?;
+            map.insert("unit".to_string(), unit.as_str().unwrap_or_default().into());
+            map.insert("format".to_string(), conversion.zones[0].datetime.clone());
+        }
+        None => {
+            map.insert("format".to_string(), conversion.seconds.to_string());
+        }
     }
     Ok(map)
 }
 
+/// 时间戳或时间字符串转换，在 `zones` 中的各个时区展示同一时刻。
+/// `unit` 为空时按位数识别时间戳单位，14 位的合法日期按紧凑格式的时间解析；
+/// `input_zone` 为不带时区的时间字符串所在的时区，默认 UTC
+pub fn convert_time(
+    input: &str,
+    unit: Option<&str>,
+    input_zone: Option<&str>,
+    zones: &[&str],
+) -> Result<TimeConversion> {
+    let unit = unit.map(TimeUnit::try_from).transpose()?;
+    datetime::convert(input, unit, input_zone.unwrap_or("UTC"), zones)
+}
+
+/// 解析 Unix、Quartz 或 systemd `OnCalendar` 表达式，给出中英文说明和 `zone` 时区中接下来的 `count` 个触发时间。
+/// `dialect` 为空时自动识别，表达式永远不会触发时报错
+pub fn cron(
+    expr: &str,
+    dialect: Option<&str>,
+    zone: Option<&str>,
+    count: usize,
+) -> Result<CronSchedule> {
+    let dialect = dialect.map(CronDialect::try_from).transpose()?;
+    cron::schedule(expr, dialect, zone.unwrap_or("UTC"), count)
+}
+
+/// 所有 IANA 时区名
+pub fn time_zones() -> Vec<&'static str> {
+    datetime::zone_names()
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Base {
@@ -0,0 +1,277 @@
+//! 密码哈希：bcrypt、scrypt、Argon2 与 PBKDF2，除 bcrypt 外均输出 PHC 字符串
+
+use anyhow::{Error, Result};
+use argon2::Argon2;
+use password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng};
+use serde::{Deserialize, Serialize};
+
+/// bcrypt 只使用密码的前 72 个字节
+const BCRYPT_MAX_BYTES: usize = 72;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PasswordAlgorithm {
+    #[default]
+    Argon2id,
+    Argon2i,
+    Argon2d,
+    Bcrypt,
+    Scrypt,
+    Pbkdf2Sha256,
+}
+
+impl PasswordAlgorithm {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Argon2id => "argon2id",
+            Self::Argon2i => "argon2i",
+            Self::Argon2d => "argon2d",
+            Self::Bcrypt => "bcrypt",
+            Self::Scrypt => "scrypt",
+            Self::Pbkdf2Sha256 => "pbkdf2_sha256",
+        }
+    }
+}
+
+impl TryFrom<&str> for PasswordAlgorithm {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value.to_lowercase().replace('-', "_").as_ref() {
+            "argon2id" | "argon2" => Ok(Self::Argon2id),
+            "argon2i" => Ok(Self::Argon2i),
+            "argon2d" => Ok(Self::Argon2d),
+            "bcrypt" => Ok(Self::Bcrypt),
+            "scrypt" => Ok(Self::Scrypt),
+            // PHC 字符串中的 `$pbkdf2$` 表示 PBKDF2-SHA1，不能当作 SHA-256
+            "pbkdf2_sha256" => Ok(Self::Pbkdf2Sha256),
+            _ => Err(Error::msg(format!("不支持的密码哈希算法: {value}"))),
+        }
+    }
+}
+
+/// 密码哈希的算法与代价参数，默认值参照 OWASP 的建议
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PasswordOptions {
+    pub algorithm: PasswordAlgorithm,
+    /// bcrypt 的 cost，4~31
+    pub bcrypt_cost: u32,
+    /// scrypt 的 log₂N
+    pub scrypt_log_n: u8,
+    pub scrypt_r: u32,
+    pub scrypt_p: u32,
+    /// Argon2 的内存，单位 KiB
+    pub argon2_memory: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+    pub pbkdf2_rounds: u32,
+}
+
+impl Default for PasswordOptions {
+    fn default() -> Self {
+        Self {
+            algorithm: PasswordAlgorithm::Argon2id,
+            bcrypt_cost: 12,
+            scrypt_log_n: 17,
+            scrypt_r: 8,
+            scrypt_p: 1,
+            argon2_memory: 19 * 1024,
+            argon2_iterations: 2,
+            argon2_parallelism: 1,
+            pbkdf2_rounds: 600_000,
+        }
+    }
+}
+
+impl PasswordOptions {
+    /// 批量生成测试数据时使用的低代价参数，每个哈希只需几毫秒，不适合保存真实密码
+    pub fn bulk(algorithm: PasswordAlgorithm) -> Self {
+        Self {
+            algorithm,
+            bcrypt_cost: 4,
+            scrypt_log_n: 10,
+            argon2_memory: 1024,
+            argon2_iterations: 1,
+            pbkdf2_rounds: 1000,
+            ..Default::default()
+        }
+    }
+}
+
+/// 校验结果，附带从哈希中读出的参数
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordVerification {
+    pub valid: bool,
+    pub algorithm: PasswordAlgorithm,
+    /// 哈希中的参数，按出现顺序排列，例如版本、代价、盐
+    pub params: Vec<(String, String)>,
+}
+
+/// 用随机盐计算密码哈希
+pub fn hash(password: &str, options: &PasswordOptions) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = match options.algorithm {
+        PasswordAlgorithm::Bcrypt => {
+            if password.len() > BCRYPT_MAX_BYTES {
+                return Err(Error::msg(format!(
+                    "bcrypt 只使用密码的前 {BCRYPT_MAX_BYTES} 个字节，请缩短密码或换用其它算法"
+                )));
+            }
+            return Ok(bcrypt::hash(password, options.bcrypt_cost)?);
+        }
+        PasswordAlgorithm::Argon2id | PasswordAlgorithm::Argon2i | PasswordAlgorithm::Argon2d => {
+            let algorithm = match options.algorithm {
+                PasswordAlgorithm::Argon2i => argon2::Algorithm::Argon2i,
+                PasswordAlgorithm::Argon2d => argon2::Algorithm::Argon2d,
+                _ => argon2::Algorithm::Argon2id,
+            };
+            let params = argon2::Params::new(
+                options.argon2_memory,
+                options.argon2_iterations,
+                options.argon2_parallelism,
+                None,
+            )
+            .map_err(|e| Error::msg(format!("Argon2 参数无效: {e}")))?;
+            Argon2::new(algorithm, argon2::Version::V0x13, params)
+                .hash_password(password.as_bytes(), &salt)
+        }
+        PasswordAlgorithm::Scrypt => {
+            let params = scrypt::Params::new(
+                options.scrypt_log_n,
+                options.scrypt_r,
+                options.scrypt_p,
+                scrypt::Params::RECOMMENDED_LEN,
+            )
+            .map_err(|e| Error::msg(format!("scrypt 参数无效: {e}")))?;
+            scrypt::Scrypt.hash_password_customized(password.as_bytes(), None, None, params, &salt)
+        }
+        PasswordAlgorithm::Pbkdf2Sha256 => pbkdf2::Pbkdf2.hash_password_customized(
+            password.as_bytes(),
+            Some(pbkdf2::Algorithm::Pbkdf2Sha256.ident()),
+            None,
+            pbkdf2::Params {
+                rounds: options.pbkdf2_rounds,
+                output_length: 32,
+            },
+            &salt,
+        ),
+    };
+    Ok(hash.map_err(|e| Error::msg(e.to_string()))?.to_string())
+}
+
+/// 校验密码与哈希是否匹配，算法由哈希的前缀识别
+pub fn verify(password: &str, hash: &str) -> Result<PasswordVerification> {
+    let hash = hash.trim();
+    if let Some(cost) = bcrypt_cost(hash) {
+        let valid = bcrypt::verify(password, hash)?;
+        return Ok(PasswordVerification {
+            valid,
+            algorithm: PasswordAlgorithm::Bcrypt,
+            params: Vec::from([
+                ("版本".to_string(), hash[1..3].to_string()),
+                ("cost".to_string(), cost.to_string()),
+                ("盐".to_string(), hash[7..29].to_string()),
+            ]),
+        });
+    }
+
+    let parsed =
+        PasswordHash::new(hash).map_err(|e| Error::msg(format!("无法识别的密码哈希: {e}")))?;
+    let algorithm = PasswordAlgorithm::try_from(parsed.algorithm.as_str())?;
+    let result = match algorithm {
+        PasswordAlgorithm::Argon2id | PasswordAlgorithm::Argon2i | PasswordAlgorithm::Argon2d => {
+            Argon2::default().verify_password(password.as_bytes(), &parsed)
+        }
+        PasswordAlgorithm::Scrypt => scrypt::Scrypt.verify_password(password.as_bytes(), &parsed),
+        PasswordAlgorithm::Pbkdf2Sha256 => {
+            pbkdf2::Pbkdf2.verify_password(password.as_bytes(), &parsed)
+        }
+        PasswordAlgorithm::Bcrypt => unreachable!("bcrypt 不使用 PHC 格式"),
+    };
+    let valid = match result {
+        Ok(()) => true,
+        Err(password_hash::Error::Password) => false,
+        Err(e) => return Err(Error::msg(e.to_string())),
+    };
+
+    let mut params = Vec::new();
+    if let Some(version) = parsed.version {
+        params.push(("版本".to_string(), version.to_string()));
+    }
+    for (key, value) in parsed.params.iter() {
+        params.push((key.to_string(), value.to_string()));
+    }
+    if let Some(salt) = parsed.salt {
+        params.push(("盐".to_string(), salt.to_string()));
+    }
+    Ok(PasswordVerification {
+        valid,
+        algorithm,
+        params,
+    })
+}
+
+/// `$2a$`、`$2b$`、`$2x$`、`$2y$` 开头的 bcrypt 哈希，返回其中的 cost
+fn bcrypt_cost(hash: &str) -> Option<u32> {
+    let rest = hash.strip_prefix("$2")?;
+    let (variant, rest) = rest.split_at_checked(1)?;
+    if !matches!(variant, "a" | "b" | "x" | "y") || hash.len() != 60 {
+        return None;
+    }
+    rest.strip_prefix('$')?.get(..2)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试中使用最低代价，避免拖慢测试
+    fn fast(algorithm: PasswordAlgorithm) -> PasswordOptions {
+        PasswordOptions {
+            scrypt_log_n: 4,
+            argon2_memory: 64,
+            ..PasswordOptions::bulk(algorithm)
+        }
+    }
+
+    #[test]
+    fn test_hash_verify() -> Result<()> {
+        let algorithms = [
+            (PasswordAlgorithm::Argon2id, "$argon2id$v=19$m=64,t=1,p=1$"),
+            (PasswordAlgorithm::Argon2i, "$argon2i$"),
+            (PasswordAlgorithm::Argon2d, "$argon2d$"),
+            (PasswordAlgorithm::Bcrypt, "$2b$04$"),
+            (PasswordAlgorithm::Scrypt, "$scrypt$ln=4,r=8,p=1$"),
+            (
+                PasswordAlgorithm::Pbkdf2Sha256,
+                "$pbkdf2-sha256$i=1000,l=32$",
+            ),
+        ];
+        for (algorithm, prefix) in algorithms {
+            let hash = hash("correct horse", &fast(algorithm))?;
+            assert!(hash.starts_with(prefix), "{hash}");
+
+            let verification = verify("correct horse", &hash)?;
+            assert!(verification.valid);
+            assert_eq!(algorithm, verification.algorithm);
+            assert!(!verify("wrong horse", &hash)?.valid);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_known() -> Result<()> {
+        // crypt_blowfish 的测试向量
+        let bcrypt = "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW";
+        let verification = verify("U*U", bcrypt)?;
+        assert!(verification.valid);
+        assert_eq!(("cost".to_string(), "5".to_string()), verification.params[1]);
+        assert!(verify("", "not a hash").is_err());
+        assert!(verify("", "$md5$abc").is_err());
+        // PBKDF2-SHA1 不支持，不能误报为 SHA-256
+        assert!(verify("", "$pbkdf2$i=1000$c2FsdA$aGFzaA").is_err());
+        Ok(())
+    }
+}
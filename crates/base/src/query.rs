@@ -0,0 +1,451 @@
+//! JSON 查询：JSONPath（RFC 9535）、JMESPath 和 jq 过滤器
+//!
+//! jq 由 jaq 实现，包含其标准库，与 jq 的差异见 jaq 的文档。
+
+use std::{
+    cell::RefCell,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Error, Result};
+use jaq_core::{
+    Compiler, Ctx, Exn, Native, RcIter,
+    load::{self, Arena, File, Loader},
+};
+use jaq_json::Val;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+/// jq 过滤器最多产生的结果数，避免 `repeat` 等无限输出
+const MAX_OUTPUTS: usize = 10_000;
+
+/// jq 过滤器的运行时间上限，`def f: f; f` 这类不产生输出的死循环只能靠超时结束
+const JQ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 覆盖标准库中的生成器，每一步都经过 `_interrupt`，超时后 `[repeat(1)]`、
+/// `last(range(infinite))` 这类在内部无限循环的过滤器也能停下
+const INTERRUPTIBLE_DEFS: &str = r#"
+def range($from; $upto; $by): _range($from; $upto; $by) | _interrupt;
+def range($from; $upto): range($from; $upto; 1);
+def range($upto): range(0; $upto; 1);
+def repeat(f): def rec: f, (_interrupt | rec); rec;
+def recurse(f): def rec: ., (f | _interrupt | rec); rec;
+def while(cond; update): def rec: if cond then ., (update | _interrupt | rec) else empty end; rec;
+def until(cond; update): def rec: if cond then . else update | _interrupt | rec end; rec;
+"#;
+
+thread_local! {
+    /// 当前线程上运行的 jq 过滤器是否已超时，超时后 `_interrupt` 报错结束执行
+    static CANCELLED: RefCell<Arc<AtomicBool>> = RefCell::default();
+}
+
+fn cancelled() -> bool {
+    CANCELLED.with(|cancelled| cancelled.borrow().load(Ordering::Relaxed))
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueryLang {
+    #[default]
+    JsonPath,
+    JmesPath,
+    Jq,
+}
+
+impl From<&str> for QueryLang {
+    fn from(value: &str) -> Self {
+        match value.to_lowercase().as_ref() {
+            "jmespath" => Self::JmesPath,
+            "jq" => Self::Jq,
+            _ => Self::JsonPath,
+        }
+    }
+}
+
+/// 一条查询结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Match {
+    /// 结果在输入中的规范化路径，仅 JSONPath 的结果有
+    pub path: Option<String>,
+    pub value: Value,
+}
+
+pub fn query(lang: QueryLang, value: &Value, expr: &str) -> Result<Vec<Match>> {
+    match lang {
+        QueryLang::JsonPath => json_path(value, expr),
+        QueryLang::JmesPath => jmes_path(value, expr),
+        QueryLang::Jq => jq(value, expr),
+    }
+}
+
+fn json_path(value: &Value, expr: &str) -> Result<Vec<Match>> {
+    let path = JsonPath::parse(expr.trim()).map_err(|e| Error::msg(e.to_string()))?;
+    Ok(path
+        .query_located(value)
+        .iter()
+        .map(|node| Match {
+            path: Some(node.location().to_string()),
+            value: node.node().clone(),
+        })
+        .collect())
+}
+
+/// JMESPath 表达式只有一个结果，没有匹配时为 `null`
+fn jmes_path(value: &Value, expr: &str) -> Result<Vec<Match>> {
+    let expr = jmespath::compile(expr.trim()).map_err(|e| Error::msg(e.to_string()))?;
+    let result = expr
+        .search(value.clone())
+        .map_err(|e| Error::msg(e.to_string()))?;
+    Ok(vec![Match {
+        path: None,
+        value: serde_json::to_value(&*result).map_err(|e| Error::msg(e.to_string()))?,
+    }])
+}
+
+/// 在单独的线程中运行 jq 过滤器，超过 [`JQ_TIMEOUT`] 时返回错误并通知线程停止。
+/// jaq 没有中断执行的接口，线程只在输出之间和标准库生成器的每一步检查是否超时，
+/// 用户自己写的无限递归（如 `def f: f; f`）仍会让线程继续运行
+fn jq(value: &Value, expr: &str) -> Result<Vec<Match>> {
+    let (sender, receiver) = mpsc::channel();
+    let value = value.clone();
+    let expr = expr.to_string();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let flag = cancelled.clone();
+    thread::Builder::new()
+        .name("jq".to_string())
+        .spawn(move || {
+            CANCELLED.with(|cancelled| *cancelled.borrow_mut() = flag);
+            let _ = sender.send(run_jq(&value, &expr));
+        })
+        .map_err(|e| Error::msg(e.to_string()))?;
+    match receiver.recv_timeout(JQ_TIMEOUT) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            cancelled.store(true, Ordering::Relaxed);
+            Err(Error::msg(format!(
+                "jq 过滤器运行超过 {} 秒，可能存在死循环",
+                JQ_TIMEOUT.as_secs()
+            )))
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(Error::msg("jq 过滤器异常退出")),
+    }
+}
+
+fn run_jq(value: &Value, expr: &str) -> Result<Vec<Match>> {
+    let program = File {
+        code: expr,
+        path: (),
+    };
+    let defs = load::parse(INTERRUPTIBLE_DEFS, |p| p.defs())
+        .ok_or_else(|| Error::msg("jq 内置定义解析失败"))?;
+    let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()).chain(defs));
+    let arena = Arena::default();
+    let modules = loader.load(&arena, program).map_err(|errs| {
+        let messages = errs
+            .into_iter()
+            .map(|(_, e)| load_error(e))
+            .collect::<Vec<_>>();
+        Error::msg(messages.join("\n"))
+    })?;
+    let filter = Compiler::default()
+        .with_funs(funs())
+        .compile(modules)
+        .map_err(|errs| {
+            let messages = errs
+                .into_iter()
+                .flat_map(|(_, e)| e)
+                .map(|(name, undefined)| format!("未定义的{}: {name}", undefined.as_str()))
+                .collect::<Vec<_>>();
+            Error::msg(messages.join("\n"))
+        })?;
+
+    let inputs = RcIter::new(core::iter::empty());
+    let mut matches = Vec::new();
+    for out in filter.run((Ctx::new([], &inputs), Val::from(value.clone()))) {
+        if cancelled() {
+            return Err(Error::msg("jq 过滤器已超时"));
+        }
+        if matches.len() >= MAX_OUTPUTS {
+            return Err(Error::msg(format!("结果超过 {MAX_OUTPUTS} 条")));
+        }
+        let out = out.map_err(|e| Error::msg(e.to_string()))?;
+        matches.push(Match {
+            path: None,
+            value: out.into(),
+        });
+    }
+    Ok(matches)
+}
+
+/// 标准库和 JSON 的原生函数，另加 [`INTERRUPTIBLE_DEFS`] 用到的 `_interrupt`
+/// 和原生 `range` 的别名 `_range`
+fn funs() -> impl Iterator<Item = jaq_std::Filter<Native<Val>>> {
+    let range = jaq_std::funs()
+        .filter(|(name, args, _)| *name == "range" && args.len() == 3)
+        .map(|(_, args, f)| ("_range", args, f));
+    let interrupt = Native::new(|_, cv| {
+        Box::new(core::iter::once(if cancelled() {
+            Err(Exn::from(jaq_core::Error::str("jq 过滤器已超时")))
+        } else {
+            Ok(cv.1)
+        }))
+    });
+    jaq_std::funs()
+        .chain(jaq_json::funs())
+        .chain(range)
+        .chain([("_interrupt", jaq_std::v(0), interrupt)])
+}
+
+fn load_error(e: load::Error<&str>) -> String {
+    let expected = |expect: &str, found: &str| {
+        let near = found.chars().take(16).collect::<String>();
+        if near.is_empty() {
+            format!("语法错误: 缺少 {expect}")
+        } else {
+            format!("语法错误: 缺少 {expect}，位于 `{near}`")
+        }
+    };
+    match e {
+        load::Error::Io(errs) => errs
+            .into_iter()
+            .map(|(path, e)| format!("{path}: {e}"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        load::Error::Lex(errs) => errs
+            .iter()
+            .map(|(expect, found)| expected(expect.as_str(), found))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        load::Error::Parse(errs) => errs
+            .iter()
+            .map(|(expect, found)| expected(expect.as_str(), found))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// 把 JSON Pointer 转为查询语言中的路径：JSONPath 为规范化路径 `$['a'][0]`，
+/// JMESPath 为 `a[0]`，jq 为 `.a[0]`。指向的节点不存在时返回 `None`
+pub fn node_path(lang: QueryLang, value: &Value, pointer: &str) -> Option<String> {
+    if lang == QueryLang::JmesPath {
+        return jmes_node_path(value, pointer);
+    }
+    let mut path = match lang {
+        QueryLang::JsonPath => "$".to_string(),
+        _ => String::new(),
+    };
+    let mut current = value;
+    for segment in pointer.split('/').skip(1) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        match current {
+            Value::Object(map) => {
+                current = map.get(&segment)?;
+                match lang {
+                    QueryLang::JsonPath => {
+                        let escaped = segment.replace('\\', "\\\\").replace('\'', "\\'");
+                        path.push_str(&format!("['{escaped}']"));
+                    }
+                    _ if is_identifier(&segment) => {
+                        path.push_str(&format!(".{segment}"));
+                    }
+                    _ => {
+                        if path.is_empty() {
+                            path.push('.');
+                        }
+                        path.push_str(&format!("[{}]", serde_json::to_string(&segment).ok()?));
+                    }
+                }
+            }
+            Value::Array(items) => {
+                let index = segment.parse::<usize>().ok()?;
+                current = items.get(index)?;
+                if path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(&format!("[{index}]"));
+            }
+            _ => return None,
+        }
+    }
+    if path.is_empty() {
+        path.push('.');
+    }
+    Some(path)
+}
+
+/// JMESPath 的路径以 `.` 连接字段，非标识符的键写成带引号的标识符，根节点为 `@`
+fn jmes_node_path(value: &Value, pointer: &str) -> Option<String> {
+    let mut path = String::new();
+    let mut current = value;
+    for segment in pointer.split('/').skip(1) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        match current {
+            Value::Object(map) => {
+                current = map.get(&segment)?;
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                if is_identifier(&segment) {
+                    path.push_str(&segment);
+                } else {
+                    path.push_str(&serde_json::to_string(&segment).ok()?);
+                }
+            }
+            Value::Array(items) => {
+                let index = segment.parse::<usize>().ok()?;
+                current = items.get(index)?;
+                if path.is_empty() {
+                    path.push('@');
+                }
+                path.push_str(&format!("[{index}]"));
+            }
+            _ => return None,
+        }
+    }
+    if path.is_empty() {
+        path.push('@');
+    }
+    Some(path)
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn data() -> Value {
+        json!({
+            "store": {
+                "books": [
+                    { "title": "A", "price": 8 },
+                    { "title": "B", "price": 12 },
+                ],
+                "owner's name": "x",
+            }
+        })
+    }
+
+    #[test]
+    fn test_json_path() -> Result<()> {
+        let matches = query(
+            QueryLang::JsonPath,
+            &data(),
+            "$.store.books[?@.price > 10].title",
+        )?;
+        assert_eq!(
+            vec![Match {
+                path: Some("$['store']['books'][1]['title']".into()),
+                value: json!("B"),
+            }],
+            matches
+        );
+        assert!(query(QueryLang::JsonPath, &data(), "$.store[").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_jq() -> Result<()> {
+        let matches = query(
+            QueryLang::Jq,
+            &data(),
+            ".store.books | map(select(.price < 10)) | .[].title, length",
+        )?;
+        let values = matches.into_iter().map(|m| m.value).collect::<Vec<_>>();
+        assert_eq!(vec![json!("A"), json!(1)], values);
+
+        let keys = query(QueryLang::Jq, &data(), ".store | keys_unsorted")?;
+        assert_eq!(json!(["books", "owner's name"]), keys[0].value);
+
+        assert!(query(QueryLang::Jq, &data(), ".store | (").is_err());
+        assert!(query(QueryLang::Jq, &data(), "nope").is_err());
+        assert!(query(QueryLang::Jq, &data(), "error(\"x\")").is_err());
+        assert!(query(QueryLang::Jq, &data(), "repeat(.)").is_err());
+        assert!(query(QueryLang::Jq, &data(), "def f: f; f").is_err());
+        assert_eq!(
+            json!([3, 5]),
+            query(QueryLang::Jq, &data(), "[range(3; 7; 2)]")?[0].value
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_jq_interrupt() {
+        // 超时后线程中的无限生成器也要停下，而不是继续占用 CPU 和内存
+        let flag = Arc::new(AtomicBool::new(true));
+        CANCELLED.with(|cancelled| *cancelled.borrow_mut() = flag);
+        for expr in ["[repeat(1)]", "last(range(infinite))", "[recurse(. + 1)]"] {
+            let e = run_jq(&json!(0), expr).unwrap_err();
+            assert_eq!("jq 过滤器已超时", e.to_string(), "{expr}");
+        }
+    }
+
+    #[test]
+    fn test_jmes_path() -> Result<()> {
+        let matches = query(
+            QueryLang::JmesPath,
+            &data(),
+            "store.books[?price > `10`].title",
+        )?;
+        assert_eq!(json!(["B"]), matches[0].value);
+        let matches = query(QueryLang::JmesPath, &data(), "store.\"owner's name\"")?;
+        assert_eq!(json!("x"), matches[0].value);
+        assert!(query(QueryLang::JmesPath, &data(), "store.[").is_err());
+
+        assert_eq!(
+            Some(r#"store."owner's name""#.to_string()),
+            node_path(QueryLang::JmesPath, &data(), "/store/owner's name")
+        );
+        assert_eq!(
+            Some("store.books[1]".to_string()),
+            node_path(QueryLang::JmesPath, &data(), "/store/books/1")
+        );
+        assert_eq!(
+            Some("@[1]".to_string()),
+            node_path(QueryLang::JmesPath, &json!([0, 1]), "/1")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_node_path() {
+        let data = data();
+        assert_eq!(
+            Some("$['store']['books'][0]".to_string()),
+            node_path(QueryLang::JsonPath, &data, "/store/books/0")
+        );
+        assert_eq!(
+            Some("$['store']['owner\\'s name']".to_string()),
+            node_path(QueryLang::JsonPath, &data, "/store/owner's name")
+        );
+        assert_eq!(
+            Some(r#".store["owner's name"]"#.to_string()),
+            node_path(QueryLang::Jq, &data, "/store/owner's name")
+        );
+        assert_eq!(
+            Some(".store.books[1]".to_string()),
+            node_path(QueryLang::Jq, &data, "/store/books/1")
+        );
+        assert_eq!(Some(".".to_string()), node_path(QueryLang::Jq, &data, ""));
+        assert_eq!(
+            Some(".[1]".to_string()),
+            node_path(QueryLang::Jq, &json!([0, 1]), "/1")
+        );
+        assert_eq!(None, node_path(QueryLang::Jq, &data, "/store/books/5"));
+    }
+}
@@ -0,0 +1,98 @@
+//! Go 结构体，带 json 标签，按 gofmt 的方式对齐各列
+//!
+//! 可选字段加 `omitempty`，可选或可空的标量和结构体使用指针，联合类型为 `any`。
+
+use std::collections::HashSet;
+
+use heck::ToSnakeCase;
+
+use super::{Model, Ty};
+
+/// 按 Go 的命名习惯整体大写的缩写
+const INITIALISMS: [&str; 14] = [
+    "api", "db", "html", "http", "https", "id", "ip", "json", "sql", "tcp", "uid", "uri", "url",
+    "uuid",
+];
+
+pub(super) fn render(model: &Model) -> String {
+    let mut blocks = Vec::new();
+    if !matches!(model.root, Ty::Named(_)) {
+        blocks.push(format!("type {} {}\n", model.name, ty(&model.root)));
+    }
+    for def in &model.defs {
+        let mut used = HashSet::new();
+        let rows = def
+            .props
+            .iter()
+            .map(|prop| {
+                let name = field_name(&prop.key, &mut used);
+                let (ty, tag) = if prop.optional {
+                    (pointer(&prop.ty), format!("{},omitempty", prop.key))
+                } else {
+                    (ty(&prop.ty), prop.key.clone())
+                };
+                (name, ty, format!("`json:\"{tag}\"`"))
+            })
+            .collect::<Vec<_>>();
+        let name_width = rows.iter().map(|r| r.0.len()).max().unwrap_or_default();
+        let ty_width = rows.iter().map(|r| r.1.len()).max().unwrap_or_default();
+
+        let mut block = format!("type {} struct {{\n", def.name);
+        for (name, ty, tag) in rows {
+            block.push_str(&format!("\t{name:name_width$} {ty:ty_width$} {tag}\n"));
+        }
+        block.push_str("}\n");
+        blocks.push(block);
+    }
+    blocks.join("\n")
+}
+
+fn ty(ty_: &Ty) -> String {
+    match ty_ {
+        Ty::Any | Ty::Null | Ty::Union { .. } => "any".into(),
+        Ty::Bool => "bool".into(),
+        Ty::Integer => "int64".into(),
+        Ty::Float => "float64".into(),
+        Ty::String => "string".into(),
+        Ty::Array(item) => format!("[]{}", ty(item)),
+        Ty::Named(name) => name.clone(),
+        Ty::Nullable(inner) => pointer(inner),
+    }
+}
+
+/// 可为空的类型：切片和 any 本身可以为 nil，其余使用指针
+fn pointer(ty_: &Ty) -> String {
+    match ty_ {
+        Ty::Any | Ty::Null | Ty::Union { .. } | Ty::Array(_) | Ty::Nullable(_) => ty(ty_),
+        _ => format!("*{}", ty(ty_)),
+    }
+}
+
+/// 字段名转为导出的 UpperCamelCase，常见缩写整体大写，重名时追加序号
+fn field_name(key: &str, used: &mut HashSet<String>) -> String {
+    let mut name = key
+        .to_snake_case()
+        .split('_')
+        .map(|word| {
+            if INITIALISMS.contains(&word) {
+                word.to_uppercase()
+            } else {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        })
+        .collect::<String>();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name = format!("Field{name}");
+    }
+    let base = name.clone();
+    let mut n = 1;
+    while !used.insert(name.clone()) {
+        n += 1;
+        name = format!("{base}{n}");
+    }
+    name
+}
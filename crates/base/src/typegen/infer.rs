@@ -0,0 +1,204 @@
+//! 从样本推断结构
+//!
+//! 每个值先推断为 [`Shape`]，多个样本以及数组中的各个元素再两两合并：
+//! 只在部分对象中出现的字段为可选，出现过 null 的为可空，整数与浮点数合并为浮点数，
+//! 其他不同类型合并为联合类型。
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// 没有样本可供推断，例如空数组的元素
+    Unknown,
+    Null,
+    Bool,
+    Integer,
+    Float,
+    String,
+    Array(Box<Shape>),
+    Object(IndexMap<String, Field>),
+    /// 可为 null 的类型
+    Nullable(Box<Shape>),
+    /// 多种类型的联合，成员的种类互不相同且都不是 null
+    Union(Vec<Shape>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub shape: Shape,
+    /// 不是每个样本中都有该字段
+    pub optional: bool,
+}
+
+pub fn infer(value: &Value) -> Shape {
+    match value {
+        Value::Null => Shape::Null,
+        Value::Bool(_) => Shape::Bool,
+        Value::Number(n) if n.is_f64() => Shape::Float,
+        Value::Number(_) => Shape::Integer,
+        Value::String(_) => Shape::String,
+        Value::Array(items) => Shape::Array(Box::new(
+            items.iter().map(infer).fold(Shape::Unknown, merge),
+        )),
+        Value::Object(map) => Shape::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let field = Field {
+                        shape: infer(v),
+                        optional: false,
+                    };
+                    (k.clone(), field)
+                })
+                .collect(),
+        ),
+    }
+}
+
+pub fn merge(a: Shape, b: Shape) -> Shape {
+    match (a, b) {
+        (Shape::Unknown, s) | (s, Shape::Unknown) => s,
+        (Shape::Null, Shape::Null) => Shape::Null,
+        (Shape::Null, s) | (s, Shape::Null) => nullable(s),
+        (Shape::Nullable(a), b) | (b, Shape::Nullable(a)) => nullable(merge(*a, b)),
+        (Shape::Union(items), s) | (s, Shape::Union(items)) => match s {
+            Shape::Union(others) => others.into_iter().fold(Shape::Union(items), merge),
+            s => union_insert(items, s),
+        },
+        (a, b) if kind(&a) == kind(&b) => merge_same(a, b),
+        (a, b) => Shape::Union(vec![a, b]),
+    }
+}
+
+fn nullable(shape: Shape) -> Shape {
+    match shape {
+        Shape::Unknown | Shape::Null => Shape::Null,
+        Shape::Nullable(_) => shape,
+        s => Shape::Nullable(Box::new(s)),
+    }
+}
+
+/// 联合类型中已有同种类的成员时与之合并，否则追加
+fn union_insert(mut items: Vec<Shape>, shape: Shape) -> Shape {
+    match items.iter().position(|s| kind(s) == kind(&shape)) {
+        Some(i) => {
+            let existing = std::mem::replace(&mut items[i], Shape::Unknown);
+            items[i] = merge_same(existing, shape);
+        }
+        None => items.push(shape),
+    }
+    Shape::Union(items)
+}
+
+/// 类型的种类，整数与浮点数同属数字
+fn kind(shape: &Shape) -> u8 {
+    match shape {
+        Shape::Unknown => 0,
+        Shape::Null => 1,
+        Shape::Bool => 2,
+        Shape::Integer | Shape::Float => 3,
+        Shape::String => 4,
+        Shape::Array(_) => 5,
+        Shape::Object(_) => 6,
+        Shape::Nullable(_) => 7,
+        Shape::Union(_) => 8,
+    }
+}
+
+/// 合并种类相同的两个类型
+fn merge_same(a: Shape, b: Shape) -> Shape {
+    match (a, b) {
+        (Shape::Integer, Shape::Integer) => Shape::Integer,
+        (Shape::Integer | Shape::Float, Shape::Integer | Shape::Float) => Shape::Float,
+        (Shape::Array(a), Shape::Array(b)) => Shape::Array(Box::new(merge(*a, *b))),
+        (Shape::Object(mut a), Shape::Object(mut b)) => {
+            for (key, field) in a.iter_mut() {
+                match b.shift_remove(key) {
+                    Some(other) => {
+                        let shape = std::mem::replace(&mut field.shape, Shape::Unknown);
+                        field.shape = merge(shape, other.shape);
+                        field.optional |= other.optional;
+                    }
+                    None => field.optional = true,
+                }
+            }
+            for (key, mut field) in b {
+                field.optional = true;
+                a.insert(key, field);
+            }
+            Shape::Object(a)
+        }
+        (a, _) => a,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn field(shape: Shape, optional: bool) -> Field {
+        Field { shape, optional }
+    }
+
+    #[test]
+    fn test_merge() {
+        let shape = [
+            json!({ "id": 1, "name": "a", "score": 1, "tags": [] }),
+            json!({ "id": "x", "score": 1.5, "tags": ["t"], "extra": null }),
+            json!({ "id": 2, "name": null, "score": 2, "tags": ["t", 1] }),
+        ]
+        .iter()
+        .map(infer)
+        .fold(Shape::Unknown, merge);
+
+        let expected = Shape::Object(IndexMap::from([
+            (
+                "id".to_string(),
+                field(Shape::Union(vec![Shape::Integer, Shape::String]), false),
+            ),
+            (
+                "name".to_string(),
+                field(Shape::Nullable(Box::new(Shape::String)), true),
+            ),
+            ("score".to_string(), field(Shape::Float, false)),
+            (
+                "tags".to_string(),
+                field(
+                    Shape::Array(Box::new(Shape::Union(vec![Shape::String, Shape::Integer]))),
+                    false,
+                ),
+            ),
+            ("extra".to_string(), field(Shape::Null, true)),
+        ]));
+        assert_eq!(expected, shape);
+    }
+
+    #[test]
+    fn test_merge_nested() {
+        let shape = infer(&json!([
+            { "a": { "x": 1 } },
+            { "a": { "y": true } },
+            { "a": null },
+        ]));
+        let inner = Shape::Object(IndexMap::from([
+            ("x".to_string(), field(Shape::Integer, true)),
+            ("y".to_string(), field(Shape::Bool, true)),
+        ]));
+        let expected = Shape::Array(Box::new(Shape::Object(IndexMap::from([(
+            "a".to_string(),
+            field(Shape::Nullable(Box::new(inner)), false),
+        )]))));
+        assert_eq!(expected, shape);
+
+        let shape = [json!(1), json!("a"), json!(null), json!(2.5)]
+            .iter()
+            .map(infer)
+            .fold(Shape::Unknown, merge);
+        assert_eq!(
+            Shape::Nullable(Box::new(Shape::Union(vec![Shape::Float, Shape::String]))),
+            shape
+        );
+    }
+}
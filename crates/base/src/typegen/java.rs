@@ -0,0 +1,156 @@
+//! Java POJO，每个类单独成文件，字段名与键名不同时用 Jackson 的 `@JsonProperty` 标注
+
+use std::collections::HashSet;
+
+use heck::{ToLowerCamelCase, ToUpperCamelCase};
+
+use super::{Model, Ty};
+
+const KEYWORDS: [&str; 53] = [
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "true",
+    "false",
+    "null",
+];
+
+/// `Object` 中与 getter/setter 同名的方法，`getClass()` 是 final 的，不能被覆盖
+const OBJECT_METHODS: [&str; 1] = ["getClass"];
+
+pub(super) fn render(model: &Model) -> String {
+    let mut blocks = Vec::new();
+    if !matches!(model.root, Ty::Named(_)) {
+        blocks.push(format!("// {}: {}\n", model.name, ty(&model.root)));
+    }
+    for def in &model.defs {
+        let mut used = HashSet::new();
+        let fields = def
+            .props
+            .iter()
+            .map(|prop| (field_name(&prop.key, &mut used), ty(&prop.ty), &prop.key))
+            .collect::<Vec<_>>();
+
+        let mut imports = Vec::new();
+        if fields.iter().any(|(name, _, key)| name != *key) {
+            imports.push("import com.fasterxml.jackson.annotation.JsonProperty;\n");
+        }
+        if fields.iter().any(|(_, ty, _)| ty.contains("List<")) {
+            imports.push("import java.util.List;\n");
+        }
+
+        let mut block = format!("// {}.java\n", def.name);
+        if !imports.is_empty() {
+            block.push_str(&imports.concat());
+            block.push('\n');
+        }
+        block.push_str(&format!("public class {} {{\n", def.name));
+        for (name, ty, key) in &fields {
+            if name != *key {
+                block.push_str(&format!("    @JsonProperty({key:?})\n"));
+            }
+            block.push_str(&format!("    private {ty} {name};\n"));
+        }
+        for (name, ty, _) in &fields {
+            let accessor = accessor(name);
+            block.push_str(&format!(
+                "\n    public {ty} get{accessor}() {{\n        return {name};\n    }}\n"
+            ));
+            block.push_str(&format!(
+                "\n    public void set{accessor}({ty} {name}) {{\n        this.{name} = {name};\n    }}\n"
+            ));
+        }
+        block.push_str("}\n");
+        blocks.push(block);
+    }
+    blocks.join("\n")
+}
+
+fn ty(ty_: &Ty) -> String {
+    match ty_ {
+        Ty::Any | Ty::Null | Ty::Union { .. } => "Object".into(),
+        Ty::Bool => "Boolean".into(),
+        Ty::Integer => "Long".into(),
+        Ty::Float => "Double".into(),
+        Ty::String => "String".into(),
+        Ty::Array(item) => format!("List<{}>", ty(item)),
+        Ty::Named(name) => name.clone(),
+        Ty::Nullable(inner) => ty(inner),
+    }
+}
+
+/// getter/setter 中的名称，与 `Object` 的方法冲突时保留字段名末尾的下划线，如 `getClass_()`
+fn accessor(name: &str) -> String {
+    let trimmed = name.trim_end_matches('_');
+    let accessor = trimmed.to_upper_camel_case();
+    if OBJECT_METHODS.contains(&format!("get{accessor}").as_str()) {
+        format!("{accessor}{}", &name[trimmed.len()..])
+    } else {
+        accessor
+    }
+}
+
+/// 字段名转为 lowerCamelCase，关键字后加下划线，重名时追加序号
+fn field_name(key: &str, used: &mut HashSet<String>) -> String {
+    let mut name = key.to_lower_camel_case();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name = format!("field{name}");
+    }
+    if KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+    let base = name.clone();
+    let mut n = 1;
+    while !used.insert(name.clone()) {
+        n += 1;
+        name = format!("{base}{n}");
+    }
+    name
+}
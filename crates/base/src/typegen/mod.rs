@@ -0,0 +1,451 @@
+//! 根据 JSON/YAML/TOML 样本数据生成类型定义
+//!
+//! 样本先由 cffc 解析，推断出结构后降为命名的类型定义，再按目标语言生成代码。
+//! 嵌套对象以字段名（数组元素取单数形式）命名，重名时追加序号。
+
+use std::collections::HashSet;
+
+use anyhow::{Error, Result};
+use heck::ToUpperCamelCase;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::cffc::{Data, Ft};
+use infer::{Field, Shape, infer, merge};
+
+mod go;
+mod infer;
+mod java;
+mod rust;
+mod schema;
+mod typescript;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Language {
+    #[default]
+    Rust,
+    TypeScript,
+    Java,
+    Go,
+    /// JSON Schema draft 2020-12
+    JsonSchema,
+}
+
+impl From<&str> for Language {
+    fn from(value: &str) -> Self {
+        match value.to_lowercase().as_ref() {
+            "rust" => Self::Rust,
+            "typescript" | "ts" => Self::TypeScript,
+            "java" => Self::Java,
+            "go" | "golang" => Self::Go,
+            "jsonschema" | "json-schema" | "schema" => Self::JsonSchema,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeGen {
+    /// 目标语言
+    pub language: Language,
+    /// 根类型的名称
+    pub root_name: String,
+    /// 样本的格式，为 Auto 时逐个识别
+    pub from: Ft,
+    /// 样本数据，合并推断
+    pub samples: Vec<String>,
+}
+
+impl TypeGen {
+    pub fn run(&self) -> Result<String> {
+        let mut shape = Shape::Unknown;
+        for (i, sample) in self.samples.iter().enumerate() {
+            let value = Data::new(self.from, Ft::Json, sample, 0)
+                .parse()
+                .map_err(|e| Error::msg(format!("第 {} 个样本解析失败: {e}", i + 1)))?;
+            shape = merge(shape, infer(&value));
+        }
+        if self.samples.is_empty() {
+            return Err(Error::msg("没有样本数据"));
+        }
+
+        let root_name = match self.root_name.trim() {
+            "" => "Root",
+            name => name,
+        };
+        let model = Model::new(&shape, root_name);
+        match self.language {
+            Language::Rust => Ok(rust::render(&model)),
+            Language::TypeScript => Ok(typescript::render(&model)),
+            Language::Java => Ok(java::render(&model)),
+            Language::Go => Ok(go::render(&model)),
+            Language::JsonSchema => schema::render(&model),
+        }
+    }
+}
+
+/// 按单独一行的 `---` 拆分多个样本，忽略空白的样本
+pub fn split_samples(text: &str) -> Vec<String> {
+    let mut samples = Vec::new();
+    let mut current = String::new();
+    for line in text.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            samples.push(std::mem::take(&mut current));
+        } else {
+            current.push_str(line);
+        }
+    }
+    samples.push(current);
+    samples.retain(|s| !s.trim().is_empty());
+    samples
+}
+
+/// 与语言无关的类型
+#[derive(Debug, Clone, PartialEq)]
+enum Ty {
+    /// 无法推断，任意值
+    Any,
+    /// 只出现过 null
+    Null,
+    Bool,
+    Integer,
+    Float,
+    String,
+    Array(Box<Ty>),
+    /// 引用 [`Model::defs`] 中的结构
+    Named(String),
+    Nullable(Box<Ty>),
+    /// 联合类型，`name` 供需要为联合类型命名的语言使用
+    Union {
+        name: String,
+        members: Vec<Ty>,
+    },
+}
+
+/// 结构定义
+#[derive(Debug)]
+struct Def {
+    name: String,
+    props: Vec<Prop>,
+}
+
+/// 结构中的字段
+#[derive(Debug)]
+struct Prop {
+    /// 样本中的原始键名
+    key: String,
+    ty: Ty,
+    optional: bool,
+}
+
+#[derive(Debug)]
+struct Model {
+    /// 根类型的名称
+    name: String,
+    /// 根类型，根为对象时即 `Named(name)`
+    root: Ty,
+    /// 所有结构，父结构排在子结构之前
+    defs: Vec<Def>,
+}
+
+impl Model {
+    fn new(shape: &Shape, root_name: &str) -> Self {
+        let mut lower = Lower::default();
+        let name = lower.name(root_name);
+        let root = match shape {
+            Shape::Object(fields) => lower.object(fields, name.clone()),
+            // 根为数组时，元素类型取根类型名的单数形式
+            _ => lower.ty(shape, &name),
+        };
+        Self {
+            name,
+            root,
+            defs: lower.defs,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Lower {
+    defs: Vec<Def>,
+    used: HashSet<String>,
+}
+
+impl Lower {
+    /// 生成不重名的类型名称
+    fn name(&mut self, hint: &str) -> String {
+        let mut base = hint.to_upper_camel_case();
+        if !base.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            base = format!("Type{base}");
+        }
+        let mut name = base.clone();
+        let mut n = 1;
+        while !self.used.insert(name.clone()) {
+            n += 1;
+            name = format!("{base}{n}");
+        }
+        name
+    }
+
+    fn ty(&mut self, shape: &Shape, hint: &str) -> Ty {
+        match shape {
+            Shape::Unknown => Ty::Any,
+            Shape::Null => Ty::Null,
+            Shape::Bool => Ty::Bool,
+            Shape::Integer => Ty::Integer,
+            Shape::Float => Ty::Float,
+            Shape::String => Ty::String,
+            Shape::Array(item) => Ty::Array(Box::new(self.ty(item, &singular(hint)))),
+            Shape::Nullable(inner) => Ty::Nullable(Box::new(self.ty(inner, hint))),
+            Shape::Union(members) => Ty::Union {
+                name: self.name(hint),
+                members: members.iter().map(|m| self.ty(m, hint)).collect(),
+            },
+            Shape::Object(fields) => {
+                let name = self.name(hint);
+                self.object(fields, name)
+            }
+        }
+    }
+
+    fn object(&mut self, fields: &IndexMap<String, Field>, name: String) -> Ty {
+        // 先占位，保证父结构排在子结构之前
+        let index = self.defs.len();
+        self.defs.push(Def {
+            name: name.clone(),
+            props: Vec::new(),
+        });
+        let props = fields
+            .iter()
+            .map(|(key, field)| Prop {
+                key: key.clone(),
+                ty: self.ty(&field.shape, key),
+                optional: field.optional,
+            })
+            .collect();
+        self.defs[index].props = props;
+        Ty::Named(name)
+    }
+}
+
+/// 数组元素的类型名称取单数形式
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if let Some(stem) = word
+        .strip_suffix("sses")
+        .or_else(|| word.strip_suffix("xes"))
+        .or_else(|| word.strip_suffix("ches"))
+        .or_else(|| word.strip_suffix("shes"))
+    {
+        format!("{stem}{}", &word[stem.len()..word.len() - 2])
+    } else if let Some(stem) = word.strip_suffix('s')
+        && !stem.is_empty()
+        && !stem.ends_with(['s', 'u', 'i'])
+    {
+        stem.to_string()
+    } else {
+        format!("{word}Item")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: &str = r#"{"id": 1, "userName": "alice", "tags": ["a"], "address": {"city": "Paris"}}
+---
+{"id": "u2", "userName": "bob", "tags": [], "address": null, "type": "admin"}
+"#;
+
+    fn generate(language: Language) -> Result<String> {
+        TypeGen {
+            language,
+            root_name: "user".into(),
+            from: Ft::Auto,
+            samples: split_samples(SAMPLES),
+        }
+        .run()
+    }
+
+    #[test]
+    fn test_split_samples() {
+        assert_eq!(2, split_samples(SAMPLES).len());
+        assert_eq!(
+            vec!["a: 1\n".to_string()],
+            split_samples("---\na: 1\n---\n")
+        );
+    }
+
+    #[test]
+    fn test_singular() {
+        assert_eq!("Category", singular("Categories"));
+        assert_eq!("Address", singular("Addresses"));
+        assert_eq!("Box", singular("Boxes"));
+        assert_eq!("Tag", singular("Tags"));
+        assert_eq!("DataItem", singular("Data"));
+        assert_eq!("StatusItem", singular("Status"));
+    }
+
+    #[test]
+    fn test_model() {
+        let shape = infer(&serde_json::json!([{ "items": [{ "a": 1 }], "user": { "b": 2 } }]));
+        let model = Model::new(&shape, "order");
+        assert_eq!("Order", model.name);
+        assert_eq!(
+            Ty::Array(Box::new(Ty::Named("OrderItem".into()))),
+            model.root
+        );
+        let names = model
+            .defs
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["OrderItem", "Item", "User"], names);
+    }
+
+    #[test]
+    fn test_rust() -> Result<()> {
+        assert_eq!(
+            r#"use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: Id,
+    #[serde(rename = "userName")]
+    pub user_name: String,
+    pub tags: Vec<String>,
+    pub address: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Address {
+    pub city: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Id {
+    Integer(i64),
+    String(String),
+}
+"#,
+            generate(Language::Rust)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_typescript() -> Result<()> {
+        assert_eq!(
+            r#"export interface User {
+  id: number | string;
+  userName: string;
+  tags: string[];
+  address: Address | null;
+  type?: string;
+}
+
+export interface Address {
+  city: string;
+}
+"#,
+            generate(Language::TypeScript)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_go() -> Result<()> {
+        assert_eq!(
+            "type User struct {
+\tID       any      `json:\"id\"`
+\tUserName string   `json:\"userName\"`
+\tTags     []string `json:\"tags\"`
+\tAddress  *Address `json:\"address\"`
+\tType     *string  `json:\"type,omitempty\"`
+}
+
+type Address struct {
+\tCity string `json:\"city\"`
+}
+",
+            generate(Language::Go)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_java() -> Result<()> {
+        let java = generate(Language::Java)?;
+        assert!(java.starts_with(
+            "// User.java
+import java.util.List;
+
+public class User {
+    private Object id;
+    private String userName;
+    private List<String> tags;
+    private Address address;
+    private String type;
+
+    public Object getId() {
+        return id;
+    }
+
+    public void setId(Object id) {
+        this.id = id;
+    }
+"
+        ));
+        assert!(
+            java.contains("// Address.java\npublic class Address {\n    private String city;\n")
+        );
+
+        // getClass() 是 Object 的 final 方法
+        let java = TypeGen {
+            language: Language::Java,
+            root_name: "flag".into(),
+            from: Ft::Auto,
+            samples: vec![r#"{"class": true}"#.to_string()],
+        }
+        .run()?;
+        assert!(java.contains("    @JsonProperty(\"class\")\n    private Boolean class_;\n"));
+        assert!(java.contains("public Boolean getClass_() {"));
+        assert!(java.contains("public void setClass_(Boolean class_) {"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_schema() -> Result<()> {
+        let schema = serde_json::from_str::<serde_json::Value>(&generate(Language::JsonSchema)?)?;
+        assert_eq!(
+            serde_json::json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "User",
+                "type": "object",
+                "properties": {
+                    "id": { "type": ["integer", "string"] },
+                    "userName": { "type": "string" },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "address": { "anyOf": [{ "$ref": "#/$defs/Address" }, { "type": "null" }] },
+                    "type": { "type": "string" },
+                },
+                "required": ["id", "userName", "tags", "address"],
+                "$defs": {
+                    "Address": {
+                        "type": "object",
+                        "properties": { "city": { "type": "string" } },
+                        "required": ["city"],
+                    },
+                },
+            }),
+            schema
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+//! Rust 结构体，通过 serde 派生序列化，联合类型生成 `untagged` 枚举
+
+use std::collections::HashSet;
+
+use heck::ToSnakeCase;
+
+use super::{Model, Ty};
+
+/// Rust 1.85关键字
+const KEYWORDS: [&str; 53] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "Self", "self", "static", "struct", "super", "trait", "true", "type", "union",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+pub(super) fn render(model: &Model) -> String {
+    let mut out = String::from("use serde::{Deserialize, Serialize};\n");
+    let mut unions = Vec::new();
+    if !matches!(model.root, Ty::Named(_)) {
+        let root = ty(&model.root, &mut unions);
+        out.push_str(&format!("\npub type {} = {root};\n", model.name));
+    }
+
+    for def in &model.defs {
+        out.push_str("\n#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+        out.push_str(&format!("pub struct {} {{\n", def.name));
+        let mut used = HashSet::new();
+        for prop in &def.props {
+            let ident = field_name(&prop.key, &mut used);
+            let mut ty = ty(&prop.ty, &mut unions);
+            let mut attrs = Vec::new();
+            if ident.trim_start_matches("r#") != prop.key {
+                attrs.push(format!("rename = {:?}", prop.key));
+            }
+            if prop.optional {
+                if !ty.starts_with("Option<") {
+                    ty = format!("Option<{ty}>");
+                }
+                attrs.push(r#"default, skip_serializing_if = "Option::is_none""#.to_string());
+            }
+            if !attrs.is_empty() {
+                out.push_str(&format!("    #[serde({})]\n", attrs.join(", ")));
+            }
+            out.push_str(&format!("    pub {ident}: {ty},\n"));
+        }
+        out.push_str("}\n");
+    }
+
+    // 联合类型的成员不会再包含联合类型，遍历时不会新增
+    for (name, members) in &unions {
+        out.push_str("\n#[derive(Debug, Clone, Serialize, Deserialize)]\n#[serde(untagged)]\n");
+        out.push_str(&format!("pub enum {name} {{\n"));
+        for member in members {
+            let variant = match member {
+                Ty::Bool => "Bool",
+                Ty::Integer => "Integer",
+                Ty::Float => "Float",
+                Ty::String => "String",
+                Ty::Array(_) => "Array",
+                Ty::Named(name) => name,
+                _ => "Any",
+            };
+            out.push_str(&format!(
+                "    {variant}({}),\n",
+                ty(member, &mut Vec::new())
+            ));
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+/// 类型名称，遇到联合类型时记入 `unions`
+fn ty(ty_: &Ty, unions: &mut Vec<(String, Vec<Ty>)>) -> String {
+    match ty_ {
+        Ty::Any => "serde_json::Value".into(),
+        Ty::Null => "Option<serde_json::Value>".into(),
+        Ty::Bool => "bool".into(),
+        Ty::Integer => "i64".into(),
+        Ty::Float => "f64".into(),
+        Ty::String => "String".into(),
+        Ty::Array(item) => format!("Vec<{}>", ty(item, unions)),
+        Ty::Named(name) => name.clone(),
+        Ty::Nullable(inner) => format!("Option<{}>", ty(inner, unions)),
+        Ty::Union { name, members } => {
+            unions.push((name.clone(), members.clone()));
+            name.clone()
+        }
+    }
+}
+
+/// 字段名转为 snake_case，关键字前加 r#，无法作为标识符的加前缀，重名时追加序号
+fn field_name(key: &str, used: &mut HashSet<String>) -> String {
+    let mut name = key.to_snake_case();
+    if name.is_empty() {
+        name = "field".into();
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name = format!("field_{name}");
+    }
+    let base = name.clone();
+    let mut n = 1;
+    while !used.insert(name.clone()) {
+        n += 1;
+        name = format!("{base}_{n}");
+    }
+    match name.as_str() {
+        // 这几个关键字不能写成原始标识符
+        "self" | "super" | "crate" => format!("{name}_"),
+        _ if KEYWORDS.contains(&name.as_str()) => format!("r#{name}"),
+        _ => name,
+    }
+}
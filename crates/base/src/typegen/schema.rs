@@ -0,0 +1,102 @@
+//! JSON Schema draft 2020-12
+//!
+//! 根结构直接写在顶层，其余结构放在 `$defs` 中通过 `$ref` 引用。
+//! 只由基本类型组成的联合类型写成 `type` 数组，否则写成 `anyOf`。
+
+use anyhow::Result;
+use serde_json::{Map, Value, json};
+
+use super::{Def, Model, Ty};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+pub(super) fn render(model: &Model) -> Result<String> {
+    let mut root = Map::new();
+    root.insert("$schema".into(), DRAFT.into());
+    root.insert("title".into(), model.name.clone().into());
+
+    let mut defs = Map::new();
+    for def in &model.defs {
+        if model.root == Ty::Named(def.name.clone()) {
+            root.extend(object(def));
+        } else {
+            defs.insert(def.name.clone(), Value::Object(object(def)));
+        }
+    }
+    if !matches!(model.root, Ty::Named(_)) {
+        root.extend(schema(&model.root));
+    }
+    if !defs.is_empty() {
+        root.insert("$defs".into(), Value::Object(defs));
+    }
+    Ok(serde_json::to_string_pretty(&root)?)
+}
+
+fn object(def: &Def) -> Map<String, Value> {
+    let properties = def
+        .props
+        .iter()
+        .map(|prop| (prop.key.clone(), Value::Object(schema(&prop.ty))))
+        .collect::<Map<_, _>>();
+    let required = def
+        .props
+        .iter()
+        .filter(|prop| !prop.optional)
+        .map(|prop| Value::String(prop.key.clone()))
+        .collect::<Vec<_>>();
+
+    let mut map = Map::new();
+    map.insert("type".into(), "object".into());
+    map.insert("properties".into(), Value::Object(properties));
+    if !required.is_empty() {
+        map.insert("required".into(), Value::Array(required));
+    }
+    map
+}
+
+fn schema(ty: &Ty) -> Map<String, Value> {
+    let value = match ty {
+        Ty::Any => json!({}),
+        Ty::Null => json!({ "type": "null" }),
+        Ty::Bool => json!({ "type": "boolean" }),
+        Ty::Integer => json!({ "type": "integer" }),
+        Ty::Float => json!({ "type": "number" }),
+        Ty::String => json!({ "type": "string" }),
+        Ty::Array(item) if **item == Ty::Any => json!({ "type": "array" }),
+        Ty::Array(item) => json!({ "type": "array", "items": schema(item) }),
+        Ty::Named(name) => json!({ "$ref": format!("#/$defs/{name}") }),
+        Ty::Nullable(inner) => {
+            let mut members = match &**inner {
+                Ty::Union { members, .. } => members.iter().map(schema).collect(),
+                inner => vec![schema(inner)],
+            };
+            members.push(schema(&Ty::Null));
+            return any_of(members);
+        }
+        Ty::Union { members, .. } => return any_of(members.iter().map(schema).collect()),
+    };
+    match value {
+        Value::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+/// 成员都只有 `type` 时合并为 `type` 数组
+fn any_of(members: Vec<Map<String, Value>>) -> Map<String, Value> {
+    let types = members
+        .iter()
+        .map(|m| match m.get("type") {
+            Some(Value::String(t)) if m.len() == 1 => Some(Value::String(t.clone())),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    let mut map = Map::new();
+    match types {
+        Some(types) => map.insert("type".into(), Value::Array(types)),
+        None => map.insert(
+            "anyOf".into(),
+            Value::Array(members.into_iter().map(Value::Object).collect()),
+        ),
+    };
+    map
+}
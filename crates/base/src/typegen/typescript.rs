@@ -0,0 +1,54 @@
+//! TypeScript 接口，可选字段加 `?`，联合类型直接写成 `A | B`
+
+use super::{Model, Ty};
+
+pub(super) fn render(model: &Model) -> String {
+    let mut blocks = Vec::new();
+    if !matches!(model.root, Ty::Named(_)) {
+        blocks.push(format!(
+            "export type {} = {};\n",
+            model.name,
+            ty(&model.root)
+        ));
+    }
+    for def in &model.defs {
+        let mut block = format!("export interface {} {{\n", def.name);
+        for prop in &def.props {
+            let key = if is_identifier(&prop.key) {
+                prop.key.clone()
+            } else {
+                format!("{:?}", prop.key)
+            };
+            let optional = if prop.optional { "?" } else { "" };
+            block.push_str(&format!("  {key}{optional}: {};\n", ty(&prop.ty)));
+        }
+        block.push_str("}\n");
+        blocks.push(block);
+    }
+    blocks.join("\n")
+}
+
+fn ty(ty_: &Ty) -> String {
+    match ty_ {
+        Ty::Any => "unknown".into(),
+        Ty::Null => "null".into(),
+        Ty::Bool => "boolean".into(),
+        Ty::Integer | Ty::Float => "number".into(),
+        Ty::String => "string".into(),
+        Ty::Array(item) => match **item {
+            Ty::Nullable(_) | Ty::Union { .. } => format!("({})[]", ty(item)),
+            _ => format!("{}[]", ty(item)),
+        },
+        Ty::Named(name) => name.clone(),
+        Ty::Nullable(inner) => format!("{} | null", ty(inner)),
+        Ty::Union { members, .. } => members.iter().map(ty).collect::<Vec<_>>().join(" | "),
+    }
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
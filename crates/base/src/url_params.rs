@@ -1,38 +1,233 @@
-use std::collections::HashMap;
-
 use anyhow::{Error, Result};
+use serde_json::{Map, Value};
+
+/// 表单编码（application/x-www-form-urlencoded），空格编码为 `+`
+pub fn form_encode(data: &str) -> String {
+    urlencoding::encode(data).replace("%20", "+")
+}
+
+/// 表单解码，`+` 还原为空格，不合法的 UTF-8 以替换字符代替
+pub fn form_decode(data: &str) -> String {
+    let data = data.replace('+', " ");
+    String::from_utf8_lossy(&urlencoding::decode_binary(data.as_bytes())).into_owned()
+}
+
+/// 编码参数名，保留 `a[]`、`a[b]` 中的方括号以便阅读
+pub fn encode_key(key: &str) -> String {
+    form_encode(key).replace("%5B", "[").replace("%5D", "]")
+}
+
+/// 按 `&` 和 `=` 拆分查询字符串，保留顺序与重复的键，键和值均已解码。
+/// 没有 `=` 的参数（如 `?x`）值为 `None`
+pub fn split(data: &str) -> Vec<(String, Option<String>)> {
+    let data = data.trim();
+    data.strip_prefix('?')
+        .unwrap_or(data)
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (form_decode(key), Some(form_decode(value))),
+            None => (form_decode(pair), None),
+        })
+        .collect()
+}
 
 /// url_params to json
+///
+/// 重复的键和 `a[]=1&a[]=2` 转为数组，`a[b]=1` 转为嵌套对象，值一律为字符串
 pub fn parse(data: &str) -> Result<String> {
     if data.trim().is_empty() {
         return Ok(String::new());
     }
 
-    let map = data
-        .split('&')
-        .map(|d| {
-            let mut key = "";
-            let mut value = "";
+    let mut root = Value::Object(Map::new());
+    for (key, value) in split(data) {
+        let path = key_path(&key);
+        insert(&mut root, &path, value.unwrap_or_default())
+            .map_err(|_| Error::msg(format!("参数 {key} 与前面的参数结构冲突")))?;
+    }
 
-            let mut p = d.split('=');
-            if let Some(k) = p.next() {
-                key = k;
-            }
+    serde_json::to_string_pretty(&root).map_err(|e| Error::msg(e.to_string()))
+}
+
+/// json to url_params，与 [`parse`] 互逆：数组输出为 `a[]=1&a[]=2`，嵌套对象输出为 `a[b]=1`。
+/// 空数组、空对象、嵌套数组等转换后无法还原的结构报错
+pub fn stringify(data: &str) -> Result<String> {
+    if data.trim().is_empty() {
+        return Ok(String::new());
+    }
 
-            if let Some(v) = p.next() {
-                value = v;
+    let value = serde_json::from_str::<Value>(data).map_err(|e| Error::msg(e.to_string()))?;
+    let Value::Object(map) = value else {
+        return Err(Error::msg("JSON 必须是对象"));
+    };
+    if map.is_empty() {
+        return Ok(String::new());
+    }
+    let mut pairs = Vec::new();
+    for (key, value) in &map {
+        flatten(encode_key(key), value, &mut pairs)?;
+    }
+    let query = pairs.join("&");
+
+    // 相邻的数组元素是键不同的对象时会被合并，键名含方括号时会被拆开，统一用解析结果检查
+    let parsed = parse(&query)
+        .ok()
+        .and_then(|json| serde_json::from_str::<Value>(&json).ok());
+    if parsed != Some(as_strings(&Value::Object(map))) {
+        return Err(Error::msg("该 JSON 转为查询参数后无法还原为相同的结构"));
+    }
+    Ok(query)
+}
+
+/// 把 `a[b][]` 拆成 `["a", "b", ""]`，格式不对时整个参数名作为普通的键
+fn key_path(key: &str) -> Vec<&str> {
+    let Some(start) = key.find('[').filter(|start| *start > 0) else {
+        return vec![key];
+    };
+    let mut path = vec![&key[..start]];
+    let mut rest = &key[start..];
+    while !rest.is_empty() {
+        let Some(end) = rest.strip_prefix('[').and_then(|r| r.find(']')) else {
+            return vec![key];
+        };
+        path.push(&rest[1..=end]);
+        rest = &rest[end + 2..];
+    }
+    path
+}
+
+fn insert(node: &mut Value, path: &[&str], value: String) -> Result<(), ()> {
+    let Some((first, rest)) = path.split_first() else {
+        return Err(());
+    };
+
+    if first.is_empty() {
+        if node.is_null() {
+            *node = Value::Array(Vec::new());
+        }
+        let Value::Array(items) = node else {
+            return Err(());
+        };
+        if rest.is_empty() {
+            items.push(Value::String(value));
+            return Ok(());
+        }
+        // a[][x]=1&a[][y]=2 合并到同一个元素，键重复时才开始新元素
+        let reuse = matches!(items.last(), Some(Value::Object(map)) if !map.contains_key(rest[0]));
+        if !reuse {
+            items.push(Value::Null);
+        }
+        return insert(items.last_mut().ok_or(())?, rest, value);
+    }
+
+    if node.is_null() {
+        *node = Value::Object(Map::new());
+    }
+    let Value::Object(map) = node else {
+        return Err(());
+    };
+    if !rest.is_empty() {
+        return insert(
+            map.entry(first.to_string()).or_insert(Value::Null),
+            rest,
+            value,
+        );
+    }
+    match map.get_mut(*first) {
+        None => {
+            map.insert(first.to_string(), Value::String(value));
+        }
+        Some(Value::Array(items)) => items.push(Value::String(value)),
+        Some(existing @ Value::String(_)) => {
+            let previous = existing.take();
+            *existing = Value::Array(vec![previous, Value::String(value)]);
+        }
+        Some(_) => return Err(()),
+    }
+    Ok(())
+}
+
+fn flatten(key: String, value: &Value, pairs: &mut Vec<String>) -> Result<()> {
+    match value {
+        Value::Array(items) if items.is_empty() => {
+            return Err(Error::msg(format!("{key} 是空数组，无法用查询参数表示")));
+        }
+        Value::Object(map) if map.is_empty() => {
+            return Err(Error::msg(format!("{key} 是空对象，无法用查询参数表示")));
+        }
+        Value::Array(items) => {
+            for item in items {
+                // a[][]=1&a[][]=2 会解析为 [["1"], ["2"]]，无法还原 [[1, 2]]
+                if item.is_array() {
+                    return Err(Error::msg(format!(
+                        "{key} 包含嵌套数组，无法用查询参数表示"
+                    )));
+                }
+                flatten(format!("{key}[]"), item, pairs)?;
             }
-            (key, value)
-        })
-        .fold(HashMap::<_, _>::new(), |mut map, (k, v)| {
-            // if let Some(vv) = map.get_mut(k) {
-            //     vv.push(v);
-            // } else {
-            //     map.insert(k, vec![v]);
-            // }
-            map.insert(k, v);
-            map
-        });
-
-    serde_json::to_string(&map).map_err(|e| Error::msg(e.to_string()))
+        }
+        Value::Object(map) => {
+            for (k, v) in map {
+                flatten(format!("{key}[{}]", encode_key(k)), v, pairs)?;
+            }
+        }
+        Value::String(s) => pairs.push(format!("{key}={}", form_encode(s))),
+        Value::Null => pairs.push(format!("{key}=")),
+        other => pairs.push(format!("{key}={other}")),
+    }
+    Ok(())
+}
+
+/// 与 [`flatten`] 一致地把标量转为字符串，`null` 为空字符串
+fn as_strings(value: &Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(as_strings).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), as_strings(v)))
+                .collect(),
+        ),
+        Value::String(_) => value.clone(),
+        Value::Null => Value::String(String::new()),
+        other => Value::String(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let json = parse("?a[]=1&a[]=2&b=x+y%21&b=z&c[d]=%E4%BD%A0&e")?;
+        let value = serde_json::from_str::<Value>(&json)?;
+        assert_eq!(
+            serde_json::json!({
+                "a": ["1", "2"],
+                "b": ["x y!", "z"],
+                "c": { "d": "你" },
+                "e": ""
+            }),
+            value
+        );
+        assert!(parse("a=1&a[b]=2").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stringify() -> Result<()> {
+        let query = "a[]=1&a[]=2&b=x+y%21&c[d]=%E4%BD%A0&u[][id]=1&u[][n]=x&u[][id]=2";
+        let json = parse(query)?;
+        assert_eq!(query, stringify(&json)?);
+        assert_eq!("n=1&t=true&z=", stringify(r#"{"n":1,"t":true,"z":null}"#)?);
+        assert!(stringify("[1]").is_err());
+        // 嵌套数组和空数组、空对象无法还原，报错而不是静默丢失
+        assert!(stringify(r#"{"a":[[1,2]]}"#).is_err());
+        assert!(stringify(r#"{"a":[],"b":1}"#).is_err());
+        assert!(stringify(r#"{"a":{"b":{}}}"#).is_err());
+        assert!(stringify(r#"{"u":[{"id":1},{"n":2}]}"#).is_err());
+        assert_eq!("", stringify("{}")?);
+        Ok(())
+    }
 }
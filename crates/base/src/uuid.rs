@@ -1,15 +1,274 @@
-use uuid::Uuid;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_512};
+use time::OffsetDateTime;
+use uuid::{Uuid, Variant, Version};
 
 use crate::{Error, Result};
 
+/// Twitter Snowflake 的起始时间（毫秒）
+pub const TWITTER_EPOCH: i64 = 1_288_834_974_657;
+
+/// KSUID 的起始时间（秒），即 2014-05-13T16:53:20Z
+pub const KSUID_EPOCH: i64 = 1_400_000_000;
+
+/// NanoID 默认的字母表，URL 安全
+pub const NANOID_ALPHABET: &str =
+    "useandom-26T198340PX75pxJACKVERYMINDBUSHWOLF_GQZbfghjklqvwyzrict";
+
+/// 上一个 ULID 的毫秒时间戳和随机部分，进程内共享
+static ULID_STATE: Mutex<(i64, u128)> = Mutex::new((-1, 0));
+/// 上一个 Snowflake 的毫秒时间戳和序列号，进程内共享
+static SNOWFLAKE_STATE: Mutex<(i64, u128)> = Mutex::new((-1, 0));
+
+const BASE36: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// ID 的种类
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdKind {
+    #[default]
+    Uuid,
+    Ulid,
+    Ksuid,
+    Snowflake,
+    NanoId,
+    Cuid2,
+}
+
+impl IdKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Uuid => "UUID",
+            Self::Ulid => "ULID",
+            Self::Ksuid => "KSUID",
+            Self::Snowflake => "Snowflake",
+            Self::NanoId => "NanoID",
+            Self::Cuid2 => "CUID2",
+        }
+    }
+}
+
+impl TryFrom<&str> for IdKind {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_ref() {
+            "uuid" => Ok(Self::Uuid),
+            "ulid" => Ok(Self::Ulid),
+            "ksuid" => Ok(Self::Ksuid),
+            "snowflake" => Ok(Self::Snowflake),
+            "nanoid" | "nano" => Ok(Self::NanoId),
+            "cuid2" | "cuid" => Ok(Self::Cuid2),
+            _ => Err(Error::msg(format!("不支持的 ID 类型: {value}"))),
+        }
+    }
+}
+
+/// Snowflake 的位布局：1 位符号、41 位毫秒时间戳，其后依次为数据中心、机器和序列号
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SnowflakeConfig {
+    /// 起始时间（毫秒）
+    pub epoch: i64,
+    pub datacenter_bits: u8,
+    pub worker_bits: u8,
+    /// 生成时使用的数据中心编号
+    pub datacenter_id: u64,
+    /// 生成时使用的机器编号
+    pub worker_id: u64,
+}
+
+impl Default for SnowflakeConfig {
+    fn default() -> Self {
+        Self {
+            epoch: TWITTER_EPOCH,
+            datacenter_bits: 5,
+            worker_bits: 5,
+            datacenter_id: 0,
+            worker_id: 0,
+        }
+    }
+}
+
+impl SnowflakeConfig {
+    /// 序列号所占的位数
+    fn sequence_bits(&self) -> Result<u8> {
+        let used = self.datacenter_bits as u16 + self.worker_bits as u16;
+        if used > 22 {
+            return Err(Error::msg(format!(
+                "数据中心和机器共占 {used} 位，不能超过 22 位"
+            )));
+        }
+        Ok(22 - used as u8)
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.sequence_bits()?;
+        if self.datacenter_id >> self.datacenter_bits != 0 {
+            return Err(Error::msg(format!(
+                "数据中心编号 {} 超出 {} 位",
+                self.datacenter_id, self.datacenter_bits
+            )));
+        }
+        if self.worker_id >> self.worker_bits != 0 {
+            return Err(Error::msg(format!(
+                "机器编号 {} 超出 {} 位",
+                self.worker_id, self.worker_bits
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 生成 ID 的参数，各字段只对相应的种类生效
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IdOptions {
+    pub kind: IdKind,
+    /// UUID 版本
+    pub version: u8,
+    /// v3/v5 的命名空间：`dns`、`url`、`oid`、`x500` 或任意 UUID
+    pub namespace: String,
+    /// v3/v5 的名称
+    pub name: String,
+    pub snowflake: SnowflakeConfig,
+    /// NanoID 的字母表
+    pub alphabet: String,
+    /// NanoID 或 CUID2 的长度，为空时分别为 21 和 24
+    pub size: Option<usize>,
+}
+
+impl Default for IdOptions {
+    fn default() -> Self {
+        Self {
+            kind: IdKind::Uuid,
+            version: 4,
+            namespace: "dns".to_string(),
+            name: String::new(),
+            snowflake: SnowflakeConfig::default(),
+            alphabet: NANOID_ALPHABET.to_string(),
+            size: None,
+        }
+    }
+}
+
+/// 按 `IdOptions` 连续生成 ID。
+/// 同一毫秒内的 ULID 随机部分和 Snowflake 序列号在整个进程内依次递增，
+/// 多次生成的结果保持有序且不重复
+pub struct IdGenerator {
+    options: IdOptions,
+    alphabet: Vec<char>,
+    size: usize,
+    /// CUID2 的计数器和主机指纹
+    counter: u64,
+    fingerprint: String,
+}
+
+impl IdGenerator {
+    pub fn new(options: IdOptions) -> Result<Self> {
+        let alphabet = options.alphabet.chars().collect::<Vec<_>>();
+        let size = match options.kind {
+            IdKind::Cuid2 => options.size.unwrap_or(24),
+            _ => options.size.unwrap_or(21),
+        };
+        match options.kind {
+            IdKind::Uuid => match options.version {
+                3 | 5 => {
+                    namespace(&options.namespace)?;
+                }
+                1 | 4 | 6 | 7 | 8 => {}
+                version => return Err(Error::msg(format!("不支持的 UUID 版本: {version}"))),
+            },
+            IdKind::Snowflake => options.snowflake.validate()?,
+            IdKind::NanoId => {
+                let mut unique = alphabet.clone();
+                unique.sort_unstable();
+                unique.dedup();
+                if alphabet.len() < 2 || unique.len() != alphabet.len() {
+                    return Err(Error::msg("NanoID 的字母表至少需要 2 个互不相同的字符"));
+                }
+                if size == 0 {
+                    return Err(Error::msg("NanoID 的长度不能为 0"));
+                }
+            }
+            IdKind::Cuid2 => {
+                if !(2..=32).contains(&size) {
+                    return Err(Error::msg("CUID2 的长度应在 2 到 32 之间"));
+                }
+            }
+            IdKind::Ulid | IdKind::Ksuid => {}
+        }
+        let fingerprint = format!("{}{}", std::process::id(), fastrand::u64(..));
+        let fingerprint = encode_base(&Sha3_512::digest(fingerprint), BASE36);
+        Ok(Self {
+            options,
+            alphabet,
+            size,
+            counter: fastrand::u64(..476_782_367),
+            fingerprint: fingerprint[..32].to_string(),
+        })
+    }
+
+    pub fn next_id(&mut self) -> Result<String> {
+        match self.options.kind {
+            IdKind::Uuid => match self.options.version {
+                1 => uuid_v1(),
+                3 => uuid_v3(&self.options.namespace, &self.options.name),
+                5 => uuid_v5(&self.options.namespace, &self.options.name),
+                6 => uuid_v6(),
+                7 => uuid_v7(),
+                8 => uuid_v8(),
+                _ => uuid_v4(),
+            },
+            IdKind::Ulid => next_ulid(),
+            IdKind::Ksuid => ksuid(),
+            IdKind::Snowflake => next_snowflake(&self.options.snowflake),
+            IdKind::NanoId => Ok((0..self.size)
+                .map(|_| self.alphabet[fastrand::usize(..self.alphabet.len())])
+                .collect()),
+            IdKind::Cuid2 => Ok(self.next_cuid2()),
+        }
+    }
+
+    /// 首字母随机，其余为时间、随机数、计数器和主机指纹的 SHA3-512 哈希
+    fn next_cuid2(&mut self) -> String {
+        self.counter += 1;
+        let entropy = (0..self.size)
+            .map(|_| BASE36[fastrand::usize(..36)] as char)
+            .collect::<String>();
+        let input = format!(
+            "{}{entropy}{}{}",
+            encode_base(&now_millis().to_be_bytes(), BASE36),
+            encode_base(&self.counter.to_be_bytes(), BASE36),
+            self.fingerprint
+        );
+        let hash = encode_base(&Sha3_512::digest(input), BASE36);
+        let letter = BASE36[fastrand::usize(10..36)] as char;
+        format!("{letter}{}", &hash[1..self.size])
+    }
+}
+
+/// 解析 v3/v5 的命名空间：`dns`、`url`、`oid`、`x500` 或任意 UUID
+pub fn namespace(namespace: &str) -> Result<Uuid> {
+    match namespace.trim().to_lowercase().as_ref() {
+        "dns" => Ok(Uuid::NAMESPACE_DNS),
+        "url" => Ok(Uuid::NAMESPACE_URL),
+        "oid" => Ok(Uuid::NAMESPACE_OID),
+        "x500" => Ok(Uuid::NAMESPACE_X500),
+        custom => Uuid::parse_str(custom)
+            .map_err(|_| Error::msg("命名空间应为 dns、url、oid、x500 或一个 UUID")),
+    }
+}
+
 pub fn uuid_v1() -> Result<String> {
     Ok(Uuid::now_v1(&[1, 2, 3, 4, 5, 6]).to_string())
 }
 
 pub fn uuid_v3(namespace: &str, name: &str) -> Result<String> {
-    Ok(Uuid::new_v3(&Uuid::NAMESPACE_DNS, name.as_bytes())
-        .to_string()
-        .to_string())
+    Ok(Uuid::new_v3(&self::namespace(namespace)?, name.as_bytes()).to_string())
 }
 
 pub fn uuid_v4() -> Result<String> {
@@ -17,7 +276,7 @@ pub fn uuid_v4() -> Result<String> {
 }
 
 pub fn uuid_v5(namespace: &str, name: &str) -> Result<String> {
-    Ok(Uuid::new_v5(&Uuid::NAMESPACE_DNS, name.as_bytes()).to_string())
+    Ok(Uuid::new_v5(&self::namespace(namespace)?, name.as_bytes()).to_string())
 }
 
 pub fn uuid_v6() -> Result<String> {
@@ -57,3 +316,531 @@ pub fn uuid_v8() -> Result<String> {
     ])
     .to_string())
 }
+
+fn next_ulid() -> Result<String> {
+    let millis = now_millis();
+    let mut state = ULID_STATE.lock().map_err(|e| Error::msg(e.to_string()))?;
+    let (last_millis, last_random) = *state;
+    // 同一毫秒或时钟回拨时沿用上一个时间戳，随机部分加一
+    let (millis, random) = if millis <= last_millis {
+        let random = last_random + 1;
+        if random >> 80 != 0 {
+            return Err(Error::msg("同一毫秒内生成的 ULID 过多"));
+        }
+        (last_millis, random)
+    } else {
+        (millis, fastrand::u128(..1 << 80))
+    };
+    *state = (millis, random);
+    Ok(encode_ulid((millis as u128) << 80 | random))
+}
+
+fn next_snowflake(config: &SnowflakeConfig) -> Result<String> {
+    let sequence_bits = config.sequence_bits()?;
+    let mut state = SNOWFLAKE_STATE
+        .lock()
+        .map_err(|e| Error::msg(e.to_string()))?;
+    let (last_millis, last_sequence) = *state;
+    let mut millis = now_millis().max(last_millis);
+    let mut sequence = if millis == last_millis {
+        last_sequence + 1
+    } else {
+        0
+    };
+    // 序列号用完时借用下一毫秒
+    if sequence >> sequence_bits != 0 {
+        millis += 1;
+        sequence = 0;
+    }
+    let elapsed = millis - config.epoch;
+    if !(0..1 << 41).contains(&elapsed) {
+        return Err(Error::msg("当前时间超出了起始时间后 41 位毫秒数的范围"));
+    }
+    *state = (millis, sequence);
+    let worker_shift = sequence_bits;
+    let datacenter_shift = worker_shift + config.worker_bits;
+    let id = (elapsed as u64) << 22
+        | config.datacenter_id << datacenter_shift
+        | config.worker_id << worker_shift
+        | sequence as u64;
+    Ok(id.to_string())
+}
+
+/// 生成一个 KSUID：4 字节秒级时间戳加 16 字节随机数，Base62 编码为 27 个字符
+pub fn ksuid() -> Result<String> {
+    let seconds = u32::try_from(now_millis().div_euclid(1000) - KSUID_EPOCH)
+        .map_err(|_| Error::msg("当前时间超出了 KSUID 的范围"))?;
+    let mut bytes = [0u8; 20];
+    bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+    bytes[4..].copy_from_slice(&fastrand::u128(..).to_be_bytes());
+    Ok(format!("{:0>27}", encode_base(&bytes, BASE62)))
+}
+
+/// ID 的解析结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdInfo {
+    /// `UUID`、`ULID`、`KSUID`、`Snowflake`、`NanoID` 或 `CUID2`
+    pub kind: String,
+    /// 规范形式，UUID 为小写带连接符
+    pub canonical: String,
+    pub version: Option<u8>,
+    pub variant: Option<String>,
+    /// 内嵌的时间，UTC，RFC 3339 格式
+    pub timestamp: Option<String>,
+    /// 其余字段，按出现顺序排列，例如节点、时钟序列、随机部分
+    pub fields: Vec<(String, String)>,
+}
+
+/// 解析 UUID（带或不带连接符、花括号、`urn:uuid:` 前缀）、ULID、KSUID、Snowflake、NanoID 或 CUID2。
+/// `kind` 为空时根据长度和字符自动识别，Snowflake 按 `snowflake` 的位布局拆分
+pub fn inspect(id: &str, kind: Option<IdKind>, snowflake: &SnowflakeConfig) -> Result<IdInfo> {
+    let id = id.trim();
+    let kind = match kind {
+        Some(kind) => kind,
+        None => detect(id)?,
+    };
+    match kind {
+        IdKind::Uuid => Uuid::parse_str(id)
+            .map(inspect_uuid)
+            .map_err(|_| Error::msg("无效的 UUID")),
+        IdKind::Ulid => inspect_ulid(id),
+        IdKind::Ksuid => inspect_ksuid(id),
+        IdKind::Snowflake => inspect_snowflake(id, snowflake),
+        IdKind::NanoId => inspect_nanoid(id),
+        IdKind::Cuid2 => inspect_cuid2(id),
+    }
+}
+
+fn detect(id: &str) -> Result<IdKind> {
+    let alphanumeric = id.bytes().all(|b| b.is_ascii_alphanumeric());
+    if Uuid::parse_str(id).is_ok() {
+        Ok(IdKind::Uuid)
+    } else if id.len() == 26 && decode_crockford(id).is_ok() {
+        Ok(IdKind::Ulid)
+    } else if id.len() == 27 && alphanumeric && decode_base(id, BASE62, 20).is_some() {
+        Ok(IdKind::Ksuid)
+    } else if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) {
+        Ok(IdKind::Snowflake)
+    } else if is_cuid2(id) {
+        Ok(IdKind::Cuid2)
+    } else if !id.is_empty()
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+    {
+        Ok(IdKind::NanoId)
+    } else {
+        Err(Error::msg(
+            "无法识别的 ID，支持 UUID、ULID、KSUID、Snowflake、NanoID 和 CUID2",
+        ))
+    }
+}
+
+fn inspect_uuid(uuid: Uuid) -> IdInfo {
+    let bytes = uuid.as_bytes();
+    let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    let mut fields = Vec::new();
+    let version = uuid.get_version();
+    let description = match version {
+        Some(Version::Nil) => "Nil UUID",
+        Some(Version::Mac) => "基于时间和节点",
+        Some(Version::Dce) => "DCE 安全",
+        Some(Version::Md5) => "基于名称（MD5）",
+        Some(Version::Random) => "随机",
+        Some(Version::Sha1) => "基于名称（SHA-1）",
+        Some(Version::SortMac) => "可排序的时间和节点",
+        Some(Version::SortRand) => "可排序的 Unix 时间和随机数",
+        Some(Version::Custom) => "自定义",
+        Some(Version::Max) => "Max UUID",
+        _ => "未知",
+    };
+    fields.push(("说明".to_string(), description.to_string()));
+    if let Some(node) = uuid.get_node_id() {
+        let clock_seq = u16::from_be_bytes([bytes[8] & 0x3f, bytes[9]]);
+        fields.push(("时钟序列".to_string(), clock_seq.to_string()));
+        let node = node.iter().map(|b| format!("{b:02x}")).collect::<Vec<_>>();
+        fields.push(("节点".to_string(), node.join(":")));
+    }
+    match version {
+        Some(Version::SortRand) => {
+            fields.push(("随机部分".to_string(), hex(&bytes[6..])));
+        }
+        Some(Version::Md5 | Version::Sha1) => {
+            fields.push(("哈希".to_string(), hex(bytes)));
+        }
+        _ => {}
+    }
+    let timestamp = uuid.get_timestamp().and_then(|ts| {
+        let (seconds, nanos) = ts.to_unix();
+        rfc3339(seconds as i128 * 1_000_000_000 + nanos as i128)
+    });
+    let variant = match uuid.get_variant() {
+        Variant::NCS => "NCS",
+        Variant::RFC4122 => "RFC 9562",
+        Variant::Microsoft => "Microsoft",
+        _ => "保留",
+    };
+    IdInfo {
+        kind: "UUID".to_string(),
+        canonical: uuid.hyphenated().to_string(),
+        version: Some(uuid.get_version_num() as u8),
+        variant: Some(variant.to_string()),
+        timestamp,
+        fields,
+    }
+}
+
+/// Crockford Base32 字母表，ULID 使用
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+fn encode_ulid(value: u128) -> String {
+    (0..26)
+        .rev()
+        .map(|i| CROCKFORD[(value >> (i * 5)) as usize & 31] as char)
+        .collect()
+}
+
+fn decode_crockford(id: &str) -> Result<u128> {
+    let mut value = 0u128;
+    for c in id.to_uppercase().chars() {
+        let c = match c {
+            'I' | 'L' => '1',
+            'O' => '0',
+            c => c,
+        };
+        let digit = CROCKFORD
+            .iter()
+            .position(|&d| d as char == c)
+            .ok_or_else(|| Error::msg(format!("ULID 中包含无效字符: {c}")))?;
+        value = value
+            .checked_mul(32)
+            .map(|v| v + digit as u128)
+            .ok_or_else(|| Error::msg("ULID 超出 128 位"))?;
+    }
+    Ok(value)
+}
+
+fn inspect_ulid(id: &str) -> Result<IdInfo> {
+    if id.len() != 26 {
+        return Err(Error::msg("ULID 应为 26 个字符"));
+    }
+    let value = decode_crockford(id)?;
+    let millis = (value >> 80) as i128;
+    let random = value & ((1 << 80) - 1);
+    Ok(IdInfo {
+        kind: "ULID".to_string(),
+        canonical: id.to_uppercase(),
+        version: None,
+        variant: None,
+        timestamp: rfc3339(millis * 1_000_000),
+        fields: vec![
+            ("毫秒时间戳".to_string(), millis.to_string()),
+            ("随机部分".to_string(), format!("{random:020x}")),
+            ("对应 UUID".to_string(), Uuid::from_u128(value).to_string()),
+        ],
+    })
+}
+
+fn inspect_ksuid(id: &str) -> Result<IdInfo> {
+    let bytes = decode_base(id, BASE62, 20)
+        .filter(|_| id.len() == 27)
+        .ok_or_else(|| Error::msg("KSUID 应为 27 个 Base62 字符"))?;
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let hex = bytes[4..]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    Ok(IdInfo {
+        kind: "KSUID".to_string(),
+        canonical: id.to_string(),
+        version: None,
+        variant: None,
+        timestamp: rfc3339((seconds as i64 + KSUID_EPOCH) as i128 * 1_000_000_000),
+        fields: vec![
+            ("时间戳".to_string(), seconds.to_string()),
+            ("随机部分".to_string(), hex),
+        ],
+    })
+}
+
+fn inspect_snowflake(id: &str, config: &SnowflakeConfig) -> Result<IdInfo> {
+    let value = id
+        .parse::<u64>()
+        .map_err(|_| Error::msg("Snowflake ID 应为不超过 64 位的整数"))?;
+    let sequence_bits = config.sequence_bits()?;
+    let mask = |bits: u8| (1u64 << bits) - 1;
+    let worker = (value >> sequence_bits) & mask(config.worker_bits);
+    let datacenter = (value >> (sequence_bits + config.worker_bits)) & mask(config.datacenter_bits);
+    let millis = (value >> 22) as i64 + config.epoch;
+    Ok(IdInfo {
+        kind: "Snowflake".to_string(),
+        canonical: value.to_string(),
+        version: None,
+        variant: None,
+        timestamp: rfc3339(millis as i128 * 1_000_000),
+        fields: vec![
+            ("毫秒时间戳".to_string(), millis.to_string()),
+            ("数据中心".to_string(), datacenter.to_string()),
+            ("机器".to_string(), worker.to_string()),
+            (
+                "序列号".to_string(),
+                (value & mask(sequence_bits)).to_string(),
+            ),
+        ],
+    })
+}
+
+fn inspect_nanoid(id: &str) -> Result<IdInfo> {
+    if id.is_empty() {
+        return Err(Error::msg("NanoID 不能为空"));
+    }
+    Ok(IdInfo {
+        kind: "NanoID".to_string(),
+        canonical: id.to_string(),
+        version: None,
+        variant: None,
+        timestamp: None,
+        fields: vec![
+            ("长度".to_string(), id.chars().count().to_string()),
+            ("说明".to_string(), "完全随机，不包含时间".to_string()),
+        ],
+    })
+}
+
+fn is_cuid2(id: &str) -> bool {
+    (2..=32).contains(&id.len())
+        && id.starts_with(|c: char| c.is_ascii_lowercase())
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+}
+
+fn inspect_cuid2(id: &str) -> Result<IdInfo> {
+    if !is_cuid2(id) {
+        return Err(Error::msg(
+            "CUID2 应为 2 到 32 个小写字母或数字，并以字母开头",
+        ));
+    }
+    Ok(IdInfo {
+        kind: "CUID2".to_string(),
+        canonical: id.to_string(),
+        version: None,
+        variant: None,
+        timestamp: None,
+        fields: vec![
+            ("长度".to_string(), id.len().to_string()),
+            (
+                "说明".to_string(),
+                "时间与随机数一起经过 SHA3 哈希，无法还原".to_string(),
+            ),
+        ],
+    })
+}
+
+/// 把大端字节串看作一个整数，转为以 `alphabet` 为数字的进制
+fn encode_base(bytes: &[u8], alphabet: &[u8]) -> String {
+    let base = alphabet.len() as u32;
+    let mut number = bytes.to_vec();
+    let mut digits = Vec::new();
+    while number.iter().any(|&b| b != 0) {
+        let mut remainder = 0u32;
+        for byte in number.iter_mut() {
+            let value = remainder << 8 | *byte as u32;
+            *byte = (value / base) as u8;
+            remainder = value % base;
+        }
+        digits.push(alphabet[remainder as usize] as char);
+    }
+    if digits.is_empty() {
+        digits.push(alphabet[0] as char);
+    }
+    digits.iter().rev().collect()
+}
+
+/// `encode_base` 的逆运算，结果超出 `len` 个字节或含有无效字符时返回 `None`
+fn decode_base(s: &str, alphabet: &[u8], len: usize) -> Option<Vec<u8>> {
+    let base = alphabet.len() as u32;
+    let mut bytes = vec![0u8; len];
+    for c in s.bytes() {
+        let mut carry = alphabet.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            let value = *byte as u32 * base + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(bytes)
+}
+
+fn now_millis() -> i64 {
+    (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+fn rfc3339(nanos: i128) -> Option<String> {
+    OffsetDateTime::from_unix_timestamp_nanos(nanos)
+        .ok()?
+        .format(&time::format_description::well_known::Rfc3339)
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_based() -> Result<()> {
+        // RFC 9562 附录中的示例
+        assert_eq!(
+            "5df41881-3aed-3515-88a7-2f4a814cf09e",
+            uuid_v3("dns", "www.example.com")?
+        );
+        assert_eq!(
+            "2ed6657d-e927-568b-95e1-2665a8aea6a2",
+            uuid_v5("dns", "www.example.com")?
+        );
+        assert_ne!(uuid_v5("url", "a")?, uuid_v5("dns", "a")?);
+        assert_eq!(
+            uuid_v5("6ba7b811-9dad-11d1-80b4-00c04fd430c8", "a")?,
+            uuid_v5("URL", "a")?
+        );
+        assert!(uuid_v5("example", "a").is_err());
+        Ok(())
+    }
+
+    fn inspect_auto(id: &str) -> Result<IdInfo> {
+        inspect(id, None, &SnowflakeConfig::default())
+    }
+
+    #[test]
+    fn test_inspect() -> Result<()> {
+        let info = inspect_auto("{C232AB00-9414-11EC-B3C8-9F6BDECED846}")?;
+        assert_eq!("c232ab00-9414-11ec-b3c8-9f6bdeced846", info.canonical);
+        assert_eq!(Some(1), info.version);
+        assert_eq!(Some("RFC 9562".to_string()), info.variant);
+        assert_eq!(Some("2022-02-22T19:22:22Z".to_string()), info.timestamp);
+        assert!(
+            info.fields
+                .contains(&("时钟序列".to_string(), "13256".to_string()))
+        );
+        assert!(
+            info.fields
+                .contains(&("节点".to_string(), "9f:6b:de:ce:d8:46".to_string()))
+        );
+
+        let info = inspect_auto("017F22E2-79B0-7CC3-98C4-DC0C0C07398F")?;
+        assert_eq!(Some(7), info.version);
+        assert_eq!(Some("2022-02-22T19:22:22Z".to_string()), info.timestamp);
+
+        let info = inspect_auto("01ARZ3NDEKTSV4RRFFQ69G5FAV")?;
+        assert_eq!("ULID", info.kind);
+        assert_eq!(Some("2016-07-30T23:54:10.259Z".to_string()), info.timestamp);
+
+        let info = inspect_auto("1541815603606036480")?;
+        assert_eq!("Snowflake", info.kind);
+        assert_eq!(Some("2022-06-28T16:07:40.105Z".to_string()), info.timestamp);
+
+        let info = inspect_auto("0ujtsYcgvSTl8PAuAdqWYSMnLOv")?;
+        assert_eq!("KSUID", info.kind);
+        assert_eq!(Some("2017-10-10T04:00:47Z".to_string()), info.timestamp);
+        assert!(info.fields.contains(&(
+            "随机部分".to_string(),
+            "b5a1cd34b5f99d1154fb6853345c9735".to_string()
+        )));
+
+        assert_eq!("CUID2", inspect_auto("tz4a98xxat96iws9zmbrgj3a")?.kind);
+        assert_eq!("NanoID", inspect_auto("V1StGXR8_Z5jdHi6B-myT")?.kind);
+        assert!(inspect_auto("not an id").is_err());
+        Ok(())
+    }
+
+    fn generate(options: IdOptions, count: usize) -> Result<Vec<String>> {
+        let mut generator = IdGenerator::new(options)?;
+        (0..count).map(|_| generator.next_id()).collect()
+    }
+
+    #[test]
+    fn test_generate() -> Result<()> {
+        let options = |kind| IdOptions {
+            kind,
+            ..Default::default()
+        };
+        let snowflake = SnowflakeConfig::default();
+
+        // 批量生成的 ULID 和 Snowflake 单调递增
+        let ids = generate(options(IdKind::Ulid), 100)?;
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert!(inspect(&ids[0], None, &snowflake)?.timestamp.is_some());
+
+        let ids = generate(options(IdKind::Ksuid), 3)?;
+        assert!(ids.iter().all(|id| id.len() == 27));
+        assert_eq!("KSUID", inspect(&ids[0], None, &snowflake)?.kind);
+
+        let config = SnowflakeConfig {
+            epoch: 1_600_000_000_000,
+            datacenter_bits: 3,
+            worker_bits: 7,
+            datacenter_id: 5,
+            worker_id: 100,
+        };
+        let ids = generate(
+            IdOptions {
+                snowflake: config,
+                ..options(IdKind::Snowflake)
+            },
+            5000,
+        )?;
+        let numbers = ids
+            .iter()
+            .map(|id| id.parse::<u64>().unwrap())
+            .collect::<Vec<_>>();
+        assert!(numbers.windows(2).all(|w| w[0] < w[1]));
+        let info = inspect(&ids[4999], Some(IdKind::Snowflake), &config)?;
+        assert!(
+            info.fields
+                .contains(&("数据中心".to_string(), "5".to_string()))
+        );
+        assert!(
+            info.fields
+                .contains(&("机器".to_string(), "100".to_string()))
+        );
+        assert!(
+            IdGenerator::new(IdOptions {
+                snowflake: SnowflakeConfig {
+                    worker_id: 32,
+                    ..Default::default()
+                },
+                ..options(IdKind::Snowflake)
+            })
+            .is_err()
+        );
+
+        let ids = generate(
+            IdOptions {
+                alphabet: "abc".to_string(),
+                size: Some(10),
+                ..options(IdKind::NanoId)
+            },
+            3,
+        )?;
+        assert!(
+            ids.iter()
+                .all(|id| id.len() == 10 && id.chars().all(|c| "abc".contains(c)))
+        );
+        assert!(
+            IdGenerator::new(IdOptions {
+                alphabet: "aa".to_string(),
+                ..options(IdKind::NanoId)
+            })
+            .is_err()
+        );
+
+        let ids = generate(options(IdKind::Cuid2), 10)?;
+        assert!(ids.iter().all(|id| id.len() == 24 && is_cuid2(id)));
+        assert_ne!(ids[0], ids[1]);
+        Ok(())
+    }
+}
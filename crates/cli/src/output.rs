@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use serde_json::json;
+
+/// 子命令的执行结果，按 `--json` 决定渲染方式
+pub enum Output {
+    /// 单个文本结果，JSON 模式下包装为 `{"output": ...}`
+    Text(String),
+    /// 键值结果，纯文本模式下每行输出 `key: value`
+    Map(BTreeMap<String, String>),
+    /// 纯文本与 JSON 各自给出的结果
+    Custom {
+        text: String,
+        json: serde_json::Value,
+    },
+    /// 与 `Custom` 相同，但表示检查未通过，输出后以退出码 1 结束
+    Failed {
+        text: String,
+        json: serde_json::Value,
+    },
+}
+
+impl Output {
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed { .. })
+    }
+
+    pub fn render(&self, json: bool) -> Result<String> {
+        if json {
+            let value = match self {
+                Self::Text(text) => json!({ "output": text }),
+                Self::Map(map) => json!(map),
+                Self::Custom { json, .. } | Self::Failed { json, .. } => json.clone(),
+            };
+            return Ok(serde_json::to_string(&value)?);
+        }
+
+        Ok(match self {
+            Self::Text(text) | Self::Custom { text, .. } | Self::Failed { text, .. } => {
+                text.clone()
+            }
+            Self::Map(map) => map
+                .iter()
+                .map(|(k, v)| format!("{k}: {v}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        })
+    }
+}
@@ -24,17 +24,24 @@ pub enum ViewType {
     TransformFiletype,
     TransformTime,
     TransformBaseConversion,
+    TransformCron,
     EncodeDecodeBase64,
+    EncodeDecodeBinary,
     EncodeDecodeUrl,
     EncodeDecodeJwt,
     EncodeDecodeCharset,
+    EncodeDecodeCipher,
+    EncodeDecodeKeyPair,
+    EncodeDecodeCertificate,
     EncodeDecodeMessyCode,
     FormatterJson,
     FormatterSql,
     FormatterXml,
     GeneratorUuid,
     GeneratorHash,
+    GeneratorPassword,
     GeneratorChecksum,
+    GeneratorType,
     DatabaseDatafaker,
     DatabaseDiff,
     TextMarkdown,
@@ -51,20 +58,27 @@ pub struct App {
     sidebar_collapsed: bool,
     uuid_generator: Option<Entity<UuidGenerator>>,
     hash_calculator: Option<Entity<HashCalculator>>,
+    password_hasher: Option<Entity<PasswordHasher>>,
     base64_encoder: Option<Entity<Base64Encoder>>,
+    binary_encoder: Option<Entity<BinaryEncoder>>,
     url_encoder: Option<Entity<UrlEncoder>>,
     jwt_decoder: Option<Entity<JwtDecoder>>,
     timestamp_converter: Option<Entity<TimestampConverter>>,
+    cron_expression: Option<Entity<CronExpression>>,
     base_converter: Option<Entity<BaseConverter>>,
     json_editor: Option<Entity<JsonEditor>>,
     markdown_editor: Option<Entity<MarkdownEditor>>,
     code_snippet: Option<Entity<CodeSnippet>>,
     todo_list: Option<Entity<TodoList>>,
     charset_encoder: Option<Entity<CharsetEncoder>>,
+    cipher_tool: Option<Entity<CipherTool>>,
+    key_workbench: Option<Entity<KeyWorkbench>>,
+    cert_decoder: Option<Entity<CertDecoder>>,
     messy_code_recover: Option<Entity<MessyCodeRecover>>,
     sql_formatter: Option<Entity<SqlFormatter>>,
     xml_formatter: Option<Entity<XmlFormatter>>,
     file_verify: Option<Entity<FileVerify>>,
+    type_generator: Option<Entity<TypeGenerator>>,
     fake_data_generator: Option<Entity<FakeDataGenerator>>,
     database_diff: Option<Entity<DatabaseDiff>>,
     system_monitor: Option<Entity<SystemMonitor>>,
@@ -84,20 +98,27 @@ impl App {
             sidebar_collapsed: false,
             uuid_generator: None,
             hash_calculator: None,
+            password_hasher: None,
             base64_encoder: None,
+            binary_encoder: None,
             url_encoder: None,
             jwt_decoder: None,
             timestamp_converter: None,
+            cron_expression: None,
             base_converter: None,
             json_editor: None,
             markdown_editor: None,
             code_snippet: None,
             todo_list: None,
             charset_encoder: None,
+            cipher_tool: None,
+            key_workbench: None,
+            cert_decoder: None,
             messy_code_recover: None,
             sql_formatter: None,
             xml_formatter: None,
             file_verify: None,
+            type_generator: None,
             fake_data_generator: None,
             database_diff: None,
             system_monitor: None,
@@ -202,6 +223,12 @@ impl Render for App {
                                                     cx,
                                                 );
                                             })),
+                                        SidebarMenuItem::new("Cron")
+                                            .icon(Icon::new(IconName::Calendar))
+                                            .active(current_view == ViewType::TransformCron)
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.set_view(ViewType::TransformCron, cx);
+                                            })),
                                     ]),
                                 // 编码/解码 — 可展开父菜单
                                 SidebarMenuItem::new("编码/解码")
@@ -214,6 +241,12 @@ impl Render for App {
                                             .on_click(cx.listener(|this, _, _, cx| {
                                                 this.set_view(ViewType::EncodeDecodeBase64, cx);
                                             })),
+                                        SidebarMenuItem::new("Base32/58/85")
+                                            .icon(Icon::new(IconName::CaseSensitive))
+                                            .active(current_view == ViewType::EncodeDecodeBinary)
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.set_view(ViewType::EncodeDecodeBinary, cx);
+                                            })),
                                         SidebarMenuItem::new("URL")
                                             .icon(Icon::new(IconName::ExternalLink))
                                             .active(current_view == ViewType::EncodeDecodeUrl)
@@ -232,6 +265,24 @@ impl Render for App {
                                             .on_click(cx.listener(|this, _, _, cx| {
                                                 this.set_view(ViewType::EncodeDecodeCharset, cx);
                                             })),
+                                        SidebarMenuItem::new("对称加密")
+                                            .icon(Icon::new(IconName::EyeOff))
+                                            .active(current_view == ViewType::EncodeDecodeCipher)
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.set_view(ViewType::EncodeDecodeCipher, cx);
+                                            })),
+                                        SidebarMenuItem::new("非对称密钥")
+                                            .icon(Icon::new(IconName::Asterisk))
+                                            .active(current_view == ViewType::EncodeDecodeKeyPair)
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.set_view(ViewType::EncodeDecodeKeyPair, cx);
+                                            })),
+                                        SidebarMenuItem::new("证书")
+                                            .icon(Icon::new(IconName::Frame))
+                                            .active(current_view == ViewType::EncodeDecodeCertificate)
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.set_view(ViewType::EncodeDecodeCertificate, cx);
+                                            })),
                                         SidebarMenuItem::new("乱码恢复")
                                             .icon(Icon::new(IconName::CaseSensitive))
                                             .active(current_view == ViewType::EncodeDecodeMessyCode)
@@ -280,12 +331,24 @@ impl Render for App {
                                             .on_click(cx.listener(|this, _, _, cx| {
                                                 this.set_view(ViewType::GeneratorHash, cx);
                                             })),
+                                        SidebarMenuItem::new("密码哈希")
+                                            .icon(Icon::new(IconName::Eye))
+                                            .active(current_view == ViewType::GeneratorPassword)
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.set_view(ViewType::GeneratorPassword, cx);
+                                            })),
                                         SidebarMenuItem::new("文件校验")
                                             .icon(Icon::new(IconName::File))
                                             .active(current_view == ViewType::GeneratorChecksum)
                                             .on_click(cx.listener(|this, _, _, cx| {
                                                 this.set_view(ViewType::GeneratorChecksum, cx);
                                             })),
+                                        SidebarMenuItem::new("类型生成")
+                                            .icon(Icon::new(IconName::SquareTerminal))
+                                            .active(current_view == ViewType::GeneratorType)
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.set_view(ViewType::GeneratorType, cx);
+                                            })),
                                     ]),
                                 // 数据库 — 可展开父菜单
                                 SidebarMenuItem::new("数据库")
@@ -393,9 +456,15 @@ impl Render for App {
                                 ViewType::TransformBaseConversion => {
                                     render_base_converter_view(self, window, cx)
                                 }
+                                ViewType::TransformCron => {
+                                    render_cron_expression_view(self, window, cx)
+                                }
                                 ViewType::EncodeDecodeBase64 => {
                                     render_base64_encoder_view(self, window, cx)
                                 }
+                                ViewType::EncodeDecodeBinary => {
+                                    render_binary_encoder_view(self, window, cx)
+                                }
                                 ViewType::EncodeDecodeUrl => {
                                     render_url_encoder_view(self, window, cx)
                                 }
@@ -405,6 +474,15 @@ impl Render for App {
                                 ViewType::EncodeDecodeCharset => {
                                     render_encode_decode_charset_view(self, window, cx)
                                 }
+                                ViewType::EncodeDecodeCipher => {
+                                    render_encode_decode_cipher_view(self, window, cx)
+                                }
+                                ViewType::EncodeDecodeKeyPair => {
+                                    render_encode_decode_key_pair_view(self, window, cx)
+                                }
+                                ViewType::EncodeDecodeCertificate => {
+                                    render_encode_decode_certificate_view(self, window, cx)
+                                }
                                 ViewType::EncodeDecodeMessyCode => {
                                     render_encode_decode_messy_code_view(self, window, cx)
                                 }
@@ -423,9 +501,15 @@ impl Render for App {
                                 ViewType::GeneratorHash => {
                                     render_hash_calculator_view(self, window, cx)
                                 }
+                                ViewType::GeneratorPassword => {
+                                    render_password_hasher_view(self, window, cx)
+                                }
                                 ViewType::GeneratorChecksum => {
                                     render_generator_checksum_view(self, window, cx)
                                 }
+                                ViewType::GeneratorType => {
+                                    render_type_generator_view(self, window, cx)
+                                }
                                 ViewType::DatabaseDatafaker => {
                                     render_database_datafaker_view(self, window, cx)
                                 }
@@ -462,10 +546,10 @@ fn render_home_view(cx: &mut Context<App>) -> Div {
     // 匹配 Tauri Home.vue：menuAll（排除首页，展平子菜单），flex-wrap 240px 卡片仅显示标题
     let titles: &[&str] = &[
         "系统监控", "代码片段", "待办事项",
-        "文件格式转换", "时间戳", "进制转换",
-        "Base64", "URL", "JWT", "字符编码", "乱码恢复",
+        "文件格式转换", "时间戳", "进制转换", "Cron",
+        "Base64", "Base32/58/85", "URL", "JWT", "字符编码", "对称加密", "非对称密钥", "证书", "乱码恢复",
         "JSON Editor", "SQL", "XML",
-        "UUID", "文本Hash", "文件校验",
+        "UUID", "文本Hash", "密码哈希", "文件校验", "类型生成",
         "假数据生成", "数据库差异",
         "Markdown",
         "IP",
@@ -531,17 +615,24 @@ fn view_for_title(title: &str) -> Option<ViewType> {
         "文件格式转换" => Some(ViewType::TransformFiletype),
         "时间戳" => Some(ViewType::TransformTime),
         "进制转换" => Some(ViewType::TransformBaseConversion),
+        "Cron" => Some(ViewType::TransformCron),
         "Base64" => Some(ViewType::EncodeDecodeBase64),
+        "Base32/58/85" => Some(ViewType::EncodeDecodeBinary),
         "URL" => Some(ViewType::EncodeDecodeUrl),
         "JWT" => Some(ViewType::EncodeDecodeJwt),
         "字符编码" => Some(ViewType::EncodeDecodeCharset),
+        "对称加密" => Some(ViewType::EncodeDecodeCipher),
+        "非对称密钥" => Some(ViewType::EncodeDecodeKeyPair),
+        "证书" => Some(ViewType::EncodeDecodeCertificate),
         "乱码恢复" => Some(ViewType::EncodeDecodeMessyCode),
         "JSON Editor" => Some(ViewType::FormatterJson),
         "SQL" => Some(ViewType::FormatterSql),
         "XML" => Some(ViewType::FormatterXml),
         "UUID" => Some(ViewType::GeneratorUuid),
         "文本Hash" => Some(ViewType::GeneratorHash),
+        "密码哈希" => Some(ViewType::GeneratorPassword),
         "文件校验" => Some(ViewType::GeneratorChecksum),
+        "类型生成" => Some(ViewType::GeneratorType),
         "假数据生成" => Some(ViewType::DatabaseDatafaker),
         "数据库差异" => Some(ViewType::DatabaseDiff),
         "Markdown" => Some(ViewType::TextMarkdown),
@@ -624,6 +715,54 @@ fn render_encode_decode_charset_view(
     }
 }
 
+fn render_encode_decode_cipher_view(
+    app: &mut App,
+    window: &mut Window,
+    cx: &mut Context<App>,
+) -> Div {
+    if app.cipher_tool.is_none() {
+        app.cipher_tool = Some(cx.new(|cx| CipherTool::new(window, cx)));
+    }
+
+    if let Some(ref cipher_tool) = app.cipher_tool {
+        div().p_6().child(cipher_tool.clone())
+    } else {
+        div().p_6().child("Loading...")
+    }
+}
+
+fn render_encode_decode_key_pair_view(
+    app: &mut App,
+    window: &mut Window,
+    cx: &mut Context<App>,
+) -> Div {
+    if app.key_workbench.is_none() {
+        app.key_workbench = Some(cx.new(|cx| KeyWorkbench::new(window, cx)));
+    }
+
+    if let Some(ref key_workbench) = app.key_workbench {
+        div().p_6().child(key_workbench.clone())
+    } else {
+        div().p_6().child("Loading...")
+    }
+}
+
+fn render_encode_decode_certificate_view(
+    app: &mut App,
+    window: &mut Window,
+    cx: &mut Context<App>,
+) -> Div {
+    if app.cert_decoder.is_none() {
+        app.cert_decoder = Some(cx.new(|cx| CertDecoder::new(window, cx)));
+    }
+
+    if let Some(ref cert_decoder) = app.cert_decoder {
+        div().p_6().child(cert_decoder.clone())
+    } else {
+        div().p_6().child("Loading...")
+    }
+}
+
 fn render_encode_decode_messy_code_view(
     app: &mut App,
     window: &mut Window,
@@ -680,6 +819,18 @@ fn render_generator_checksum_view(
     }
 }
 
+fn render_type_generator_view(app: &mut App, window: &mut Window, cx: &mut Context<App>) -> Div {
+    if app.type_generator.is_none() {
+        app.type_generator = Some(cx.new(|cx| TypeGenerator::new(window, cx)));
+    }
+
+    if let Some(ref type_generator) = app.type_generator {
+        div().p_6().child(type_generator.clone())
+    } else {
+        div().p_6().child("Loading...")
+    }
+}
+
 fn render_database_datafaker_view(
     app: &mut App,
     window: &mut Window,
@@ -732,6 +883,22 @@ fn render_hash_calculator_view(app: &mut App, window: &mut Window, cx: &mut Cont
     }
 }
 
+fn render_password_hasher_view(
+    app: &mut App,
+    window: &mut Window,
+    cx: &mut Context<App>,
+) -> Div {
+    if app.password_hasher.is_none() {
+        app.password_hasher = Some(cx.new(|cx| PasswordHasher::new(window, cx)));
+    }
+
+    if let Some(ref password_hasher) = app.password_hasher {
+        div().p_6().child(password_hasher.clone())
+    } else {
+        div().p_6().child("Loading...")
+    }
+}
+
 fn render_base64_encoder_view(app: &mut App, window: &mut Window, cx: &mut Context<App>) -> Div {
     if app.base64_encoder.is_none() {
         app.base64_encoder = Some(cx.new(|cx| Base64Encoder::new(window, cx)));
@@ -744,6 +911,18 @@ fn render_base64_encoder_view(app: &mut App, window: &mut Window, cx: &mut Conte
     }
 }
 
+fn render_binary_encoder_view(app: &mut App, window: &mut Window, cx: &mut Context<App>) -> Div {
+    if app.binary_encoder.is_none() {
+        app.binary_encoder = Some(cx.new(|cx| BinaryEncoder::new(window, cx)));
+    }
+
+    if let Some(ref binary_encoder) = app.binary_encoder {
+        div().p_6().child(binary_encoder.clone())
+    } else {
+        div().p_6().child("Loading...")
+    }
+}
+
 fn render_url_encoder_view(app: &mut App, window: &mut Window, cx: &mut Context<App>) -> Div {
     if app.url_encoder.is_none() {
         app.url_encoder = Some(cx.new(|cx| UrlEncoder::new(window, cx)));
@@ -772,6 +951,18 @@ fn render_timestamp_converter_view(
     }
 }
 
+fn render_cron_expression_view(app: &mut App, window: &mut Window, cx: &mut Context<App>) -> Div {
+    if app.cron_expression.is_none() {
+        app.cron_expression = Some(cx.new(|cx| CronExpression::new(window, cx)));
+    }
+
+    if let Some(ref cron_expression) = app.cron_expression {
+        div().p_6().child(cron_expression.clone())
+    } else {
+        div().p_6().child("Loading...")
+    }
+}
+
 fn render_jwt_decoder_view(app: &mut App, window: &mut Window, cx: &mut Context<App>) -> Div {
     if app.jwt_decoder.is_none() {
         app.jwt_decoder = Some(cx.new(|cx| JwtDecoder::new(window, cx)));
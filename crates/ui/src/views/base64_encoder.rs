@@ -1,15 +1,32 @@
-use gpui::*;
+use std::sync::Arc;
+
+use gpui::{prelude::FluentBuilder as _, *};
 use gpui_component::{
     button::*,
+    checkbox::Checkbox,
     input::{Input, InputEvent, InputState},
     *,
 };
 
+/// 解码出的图片，`preview` 为 `None` 表示 gpui 无法渲染该格式
+struct DecodedImage {
+    info: base::ImageInfo,
+    bytes: Vec<u8>,
+    preview: Option<Arc<Image>>,
+}
+
 pub struct Base64Encoder {
     input: String,
     output: String,
+    options: base::Base64Options,
+    data_uri: bool,
+    image: Option<DecodedImage>,
+    /// 后台读取的图片文件，等下一次渲染时编码并回填
+    picked_image: Option<Vec<u8>>,
+    error: String,
     input_state: Entity<InputState>,
     output_state: Entity<InputState>,
+    image_state: Entity<InputState>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -26,6 +43,12 @@ impl Base64Encoder {
                 .multi_line(true)
         });
 
+        let image_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("选择图片编码，或粘贴 Base64、data:image/...;base64, 后点击解码")
+                .multi_line(true)
+        });
+
         let _subscriptions = vec![cx.subscribe_in(&input_state, window, {
             let input_state = input_state.clone();
             move |this, _, ev: &InputEvent, _, cx| {
@@ -40,8 +63,14 @@ impl Base64Encoder {
         Self {
             input: String::new(),
             output: String::new(),
+            options: base::Base64Options::default(),
+            data_uri: true,
+            image: None,
+            picked_image: None,
+            error: String::new(),
             input_state,
             output_state,
+            image_state,
             _subscriptions,
         }
     }
@@ -50,7 +79,7 @@ impl Base64Encoder {
         if self.input.is_empty() {
             return;
         }
-        self.output = base::encode_base64_text(&self.input).unwrap_or_default();
+        self.output = base::encode_base64_text(&self.input, &self.options).unwrap_or_default();
         self.output_state.update(cx, |state, cx| {
             state.set_value(self.output.clone(), window, cx);
         });
@@ -95,6 +124,94 @@ impl Base64Encoder {
         }
     }
 
+    fn select_image(&mut self, cx: &mut Context<Self>) {
+        let task = cx.background_executor().spawn(async move {
+            let file = rfd::AsyncFileDialog::new()
+                .set_title("选择图片")
+                .add_filter(
+                    "图片",
+                    &["png", "jpg", "jpeg", "gif", "webp", "bmp", "ico", "svg", "tif", "tiff"],
+                )
+                .pick_file()
+                .await?;
+            Some(std::fs::read(file.path()).map_err(|e| e.to_string()))
+        });
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            if let Some(result) = task.await {
+                let _ = this.update(cx, |this, cx| {
+                    match result {
+                        Ok(bytes) => this.picked_image = Some(bytes),
+                        Err(err) => this.error = err,
+                    }
+                    cx.notify();
+                });
+            }
+        })
+        .detach();
+    }
+
+    fn encode_image(&mut self, bytes: &[u8], window: &mut Window, cx: &mut Context<Self>) {
+        match base::encode_base64_img(bytes, &self.options, self.data_uri) {
+            Ok(encoded) => {
+                self.image_state.update(cx, |state, cx| {
+                    state.set_value(encoded, window, cx);
+                });
+                self.decode_image(cx);
+            }
+            Err(err) => self.error = err.to_string(),
+        }
+    }
+
+    fn decode_image(&mut self, cx: &mut Context<Self>) {
+        let input = self.image_state.read(cx).value().to_string();
+        self.image = None;
+        self.error.clear();
+        if !input.trim().is_empty() {
+            match base::decode_base64_img(&input) {
+                Ok((info, bytes)) => {
+                    let preview = image_format(&info.mime)
+                        .map(|format| Arc::new(Image::from_bytes(format, bytes.clone())));
+                    self.image = Some(DecodedImage {
+                        info,
+                        bytes,
+                        preview,
+                    });
+                }
+                Err(err) => self.error = err.to_string(),
+            }
+        }
+        cx.notify();
+    }
+
+    fn save_image(&mut self, cx: &mut Context<Self>) {
+        let Some(ref image) = self.image else {
+            return;
+        };
+        let bytes = image.bytes.clone();
+        let file_name = format!("image.{}", image.info.extension);
+
+        cx.spawn(async move |_, cx| {
+            let file_path = cx
+                .background_executor()
+                .spawn(async move {
+                    rfd::AsyncFileDialog::new()
+                        .set_file_name(file_name)
+                        .save_file()
+                        .await
+                })
+                .await;
+
+            if let Some(path) = file_path {
+                let _ = cx
+                    .background_executor()
+                    .spawn(async move { std::fs::write(path.path(), bytes) })
+                    .await;
+            }
+        })
+        .detach();
+    }
+
     fn copy_output(&mut self, cx: &mut Context<Self>) {
         if !self.output.is_empty() {
             cx.write_to_clipboard(ClipboardItem::new_string(self.output.clone()));
@@ -113,8 +230,27 @@ impl Base64Encoder {
     }
 }
 
+/// gpui 能直接渲染的图片格式
+fn image_format(mime: &str) -> Option<ImageFormat> {
+    match mime {
+        "image/png" => Some(ImageFormat::Png),
+        "image/jpeg" => Some(ImageFormat::Jpeg),
+        "image/gif" => Some(ImageFormat::Gif),
+        "image/webp" => Some(ImageFormat::Webp),
+        "image/bmp" => Some(ImageFormat::Bmp),
+        "image/tiff" => Some(ImageFormat::Tiff),
+        "image/svg+xml" => Some(ImageFormat::Svg),
+        _ => None,
+    }
+}
+
 impl Render for Base64Encoder {
-    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if let Some(bytes) = self.picked_image.take() {
+            self.encode_image(&bytes, window, cx);
+        }
+        let options = self.options;
+
         div()
             .flex()
             .flex_col()
@@ -185,6 +321,34 @@ impl Render for Base64Encoder {
                             .on_click(cx.listener(|this, _, window, cx| {
                                 this.decode(window, cx);
                             })),
+                    )
+                    .child(
+                        Checkbox::new("url-safe")
+                            .label("URL 安全")
+                            .checked(options.url_safe)
+                            .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                this.options.url_safe = *v;
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Checkbox::new("padding")
+                            .label("填充 =")
+                            .checked(options.padding)
+                            .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                this.options.padding = *v;
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Checkbox::new("mime-wrap")
+                            .label("MIME 换行（76）")
+                            .checked(options.line_width > 0)
+                            .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                this.options.line_width =
+                                    if *v { base::MIME_LINE_WIDTH } else { 0 };
+                                cx.notify();
+                            })),
                     ),
             )
             .child(
@@ -231,5 +395,96 @@ impl Render for Base64Encoder {
                     .child(div().text_sm().w(px(80.0)).child("输出"))
                     .child(Input::new(&self.output_state).h(px(250.0))),
             )
+            .child(
+                // Row 6: 图片 → 选择 + 解码 + 保存 + data URI
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().w(px(80.0)).child("图片"))
+                    .child(
+                        ButtonGroup::new("image-buttons")
+                            .child(
+                                Button::new("select-image")
+                                    .icon(Icon::new(IconName::Folder))
+                                    .tooltip("选择图片")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.select_image(cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("decode-image")
+                                    .icon(Icon::new(IconName::ArrowDown))
+                                    .tooltip("解码图片")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.decode_image(cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("save-image")
+                                    .icon(Icon::new(IconName::ArrowUp))
+                                    .tooltip("保存图片")
+                                    .disabled(self.image.is_none())
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.save_image(cx);
+                                    })),
+                            ),
+                    )
+                    .child(
+                        Checkbox::new("data-uri")
+                            .label("data URI")
+                            .checked(self.data_uri)
+                            .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                this.data_uri = *v;
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().w(px(80.0)).child("Base64"))
+                    .child(Input::new(&self.image_state).h(px(160.0))),
+            )
+            .when(!self.error.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(self.error.clone()),
+                )
+            })
+            .when_some(self.image.as_ref(), |this, image| {
+                let info = &image.info;
+                let size = match (info.width, info.height) {
+                    (Some(width), Some(height)) => format!(" · {width}×{height}"),
+                    _ => String::new(),
+                };
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_2()
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(format!("{}{size} · {} 字节", info.mime, info.size)),
+                        )
+                        .child(match &image.preview {
+                            Some(preview) => img(preview.clone())
+                                .max_w_full()
+                                .max_h(px(360.0))
+                                .into_any_element(),
+                            None => div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child("该格式不支持预览，可保存后查看")
+                                .into_any_element(),
+                        }),
+                )
+            })
     }
 }
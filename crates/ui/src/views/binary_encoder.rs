@@ -0,0 +1,315 @@
+use gpui::{prelude::FluentBuilder as _, *};
+use gpui_component::{
+    button::*,
+    input::{Input, InputEvent, InputState},
+    *,
+};
+
+const INPUT_TYPES: [(&str, &str); 3] = [("text", "文本"), ("hex", "Hex"), ("base64", "Base64")];
+
+pub struct BinaryEncoder {
+    input_type: &'static str,
+    encoding: base::Encoding,
+    /// 全部编码的结果，输入为空时为空
+    encoded: Vec<base::Encoded>,
+    /// 解码结果：UTF-8 文本（不合法时为 `None`）与十六进制
+    decoded: Option<(Option<String>, String)>,
+    error: String,
+    input_state: Entity<InputState>,
+    hrp_state: Entity<InputState>,
+    encoded_state: Entity<InputState>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl BinaryEncoder {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("请输入文本...")
+                .multi_line(true)
+        });
+        let hrp_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Bech32 前缀")
+                .default_value(base::encoding::DEFAULT_HRP)
+        });
+        let encoded_state = cx.new(|cx| InputState::new(window, cx).placeholder("待解码的文本"));
+
+        let _subscriptions = vec![
+            cx.subscribe_in(&input_state, window, |this, _, ev: &InputEvent, _, cx| {
+                if let InputEvent::Change = ev {
+                    this.encode(cx);
+                }
+            }),
+            cx.subscribe_in(&hrp_state, window, |this, _, ev: &InputEvent, _, cx| {
+                if let InputEvent::Change = ev {
+                    this.encode(cx);
+                }
+            }),
+        ];
+
+        Self {
+            input_type: INPUT_TYPES[0].0,
+            encoding: base::Encoding::Base58,
+            encoded: Vec::new(),
+            decoded: None,
+            error: String::new(),
+            input_state,
+            hrp_state,
+            encoded_state,
+            _subscriptions,
+        }
+    }
+
+    fn encode(&mut self, cx: &mut Context<Self>) {
+        let input = self.input_state.read(cx).value().to_string();
+        let hrp = self.hrp_state.read(cx).value().to_string();
+        self.error.clear();
+        self.encoded.clear();
+        if !input.is_empty() {
+            match base::encode_binary(&input, Some(self.input_type), &hrp) {
+                Ok(encoded) => self.encoded = encoded,
+                Err(err) => self.error = err.to_string(),
+            }
+        }
+        cx.notify();
+    }
+
+    fn decode(&mut self, cx: &mut Context<Self>) {
+        let input = self.encoded_state.read(cx).value().to_string();
+        match base::decode_binary(self.encoding, &input) {
+            Ok(bytes) => {
+                self.error.clear();
+                let hex = base::hash::encode_output(&bytes, Some("hex"), false);
+                self.decoded = Some((String::from_utf8(bytes).ok(), hex));
+            }
+            Err(err) => {
+                self.decoded = None;
+                self.error = err.to_string();
+            }
+        }
+        cx.notify();
+    }
+
+    /// 把某种编码的结果填入解码框
+    fn use_encoded(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(encoded) = self.encoded.get(index) else {
+            return;
+        };
+        self.encoding = encoded.encoding;
+        let value = encoded.value.clone();
+        self.encoded_state.update(cx, |state, cx| {
+            state.set_value(value, window, cx);
+        });
+        self.decode(cx);
+    }
+
+    fn paste_input(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(item) = cx.read_from_clipboard() {
+            if let Some(text) = item.text() {
+                self.input_state.update(cx, |state, cx| {
+                    state.set_value(text.to_string(), window, cx);
+                });
+                self.encode(cx);
+            }
+        }
+    }
+
+    fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.input_state.update(cx, |state, cx| {
+            state.set_value("".to_string(), window, cx);
+        });
+        self.encoded_state.update(cx, |state, cx| {
+            state.set_value("".to_string(), window, cx);
+        });
+        self.encoded.clear();
+        self.decoded = None;
+        self.error.clear();
+        cx.notify();
+    }
+}
+
+impl Render for BinaryEncoder {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let input_type = self.input_type;
+        let encoding = self.encoding;
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                // Row 1: 操作 → Paste + Close + 输入格式 + Bech32 前缀
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().w(px(80.0)).child("操作"))
+                    .child(
+                        ButtonGroup::new("input-buttons")
+                            .child(
+                                Button::new("paste-input")
+                                    .icon(Icon::new(IconName::File))
+                                    .tooltip("粘贴")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.paste_input(window, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("clear-input")
+                                    .icon(Icon::new(IconName::Close))
+                                    .tooltip("清空")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.clear(window, cx);
+                                    })),
+                            ),
+                    )
+                    .child(INPUT_TYPES.into_iter().fold(
+                        ButtonGroup::new("input-type"),
+                        |group, (value, label)| {
+                            group.child(
+                                Button::new(value)
+                                    .label(label)
+                                    .selected(input_type == value)
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.input_type = value;
+                                        this.encode(cx);
+                                    })),
+                            )
+                        },
+                    ))
+                    .child(div().w(px(140.0)).child(Input::new(&self.hrp_state))),
+            )
+            .child(
+                // Row 2: 输入 → textarea
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().w(px(80.0)).child("输入"))
+                    .child(Input::new(&self.input_state).h(px(120.0))),
+            )
+            .child(
+                // Row 3: 每种编码一行 → 名称 + 结果 + 复制 + 解码
+                div().flex().flex_col().gap_1().children(
+                    self.encoded.iter().enumerate().map(|(index, encoded)| {
+                        let value = encoded.value.clone();
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .text_sm()
+                            .child(
+                                div()
+                                    .w(px(140.0))
+                                    .flex_none()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(encoded.name),
+                            )
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .font_family("monospace")
+                                    .child(encoded.value.clone()),
+                            )
+                            .child(
+                                Button::new(SharedString::from(format!("copy-{index}")))
+                                    .icon(Icon::new(IconName::Copy))
+                                    .tooltip("复制")
+                                    .on_click(cx.listener(move |_, _, _, cx| {
+                                        cx.write_to_clipboard(ClipboardItem::new_string(
+                                            value.clone(),
+                                        ));
+                                    })),
+                            )
+                            .child(
+                                Button::new(SharedString::from(format!("use-{index}")))
+                                    .icon(Icon::new(IconName::ArrowDown))
+                                    .tooltip("解码")
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.use_encoded(index, window, cx);
+                                    })),
+                            )
+                    }),
+                ),
+            )
+            .child(
+                // Row 4: 解码 → 编码方式
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().w(px(80.0)).child("解码"))
+                    .child(
+                        div().flex().flex_wrap().child(
+                            base::Encoding::ALL.into_iter().fold(
+                                ButtonGroup::new("encoding"),
+                                |group, value| {
+                                    group.child(
+                                        Button::new(value.name())
+                                            .label(value.name())
+                                            .selected(encoding == value)
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.encoding = value;
+                                                cx.notify();
+                                            })),
+                                    )
+                                },
+                            ),
+                        ),
+                    ),
+            )
+            .child(
+                // Row 5: 待解码文本 + 解码按钮
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(80.0)))
+                    .child(div().flex_1().child(Input::new(&self.encoded_state)))
+                    .child(
+                        Button::new("decode")
+                            .label("解码")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.decode(cx);
+                            })),
+                    ),
+            )
+            .when_some(self.decoded.as_ref(), |this, (text, hex)| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .text_sm()
+                        .child(
+                            div()
+                                .flex()
+                                .gap_2()
+                                .child(div().w(px(80.0)).child("文本"))
+                                .child(match text {
+                                    Some(text) => div().child(text.clone()),
+                                    None => div()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child("不是合法的 UTF-8 文本"),
+                                }),
+                        )
+                        .child(
+                            div()
+                                .flex()
+                                .gap_2()
+                                .child(div().w(px(80.0)).child("Hex"))
+                                .child(div().font_family("monospace").child(hex.clone())),
+                        ),
+                )
+            })
+            .when(!self.error.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(self.error.clone()),
+                )
+            })
+    }
+}
@@ -0,0 +1,449 @@
+use gpui::{prelude::FluentBuilder as _, *};
+use gpui_component::{
+    button::*,
+    checkbox::Checkbox,
+    input::{Input, InputEvent, InputState},
+    *,
+};
+
+const ALGORITHMS: [(base::KeyAlgorithm, &str); 4] = [
+    (base::KeyAlgorithm::Rsa, "RSA"),
+    (base::KeyAlgorithm::P256, "P-256"),
+    (base::KeyAlgorithm::P384, "P-384"),
+    (base::KeyAlgorithm::Ed25519, "Ed25519"),
+];
+
+pub struct CertDecoder {
+    /// 选中的文件，PKCS#12 和 DER 只能按文件读取；为空时解析输入框
+    file_path: Option<String>,
+    bundle: Option<base::CertBundle>,
+    error: String,
+    algorithm: base::KeyAlgorithm,
+    ca: bool,
+    is_generating: bool,
+    /// 后台生成的证书，等下一次渲染时回填到输入框
+    generated: Option<base::SelfSignedCertificate>,
+    input_state: Entity<InputState>,
+    password_state: Entity<InputState>,
+    subject_state: Entity<InputState>,
+    san_state: Entity<InputState>,
+    days_state: Entity<InputState>,
+    key_state: Entity<InputState>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CertDecoder {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("PEM 证书链、CSR，或 Base64/Hex 编码的 DER；PKCS#12 请选择文件")
+                .multi_line(true)
+        });
+        let password_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder("PKCS#12 密码"));
+        let subject_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("CN=example.com,O=Example 或只写域名")
+                .default_value("CN=localhost")
+        });
+        let san_state = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("SAN，逗号分隔，可带 DNS:、IP: 前缀")
+        });
+        let days_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("有效天数")
+                .default_value("365")
+        });
+        let key_state = cx.new(|cx| InputState::new(window, cx).multi_line(true));
+        let _subscriptions = vec![
+            cx.subscribe_in(&input_state, window, |this, _, ev: &InputEvent, _, cx| {
+                if let InputEvent::Change = ev {
+                    this.file_path = None;
+                    this.decode(cx);
+                }
+            }),
+            cx.subscribe_in(&password_state, window, |this, _, ev: &InputEvent, _, cx| {
+                if let InputEvent::Change = ev {
+                    this.decode(cx);
+                }
+            }),
+        ];
+
+        Self {
+            file_path: None,
+            bundle: None,
+            error: String::new(),
+            algorithm: base::KeyAlgorithm::P256,
+            ca: false,
+            is_generating: false,
+            generated: None,
+            input_state,
+            password_state,
+            subject_state,
+            san_state,
+            days_state,
+            key_state,
+            _subscriptions,
+        }
+    }
+
+    fn decode(&mut self, cx: &mut Context<Self>) {
+        let password = self.password_state.read(cx).value().to_string();
+        let input = match &self.file_path {
+            Some(path) => std::fs::read(path).map_err(|e| e.to_string()),
+            None => Ok(self.input_state.read(cx).value().as_bytes().to_vec()),
+        };
+        self.error.clear();
+        self.bundle = None;
+        match input {
+            Ok(input) if input.iter().all(u8::is_ascii_whitespace) => {}
+            Ok(input) => match base::cert_decode(&input, &password) {
+                Ok(bundle) => self.bundle = Some(bundle),
+                Err(err) => self.error = err.to_string(),
+            },
+            Err(err) => self.error = err,
+        }
+        cx.notify();
+    }
+
+    fn select_file(&mut self, cx: &mut Context<Self>) {
+        let task = cx.background_executor().spawn(async move {
+            rfd::AsyncFileDialog::new()
+                .set_title("选择证书")
+                .add_filter("证书", &["pem", "crt", "cer", "der", "csr", "p12", "pfx"])
+                .pick_file()
+                .await
+        });
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            if let Some(file) = task.await {
+                let path = file.path().to_string_lossy().to_string();
+                let _ = this.update(cx, |this, cx| {
+                    this.file_path = Some(path);
+                    this.decode(cx);
+                });
+            }
+        })
+        .detach();
+    }
+
+    fn generate(&mut self, cx: &mut Context<Self>) {
+        let days = self.days_state.read(cx).value().trim().parse::<u32>();
+        let Ok(days) = days else {
+            self.error = "有效天数必须是正整数".to_string();
+            cx.notify();
+            return;
+        };
+        let options = base::SelfSignedOptions {
+            subject: self.subject_state.read(cx).value().to_string(),
+            subject_alt_names: self
+                .san_state
+                .read(cx)
+                .value()
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect(),
+            days,
+            algorithm: self.algorithm,
+            ca: self.ca,
+            ..Default::default()
+        };
+        self.is_generating = true;
+        self.error.clear();
+        cx.notify();
+
+        // RSA 密钥生成较慢，放到后台线程
+        let task = cx
+            .background_executor()
+            .spawn(async move { base::cert_self_signed(&options) });
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            let result = task.await;
+            let _ = this.update(cx, |this, cx| {
+                match result {
+                    Ok(generated) => this.generated = Some(generated),
+                    Err(err) => this.error = err.to_string(),
+                }
+                this.is_generating = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn render_row(label: &str, value: impl IntoElement, cx: &App) -> Div {
+        div()
+            .flex()
+            .gap_2()
+            .child(
+                div()
+                    .w(px(100.0))
+                    .flex_none()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(label.to_string()),
+            )
+            .child(div().flex_1().child(value))
+    }
+
+    fn render_cert(index: usize, cert: &base::CertInfo, cx: &App) -> Div {
+        let issuer = if cert.self_signed {
+            format!("{}（自签名）", cert.issuer)
+        } else if let Some(issuer) = cert.issuer_index {
+            format!("{}（#{}）", cert.issuer, issuer + 1)
+        } else {
+            cert.issuer.clone()
+        };
+        let link_color = match cert.link {
+            base::LinkStatus::Valid => cx.theme().success,
+            base::LinkStatus::Invalid => cx.theme().danger,
+            _ => cx.theme().muted_foreground,
+        };
+        let (validity, validity_color) = match cert.validity {
+            base::ValidityStatus::Valid => (
+                format!("剩余 {} 天", cert.days_remaining),
+                cx.theme().success,
+            ),
+            base::ValidityStatus::Expired => (
+                format!("已过期 {} 天", -cert.days_remaining),
+                cx.theme().danger,
+            ),
+            base::ValidityStatus::NotYetValid => ("尚未生效".to_string(), cx.theme().danger),
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .p_3()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .text_sm()
+            .child(div().font_semibold().child(format!("证书 #{}", index + 1)))
+            .child(Self::render_row("主题", cert.subject.clone(), cx))
+            .child(Self::render_row("签发者", issuer, cx))
+            .child(Self::render_row(
+                "签名",
+                div().text_color(link_color).child(cert.link.name()),
+                cx,
+            ))
+            .child(Self::render_row("序列号", cert.serial_number.clone(), cx))
+            .child(Self::render_row(
+                "有效期",
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(format!("{} ~ {}", cert.not_before, cert.not_after))
+                    .child(div().text_color(validity_color).child(validity)),
+                cx,
+            ))
+            .child(Self::render_row("公钥", cert.public_key.clone(), cx))
+            .child(Self::render_row("签名算法", cert.signature_algorithm.clone(), cx))
+            .when(!cert.subject_alt_names.is_empty(), |this| {
+                this.child(Self::render_row("SAN", cert.subject_alt_names.join(", "), cx))
+            })
+            .children(cert.extensions.iter().map(|extension| {
+                let value = if extension.critical {
+                    format!("[critical] {}", extension.value)
+                } else {
+                    extension.value.clone()
+                };
+                Self::render_row(&extension.name, value, cx)
+            }))
+            .children(cert.fingerprints.iter().map(|(name, value)| {
+                Self::render_row(
+                    name,
+                    div().font_family("monospace").child(value.clone()),
+                    cx,
+                )
+            }))
+    }
+
+    fn render_request(index: usize, csr: &base::CsrInfo, cx: &App) -> Div {
+        let (signature, color) = match csr.signature_valid {
+            Some(true) => ("签名有效", cx.theme().success),
+            Some(false) => ("签名无效", cx.theme().danger),
+            None => ("不支持的签名算法", cx.theme().muted_foreground),
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .p_3()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .text_sm()
+            .child(div().font_semibold().child(format!("CSR #{}", index + 1)))
+            .child(Self::render_row("主题", csr.subject.clone(), cx))
+            .child(Self::render_row(
+                "签名",
+                div().text_color(color).child(signature),
+                cx,
+            ))
+            .child(Self::render_row("公钥", csr.public_key.clone(), cx))
+            .child(Self::render_row("签名算法", csr.signature_algorithm.clone(), cx))
+            .when(!csr.subject_alt_names.is_empty(), |this| {
+                this.child(Self::render_row("SAN", csr.subject_alt_names.join(", "), cx))
+            })
+            .children(
+                csr.extensions
+                    .iter()
+                    .map(|extension| Self::render_row(&extension.name, extension.value.clone(), cx)),
+            )
+            .children(csr.fingerprints.iter().map(|(name, value)| {
+                Self::render_row(
+                    name,
+                    div().font_family("monospace").child(value.clone()),
+                    cx,
+                )
+            }))
+    }
+}
+
+impl Render for CertDecoder {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if let Some(generated) = self.generated.take() {
+            self.input_state.update(cx, |state, cx| {
+                state.set_value(generated.certificate, window, cx);
+            });
+            self.key_state.update(cx, |state, cx| {
+                state.set_value(generated.private_key, window, cx);
+            });
+            self.file_path = None;
+            self.decode(cx);
+        }
+        let algorithm = self.algorithm;
+        let private_keys = self
+            .bundle
+            .as_ref()
+            .map(|bundle| bundle.private_keys.join("\n"))
+            .unwrap_or_default();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        Button::new("select-file")
+                            .label("选择文件")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.select_file(cx);
+                            })),
+                    )
+                    .child(div().w(px(200.0)).child(Input::new(&self.password_state)))
+                    .when_some(self.file_path.clone(), |this, path| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(path),
+                        )
+                    }),
+            )
+            .child(Input::new(&self.input_state).h(px(180.0)))
+            .when(!self.error.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(self.error.clone()),
+                )
+            })
+            .when_some(self.bundle.as_ref(), |this, bundle| {
+                this.children(
+                    bundle
+                        .certificates
+                        .iter()
+                        .enumerate()
+                        .map(|(index, cert)| Self::render_cert(index, cert, cx)),
+                )
+                .children(
+                    bundle
+                        .requests
+                        .iter()
+                        .enumerate()
+                        .map(|(index, csr)| Self::render_request(index, csr, cx)),
+                )
+            })
+            .when(!private_keys.is_empty(), |this| {
+                this.child(
+                    div()
+                        .p_3()
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .rounded_md()
+                        .text_sm()
+                        .font_family("monospace")
+                        .child(private_keys),
+                )
+            })
+            .child(div().font_semibold().child("生成自签名证书"))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().flex_1().child(Input::new(&self.subject_state)))
+                    .child(div().flex_1().child(Input::new(&self.san_state)))
+                    .child(div().w(px(100.0)).child(Input::new(&self.days_state))),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap_2()
+                    .child(ALGORITHMS.into_iter().fold(
+                        ButtonGroup::new("cert-algorithm"),
+                        |group, (value, label)| {
+                            group.child(
+                                Button::new(label)
+                                    .label(label)
+                                    .selected(algorithm == value)
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.algorithm = value;
+                                        cx.notify();
+                                    })),
+                            )
+                        },
+                    ))
+                    .child(
+                        Checkbox::new("ca")
+                            .label("CA 证书")
+                            .checked(self.ca)
+                            .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                this.ca = *v;
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("generate")
+                            .primary()
+                            .disabled(self.is_generating)
+                            .label(if self.is_generating {
+                                "生成中..."
+                            } else {
+                                "生成"
+                            })
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.generate(cx);
+                            })),
+                    )
+                    .child(Button::new("copy-key").label("复制私钥").on_click(cx.listener(
+                        |this, _, _, cx| {
+                            let key = this.key_state.read(cx).value().to_string();
+                            cx.write_to_clipboard(ClipboardItem::new_string(key));
+                        },
+                    ))),
+            )
+            .child(Input::new(&self.key_state).h(px(140.0)))
+    }
+}
@@ -58,6 +58,7 @@ impl CharsetEncoder {
             "十进制".to_string(),
             "八进制".to_string(),
             "二进制".to_string(),
+            "Base64".to_string(),
         ];
         let target_charset_items = vec![
             "UTF-8".to_string(),
@@ -73,6 +74,7 @@ impl CharsetEncoder {
             "十进制(Dec)".to_string(),
             "八进制(Oct)".to_string(),
             "二进制(Bin)".to_string(),
+            "Base64".to_string(),
         ];
         let delimiter_items = vec![
             "空格".to_string(),
@@ -138,6 +140,7 @@ impl CharsetEncoder {
                             "十进制" => "decimal",
                             "八进制" => "octal",
                             "二进制" => "binary",
+                            "Base64" => "base64",
                             _ => "text",
                         }
                         .to_string();
@@ -165,6 +168,7 @@ impl CharsetEncoder {
                             "十进制(Dec)" => "decimal",
                             "八进制(Oct)" => "octal",
                             "二进制(Bin)" => "binary",
+                            "Base64" => "base64",
                             _ => "hex",
                         }
                         .to_string();
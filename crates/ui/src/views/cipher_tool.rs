@@ -0,0 +1,374 @@
+use gpui::{prelude::FluentBuilder as _, *};
+use gpui_component::{
+    button::*,
+    input::{Input, InputState},
+    *,
+};
+
+const ALGORITHMS: [(base::CipherAlgorithm, &str); 5] = [
+    (base::CipherAlgorithm::Aes128, "AES-128"),
+    (base::CipherAlgorithm::Aes192, "AES-192"),
+    (base::CipherAlgorithm::Aes256, "AES-256"),
+    (base::CipherAlgorithm::Sm4, "SM4"),
+    (base::CipherAlgorithm::ChaCha20Poly1305, "ChaCha20-Poly1305"),
+];
+
+const MODES: [(base::CipherMode, &str); 4] = [
+    (base::CipherMode::Cbc, "CBC"),
+    (base::CipherMode::Ecb, "ECB"),
+    (base::CipherMode::Ctr, "CTR"),
+    (base::CipherMode::Gcm, "GCM"),
+];
+
+const PADDINGS: [(base::Padding, &str); 3] = [
+    (base::Padding::Pkcs7, "PKCS7"),
+    (base::Padding::Zero, "Zero"),
+    (base::Padding::None, "不填充"),
+];
+
+const BYTE_TYPES: [(&str, &str); 3] = [("text", "文本"), ("hex", "Hex"), ("base64", "Base64")];
+
+pub struct CipherTool {
+    algorithm: base::CipherAlgorithm,
+    mode: base::CipherMode,
+    padding: base::Padding,
+    key_type: &'static str,
+    iv_type: &'static str,
+    plaintext_type: &'static str,
+    ciphertext_type: &'static str,
+    error: String,
+    key_state: Entity<InputState>,
+    iv_state: Entity<InputState>,
+    aad_state: Entity<InputState>,
+    plaintext_state: Entity<InputState>,
+    ciphertext_state: Entity<InputState>,
+}
+
+impl CipherTool {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let key_state = cx.new(|cx| InputState::new(window, cx).placeholder("密钥"));
+        let iv_state = cx.new(|cx| InputState::new(window, cx).placeholder("IV 或 Nonce"));
+        let aad_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder("附加认证数据（可选）"));
+        let plaintext_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("明文...")
+                .multi_line(true)
+        });
+        let ciphertext_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("密文，GCM 与 ChaCha20-Poly1305 末尾附带 16 字节认证标签...")
+                .multi_line(true)
+        });
+
+        Self {
+            algorithm: base::CipherAlgorithm::default(),
+            mode: base::CipherMode::default(),
+            padding: base::Padding::default(),
+            key_type: "hex",
+            iv_type: "hex",
+            plaintext_type: "text",
+            ciphertext_type: "hex",
+            error: String::new(),
+            key_state,
+            iv_state,
+            aad_state,
+            plaintext_state,
+            ciphertext_state,
+        }
+    }
+
+    fn is_aead(&self) -> bool {
+        self.algorithm == base::CipherAlgorithm::ChaCha20Poly1305
+            || self.mode == base::CipherMode::Gcm
+    }
+
+    fn set_algorithm(&mut self, algorithm: base::CipherAlgorithm, cx: &mut Context<Self>) {
+        self.algorithm = algorithm;
+        // SM4 没有 GCM 模式
+        if algorithm == base::CipherAlgorithm::Sm4 && self.mode == base::CipherMode::Gcm {
+            self.mode = base::CipherMode::Cbc;
+        }
+        self.error.clear();
+        cx.notify();
+    }
+
+    fn options(&self, decrypt: bool, cx: &App) -> base::CipherOptions {
+        let (input_type, output_type) = if decrypt {
+            (self.ciphertext_type, self.plaintext_type)
+        } else {
+            (self.plaintext_type, self.ciphertext_type)
+        };
+        base::CipherOptions {
+            algorithm: self.algorithm,
+            mode: self.mode,
+            padding: self.padding,
+            key: self.key_state.read(cx).value().to_string(),
+            key_type: self.key_type.to_string(),
+            iv: self.iv_state.read(cx).value().to_string(),
+            iv_type: self.iv_type.to_string(),
+            aad: self.aad_state.read(cx).value().to_string(),
+            input_type: input_type.to_string(),
+            output_type: output_type.to_string(),
+        }
+    }
+
+    fn encrypt(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let plaintext = self.plaintext_state.read(cx).value().to_string();
+        match base::cipher_encrypt(&plaintext, &self.options(false, cx)) {
+            Ok(ciphertext) => {
+                self.error.clear();
+                self.ciphertext_state.update(cx, |state, cx| {
+                    state.set_value(ciphertext, window, cx);
+                });
+            }
+            Err(err) => self.error = err.to_string(),
+        }
+        cx.notify();
+    }
+
+    fn decrypt(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let ciphertext = self.ciphertext_state.read(cx).value().to_string();
+        match base::cipher_decrypt(&ciphertext, &self.options(true, cx)) {
+            Ok(plaintext) => {
+                self.error.clear();
+                self.plaintext_state.update(cx, |state, cx| {
+                    state.set_value(plaintext, window, cx);
+                });
+            }
+            Err(err) => self.error = err.to_string(),
+        }
+        cx.notify();
+    }
+
+    /// 生成随机密钥或 IV，文本格式改为 Hex
+    fn randomize(&mut self, iv: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let (len, byte_type) = if iv {
+            (self.algorithm.iv_len(self.mode), &mut self.iv_type)
+        } else {
+            (self.algorithm.key_len(), &mut self.key_type)
+        };
+        if *byte_type == "text" {
+            *byte_type = "hex";
+        }
+        let Ok(value) = base::cipher_random(len, *byte_type) else {
+            return;
+        };
+        let state = if iv { &self.iv_state } else { &self.key_state };
+        state.update(cx, |state, cx| {
+            state.set_value(value, window, cx);
+        });
+        cx.notify();
+    }
+}
+
+impl Render for CipherTool {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let algorithm = self.algorithm;
+        let mode = self.mode;
+        let padding = self.padding;
+        let is_stream = algorithm == base::CipherAlgorithm::ChaCha20Poly1305;
+        let needs_padding =
+            !is_stream && matches!(mode, base::CipherMode::Ecb | base::CipherMode::Cbc);
+        let iv_len = algorithm.iv_len(mode);
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(ALGORITHMS.into_iter().fold(
+                ButtonGroup::new("cipher-algorithm"),
+                |group, (value, label)| {
+                    group.child(
+                        Button::new(label)
+                            .label(label)
+                            .selected(algorithm == value)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.set_algorithm(value, cx);
+                            })),
+                    )
+                },
+            ))
+            .when(!is_stream, |this| {
+                this.child(
+                    MODES
+                        .into_iter()
+                        .filter(|(value, _)| {
+                            algorithm != base::CipherAlgorithm::Sm4
+                                || *value != base::CipherMode::Gcm
+                        })
+                        .fold(ButtonGroup::new("cipher-mode"), |group, (value, label)| {
+                            group.child(
+                                Button::new(label)
+                                    .label(label)
+                                    .selected(mode == value)
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.mode = value;
+                                        this.error.clear();
+                                        cx.notify();
+                                    })),
+                            )
+                        }),
+                )
+            })
+            .when(needs_padding, |this| {
+                this.child(PADDINGS.into_iter().fold(
+                    ButtonGroup::new("cipher-padding"),
+                    |group, (value, label)| {
+                        group.child(
+                            Button::new(label)
+                                .label(label)
+                                .selected(padding == value)
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.padding = value;
+                                    cx.notify();
+                                })),
+                        )
+                    },
+                ))
+            })
+            .child(byte_input(
+                format!("密钥 ({} 字节)", algorithm.key_len()),
+                "key",
+                self.key_type,
+                &self.key_state,
+                false,
+                cx,
+            ))
+            .when(iv_len > 0, |this| {
+                this.child(byte_input(
+                    format!(
+                        "{} ({iv_len} 字节)",
+                        if self.is_aead() { "Nonce" } else { "IV" }
+                    ),
+                    "iv",
+                    self.iv_type,
+                    &self.iv_state,
+                    true,
+                    cx,
+                ))
+            })
+            .when(self.is_aead(), |this| {
+                this.child(Input::new(&self.aad_state))
+            })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().w(px(60.0)).child("明文"))
+                    .child(byte_type_group(
+                        "plaintext",
+                        self.plaintext_type,
+                        &BYTE_TYPES,
+                        cx,
+                        |this, value| {
+                            this.plaintext_type = value;
+                        },
+                    )),
+            )
+            .child(Input::new(&self.plaintext_state).h(px(140.0)))
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        Button::new("encrypt")
+                            .primary()
+                            .label("加密 ↓")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.encrypt(window, cx);
+                            })),
+                    )
+                    .child(Button::new("decrypt").label("解密 ↑").on_click(cx.listener(
+                        |this, _, window, cx| {
+                            this.decrypt(window, cx);
+                        },
+                    ))),
+            )
+            .when(!self.error.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(self.error.clone()),
+                )
+            })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().w(px(60.0)).child("密文"))
+                    .child(byte_type_group(
+                        "ciphertext",
+                        self.ciphertext_type,
+                        &BYTE_TYPES[1..],
+                        cx,
+                        |this, value| {
+                            this.ciphertext_type = value;
+                        },
+                    )),
+            )
+            .child(Input::new(&self.ciphertext_state).h(px(140.0)))
+    }
+}
+
+/// 密钥或 IV：格式选择、输入框和随机生成按钮
+fn byte_input(
+    label: String,
+    id: &'static str,
+    byte_type: &'static str,
+    state: &Entity<InputState>,
+    iv: bool,
+    cx: &mut Context<CipherTool>,
+) -> Div {
+    div()
+        .flex()
+        .items_center()
+        .gap_2()
+        .child(div().text_sm().w(px(120.0)).child(label))
+        .child(byte_type_group(
+            id,
+            byte_type,
+            &BYTE_TYPES,
+            cx,
+            move |this, value| {
+                if iv {
+                    this.iv_type = value;
+                } else {
+                    this.key_type = value;
+                }
+            },
+        ))
+        .child(div().flex_1().child(Input::new(state)))
+        .child(
+            Button::new(SharedString::from(format!("{id}-random")))
+                .label("随机")
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.randomize(iv, window, cx);
+                })),
+        )
+}
+
+fn byte_type_group(
+    id: &'static str,
+    selected: &'static str,
+    types: &[(&'static str, &'static str)],
+    cx: &mut Context<CipherTool>,
+    set: impl Fn(&mut CipherTool, &'static str) + Copy + 'static,
+) -> ButtonGroup {
+    types
+        .iter()
+        .fold(ButtonGroup::new(id), |group, &(value, label)| {
+            group.child(
+                Button::new(SharedString::from(format!("{id}-{value}")))
+                    .label(label)
+                    .selected(selected == value)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        set(this, value);
+                        cx.notify();
+                    })),
+            )
+        })
+}
@@ -0,0 +1,383 @@
+use gpui::{prelude::FluentBuilder as _, *};
+use gpui_component::{
+    button::*,
+    input::{Input, InputEvent, InputState},
+    *,
+};
+
+/// 列出的触发时间个数
+const NEXT_COUNT: usize = 10;
+
+/// 表达式写法选项，`None` 为自动识别
+const DIALECTS: [(Option<&str>, &str); 4] = [
+    (None, "自动"),
+    (Some("unix"), "Unix"),
+    (Some("quartz"), "Quartz"),
+    (Some("systemd"), "systemd"),
+];
+
+/// 常用表达式，Unix 与 Quartz 两种写法
+const PRESETS: [(&str, &str, &str); 6] = [
+    ("每分钟", "* * * * *", "0 * * * * ?"),
+    ("每小时", "0 * * * *", "0 0 * * * ?"),
+    ("每天 0 点", "0 0 * * *", "0 0 0 * * ?"),
+    ("工作日 9 点", "0 9 * * 1-5", "0 0 9 ? * MON-FRI"),
+    ("每周一 0 点", "0 0 * * 1", "0 0 0 ? * MON"),
+    ("每月 1 日 0 点", "0 0 1 * *", "0 0 0 1 * ?"),
+];
+
+/// 生成器中各段的名称，Quartz 在前面多一段秒
+const BUILDER_FIELDS: [&str; 6] = ["秒", "分", "时", "日", "月", "周"];
+
+/// Cron 表达式解析、说明与生成
+pub struct CronExpression {
+    expr: String,
+    dialect: Option<&'static str>,
+    zone: String,
+    schedule: Option<base::CronSchedule>,
+    error: String,
+    /// 生成器使用 Quartz 写法
+    builder_quartz: bool,
+    expr_state: Entity<InputState>,
+    zone_state: Entity<InputState>,
+    builder_states: Vec<Entity<InputState>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CronExpression {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let expr_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("例如 */5 * * * *、0 0 9 ? * MON-FRI、Mon..Fri *-*-* 09:00")
+        });
+        let zone_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("时区，如 Asia/Shanghai")
+                .default_value("UTC")
+        });
+        let builder_states = BUILDER_FIELDS
+            .iter()
+            .map(|_| cx.new(|cx| InputState::new(window, cx).default_value("*")))
+            .collect::<Vec<_>>();
+
+        let mut _subscriptions = vec![
+            cx.subscribe_in(&expr_state, window, {
+                let expr_state = expr_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.expr = expr_state.read(cx).value().to_string();
+                        this.parse();
+                        cx.notify();
+                    }
+                }
+            }),
+            cx.subscribe_in(&zone_state, window, {
+                let zone_state = zone_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.zone = zone_state.read(cx).value().trim().to_string();
+                        this.parse();
+                        cx.notify();
+                    }
+                }
+            }),
+        ];
+        for state in &builder_states {
+            _subscriptions.push(cx.subscribe_in(
+                state,
+                window,
+                |this, _, ev: &InputEvent, window, cx| {
+                    if let InputEvent::Change = ev {
+                        this.build(window, cx);
+                        cx.notify();
+                    }
+                },
+            ));
+        }
+
+        Self {
+            expr: String::new(),
+            dialect: None,
+            zone: "UTC".to_string(),
+            schedule: None,
+            error: String::new(),
+            builder_quartz: false,
+            expr_state,
+            zone_state,
+            builder_states,
+            _subscriptions,
+        }
+    }
+
+    fn parse(&mut self) {
+        self.error.clear();
+        if self.expr.trim().is_empty() {
+            self.schedule = None;
+            return;
+        }
+        let zone = (!self.zone.is_empty()).then_some(self.zone.as_str());
+        match base::cron(&self.expr, self.dialect, zone, NEXT_COUNT) {
+            Ok(schedule) => self.schedule = Some(schedule),
+            Err(e) => {
+                self.schedule = None;
+                self.error = e.to_string();
+            }
+        }
+    }
+
+    fn set_dialect(&mut self, dialect: Option<&'static str>) {
+        self.dialect = dialect;
+        self.parse();
+    }
+
+    fn set_expr(&mut self, expr: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.expr = expr.clone();
+        self.expr_state.update(cx, |state, cx| {
+            state.set_value(expr, window, cx);
+        });
+        self.parse();
+    }
+
+    /// 用生成器各段拼出表达式，空的段视为 `*`
+    fn build(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let skip = if self.builder_quartz { 0 } else { 1 };
+        let mut parts = self
+            .builder_states
+            .iter()
+            .skip(skip)
+            .map(|state| {
+                let value = state.read(cx).value().trim().to_string();
+                if value.is_empty() {
+                    "*".to_string()
+                } else {
+                    value
+                }
+            })
+            .collect::<Vec<_>>();
+        // Quartz 的日和周必须有一个为 ?
+        if self.builder_quartz {
+            let (day, weekday) = (3, 5);
+            if parts[weekday] == "*" || parts[day] != "*" && parts[day] != "?" {
+                parts[weekday] = "?".to_string();
+            } else if parts[day] == "*" {
+                parts[day] = "?".to_string();
+            }
+        }
+        self.dialect = Some(if self.builder_quartz {
+            "quartz"
+        } else {
+            "unix"
+        });
+        self.set_expr(parts.join(" "), window, cx);
+    }
+
+    fn set_builder_quartz(&mut self, quartz: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.builder_quartz = quartz;
+        self.build(window, cx);
+    }
+
+    fn apply_preset(&mut self, expr: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.dialect = None;
+        self.set_expr(expr.to_string(), window, cx);
+    }
+
+    fn copy_expr(&mut self, cx: &mut Context<Self>) {
+        if !self.expr.is_empty() {
+            cx.write_to_clipboard(ClipboardItem::new_string(self.expr.clone()));
+        }
+    }
+
+    fn builder(&self, cx: &mut Context<Self>) -> Div {
+        let quartz = self.builder_quartz;
+        let skip = if quartz { 0 } else { 1 };
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("生成"))
+                    .child(
+                        ButtonGroup::new("builder-dialect")
+                            .child(
+                                Button::new("builder-unix")
+                                    .label("Unix")
+                                    .selected(!quartz)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.set_builder_quartz(false, window, cx);
+                                        cx.notify();
+                                    })),
+                            )
+                            .child(
+                                Button::new("builder-quartz")
+                                    .label("Quartz")
+                                    .selected(quartz)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.set_builder_quartz(true, window, cx);
+                                        cx.notify();
+                                    })),
+                            ),
+                    )
+                    .children(
+                        BUILDER_FIELDS
+                            .iter()
+                            .zip(&self.builder_states)
+                            .skip(skip)
+                            .map(|(label, state)| {
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_1()
+                                    .child(div().text_sm().child(*label))
+                                    .child(div().w(px(90.0)).child(Input::new(state)))
+                            }),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("常用"))
+                    .children(PRESETS.iter().enumerate().map(|(i, (label, unix, q))| {
+                        let expr = if quartz { *q } else { *unix };
+                        Button::new(("preset", i))
+                            .label(*label)
+                            .tooltip(expr)
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.apply_preset(expr, window, cx);
+                                cx.notify();
+                            }))
+                    })),
+            )
+    }
+
+    fn result(&self, schedule: &base::CronSchedule, cx: &mut Context<Self>) -> Div {
+        let dialect = match schedule.dialect {
+            base::CronDialect::Unix => "Unix",
+            base::CronDialect::Quartz => "Quartz",
+            base::CronDialect::Systemd => "systemd OnCalendar",
+        };
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("格式"))
+                    .child(div().text_sm().child(dialect)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("说明"))
+                    .child(div().text_sm().child(schedule.description_zh.clone())),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("Description"))
+                    .child(div().text_sm().child(schedule.description_en.clone())),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .w(px(100.0))
+                            .text_sm()
+                            .child(format!("接下来 {NEXT_COUNT} 次")),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .text_sm()
+                            .font_family("monospace")
+                            .children(
+                                schedule.next.iter().map(|t| {
+                                    format!("{} {} {}", t.datetime, t.offset, schedule.zone)
+                                }),
+                            ),
+                    ),
+            )
+    }
+}
+
+impl Render for CronExpression {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let dialect = self.dialect;
+        let schedule = self.schedule.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            // Row: 表达式 → Input + Copy
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("表达式"))
+                    .child(div().flex_1().child(Input::new(&self.expr_state)))
+                    .child(
+                        Button::new("copy-expr")
+                            .icon(Icon::new(IconName::Copy))
+                            .tooltip("复制")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.copy_expr(cx);
+                            })),
+                    ),
+            )
+            // Row: 格式与时区
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("格式"))
+                    .child(DIALECTS.into_iter().fold(
+                        ButtonGroup::new("cron-dialect"),
+                        |group, (value, label)| {
+                            group.child(
+                                Button::new(label)
+                                    .label(label)
+                                    .selected(dialect == value)
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.set_dialect(value);
+                                        cx.notify();
+                                    })),
+                            )
+                        },
+                    ))
+                    .child(div().text_sm().child("时区"))
+                    .child(div().w(px(220.0)).child(Input::new(&self.zone_state))),
+            )
+            .child(self.builder(cx))
+            .when(!self.error.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(self.error.clone()),
+                )
+            })
+            .when_some(schedule, |this, schedule| {
+                this.child(self.result(&schedule, cx))
+            })
+    }
+}
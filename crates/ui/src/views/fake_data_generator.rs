@@ -1455,13 +1455,20 @@ impl FakeDataGenerator {
         if generator.is_empty() {
             return;
         }
-        let value = generate_column_value(&ColumnPreview {
+        let value = match generate_column_value(&ColumnPreview {
             name: column_name,
             column_type: String::new(),
             comment: String::new(),
             generator: generator.clone(),
             config: GeneratorConfig::default(),
-        });
+        }) {
+            Ok(value) => value,
+            Err(err) => {
+                self.status = err;
+                cx.notify();
+                return;
+            }
+        };
         self.gen_preview_state.update(cx, |state, cx| {
             state.set_value(value, window, cx);
         });
@@ -1572,14 +1579,19 @@ impl FakeDataGenerator {
                 .join(","),
         );
         for _ in 0..count {
-            lines.push(
-                table
-                    .columns
-                    .iter()
-                    .map(|column| csv_escape(&generate_column_value(column)))
-                    .collect::<Vec<_>>()
-                    .join(","),
-            );
+            let row = table
+                .columns
+                .iter()
+                .map(|column| generate_column_value(column).map(|value| csv_escape(&value)))
+                .collect::<Result<Vec<_>, _>>();
+            match row {
+                Ok(row) => lines.push(row.join(",")),
+                Err(err) => {
+                    self.status = err;
+                    cx.notify();
+                    return;
+                }
+            }
         }
         self.output = lines.join("\n");
         self.status = format!(
@@ -3044,7 +3056,7 @@ async fn run_fake_data_insert(
         let mut row_index = 0usize;
         while row_index < row_count {
             let batch_size = INSERT_BATCH_SIZE.min(row_count - row_index);
-            let sql = batch_insert_sql(&info.driver, &table, &columns, batch_size);
+            let sql = batch_insert_sql(&info.driver, &table, &columns, batch_size)?;
             match sqlx::query(&sql).execute(&pool).await {
                 Ok(_) => {
                     inserted_rows += batch_size;
@@ -3066,7 +3078,7 @@ async fn run_fake_data_insert(
                         error
                     ));
                     for offset in 0..batch_size {
-                        let sql = insert_sql(&info.driver, &table, &columns);
+                        let sql = insert_sql(&info.driver, &table, &columns)?;
                         match sqlx::query(&sql).execute(&pool).await {
                             Ok(_) => {
                                 inserted_rows += 1;
@@ -3140,7 +3152,7 @@ async fn run_fake_data_insert_single(
     let mut row_index = 0usize;
     while row_index < row_count {
         let batch_size = INSERT_BATCH_SIZE.min(row_count - row_index);
-        let sql = batch_insert_sql(&info.driver, table, &columns, batch_size);
+        let sql = batch_insert_sql(&info.driver, table, &columns, batch_size)?;
         match sqlx::query(&sql).execute(&pool).await {
             Ok(_) => {
                 inserted_rows += batch_size;
@@ -3155,7 +3167,7 @@ async fn run_fake_data_insert_single(
                     table.schema, table.table_name, row_index + 1, row_index + batch_size, error
                 ));
                 for offset in 0..batch_size {
-                    let sql = insert_sql(&info.driver, table, &columns);
+                    let sql = insert_sql(&info.driver, table, &columns)?;
                     match sqlx::query(&sql).execute(&pool).await {
                         Ok(_) => {
                             inserted_rows += 1;
@@ -3187,41 +3199,35 @@ fn batch_insert_sql(
     table: &TablePreview,
     columns: &[String],
     batch_size: usize,
-) -> String {
+) -> Result<String, String> {
     let rows = (0..batch_size)
-        .map(|_| {
-            format!(
-                "({})",
-                table
-                    .columns
-                    .iter()
-                    .map(|column| sql_value(generate_column_value(column)))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )
-        })
-        .collect::<Vec<_>>();
+        .map(|_| Ok(format!("({})", row_values(table)?)))
+        .collect::<Result<Vec<_>, String>>()?;
 
-    format!(
+    Ok(format!(
         "INSERT INTO {} ({}) VALUES {}",
         quote_table(driver, &table.schema, &table.table_name),
         columns.join(", "),
         rows.join(", ")
-    )
+    ))
 }
 
-fn insert_sql(driver: &Driver, table: &TablePreview, columns: &[String]) -> String {
-    format!(
+fn insert_sql(driver: &Driver, table: &TablePreview, columns: &[String]) -> Result<String, String> {
+    Ok(format!(
         "INSERT INTO {} ({}) VALUES ({})",
         quote_table(driver, &table.schema, &table.table_name),
         columns.join(", "),
-        table
-            .columns
-            .iter()
-            .map(|column| sql_value(generate_column_value(column)))
-            .collect::<Vec<_>>()
-            .join(", ")
-    )
+        row_values(table)?
+    ))
+}
+
+fn row_values(table: &TablePreview) -> Result<String, String> {
+    Ok(table
+        .columns
+        .iter()
+        .map(|column| generate_column_value(column).map(sql_value))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(", "))
 }
 
 fn quote_table(driver: &Driver, schema: &str, table: &str) -> String {
@@ -3300,12 +3306,17 @@ fn value_to_type_text(value: &serde_json::Value) -> String {
     }
 }
 
-fn generate_column_value(column: &ColumnPreview) -> String {
-    let value = generate_preview_value(&column.generator);
-    match character_length_limit(&column.column_type) {
+/// 生成一个字段值；哈希失败时返回错误，不能把明文写进哈希字段
+fn generate_column_value(column: &ColumnPreview) -> Result<String, String> {
+    let value = match column.generator.as_str() {
+        "password_bcrypt" => hashed_password(base::PasswordAlgorithm::Bcrypt)?,
+        "password_argon2id" => hashed_password(base::PasswordAlgorithm::Argon2id)?,
+        generator => generate_preview_value(generator),
+    };
+    Ok(match character_length_limit(&column.column_type) {
         Some(limit) => truncate_chars(&value, limit),
         None => value,
-    }
+    })
 }
 
 fn character_length_limit(column_type: &str) -> Option<usize> {
@@ -3332,6 +3343,13 @@ fn truncate_chars(value: &str, limit: usize) -> String {
     value.chars().take(limit).collect()
 }
 
+/// 随机强密码的哈希，使用与 Tauri 版相同的批量生成低代价参数
+fn hashed_password(algorithm: base::PasswordAlgorithm) -> Result<String, String> {
+    let password = datafaker::Faker::new().person().strong_password();
+    base::hash_password(&password, &base::PasswordOptions::bulk(algorithm))
+        .map_err(|err| format!("密码哈希失败: {err}"))
+}
+
 fn generate_preview_value(generator: &str) -> String {
     let faker = datafaker::Faker::new();
     let mut rng = rand::rng();
@@ -3656,6 +3674,8 @@ fn generator_groups() -> Vec<GeneratorGroup> {
                 GeneratorItem { label: "身份证号", value: "id_card" },
                 GeneratorItem { label: "用户名", value: "username" },
                 GeneratorItem { label: "密码", value: "password" },
+                GeneratorItem { label: "密码 (bcrypt)", value: "password_bcrypt" },
+                GeneratorItem { label: "密码 (Argon2id)", value: "password_argon2id" },
                 GeneratorItem { label: "QQ号", value: "qq" },
                 GeneratorItem { label: "昵称", value: "nickname" },
                 GeneratorItem { label: "民族", value: "ethnicity" },
@@ -1,3 +1,8 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
 use gpui::{prelude::FluentBuilder as _, *};
 use gpui_component::{
     button::*,
@@ -13,6 +18,13 @@ pub struct FileVerify {
     valid_value: String,
     algorithm: String,
     is_calculating: bool,
+    /// 生成或校验清单的目录
+    manifest_dir: String,
+    manifest_style: base::ManifestStyle,
+    manifest_running: bool,
+    manifest_progress: Arc<Mutex<base::ManifestProgress>>,
+    manifest_status: String,
+    report: Option<base::VerifyReport>,
     algorithm_state: Entity<SelectState<Vec<String>>>,
     valid_value_state: Entity<InputState>,
     _subscriptions: Vec<Subscription>,
@@ -20,21 +32,14 @@ pub struct FileVerify {
 
 impl FileVerify {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let algorithm_items = vec![
-            "md5sum".to_string(),
-            "sha1sum".to_string(),
-            "sha2_224sum".to_string(),
-            "sha2_256sum".to_string(),
-            "sha2_384sum".to_string(),
-            "sha2_512sum".to_string(),
-            "sha3_256sum".to_string(),
-            "sha3_384sum".to_string(),
-            "sha3_512sum".to_string(),
-        ];
+        let algorithm_items = base::hash::algorithms()
+            .into_iter()
+            .map(|algorithm| algorithm.name.to_string())
+            .collect::<Vec<_>>();
 
         let algorithm_state = cx.new(|cx| {
             let mut state = SelectState::new(algorithm_items, None, window, cx);
-            state.set_selected_value(&"md5sum".to_string(), window, cx);
+            state.set_selected_value(&"md5".to_string(), window, cx);
             state
         });
 
@@ -69,8 +74,14 @@ impl FileVerify {
             file_path: String::new(),
             checksum: String::new(),
             valid_value: String::new(),
-            algorithm: "md5sum".to_string(),
+            algorithm: "md5".to_string(),
             is_calculating: false,
+            manifest_dir: String::new(),
+            manifest_style: base::ManifestStyle::Gnu,
+            manifest_running: false,
+            manifest_progress: Default::default(),
+            manifest_status: String::new(),
+            report: None,
             algorithm_state,
             valid_value_state,
             _subscriptions,
@@ -174,6 +185,340 @@ impl FileVerify {
     }
 }
 
+impl FileVerify {
+    fn select_manifest_dir(&mut self, cx: &mut Context<Self>) {
+        let task = cx.background_executor().spawn(async move {
+            rfd::AsyncFileDialog::new()
+                .set_title("选择目录")
+                .pick_folder()
+                .await
+        });
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            if let Some(dir) = task.await {
+                let path = dir.path().to_string_lossy().to_string();
+                let _ = this.update(cx, |this, cx| {
+                    this.manifest_dir = path;
+                    cx.notify();
+                });
+            }
+        })
+        .detach();
+    }
+
+    fn start_manifest(&mut self, status: String, cx: &mut Context<Self>) {
+        self.manifest_running = true;
+        self.manifest_status = status;
+        self.report = None;
+        if let Ok(mut progress) = self.manifest_progress.lock() {
+            *progress = Default::default();
+        }
+        self.watch_manifest_progress(cx);
+        cx.notify();
+    }
+
+    /// 计算期间定时刷新，让进度跟上已算完的文件数
+    fn watch_manifest_progress(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(Duration::from_millis(200))
+                    .await;
+                let Ok(running) = this.update(cx, |this, cx| {
+                    cx.notify();
+                    this.manifest_running
+                }) else {
+                    break;
+                };
+                if !running {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// 为目录生成清单，保存到选择的文件
+    fn generate_manifest(&mut self, cx: &mut Context<Self>) {
+        if self.manifest_dir.is_empty() {
+            return;
+        }
+        let dir = self.manifest_dir.clone();
+        let algorithm = self.algorithm.clone();
+        let style = match self.manifest_style {
+            base::ManifestStyle::Gnu => "gnu",
+            base::ManifestStyle::Bsd => "bsd",
+        };
+        let progress = self.manifest_progress.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            let file_name = base::checksum_manifest_name(&algorithm);
+            let output = cx
+                .background_executor()
+                .spawn({
+                    let dir = dir.clone();
+                    async move {
+                        rfd::AsyncFileDialog::new()
+                            .set_title("保存清单")
+                            .set_directory(dir)
+                            .set_file_name(file_name)
+                            .save_file()
+                            .await
+                    }
+                })
+                .await;
+            let Some(output) = output else { return };
+            let output = output.path().to_path_buf();
+
+            let _ = this.update(cx, |this, cx| {
+                this.start_manifest(format!("正在生成 {}", output.display()), cx);
+            });
+            let result = base::checksum_manifest(&dir, &algorithm, Some(style), |p| {
+                if let Ok(mut progress) = progress.lock() {
+                    *progress = p.clone();
+                }
+            })
+            .await
+            .and_then(|manifest| {
+                std::fs::write(&output, &manifest)?;
+                Ok(manifest.lines().count())
+            });
+
+            let _ = this.update(cx, |this, cx| {
+                this.manifest_running = false;
+                this.manifest_status = match result {
+                    Ok(count) => format!("已写入 {count} 个文件的校验和: {}", output.display()),
+                    Err(e) => format!("生成失败: {e}"),
+                };
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// 选择清单文件并校验，目录为空时使用清单所在目录
+    fn verify_manifest(&mut self, cx: &mut Context<Self>) {
+        let dir = (!self.manifest_dir.is_empty()).then(|| self.manifest_dir.clone());
+        let progress = self.manifest_progress.clone();
+        let task = cx.background_executor().spawn(async move {
+            rfd::AsyncFileDialog::new()
+                .set_title("选择清单")
+                .pick_file()
+                .await
+        });
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            let Some(manifest) = task.await else { return };
+            let manifest = manifest.path().to_string_lossy().to_string();
+
+            let _ = this.update(cx, |this, cx| {
+                this.start_manifest(format!("正在校验 {manifest}"), cx);
+            });
+            let result = base::checksum_verify(&manifest, dir.as_deref(), None, |p| {
+                if let Ok(mut progress) = progress.lock() {
+                    *progress = p.clone();
+                }
+            })
+            .await;
+
+            let _ = this.update(cx, |this, cx| {
+                this.manifest_running = false;
+                match result {
+                    Ok(report) => {
+                        this.manifest_status = if report.is_ok() {
+                            format!("全部一致: {manifest}")
+                        } else {
+                            format!("校验未通过: {manifest}")
+                        };
+                        this.report = Some(report);
+                    }
+                    Err(e) => this.manifest_status = format!("校验失败: {e}"),
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn manifest_section(&self, cx: &mut Context<Self>) -> Div {
+        let running = self.manifest_running;
+        let style = self.manifest_style;
+        let progress = self
+            .manifest_progress
+            .lock()
+            .map(|p| p.clone())
+            .unwrap_or_default();
+        let percent = if progress.total > 0 {
+            progress.done as f32 / progress.total as f32
+        } else {
+            0.0
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            // label "目录清单" → 选择目录 + 目录
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(80.0)).text_sm().child("目录清单"))
+                    .child(
+                        Button::new("select_manifest_dir")
+                            .icon(Icon::new(IconName::Folder))
+                            .tooltip("选择目录")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.select_manifest_dir(cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .border_1()
+                            .border_color(cx.theme().border)
+                            .rounded_md()
+                            .px_3()
+                            .py_2()
+                            .text_sm()
+                            .font_family("monospace")
+                            .child(if self.manifest_dir.is_empty() {
+                                "请选择目录，校验时可不选...".to_string()
+                            } else {
+                                self.manifest_dir.clone()
+                            }),
+                    ),
+            )
+            // label " " → 格式 + 生成 / 校验
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(80.0)).text_sm().child(" "))
+                    .child(
+                        ButtonGroup::new("manifest-style")
+                            .child(
+                                Button::new("style-gnu")
+                                    .label("GNU")
+                                    .selected(style == base::ManifestStyle::Gnu)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.manifest_style = base::ManifestStyle::Gnu;
+                                        cx.notify();
+                                    })),
+                            )
+                            .child(
+                                Button::new("style-bsd")
+                                    .label("BSD")
+                                    .selected(style == base::ManifestStyle::Bsd)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.manifest_style = base::ManifestStyle::Bsd;
+                                        cx.notify();
+                                    })),
+                            ),
+                    )
+                    .child(
+                        Button::new("generate_manifest")
+                            .primary()
+                            .label("生成清单")
+                            .disabled(running || self.manifest_dir.is_empty())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.generate_manifest(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("verify_manifest")
+                            .label("校验清单")
+                            .disabled(running)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.verify_manifest(cx);
+                            })),
+                    ),
+            )
+            .when(running, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(div().w(px(80.0)).text_sm().child("进度"))
+                        .child(
+                            div()
+                                .flex_1()
+                                .h_2()
+                                .rounded_md()
+                                .bg(cx.theme().border)
+                                .child(
+                                    div()
+                                        .h_full()
+                                        .w(relative(percent))
+                                        .rounded_md()
+                                        .bg(cx.theme().accent),
+                                ),
+                        )
+                        .child(div().text_sm().child(format!(
+                            "{}/{} {}",
+                            progress.done, progress.total, progress.path
+                        ))),
+                )
+            })
+            .when(!self.manifest_status.is_empty(), |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(div().w(px(80.0)))
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(self.manifest_status.clone()),
+                        ),
+                )
+            })
+            .when_some(self.report.as_ref(), |this, report| {
+                this.child(
+                    div()
+                        .flex()
+                        .items_start()
+                        .gap_2()
+                        .child(div().w(px(80.0)).text_sm().child("校验结果"))
+                        .child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .flex_col()
+                                .gap_1()
+                                .text_sm()
+                                .font_family("monospace")
+                                .children(report.entries.iter().map(|entry| {
+                                    let (label, color) = match entry.status {
+                                        base::VerifyStatus::Ok => ("一致", gpui::rgb(0x18a058)),
+                                        base::VerifyStatus::Modified => {
+                                            ("已修改", gpui::rgb(0xd03050))
+                                        }
+                                        base::VerifyStatus::Missing => {
+                                            ("缺失", gpui::rgb(0xf0a020))
+                                        }
+                                        base::VerifyStatus::Extra => ("多出", gpui::rgb(0x2080f0)),
+                                        base::VerifyStatus::Error => {
+                                            ("读取失败", gpui::rgb(0xd03050))
+                                        }
+                                    };
+                                    div()
+                                        .flex()
+                                        .gap_2()
+                                        .child(div().w(px(64.0)).text_color(color).child(label))
+                                        .child(entry.path.clone())
+                                })),
+                        ),
+                )
+            })
+    }
+}
+
 impl Render for FileVerify {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let checksum = self.checksum.clone();
@@ -359,7 +704,8 @@ impl Render for FileVerify {
                                 div()
                             }),
                     )
-                }),
+                })
+                .child(self.manifest_section(cx)),
         )
     }
 }
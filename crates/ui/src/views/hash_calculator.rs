@@ -1,4 +1,4 @@
-use gpui::*;
+use gpui::{prelude::FluentBuilder as _, *};
 use gpui_component::{
     button::*,
     input::{Input, InputEvent, InputState},
@@ -6,14 +6,16 @@ use gpui_component::{
     *,
 };
 
+/// 输入的编码
+const INPUT_TYPES: [(&str, &str); 3] = [("text", "文本"), ("hex", "Hex"), ("base64", "Base64")];
+
 pub struct HashCalculator {
     input: String,
-    md5: String,
-    sha1: String,
-    sha256: String,
-    sha512: String,
-    sha3_256: String,
-    sha3_512: String,
+    input_type: &'static str,
+    /// 勾选的算法，按注册表顺序排列
+    algorithms: Vec<base::hash::Algorithm>,
+    results: std::collections::HashMap<String, String>,
+    error: String,
     is_calculating: bool,
     generation: u64,
     uppercase: bool,
@@ -68,14 +70,17 @@ impl HashCalculator {
             ),
         ];
 
+        let algorithms = base::hash::algorithms()
+            .into_iter()
+            .filter(|algorithm| base::hash::DEFAULT_ALGORITHMS.contains(&algorithm.name))
+            .collect();
+
         Self {
             input: String::new(),
-            md5: String::new(),
-            sha1: String::new(),
-            sha256: String::new(),
-            sha512: String::new(),
-            sha3_256: String::new(),
-            sha3_512: String::new(),
+            input_type: "text",
+            algorithms,
+            results: Default::default(),
+            error: String::new(),
             is_calculating: false,
             generation: 0,
             uppercase: false,
@@ -90,12 +95,8 @@ impl HashCalculator {
 
     fn calculate(&mut self, cx: &mut Context<Self>) {
         if self.input.is_empty() {
-            self.md5.clear();
-            self.sha1.clear();
-            self.sha256.clear();
-            self.sha512.clear();
-            self.sha3_256.clear();
-            self.sha3_512.clear();
+            self.results.clear();
+            self.error.clear();
             self.is_calculating = false;
             return;
         }
@@ -108,6 +109,12 @@ impl HashCalculator {
         let output_type = self.output_type.clone();
         let hmac_mode = self.hmac_mode;
         let secret = self.secret_state.read(cx).value().to_string();
+        let input_type = self.input_type;
+        let algorithms = self
+            .algorithms
+            .iter()
+            .map(|algorithm| algorithm.name)
+            .collect::<Vec<_>>();
         cx.notify();
 
         cx.spawn(async move |this: WeakEntity<Self>, cx| {
@@ -125,19 +132,24 @@ impl HashCalculator {
                     None
                 },
                 Some(&input),
+                Some(input_type),
+                &algorithms,
             )
-            .await
-            .unwrap_or_default();
+            .await;
             let _ = this.update(cx, |this, cx| {
                 if this.generation != generation {
                     return;
                 }
-                this.md5 = result.get("md5").cloned().unwrap_or_default();
-                this.sha1 = result.get("sha1").cloned().unwrap_or_default();
-                this.sha256 = result.get("sha256").cloned().unwrap_or_default();
-                this.sha512 = result.get("sha512").cloned().unwrap_or_default();
-                this.sha3_256 = result.get("sha3_256").cloned().unwrap_or_default();
-                this.sha3_512 = result.get("sha3_512").cloned().unwrap_or_default();
+                match result {
+                    Ok(results) => {
+                        this.results = results;
+                        this.error.clear();
+                    }
+                    Err(e) => {
+                        this.results.clear();
+                        this.error = e.to_string();
+                    }
+                }
                 this.is_calculating = false;
                 cx.notify();
             });
@@ -165,12 +177,8 @@ impl HashCalculator {
 
     fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.input.clear();
-        self.md5.clear();
-        self.sha1.clear();
-        self.sha256.clear();
-        self.sha512.clear();
-        self.sha3_256.clear();
-        self.sha3_512.clear();
+        self.results.clear();
+        self.error.clear();
         self.is_calculating = false;
         self.input_state.update(cx, |state, cx| {
             state.set_value("".to_string(), window, cx);
@@ -183,6 +191,34 @@ impl HashCalculator {
         self.calculate(cx);
     }
 
+    fn set_input_type(&mut self, input_type: &'static str, cx: &mut Context<Self>) {
+        self.input_type = input_type;
+        self.calculate(cx);
+    }
+
+    /// 勾选或取消一个算法，至少保留一个
+    fn toggle_algorithm(&mut self, algorithm: base::hash::Algorithm, cx: &mut Context<Self>) {
+        if let Some(i) = self
+            .algorithms
+            .iter()
+            .position(|a| a.name == algorithm.name)
+        {
+            if self.algorithms.len() > 1 {
+                self.algorithms.remove(i);
+            }
+        } else {
+            let order = |name: &str| {
+                base::hash::algorithms()
+                    .iter()
+                    .position(|a| a.name == name)
+                    .unwrap_or(usize::MAX)
+            };
+            self.algorithms.push(algorithm);
+            self.algorithms.sort_by_key(|a| order(a.name));
+        }
+        self.calculate(cx);
+    }
+
     fn set_hmac_mode(&mut self, hmac_mode: bool, cx: &mut Context<Self>) {
         self.hmac_mode = hmac_mode;
         self.calculate(cx);
@@ -191,12 +227,24 @@ impl HashCalculator {
 
 impl Render for HashCalculator {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let md5 = self.md5.clone();
-        let sha1 = self.sha1.clone();
-        let sha256 = self.sha256.clone();
-        let sha512 = self.sha512.clone();
-        let sha3_256 = self.sha3_256.clone();
-        let sha3_512 = self.sha3_512.clone();
+        let input_type = self.input_type;
+        let selected = self
+            .algorithms
+            .iter()
+            .map(|algorithm| algorithm.name)
+            .collect::<Vec<_>>();
+        let rows = self
+            .algorithms
+            .iter()
+            .map(|algorithm| {
+                let value = self
+                    .results
+                    .get(algorithm.name)
+                    .cloned()
+                    .unwrap_or_default();
+                hash_row(cx, algorithm.label, algorithm.name, value)
+            })
+            .collect::<Vec<_>>();
 
         div().child(
             div()
@@ -230,6 +278,47 @@ impl Render for HashCalculator {
                                 ),
                         ),
                 )
+                // label "算法" → 可切换的算法按钮
+                .child(
+                    div()
+                        .flex()
+                        .items_start()
+                        .gap_2()
+                        .child(div().w(px(80.0)).text_sm().mt_1().child("算法"))
+                        .child(div().flex_1().flex().flex_wrap().gap_1().children(
+                            base::hash::algorithms().into_iter().map(|algorithm| {
+                                Button::new(algorithm.name)
+                                    .label(algorithm.label)
+                                    .small()
+                                    .selected(selected.contains(&algorithm.name))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.toggle_algorithm(algorithm, cx);
+                                    }))
+                            }),
+                        )),
+                )
+                // label "输入编码" → ButtonGroup
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(div().w(px(80.0)).text_sm().child("输入编码"))
+                        .child(INPUT_TYPES.into_iter().fold(
+                            ButtonGroup::new("input-type"),
+                            |group, (value, label)| {
+                                group.child(
+                                    Button::new(value)
+                                        .label(label)
+                                        .selected(input_type == value)
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.set_input_type(value, cx);
+                                            cx.notify();
+                                        })),
+                                )
+                            },
+                        )),
+                )
                 // label "输入" → textarea
                 .child(
                     div()
@@ -243,37 +332,16 @@ impl Render for HashCalculator {
                                 .child(Input::new(&self.input_state).h(px(100.0))),
                         ),
                 )
+                .when(!self.error.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().danger)
+                            .child(self.error.clone()),
+                    )
+                })
                 // hash result rows
-                .child(hash_row(cx, "MD5", md5, "copy_md5", |this, cx| {
-                    cx.write_to_clipboard(ClipboardItem::new_string(this.md5.clone()));
-                }))
-                .child(hash_row(cx, "SHA1", sha1, "copy_sha1", |this, cx| {
-                    cx.write_to_clipboard(ClipboardItem::new_string(this.sha1.clone()));
-                }))
-                .child(hash_row(cx, "SHA256", sha256, "copy_sha256", |this, cx| {
-                    cx.write_to_clipboard(ClipboardItem::new_string(this.sha256.clone()));
-                }))
-                .child(hash_row(cx, "SHA512", sha512, "copy_sha512", |this, cx| {
-                    cx.write_to_clipboard(ClipboardItem::new_string(this.sha512.clone()));
-                }))
-                .child(hash_row(
-                    cx,
-                    "SHA3 256",
-                    sha3_256,
-                    "copy_sha3_256",
-                    |this, cx| {
-                        cx.write_to_clipboard(ClipboardItem::new_string(this.sha3_256.clone()));
-                    },
-                ))
-                .child(hash_row(
-                    cx,
-                    "SHA3 512",
-                    sha3_512,
-                    "copy_sha3_512",
-                    |this, cx| {
-                        cx.write_to_clipboard(ClipboardItem::new_string(this.sha3_512.clone()));
-                    },
-                )),
+                .children(rows),
         )
     }
 }
@@ -281,9 +349,8 @@ impl Render for HashCalculator {
 fn hash_row(
     cx: &mut Context<HashCalculator>,
     label: &'static str,
+    index: usize,
     value: String,
-    copy_id: &'static str,
-    on_copy: fn(&mut HashCalculator, &mut Context<HashCalculator>),
 ) -> Div {
     div()
         .flex()
@@ -303,13 +370,17 @@ fn hash_row(
                 .child(if value.is_empty() {
                     "-".to_string()
                 } else {
-                    value
+                    value.clone()
                 }),
         )
         .child(
-            Button::new(copy_id)
+            Button::new(("copy-hash", index))
                 .icon(Icon::new(IconName::Copy))
                 .tooltip("复制")
-                .on_click(cx.listener(move |this, _, _, cx| on_copy(this, cx))),
+                .on_click(cx.listener(move |_, _, _, cx| {
+                    if !value.is_empty() {
+                        cx.write_to_clipboard(ClipboardItem::new_string(value.clone()));
+                    }
+                })),
         )
 }
@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use gpui::{prelude::FluentBuilder as _, *};
 use gpui_component::{
     button::*,
+    checkbox::Checkbox,
     input::{Input, InputEvent, InputState},
     scroll::ScrollableElement,
     *,
@@ -18,6 +19,19 @@ struct TreeOptions {
     show_icon: bool,
 }
 
+/// 树查看器展示的内容，决定节点的标记方式
+#[derive(Clone, Copy, PartialEq)]
+enum Tree {
+    /// 输入：标出校验错误，可选中节点
+    Input,
+    /// 查询结果
+    QueryResult,
+    /// 对比的左侧（输入）：标出删除和修改的节点
+    DiffLeft,
+    /// 对比的右侧：标出新增和修改的节点
+    DiffRight,
+}
+
 pub struct JsonEditor {
     input: String,
     /// 解析后的 JSON 值（用于树查看器）
@@ -28,7 +42,40 @@ pub struct JsonEditor {
     expanded: HashSet<String>,
     /// 树查看器选项
     tree_options: TreeOptions,
+    /// JSON Schema 文本，非空时输入变化后自动校验
+    schema: String,
+    /// 从文件加载的 Schema 所在目录，用于解析 `$ref` 的相对路径
+    schema_dir: Option<String>,
+    /// 校验结果，`None` 表示尚未校验
+    violations: Option<Vec<base::Violation>>,
+    /// Schema 本身的错误
+    schema_error: String,
+    /// 查询语言：jsonpath、jmespath 或 jq
+    query_lang: String,
+    /// 已执行的查询表达式，输入变化后重新执行
+    query: String,
+    /// 查询结果，`None` 表示没有查询
+    query_results: Option<Vec<base::Match>>,
+    query_error: String,
+    /// 查询是否在后台执行中
+    query_running: bool,
+    /// 每次查询递增，只采用最后一次查询的结果
+    query_generation: u64,
+    /// 树查看器中选中节点的 JSON Pointer
+    selected: Option<String>,
+    /// 与输入对比的 JSON/YAML/TOML 文本
+    diff_input: String,
+    diff_ignore_array_order: bool,
+    /// 对比时忽略的节点，逗号分隔的 JSON Pointer
+    diff_ignore: String,
+    /// 对比结果，`None` 表示没有对比
+    diff: Option<base::JsonDiff>,
+    diff_error: String,
     input_state: Entity<InputState>,
+    schema_state: Entity<InputState>,
+    query_state: Entity<InputState>,
+    diff_state: Entity<InputState>,
+    diff_ignore_state: Entity<InputState>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -39,19 +86,79 @@ impl JsonEditor {
                 .placeholder("请输入JSON...")
                 .multi_line(true)
         });
+        let schema_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("粘贴 JSON Schema 或从文件加载...")
+                .multi_line(true)
+        });
+        let query_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("JSONPath 如 $.items[*].name，JMESPath 如 items[*].name，jq 如 .items[].name，回车执行")
+        });
+        let diff_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("粘贴要与输入对比的 JSON/YAML/TOML...")
+                .multi_line(true)
+        });
+        let diff_ignore_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("忽略的节点，如 /items/*/updatedAt，多个用逗号分隔")
+        });
 
-        let _subscriptions = vec![cx.subscribe_in(&input_state, window, {
-            let input_state = input_state.clone();
-            move |this, _, ev: &InputEvent, _, cx| {
-                if let InputEvent::Change = ev {
-                    let value = input_state.read(cx).value();
-                    this.input = value.to_string();
-                    // 输入变化时自动解析
-                    this.parse_input();
-                    cx.notify();
+        let _subscriptions = vec![
+            cx.subscribe_in(&input_state, window, {
+                let input_state = input_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        let value = input_state.read(cx).value();
+                        this.input = value.to_string();
+                        // 输入变化时自动解析
+                        this.parse_input(cx);
+                        cx.notify();
+                    }
                 }
-            }
-        })];
+            }),
+            cx.subscribe_in(&schema_state, window, {
+                let schema_state = schema_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.schema = schema_state.read(cx).value().to_string();
+                        this.validate();
+                        cx.notify();
+                    }
+                }
+            }),
+            cx.subscribe_in(&query_state, window, {
+                let query_state = query_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::PressEnter { .. } = ev {
+                        this.query = query_state.read(cx).value().to_string();
+                        this.run_query(cx);
+                        cx.notify();
+                    }
+                }
+            }),
+            cx.subscribe_in(&diff_state, window, {
+                let diff_state = diff_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.diff_input = diff_state.read(cx).value().to_string();
+                        this.run_diff();
+                        cx.notify();
+                    }
+                }
+            }),
+            cx.subscribe_in(&diff_ignore_state, window, {
+                let diff_ignore_state = diff_ignore_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.diff_ignore = diff_ignore_state.read(cx).value().to_string();
+                        this.run_diff();
+                        cx.notify();
+                    }
+                }
+            }),
+        ];
 
         let mut this = Self {
             input: String::new(),
@@ -63,36 +170,293 @@ impl JsonEditor {
                 show_line_number: false,
                 show_icon: true,
             },
+            schema: String::new(),
+            schema_dir: None,
+            violations: None,
+            schema_error: String::new(),
+            query_lang: "jsonpath".to_string(),
+            query: String::new(),
+            query_results: None,
+            query_error: String::new(),
+            query_running: false,
+            query_generation: 0,
+            selected: None,
+            diff_input: String::new(),
+            diff_ignore_array_order: false,
+            diff_ignore: String::new(),
+            diff: None,
+            diff_error: String::new(),
             input_state,
+            schema_state,
+            query_state,
+            diff_state,
+            diff_ignore_state,
             _subscriptions,
         };
-        this.parse_input();
+        this.parse_input(cx);
         this
     }
 
-    fn parse_input(&mut self) {
+    fn parse_input(&mut self, cx: &mut Context<Self>) {
         if self.input.trim().is_empty() {
             self.parsed = None;
             self.tree_error.clear();
+        } else {
+            match serde_json::from_str::<serde_json::Value>(&self.input) {
+                Ok(parsed) => {
+                    self.parsed = Some(parsed);
+                    self.tree_error.clear();
+                }
+                Err(_) => {
+                    self.parsed = None;
+                    self.tree_error = "无法解析为 JSON".to_string();
+                }
+            }
+        }
+        self.validate();
+        self.run_query(cx);
+        self.run_diff();
+    }
+
+    /// 用 Schema 校验当前输入；Schema 或输入为空、输入无法解析时清空结果
+    fn validate(&mut self) {
+        self.schema_error.clear();
+        if self.schema.trim().is_empty() || self.parsed.is_none() {
+            self.violations = None;
             return;
         }
-        match serde_json::from_str::<serde_json::Value>(&self.input) {
-            Ok(parsed) => {
-                self.parsed = Some(parsed);
-                self.tree_error.clear();
+        match base::validate_json_schema(&self.input, &self.schema, self.schema_dir.as_deref()) {
+            Ok(violations) => self.violations = Some(violations),
+            Err(e) => {
+                self.violations = None;
+                self.schema_error = e.to_string();
+            }
+        }
+    }
+
+    /// 从文件加载 Schema，`$ref` 的相对路径基于该文件所在目录
+    fn load_schema(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let task = cx.background_executor().spawn(async move {
+            rfd::AsyncFileDialog::new()
+                .set_title("选择 JSON Schema")
+                .add_filter("JSON Schema", &["json"])
+                .pick_file()
+                .await
+        });
+
+        cx.spawn_in(window, async move |this: WeakEntity<Self>, cx| {
+            if let Some(file) = task.await {
+                let dir = file
+                    .path()
+                    .parent()
+                    .map(|p| p.to_string_lossy().to_string());
+                let content = String::from_utf8_lossy(&file.read().await).to_string();
+
+                let _ = this.update_in(cx, |this, window, cx| {
+                    this.schema_dir = dir;
+                    this.schema = content.clone();
+                    this.schema_state.update(cx, |state, cx| {
+                        state.set_value(content, window, cx);
+                    });
+                    this.validate();
+                    cx.notify();
+                });
             }
-            Err(_) => {
-                self.parsed = None;
-                self.tree_error = "无法解析为 JSON".to_string();
+        })
+        .detach();
+    }
+
+    fn paste_schema(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(item) = cx.read_from_clipboard() {
+            if let Some(text) = item.text() {
+                // 粘贴的 Schema 没有所在目录，相对引用基于当前工作目录
+                self.schema_dir = None;
+                self.schema = text.to_string();
+                self.schema_state.update(cx, |state, cx| {
+                    state.set_value(text.to_string(), window, cx);
+                });
+                self.validate();
+            }
+        }
+    }
+
+    fn clear_schema(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.schema.clear();
+        self.schema_dir = None;
+        self.violations = None;
+        self.schema_error.clear();
+        self.schema_state.update(cx, |state, cx| {
+            state.set_value("".to_string(), window, cx);
+        });
+    }
+
+    /// 在后台对当前输入执行查询，避免耗时或死循环的 jq 过滤器卡住界面；表达式为空时清空结果
+    fn run_query(&mut self, cx: &mut Context<Self>) {
+        self.query_generation += 1;
+        self.query_running = false;
+        self.query_error.clear();
+        if self.query.trim().is_empty() {
+            self.query_results = None;
+            return;
+        }
+        if self.parsed.is_none() {
+            self.query_results = None;
+            self.query_error = "请先输入有效的 JSON".to_string();
+            return;
+        }
+
+        self.query_running = true;
+        let generation = self.query_generation;
+        let lang = self.query_lang.clone();
+        let input = self.input.clone();
+        let query = self.query.clone();
+        let task = cx
+            .background_executor()
+            .spawn(async move { base::query_json(&lang, &input, &query) });
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            let result = task.await;
+            let _ = this.update(cx, |this, cx| {
+                // 输入或表达式已经变化，丢弃过期的结果
+                if this.query_generation != generation {
+                    return;
+                }
+                this.query_running = false;
+                match result {
+                    Ok(results) => this.query_results = Some(results),
+                    Err(e) => {
+                        this.query_results = None;
+                        this.query_error = e.to_string();
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// 对比输入与右侧文本，两侧各自识别格式；任一侧为空时清空结果
+    fn run_diff(&mut self) {
+        self.diff_error.clear();
+        if self.input.trim().is_empty() || self.diff_input.trim().is_empty() {
+            self.diff = None;
+            return;
+        }
+        let options = base::DiffOptions {
+            ignore_array_order: self.diff_ignore_array_order,
+            ignore_paths: self
+                .diff_ignore
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(String::from)
+                .collect(),
+        };
+        match base::json_diff("auto", &self.input, &self.diff_input, &options) {
+            Ok(diff) => self.diff = Some(diff),
+            Err(e) => {
+                self.diff = None;
+                self.diff_error = e.to_string();
             }
         }
     }
 
+    fn toggle_diff_ignore_array_order(&mut self, checked: bool, cx: &mut Context<Self>) {
+        self.diff_ignore_array_order = checked;
+        self.run_diff();
+        cx.notify();
+    }
+
+    /// 复制 JSON Patch 或 JSON Merge Patch
+    fn copy_patch(&mut self, merge: bool, cx: &mut Context<Self>) {
+        let Some(ref diff) = self.diff else {
+            return;
+        };
+        let patch = if merge {
+            &diff.merge_patch
+        } else {
+            &diff.patch
+        };
+        if let Ok(text) = serde_json::to_string_pretty(patch) {
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
+    fn set_query_lang(&mut self, lang: &str, cx: &mut Context<Self>) {
+        if self.query_lang == lang {
+            return;
+        }
+        self.query_lang = lang.to_string();
+        self.run_query(cx);
+        cx.notify();
+    }
+
+    /// 复制查询结果：单个结果直接复制，多个结果复制为数组
+    fn copy_query_results(&mut self, cx: &mut Context<Self>) {
+        let Some(ref results) = self.query_results else {
+            return;
+        };
+        let value = match results.as_slice() {
+            [single] => single.value.clone(),
+            _ => serde_json::Value::Array(results.iter().map(|m| m.value.clone()).collect()),
+        };
+        if let Ok(text) = serde_json::to_string_pretty(&value) {
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
+    /// 复制 JSONPath 结果的规范化路径，每行一个
+    fn copy_query_paths(&mut self, cx: &mut Context<Self>) {
+        let paths = self
+            .query_results
+            .iter()
+            .flatten()
+            .filter_map(|m| m.path.clone())
+            .collect::<Vec<_>>();
+        if !paths.is_empty() {
+            cx.write_to_clipboard(ClipboardItem::new_string(paths.join("\n")));
+        }
+    }
+
+    /// 选中的节点在当前查询语言中的路径
+    fn selected_path(&self) -> Option<String> {
+        let value = self.parsed.as_ref()?;
+        base::json_node_path(&self.query_lang, value, self.selected.as_deref()?)
+    }
+
+    fn select_node(&mut self, pointer: &str, cx: &mut Context<Self>) {
+        self.selected = Some(pointer.to_string());
+        cx.notify();
+    }
+
+    fn copy_selected_path(&mut self, cx: &mut Context<Self>) {
+        if let Some(path) = self.selected_path() {
+            cx.write_to_clipboard(ClipboardItem::new_string(path));
+        }
+    }
+
+    /// 展开 JSON Pointer 指向的节点及其所有祖先
+    fn reveal(&mut self, pointer: &str, cx: &mut Context<Self>) {
+        let mut path = "$root".to_string();
+        for segment in pointer.split('/').skip(1) {
+            path = format!("{path}.{}", segment.replace("~1", "/").replace("~0", "~"));
+            self.expanded.insert(path.clone());
+        }
+        cx.notify();
+    }
+
     fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.input.clear();
         self.parsed = None;
         self.tree_error.clear();
         self.expanded.clear();
+        self.selected = None;
+        self.query_results = None;
+        self.query_error.clear();
+        self.query_running = false;
+        self.query_generation += 1;
+        self.diff = None;
+        self.diff_error.clear();
         self.input_state.update(cx, |state, cx| {
             state.set_value("".to_string(), window, cx);
         });
@@ -105,7 +469,7 @@ impl JsonEditor {
                 self.input_state.update(cx, |state, cx| {
                     state.set_value(text.to_string(), window, cx);
                 });
-                self.parse_input();
+                self.parse_input(cx);
             }
         }
     }
@@ -260,68 +624,433 @@ impl Render for JsonEditor {
                         ),
                 )
                 // 树查看器（匹配 Tauri VueJsonPretty）
-                .child(tree_viewer_panel(self, cx)),
+                .child(tree_viewer_panel(self, cx))
+                // JSONPath / jq 查询
+                .child(query_panel(self, cx))
+                // 结构化对比
+                .child(diff_panel(self, cx))
+                // JSON Schema 校验
+                .child(schema_panel(self, cx)),
         )
     }
 }
 
 /// 渲染交互式树查看器面板（匹配 Tauri VueJsonPretty：工具栏内嵌在树区域顶部）
 fn tree_viewer_panel(this: &JsonEditor, cx: &mut Context<JsonEditor>) -> Div {
+    let selected_path = this.selected_path();
     div()
         .flex()
         .flex_col()
         .gap_1()
         // 工具栏（内嵌在树查看器顶部，匹配 VueJsonPretty 的内置控制）
         .child(
-            div().flex().items_center().gap_2().child(
-                ButtonGroup::new("tree-toolbar")
+            div()
+                .flex()
+                .items_center()
+                .gap_2()
+                .child(
+                    ButtonGroup::new("tree-toolbar")
+                        .child(
+                            Button::new("tree-expand-all")
+                                .icon(Icon::new(IconName::Plus))
+                                .tooltip("全部展开")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.expand_all(cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("tree-collapse-all")
+                                .icon(Icon::new(IconName::Minus))
+                                .tooltip("全部折叠")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.collapse_all(cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("tree-toggle-length")
+                                .child("长度")
+                                .selected(this.tree_options.show_length)
+                                .tooltip("显示子节点数量")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.toggle_show_length(cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("tree-toggle-line")
+                                .child("行号")
+                                .selected(this.tree_options.show_line_number)
+                                .tooltip("显示行号")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.toggle_show_line_number(cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("tree-toggle-icon")
+                                .child("图标")
+                                .selected(this.tree_options.show_icon)
+                                .tooltip("显示类型图标")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.toggle_show_icon(cx);
+                                })),
+                        ),
+                )
+                .child(
+                    Button::new("tree-copy-path")
+                        .icon(Icon::new(IconName::Copy))
+                        .tooltip("复制选中节点的路径")
+                        .disabled(selected_path.is_none())
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.copy_selected_path(cx);
+                        })),
+                )
+                .child(
+                    div()
+                        .text_sm()
+                        .font_family("monospace")
+                        .text_color(cx.theme().muted_foreground)
+                        .child(selected_path.unwrap_or_default()),
+                ),
+        )
+        // 树内容
+        .child(render_tree_viewer(this, cx))
+}
+
+/// 渲染查询面板：查询语言切换、表达式输入和以树展示的结果
+fn query_panel(this: &JsonEditor, cx: &mut Context<JsonEditor>) -> Div {
+    let is_json_path = this.query_lang == "jsonpath";
+    let has_results = this
+        .query_results
+        .as_ref()
+        .is_some_and(|results| !results.is_empty());
+
+    let status = if this.query_running {
+        div()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child("查询中...")
+    } else if !this.query_error.is_empty() {
+        div()
+            .text_sm()
+            .text_color(cx.theme().danger)
+            .child(this.query_error.clone())
+    } else {
+        match &this.query_results {
+            Some(results) => div()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child(format!("{} 条结果", results.len())),
+            None => div(),
+        }
+    };
+
+    let mut panel = div().flex().flex_col().gap_2().child(
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .child(
+                ButtonGroup::new("query-lang")
                     .child(
-                        Button::new("tree-expand-all")
-                            .icon(Icon::new(IconName::Plus))
-                            .tooltip("全部展开")
-                            .on_click(cx.listener(|this, _, _, cx| {
-                                this.expand_all(cx);
+                        Button::new("query-jsonpath")
+                            .child("JSONPath")
+                            .selected(is_json_path)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_query_lang("jsonpath", cx);
                             })),
                     )
                     .child(
-                        Button::new("tree-collapse-all")
-                            .icon(Icon::new(IconName::Minus))
-                            .tooltip("全部折叠")
-                            .on_click(cx.listener(|this, _, _, cx| {
-                                this.collapse_all(cx);
+                        Button::new("query-jmespath")
+                            .child("JMESPath")
+                            .selected(this.query_lang == "jmespath")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_query_lang("jmespath", cx);
                             })),
                     )
                     .child(
-                        Button::new("tree-toggle-length")
-                            .child("长度")
-                            .selected(this.tree_options.show_length)
-                            .tooltip("显示子节点数量")
-                            .on_click(cx.listener(|this, _, _, cx| {
-                                this.toggle_show_length(cx);
+                        Button::new("query-jq")
+                            .child("jq")
+                            .selected(this.query_lang == "jq")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_query_lang("jq", cx);
                             })),
-                    )
+                    ),
+            )
+            .child(div().flex_1().child(Input::new(&this.query_state)))
+            .child(
+                ButtonGroup::new("query-buttons")
                     .child(
-                        Button::new("tree-toggle-line")
-                            .child("行号")
-                            .selected(this.tree_options.show_line_number)
-                            .tooltip("显示行号")
+                        Button::new("copy-query-results")
+                            .icon(Icon::new(IconName::Copy))
+                            .tooltip("复制结果")
+                            .disabled(!has_results)
                             .on_click(cx.listener(|this, _, _, cx| {
-                                this.toggle_show_line_number(cx);
+                                this.copy_query_results(cx);
                             })),
                     )
                     .child(
-                        Button::new("tree-toggle-icon")
-                            .child("图标")
-                            .selected(this.tree_options.show_icon)
-                            .tooltip("显示类型图标")
+                        Button::new("copy-query-paths")
+                            .child("路径")
+                            .tooltip("复制结果的路径，每行一个")
+                            .disabled(!has_results || !is_json_path)
                             .on_click(cx.listener(|this, _, _, cx| {
-                                this.toggle_show_icon(cx);
+                                this.copy_query_paths(cx);
                             })),
                     ),
-            ),
+            )
+            .child(status),
+    );
+
+    if let Some(ref results) = this.query_results {
+        let value = serde_json::Value::Array(results.iter().map(|m| m.value.clone()).collect());
+        let mut line_counter = 1usize;
+        panel = panel.child(
+            div()
+                .border_1()
+                .border_color(cx.theme().border)
+                .rounded_lg()
+                .h(px(200.0))
+                .child(
+                    div()
+                        .p_3()
+                        .h_full()
+                        .overflow_y_scrollbar()
+                        .text_sm()
+                        .font_family("monospace")
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .child(render_node(
+                            &value,
+                            "$result",
+                            "$result",
+                            "",
+                            Tree::QueryResult,
+                            0,
+                            this,
+                            &mut line_counter,
+                            cx,
+                        )),
+                ),
+        );
+    }
+    panel
+}
+
+/// 渲染对比面板：对比文本、选项、Patch 导出和左右两侧标出差异的树
+fn diff_panel(this: &JsonEditor, cx: &mut Context<JsonEditor>) -> Div {
+    let status = if !this.diff_error.is_empty() {
+        div()
+            .text_sm()
+            .text_color(cx.theme().danger)
+            .child(this.diff_error.clone())
+    } else {
+        match &this.diff {
+            Some(diff) if diff.changes.is_empty() => div()
+                .text_sm()
+                .text_color(cx.theme().success)
+                .child("没有差异"),
+            Some(diff) => {
+                let count = |kind| diff.changes.iter().filter(|c| c.kind == kind).count();
+                div()
+                    .flex()
+                    .gap_2()
+                    .text_sm()
+                    .child(
+                        div()
+                            .text_color(cx.theme().success)
+                            .child(format!("新增 {}", count(base::ChangeKind::Added))),
+                    )
+                    .child(
+                        div()
+                            .text_color(cx.theme().danger)
+                            .child(format!("删除 {}", count(base::ChangeKind::Removed))),
+                    )
+                    .child(
+                        div()
+                            .text_color(cx.theme().warning)
+                            .child(format!("修改 {}", count(base::ChangeKind::Changed))),
+                    )
+            }
+            None => div(),
+        }
+    };
+
+    let mut panel = div()
+        .flex()
+        .flex_col()
+        .gap_2()
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap_2()
+                .child(div().text_sm().child("对比"))
+                .child(
+                    Checkbox::new("diff-ignore-array-order")
+                        .label("忽略数组顺序")
+                        .checked(this.diff_ignore_array_order)
+                        .on_click(cx.listener(|this, v: &bool, _, cx| {
+                            this.toggle_diff_ignore_array_order(*v, cx);
+                        })),
+                )
+                .child(div().flex_1().child(Input::new(&this.diff_ignore_state)))
+                .child(
+                    ButtonGroup::new("diff-buttons")
+                        .child(
+                            Button::new("copy-json-patch")
+                                .child("JSON Patch")
+                                .tooltip("复制 RFC 6902 JSON Patch")
+                                .disabled(this.diff.is_none())
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.copy_patch(false, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("copy-merge-patch")
+                                .child("Merge Patch")
+                                .tooltip("复制 RFC 7386 JSON Merge Patch")
+                                .disabled(this.diff.is_none())
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.copy_patch(true, cx);
+                                })),
+                        ),
+                )
+                .child(status),
         )
-        // 树内容
-        .child(render_tree_viewer(this, cx))
+        .child(Input::new(&this.diff_state).h(px(160.0)));
+
+    if let Some(ref diff) = this.diff {
+        let side =
+            |value: &serde_json::Value, root: &str, tree: Tree, cx: &mut Context<JsonEditor>| {
+                let mut line_counter = 1usize;
+                div()
+                    .flex_1()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .rounded_lg()
+                    .h(px(280.0))
+                    .child(
+                        div()
+                            .p_3()
+                            .h_full()
+                            .overflow_y_scrollbar()
+                            .text_sm()
+                            .font_family("monospace")
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(render_node(
+                                value,
+                                root,
+                                root,
+                                "",
+                                tree,
+                                0,
+                                this,
+                                &mut line_counter,
+                                cx,
+                            )),
+                    )
+            };
+        panel = panel.child(
+            div()
+                .flex()
+                .gap_2()
+                .child(side(&diff.left, "$left", Tree::DiffLeft, cx))
+                .child(side(&diff.right, "$right", Tree::DiffRight, cx)),
+        );
+    }
+    panel
+}
+
+/// 渲染 JSON Schema 校验面板：Schema 输入、操作按钮和校验错误列表
+fn schema_panel(this: &JsonEditor, cx: &mut Context<JsonEditor>) -> Div {
+    let status = if !this.schema_error.is_empty() {
+        div()
+            .text_sm()
+            .text_color(cx.theme().danger)
+            .child(this.schema_error.clone())
+    } else {
+        match &this.violations {
+            Some(v) if v.is_empty() => div()
+                .text_sm()
+                .text_color(cx.theme().success)
+                .child("校验通过"),
+            Some(v) => div()
+                .text_sm()
+                .text_color(cx.theme().danger)
+                .child(format!("{} 处不符合 Schema", v.len())),
+            None => div(),
+        }
+    };
+
+    let mut list = div().flex().flex_col().gap_1().text_sm();
+    for (i, violation) in this.violations.iter().flatten().enumerate() {
+        let pointer = violation.pointer.clone();
+        list = list.child(
+            div()
+                .id(("schema-violation", i))
+                .flex()
+                .gap_2()
+                .cursor_pointer()
+                .child(
+                    div()
+                        .font_family("monospace")
+                        .text_color(cx.theme().danger)
+                        .child(if pointer.is_empty() {
+                            "(根节点)".to_string()
+                        } else {
+                            pointer.clone()
+                        }),
+                )
+                .child(div().child(violation.message.clone()))
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.reveal(&pointer, cx);
+                })),
+        );
+    }
+
+    div()
+        .flex()
+        .flex_col()
+        .gap_2()
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap_2()
+                .child(div().text_sm().child("JSON Schema"))
+                .child(
+                    ButtonGroup::new("schema-buttons")
+                        .child(
+                            Button::new("paste-schema")
+                                .icon(Icon::new(IconName::File))
+                                .tooltip("粘贴 Schema")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.paste_schema(window, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("load-schema")
+                                .icon(Icon::new(IconName::Folder))
+                                .tooltip("从文件加载，$ref 相对该文件解析")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.load_schema(window, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("clear-schema")
+                                .icon(Icon::new(IconName::Close))
+                                .tooltip("清除 Schema")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.clear_schema(window, cx);
+                                })),
+                        ),
+                )
+                .child(status),
+        )
+        .child(Input::new(&this.schema_state).h(px(160.0)))
+        .child(list)
 }
 
 /// 渲染树查看器内容
@@ -348,9 +1077,10 @@ fn render_tree_viewer(this: &JsonEditor, cx: &mut Context<JsonEditor>) -> Div {
             value,
             "$root",
             "$root",
+            "",
+            Tree::Input,
             0,
-            &this.expanded,
-            this.tree_options,
+            this,
             &mut line_counter,
             cx,
         ))
@@ -370,26 +1100,87 @@ fn render_tree_viewer(this: &JsonEditor, cx: &mut Context<JsonEditor>) -> Div {
         .child(inner)
 }
 
-/// 递归渲染一个 JSON 节点
+/// 节点的标记：节点本身的标记，以及折叠时子孙节点的标记
+fn node_mark(
+    this: &JsonEditor,
+    tree: Tree,
+    pointer: &str,
+    collapsed: bool,
+    cx: &Context<JsonEditor>,
+) -> (Option<Hsla>, Option<Hsla>) {
+    let marks: Vec<(&str, Hsla)> = match tree {
+        Tree::Input => this
+            .violations
+            .iter()
+            .flatten()
+            .map(|v| (v.pointer.as_str(), cx.theme().danger))
+            .collect(),
+        Tree::QueryResult => Vec::new(),
+        Tree::DiffLeft | Tree::DiffRight => this
+            .diff
+            .iter()
+            .flat_map(|diff| diff.changes.iter())
+            .filter_map(|c| match (c.kind, tree) {
+                (base::ChangeKind::Removed, Tree::DiffLeft) => {
+                    Some((c.pointer.as_str(), cx.theme().danger))
+                }
+                (base::ChangeKind::Added, Tree::DiffRight) => {
+                    Some((c.pointer.as_str(), cx.theme().success))
+                }
+                (base::ChangeKind::Changed, _) => Some((c.pointer.as_str(), cx.theme().warning)),
+                _ => None,
+            })
+            .collect(),
+    };
+    let own = marks.iter().find(|(p, _)| *p == pointer).map(|(_, c)| *c);
+    let prefix = format!("{pointer}/");
+    let nested = collapsed
+        .then(|| {
+            marks
+                .iter()
+                .find(|(p, _)| p.starts_with(&prefix))
+                .map(|(_, c)| *c)
+        })
+        .flatten();
+    (own, nested)
+}
+
+/// 递归渲染一个 JSON 节点，`pointer` 为节点在所属树中的 JSON Pointer，用于标出校验错误、差异和选中节点
 fn render_node(
     value: &serde_json::Value,
     key: &str,
     path: &str,
+    pointer: &str,
+    tree: Tree,
     depth: usize,
-    expanded: &HashSet<String>,
-    options: TreeOptions,
+    this: &JsonEditor,
     line_counter: &mut usize,
     cx: &mut Context<JsonEditor>,
 ) -> Div {
+    let options = this.tree_options;
     let expandable = is_expandable(value);
-    let is_expanded = expandable && (depth == 0 || expanded.contains(path));
+    let is_expanded = expandable && (depth == 0 || this.expanded.contains(path));
+    // 子孙节点有错误或差异时在折叠的节点上标记
+    let (mark, nested_mark) = node_mark(this, tree, pointer, !is_expanded, cx);
+    let selected = tree == Tree::Input && this.selected.as_deref() == Some(pointer);
 
     // 行容器
     let mut row = div()
         .flex()
         .items_center()
         .gap_1()
-        .pl(px(8.0 * depth as f32)); // 缩进：每层 8px
+        .pl(px(8.0 * depth as f32)) // 缩进：每层 8px
+        .when_some(mark, |row, color| row.bg(color.opacity(0.15)))
+        .when(selected, |row| row.bg(cx.theme().accent));
+    if tree == Tree::Input {
+        let pointer = pointer.to_string();
+        row = row.on_mouse_down(
+            MouseButton::Left,
+            cx.listener(move |this, _, _, cx| {
+                this.select_node(&pointer, cx);
+            }),
+        );
+    }
 
     // 行号
     if options.show_line_number {
@@ -479,6 +1270,9 @@ fn render_node(
         };
         row = row.child(div().text_color(color).child(display));
     }
+    if let Some(color) = mark.or(nested_mark) {
+        row = row.child(div().text_color(color).child("●"));
+    }
 
     // 递归渲染子节点
     let mut result = div().flex().flex_col().gap_1().child(row);
@@ -488,13 +1282,18 @@ fn render_node(
             serde_json::Value::Object(map) => {
                 for (child_key, child_value) in map {
                     let child_path = format!("{path}.{child_key}");
+                    let child_pointer = format!(
+                        "{pointer}/{}",
+                        child_key.replace('~', "~0").replace('/', "~1")
+                    );
                     result = result.child(render_node(
                         child_value,
                         child_key,
                         &child_path,
+                        &child_pointer,
+                        tree,
                         depth + 1,
-                        expanded,
-                        options,
+                        this,
                         line_counter,
                         cx,
                     ));
@@ -504,13 +1303,15 @@ fn render_node(
                 for (i, child_value) in arr.iter().enumerate() {
                     let child_key = i.to_string();
                     let child_path = format!("{path}.{i}");
+                    let child_pointer = format!("{pointer}/{i}");
                     result = result.child(render_node(
                         child_value,
                         &child_key,
                         &child_path,
+                        &child_pointer,
+                        tree,
                         depth + 1,
-                        expanded,
-                        options,
+                        this,
                         line_counter,
                         cx,
                     ));
@@ -14,7 +14,18 @@ pub struct JwtDecoder {
     payload: String,
     decoded: String,
     error: String,
+    /// 验证或签发用的密钥
+    key: String,
+    /// 签名验证结果，`None` 表示尚未验证
+    verification: Option<base::Verification>,
+    /// `exp`、`nbf`、`iat` 的有效性
+    claims: Vec<base::TimeClaim>,
+    /// 签发模式：编辑 Header 和 Payload 后生成 Token
+    signing: bool,
     token_state: Entity<InputState>,
+    key_state: Entity<InputState>,
+    header_state: Entity<InputState>,
+    payload_state: Entity<InputState>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -25,15 +36,43 @@ impl JwtDecoder {
                 .placeholder("粘贴 JWT Token...")
                 .multi_line(true)
         });
-        let _subscriptions = vec![cx.subscribe_in(&token_state, window, {
-            let token_state = token_state.clone();
-            move |this, _, ev: &InputEvent, _, cx| {
-                if let InputEvent::Change = ev {
-                    this.token = token_state.read(cx).value().to_string();
-                    cx.notify();
+        let key_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(
+                    "验证：HMAC 密钥、PEM 公钥、JWK 或 JWKS；解密 JWE：对称密钥或私钥；签发：HMAC 密钥或 PEM 私钥",
+                )
+                .multi_line(true)
+        });
+        let header_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value("{\n  \"alg\": \"HS256\",\n  \"typ\": \"JWT\"\n}")
+                .multi_line(true)
+        });
+        let payload_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value("{}")
+                .multi_line(true)
+        });
+        let _subscriptions = vec![
+            cx.subscribe_in(&token_state, window, {
+                let token_state = token_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.token = token_state.read(cx).value().to_string();
+                        cx.notify();
+                    }
                 }
-            }
-        })];
+            }),
+            cx.subscribe_in(&key_state, window, {
+                let key_state = key_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.key = key_state.read(cx).value().to_string();
+                        cx.notify();
+                    }
+                }
+            }),
+        ];
 
         Self {
             token: String::new(),
@@ -41,14 +80,29 @@ impl JwtDecoder {
             payload: "{}".to_string(),
             decoded: "{}".to_string(),
             error: String::new(),
+            key: String::new(),
+            verification: None,
+            claims: Vec::new(),
+            signing: false,
             token_state,
+            key_state,
+            header_state,
+            payload_state,
             _subscriptions,
         }
     }
 
     fn decode(&mut self) {
         self.error.clear();
-        match base::decode_jwt(self.token.trim()) {
+        self.verification = None;
+        self.claims = base::jwt_time_claims(self.token.trim()).unwrap_or_default();
+        // 填写了密钥时解密 JWE，并递归解码嵌套的 Token
+        let result = if is_jwe(&self.token) && !self.key.trim().is_empty() {
+            base::decrypt_jwt(self.token.trim(), &self.key)
+        } else {
+            base::decode_jwt(self.token.trim())
+        };
+        match result {
             Ok(value) => {
                 self.decoded = value.clone();
                 match serde_json::from_str::<serde_json::Value>(&value) {
@@ -76,6 +130,53 @@ impl JwtDecoder {
         }
     }
 
+    /// 验证签名，同时刷新时间声明
+    fn verify(&mut self) {
+        self.decode();
+        if !self.error.is_empty() || is_jwe(&self.token) {
+            return;
+        }
+        match base::verify_jwt(self.token.trim(), &self.key, None) {
+            Ok(verification) => {
+                self.claims = verification.claims.clone();
+                self.verification = Some(verification);
+            }
+            Err(err) => self.error = err.to_string(),
+        }
+    }
+
+    /// 切换签发模式，进入时用当前解码结果填充 Header 和 Payload
+    fn set_signing(&mut self, signing: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if signing && !self.signing && self.decoded != "{}" {
+            let (header, payload) = (self.header.clone(), self.payload.clone());
+            self.header_state.update(cx, |state, cx| {
+                state.set_value(header, window, cx);
+            });
+            self.payload_state.update(cx, |state, cx| {
+                state.set_value(payload, window, cx);
+            });
+        }
+        self.signing = signing;
+        self.error.clear();
+        cx.notify();
+    }
+
+    fn sign(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let header = self.header_state.read(cx).value().to_string();
+        let payload = self.payload_state.read(cx).value().to_string();
+        match base::sign_jwt(&header, &payload, &self.key) {
+            Ok(token) => {
+                self.token = token.clone();
+                self.token_state.update(cx, |state, cx| {
+                    state.set_value(token, window, cx);
+                });
+                self.decode();
+            }
+            Err(err) => self.error = err.to_string(),
+        }
+        cx.notify();
+    }
+
     fn paste(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(item) = cx.read_from_clipboard() {
             if let Some(text) = item.text() {
@@ -94,6 +195,8 @@ impl JwtDecoder {
         self.payload = "{}".to_string();
         self.decoded = "{}".to_string();
         self.error.clear();
+        self.verification = None;
+        self.claims.clear();
         self.token_state.update(cx, |state, cx| {
             state.set_value(String::new(), window, cx);
         });
@@ -103,10 +206,31 @@ impl JwtDecoder {
 impl Render for JwtDecoder {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let token_empty = self.token.trim().is_empty();
+        let key_empty = self.key.trim().is_empty();
+        let signing = self.signing;
         div()
             .flex()
             .flex_col()
             .gap_4()
+            .child(
+                ButtonGroup::new("jwt-mode")
+                    .child(
+                        Button::new("mode-decode")
+                            .label("解码 / 验证")
+                            .selected(!signing)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_signing(false, window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("mode-sign")
+                            .label("签发")
+                            .selected(signing)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_signing(true, window, cx);
+                            })),
+                    ),
+            )
             .child(Input::new(&self.token_state).h(px(130.0)))
             .child(
                 div()
@@ -141,6 +265,29 @@ impl Render for JwtDecoder {
                             })),
                     ),
             )
+            .child(Input::new(&self.key_state).h(px(100.0)))
+            .child(if signing {
+                Button::new("sign")
+                    .primary()
+                    .disabled(key_empty)
+                    .label("签发")
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.sign(window, cx);
+                    }))
+            } else {
+                Button::new("verify")
+                    .primary()
+                    .disabled(token_empty || key_empty)
+                    .label(if is_jwe(&self.token) {
+                        "解密"
+                    } else {
+                        "验证签名"
+                    })
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.verify();
+                        cx.notify();
+                    }))
+            })
             .when(!self.error.is_empty(), |this| {
                 this.child(
                     div()
@@ -149,6 +296,32 @@ impl Render for JwtDecoder {
                         .child(self.error.clone()),
                 )
             })
+            .when(!signing, |this| this.child(validity_panel(self, cx)))
+            .when(signing, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .gap_4()
+                        .child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .flex_col()
+                                .gap_2()
+                                .child(div().text_sm().font_semibold().child("Header"))
+                                .child(Input::new(&self.header_state).h(px(200.0))),
+                        )
+                        .child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .flex_col()
+                                .gap_2()
+                                .child(div().text_sm().font_semibold().child("Payload"))
+                                .child(Input::new(&self.payload_state).h(px(200.0))),
+                        ),
+                )
+            })
             .child(
                 div()
                     .flex()
@@ -175,6 +348,45 @@ impl Render for JwtDecoder {
     }
 }
 
+/// 5 段的 Token 为 JWE
+fn is_jwe(token: &str) -> bool {
+    token.trim().split('.').count() == 5
+}
+
+/// 签名验证结果和 `exp`、`nbf`、`iat` 的有效性
+fn validity_panel(this: &JwtDecoder, cx: &mut Context<JwtDecoder>) -> Div {
+    let mut panel = div().flex().flex_col().gap_1().text_sm();
+    if let Some(verification) = &this.verification {
+        let (text, color) = if verification.signature_valid {
+            ("有效", cx.theme().success)
+        } else {
+            ("无效", cx.theme().danger)
+        };
+        panel = panel.child(
+            div()
+                .text_color(color)
+                .child(format!("签名 ({}): {text}", verification.algorithm)),
+        );
+    }
+    for claim in &this.claims {
+        let (text, color) = match claim.status {
+            base::ClaimStatus::Valid => ("有效", cx.theme().success),
+            base::ClaimStatus::Expired => ("已过期", cx.theme().danger),
+            base::ClaimStatus::NotYetValid => ("尚未生效", cx.theme().warning),
+            base::ClaimStatus::IssuedInFuture => ("签发时间晚于当前时间", cx.theme().warning),
+        };
+        panel = panel.child(
+            div()
+                .flex()
+                .gap_2()
+                .child(div().font_family("monospace").child(claim.name.clone()))
+                .child(format!("{} UTC", claim.time))
+                .child(div().text_color(color).child(text)),
+        );
+    }
+    panel
+}
+
 fn json_panel(
     title: &'static str,
     id: &'static str,
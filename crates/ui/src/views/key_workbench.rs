@@ -0,0 +1,387 @@
+use gpui::{prelude::FluentBuilder as _, *};
+use gpui_component::{
+    button::*,
+    checkbox::Checkbox,
+    input::{Input, InputEvent, InputState},
+    *,
+};
+
+const ALGORITHMS: [(base::KeyAlgorithm, &str); 5] = [
+    (base::KeyAlgorithm::Rsa, "RSA"),
+    (base::KeyAlgorithm::P256, "P-256"),
+    (base::KeyAlgorithm::P384, "P-384"),
+    (base::KeyAlgorithm::Ed25519, "Ed25519"),
+    (base::KeyAlgorithm::X25519, "X25519"),
+];
+
+const RSA_BITS: [usize; 3] = [2048, 3072, 4096];
+
+const FORMATS: [(base::KeyFormat, &str); 6] = [
+    (base::KeyFormat::Pkcs1, "PKCS#1"),
+    (base::KeyFormat::Pkcs8, "PKCS#8"),
+    (base::KeyFormat::Sec1, "SEC1"),
+    (base::KeyFormat::Spki, "SPKI"),
+    (base::KeyFormat::Jwk, "JWK"),
+    (base::KeyFormat::OpenSsh, "OpenSSH"),
+];
+
+pub struct KeyWorkbench {
+    algorithm: base::KeyAlgorithm,
+    rsa_bits: usize,
+    format: base::KeyFormat,
+    public_only: bool,
+    der: bool,
+    is_generating: bool,
+    /// 后台生成的密钥对，等下一次渲染时回填到输入框
+    generated: Option<base::KeyPair>,
+    /// 当前密钥的识别结果，输入为空时为 `None`
+    info: Option<Result<base::KeyInfo, String>>,
+    error: String,
+    /// 签名校验结果，`None` 表示尚未校验
+    verified: Option<bool>,
+    key_state: Entity<InputState>,
+    output_state: Entity<InputState>,
+    message_state: Entity<InputState>,
+    signature_state: Entity<InputState>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl KeyWorkbench {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let key_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("PEM、Base64/Hex 编码的 DER、JWK 或 OpenSSH 格式的私钥或公钥")
+                .multi_line(true)
+        });
+        let output_state = cx.new(|cx| InputState::new(window, cx).multi_line(true));
+        let message_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("待签名或待校验的文本")
+                .multi_line(true)
+        });
+        let signature_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Base64 编码的签名"));
+        let _subscriptions = vec![
+            cx.subscribe_in(&key_state, window, {
+                let key_state = key_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        let key = key_state.read(cx).value().to_string();
+                        this.inspect(&key, cx);
+                    }
+                }
+            }),
+            cx.subscribe_in(&signature_state, window, |this, _, ev: &InputEvent, _, cx| {
+                if let InputEvent::Change = ev {
+                    this.verified = None;
+                    cx.notify();
+                }
+            }),
+        ];
+
+        Self {
+            algorithm: base::KeyAlgorithm::default(),
+            rsa_bits: RSA_BITS[0],
+            format: base::KeyFormat::default(),
+            public_only: false,
+            der: false,
+            is_generating: false,
+            generated: None,
+            info: None,
+            error: String::new(),
+            verified: None,
+            key_state,
+            output_state,
+            message_state,
+            signature_state,
+            _subscriptions,
+        }
+    }
+
+    fn inspect(&mut self, key: &str, cx: &mut Context<Self>) {
+        self.info = if key.trim().is_empty() {
+            None
+        } else {
+            Some(base::key_inspect(key).map_err(|e| e.to_string()))
+        };
+        self.verified = None;
+        cx.notify();
+    }
+
+    fn generate(&mut self, cx: &mut Context<Self>) {
+        let (algorithm, rsa_bits) = (self.algorithm, self.rsa_bits);
+        self.is_generating = true;
+        self.error.clear();
+        cx.notify();
+
+        // RSA 4096 的素数搜索可能需要数秒，放到后台线程
+        let task = cx
+            .background_executor()
+            .spawn(async move { base::key_generate(algorithm, rsa_bits) });
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            let result = task.await;
+            let _ = this.update(cx, |this, cx| {
+                match result {
+                    Ok(pair) => this.generated = Some(pair),
+                    Err(err) => this.error = err.to_string(),
+                }
+                this.is_generating = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn key(&self, cx: &App) -> String {
+        self.key_state.read(cx).value().to_string()
+    }
+
+    fn convert(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let options = base::KeyConvertOptions {
+            format: self.format,
+            public: self.public_only,
+            der: self.der,
+        };
+        match base::key_convert(&self.key(cx), &options) {
+            Ok(output) => {
+                self.error.clear();
+                self.output_state.update(cx, |state, cx| {
+                    state.set_value(output, window, cx);
+                });
+            }
+            Err(err) => self.error = err.to_string(),
+        }
+        cx.notify();
+    }
+
+    fn sign(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let message = self.message_state.read(cx).value().to_string();
+        match base::key_sign(&self.key(cx), &message) {
+            Ok(signature) => {
+                self.error.clear();
+                self.signature_state.update(cx, |state, cx| {
+                    state.set_value(signature, window, cx);
+                });
+            }
+            Err(err) => self.error = err.to_string(),
+        }
+        cx.notify();
+    }
+
+    fn verify(&mut self, cx: &mut Context<Self>) {
+        let message = self.message_state.read(cx).value().to_string();
+        let signature = self.signature_state.read(cx).value().to_string();
+        match base::key_verify(&self.key(cx), &message, &signature) {
+            Ok(valid) => {
+                self.error.clear();
+                self.verified = Some(valid);
+            }
+            Err(err) => self.error = err.to_string(),
+        }
+        cx.notify();
+    }
+}
+
+impl Render for KeyWorkbench {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if let Some(pair) = self.generated.take() {
+            self.key_state.update(cx, |state, cx| {
+                state.set_value(pair.private_key.clone(), window, cx);
+            });
+            self.output_state.update(cx, |state, cx| {
+                state.set_value(pair.public_key, window, cx);
+            });
+            self.inspect(&pair.private_key, cx);
+        }
+        let algorithm = self.algorithm;
+        let rsa_bits = self.rsa_bits;
+        let format = self.format;
+        let key_info = self.info.as_ref().and_then(|info| info.as_ref().ok());
+        let can_sign = key_info.is_some_and(|info| info.algorithm.signature_scheme().is_some());
+        let is_private = key_info.is_some_and(|info| info.private);
+        let supports_der = !matches!(format, base::KeyFormat::Jwk | base::KeyFormat::OpenSsh);
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap_2()
+                    .child(ALGORITHMS.into_iter().fold(
+                        ButtonGroup::new("key-algorithm"),
+                        |group, (value, label)| {
+                            group.child(
+                                Button::new(label)
+                                    .label(label)
+                                    .selected(algorithm == value)
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.algorithm = value;
+                                        cx.notify();
+                                    })),
+                            )
+                        },
+                    ))
+                    .when(algorithm == base::KeyAlgorithm::Rsa, |this| {
+                        this.child(RSA_BITS.into_iter().fold(
+                            ButtonGroup::new("key-rsa-bits"),
+                            |group, bits| {
+                                group.child(
+                                    Button::new(SharedString::from(format!("rsa-{bits}")))
+                                        .label(format!("{bits} 位"))
+                                        .selected(rsa_bits == bits)
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.rsa_bits = bits;
+                                            cx.notify();
+                                        })),
+                                )
+                            },
+                        ))
+                    })
+                    .child(
+                        Button::new("generate")
+                            .primary()
+                            .disabled(self.is_generating)
+                            .label(if self.is_generating {
+                                "生成中..."
+                            } else {
+                                "生成密钥对"
+                            })
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.generate(cx);
+                            })),
+                    ),
+            )
+            .child(Input::new(&self.key_state).h(px(180.0)))
+            .when_some(self.info.as_ref(), |this, info| match info {
+                Ok(info) => this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .text_sm()
+                        .child(format!(
+                            "{} {} 位 · {} · {}",
+                            info.algorithm.name(),
+                            info.bits,
+                            if info.private { "私钥" } else { "公钥" },
+                            info.format
+                        ))
+                        .children(info.fingerprints.iter().map(|(name, value)| {
+                            div()
+                                .flex()
+                                .gap_2()
+                                .child(
+                                    div()
+                                        .w(px(100.0))
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(name.clone()),
+                                )
+                                .child(div().font_family("monospace").child(value.clone()))
+                        })),
+                ),
+                Err(err) => this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(err.clone()),
+                ),
+            })
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap_2()
+                    .child(FORMATS.into_iter().fold(
+                        ButtonGroup::new("key-format"),
+                        |group, (value, label)| {
+                            group.child(
+                                Button::new(label)
+                                    .label(label)
+                                    .selected(format == value)
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.format = value;
+                                        cx.notify();
+                                    })),
+                            )
+                        },
+                    ))
+                    .child(
+                        Checkbox::new("public-only")
+                            .label("只导出公钥")
+                            .checked(self.public_only)
+                            .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                this.public_only = *v;
+                                cx.notify();
+                            })),
+                    )
+                    .when(supports_der, |this| {
+                        this.child(
+                            Checkbox::new("der")
+                                .label("DER（Base64）")
+                                .checked(self.der)
+                                .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                    this.der = *v;
+                                    cx.notify();
+                                })),
+                        )
+                    })
+                    .child(Button::new("convert").label("转换").on_click(cx.listener(
+                        |this, _, window, cx| {
+                            this.convert(window, cx);
+                        },
+                    )))
+                    .child(Button::new("copy-output").label("复制").on_click(cx.listener(
+                        |this, _, _, cx| {
+                            let output = this.output_state.read(cx).value().to_string();
+                            cx.write_to_clipboard(ClipboardItem::new_string(output));
+                        },
+                    ))),
+            )
+            .child(Input::new(&self.output_state).h(px(180.0)))
+            .child(Input::new(&self.message_state).h(px(80.0)))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().flex_1().child(Input::new(&self.signature_state)))
+                    .child(
+                        Button::new("sign")
+                            .disabled(!can_sign || !is_private)
+                            .label("签名")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.sign(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("verify")
+                            .disabled(!can_sign)
+                            .label("验证")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.verify(cx);
+                            })),
+                    ),
+            )
+            .when_some(self.verified, |this, valid| {
+                let (text, color) = if valid {
+                    ("签名有效", cx.theme().success)
+                } else {
+                    ("签名无效", cx.theme().danger)
+                };
+                this.child(div().text_sm().text_color(color).child(text))
+            })
+            .when(!self.error.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(self.error.clone()),
+                )
+            })
+    }
+}
@@ -1,8 +1,12 @@
 pub mod base64_encoder;
 pub mod base_converter;
+pub mod binary_encoder;
+pub mod cert_decoder;
 pub mod charset_encoder;
+pub mod cipher_tool;
 pub mod clipboard_manager;
 pub mod code_snippet;
+pub mod cron_expression;
 pub mod database_diff;
 pub mod excalidraw;
 pub mod fake_data_generator;
@@ -11,8 +15,10 @@ pub mod hash_calculator;
 pub mod ip_converter;
 pub mod json_editor;
 pub mod jwt_decoder;
+pub mod key_workbench;
 pub mod markdown_editor;
 pub mod messy_code_recover;
+pub mod password_hasher;
 pub mod qrcode_generator;
 pub mod regex_visualizer;
 pub mod settings;
@@ -23,15 +29,20 @@ pub mod timestamp_converter;
 pub mod todo_list;
 pub mod transform_filetype;
 pub mod transform_openapi;
+pub mod type_generator;
 pub mod url_encoder;
 pub mod uuid_generator;
 pub mod xml_formatter;
 
 pub use base_converter::BaseConverter;
 pub use base64_encoder::Base64Encoder;
+pub use binary_encoder::BinaryEncoder;
+pub use cert_decoder::CertDecoder;
 pub use charset_encoder::CharsetEncoder;
+pub use cipher_tool::CipherTool;
 pub use clipboard_manager::ClipboardManager;
 pub use code_snippet::CodeSnippet;
+pub use cron_expression::CronExpression;
 pub use database_diff::DatabaseDiff;
 pub use excalidraw::ExcalidrawView;
 pub use fake_data_generator::FakeDataGenerator;
@@ -40,8 +51,10 @@ pub use hash_calculator::HashCalculator;
 pub use ip_converter::IpConverter;
 pub use json_editor::JsonEditor;
 pub use jwt_decoder::JwtDecoder;
+pub use key_workbench::KeyWorkbench;
 pub use markdown_editor::MarkdownEditor;
 pub use messy_code_recover::MessyCodeRecover;
+pub use password_hasher::PasswordHasher;
 pub use qrcode_generator::QrCodeGenerator;
 pub use regex_visualizer::RegexVisualizer;
 pub use settings::SettingsView;
@@ -51,6 +64,7 @@ pub use timestamp_converter::TimestampConverter;
 pub use todo_list::TodoList;
 pub use transform_filetype::TransformFiletype;
 pub use transform_openapi::TransformOpenapi;
+pub use type_generator::TypeGenerator;
 pub use url_encoder::UrlEncoder;
 pub use uuid_generator::UuidGenerator;
 pub use xml_formatter::XmlFormatter;
@@ -0,0 +1,331 @@
+use gpui::{prelude::FluentBuilder as _, *};
+use gpui_component::{
+    button::*,
+    input::{Input, InputEvent, InputState},
+    *,
+};
+
+const ALGORITHMS: [(base::PasswordAlgorithm, &str); 6] = [
+    (base::PasswordAlgorithm::Argon2id, "Argon2id"),
+    (base::PasswordAlgorithm::Argon2i, "Argon2i"),
+    (base::PasswordAlgorithm::Argon2d, "Argon2d"),
+    (base::PasswordAlgorithm::Bcrypt, "bcrypt"),
+    (base::PasswordAlgorithm::Scrypt, "scrypt"),
+    (base::PasswordAlgorithm::Pbkdf2Sha256, "PBKDF2-SHA256"),
+];
+
+pub struct PasswordHasher {
+    algorithm: base::PasswordAlgorithm,
+    password: String,
+    hash: String,
+    error: String,
+    /// 校验结果，`None` 表示尚未校验
+    verification: Option<base::PasswordVerification>,
+    is_running: bool,
+    password_state: Entity<InputState>,
+    hash_state: Entity<InputState>,
+    /// 当前算法的代价参数，含义随算法变化
+    cost_state: Entity<InputState>,
+    /// Argon2 的迭代次数
+    iterations_state: Entity<InputState>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl PasswordHasher {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let defaults = base::PasswordOptions::default();
+        let password_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("请输入密码")
+                .masked(true)
+        });
+        let hash_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("生成的哈希，或粘贴已有哈希后校验")
+                .multi_line(true)
+        });
+        let cost_state = cx.new(|cx| {
+            InputState::new(window, cx).default_value(defaults.argon2_memory.to_string())
+        });
+        let iterations_state = cx.new(|cx| {
+            InputState::new(window, cx).default_value(defaults.argon2_iterations.to_string())
+        });
+        let _subscriptions = vec![
+            cx.subscribe_in(&password_state, window, {
+                let password_state = password_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.password = password_state.read(cx).value().to_string();
+                        this.verification = None;
+                        cx.notify();
+                    }
+                }
+            }),
+            cx.subscribe_in(&hash_state, window, {
+                let hash_state = hash_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.hash = hash_state.read(cx).value().to_string();
+                        this.verification = None;
+                        cx.notify();
+                    }
+                }
+            }),
+        ];
+
+        Self {
+            algorithm: defaults.algorithm,
+            password: String::new(),
+            hash: String::new(),
+            error: String::new(),
+            verification: None,
+            is_running: false,
+            password_state,
+            hash_state,
+            cost_state,
+            iterations_state,
+            _subscriptions,
+        }
+    }
+
+    /// 切换算法时把代价参数重置为该算法的默认值
+    fn set_algorithm(
+        &mut self,
+        algorithm: base::PasswordAlgorithm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let defaults = base::PasswordOptions::default();
+        let cost = match algorithm {
+            base::PasswordAlgorithm::Bcrypt => defaults.bcrypt_cost,
+            base::PasswordAlgorithm::Scrypt => defaults.scrypt_log_n.into(),
+            base::PasswordAlgorithm::Pbkdf2Sha256 => defaults.pbkdf2_rounds,
+            _ => defaults.argon2_memory,
+        };
+        self.cost_state.update(cx, |state, cx| {
+            state.set_value(cost.to_string(), window, cx);
+        });
+        self.algorithm = algorithm;
+        self.error.clear();
+        cx.notify();
+    }
+
+    fn options(&self, cx: &App) -> Result<base::PasswordOptions, String> {
+        let parse = |state: &Entity<InputState>, name: &str| {
+            state
+                .read(cx)
+                .value()
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("{name}必须是正整数"))
+        };
+        let mut options = base::PasswordOptions {
+            algorithm: self.algorithm,
+            ..Default::default()
+        };
+        match self.algorithm {
+            base::PasswordAlgorithm::Bcrypt => {
+                options.bcrypt_cost = parse(&self.cost_state, "cost")?;
+            }
+            base::PasswordAlgorithm::Scrypt => {
+                options.scrypt_log_n = parse(&self.cost_state, "log₂N")?
+                    .try_into()
+                    .map_err(|_| "log₂N 过大".to_string())?;
+            }
+            base::PasswordAlgorithm::Pbkdf2Sha256 => {
+                options.pbkdf2_rounds = parse(&self.cost_state, "迭代次数")?;
+            }
+            _ => {
+                options.argon2_memory = parse(&self.cost_state, "内存")?;
+                options.argon2_iterations = parse(&self.iterations_state, "迭代次数")?;
+            }
+        }
+        Ok(options)
+    }
+
+    fn generate(&mut self, cx: &mut Context<Self>) {
+        let options = match self.options(cx) {
+            Ok(options) => options,
+            Err(err) => {
+                self.error = err;
+                cx.notify();
+                return;
+            }
+        };
+        let password = self.password.clone();
+        self.is_running = true;
+        self.error.clear();
+        self.verification = None;
+        cx.notify();
+
+        // 代价较高的算法需要数百毫秒，放到后台线程计算
+        let task = cx
+            .background_executor()
+            .spawn(async move { base::hash_password(&password, &options) });
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            let result = task.await;
+            let _ = this.update(cx, |this, cx| {
+                match result {
+                    Ok(hash) => this.hash = hash,
+                    Err(err) => this.error = err.to_string(),
+                }
+                this.is_running = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn verify(&mut self, cx: &mut Context<Self>) {
+        let password = self.password.clone();
+        let hash = self.hash.clone();
+        self.is_running = true;
+        self.error.clear();
+        cx.notify();
+
+        let task = cx
+            .background_executor()
+            .spawn(async move { base::verify_password(&password, &hash) });
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            let result = task.await;
+            let _ = this.update(cx, |this, cx| {
+                match result {
+                    Ok(verification) => this.verification = Some(verification),
+                    Err(err) => this.error = err.to_string(),
+                }
+                this.is_running = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+}
+
+impl Render for PasswordHasher {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // 生成的哈希需要回填到输入框
+        if self.hash_state.read(cx).value() != self.hash {
+            let hash = self.hash.clone();
+            self.hash_state.update(cx, |state, cx| {
+                state.set_value(hash, window, cx);
+            });
+        }
+        let algorithm = self.algorithm;
+        let is_argon2 = matches!(
+            algorithm,
+            base::PasswordAlgorithm::Argon2id
+                | base::PasswordAlgorithm::Argon2i
+                | base::PasswordAlgorithm::Argon2d
+        );
+        let cost_label = match algorithm {
+            base::PasswordAlgorithm::Bcrypt => "cost",
+            base::PasswordAlgorithm::Scrypt => "log₂N",
+            base::PasswordAlgorithm::Pbkdf2Sha256 => "迭代次数",
+            _ => "内存 (KiB)",
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(ALGORITHMS.into_iter().fold(
+                ButtonGroup::new("password-algorithm"),
+                |group, (value, label)| {
+                    group.child(
+                        Button::new(value.name())
+                            .label(label)
+                            .selected(algorithm == value)
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.set_algorithm(value, window, cx);
+                            })),
+                    )
+                },
+            ))
+            .child(Input::new(&self.password_state))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().child(cost_label))
+                    .child(div().w(px(160.0)).child(Input::new(&self.cost_state)))
+                    .when(is_argon2, |this| {
+                        this.child(div().text_sm().child("迭代次数"))
+                            .child(div().w(px(100.0)).child(Input::new(&self.iterations_state)))
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        Button::new("generate")
+                            .primary()
+                            .disabled(self.is_running)
+                            .label(if self.is_running {
+                                "计算中..."
+                            } else {
+                                "生成"
+                            })
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.generate(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("verify")
+                            .disabled(self.is_running || self.hash.trim().is_empty())
+                            .label("校验")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.verify(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("copy-hash")
+                            .disabled(self.hash.is_empty())
+                            .label("复制")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                cx.write_to_clipboard(ClipboardItem::new_string(this.hash.clone()));
+                            })),
+                    ),
+            )
+            .child(Input::new(&self.hash_state).h(px(100.0)))
+            .when(!self.error.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(self.error.clone()),
+                )
+            })
+            .when_some(self.verification.as_ref(), |this, verification| {
+                let (text, color) = if verification.valid {
+                    ("匹配", cx.theme().success)
+                } else {
+                    ("不匹配", cx.theme().danger)
+                };
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .text_sm()
+                        .child(div().text_color(color).child(format!(
+                            "{} ({})",
+                            text,
+                            verification.algorithm.name()
+                        )))
+                        .children(verification.params.iter().map(|(key, value)| {
+                            div()
+                                .flex()
+                                .gap_2()
+                                .child(
+                                    div()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(key.clone()),
+                                )
+                                .child(div().font_family("monospace").child(value.clone()))
+                        })),
+                )
+            })
+    }
+}
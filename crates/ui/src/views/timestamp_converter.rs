@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use gpui::*;
+use gpui::{prelude::FluentBuilder as _, *};
 use gpui_component::{
     button::*,
     input::{Input, InputEvent, InputState},
@@ -8,12 +8,36 @@ use gpui_component::{
 };
 use time::OffsetDateTime;
 
+/// 默认展示的时区
+const DEFAULT_ZONES: [&str; 5] = [
+    "UTC",
+    "Asia/Shanghai",
+    "Asia/Tokyo",
+    "Europe/London",
+    "America/New_York",
+];
+
+/// 时间戳单位选项，`None` 为按位数识别
+const UNITS: [(Option<&str>, &str); 5] = [
+    (None, "自动"),
+    (Some("s"), "秒"),
+    (Some("ms"), "毫秒"),
+    (Some("us"), "微秒"),
+    (Some("ns"), "纳秒"),
+];
+
 pub struct TimestampConverter {
     input: String,
-    timestamp: String,
-    datetime: String,
+    unit: Option<&'static str>,
+    /// 不带时区的时间字符串所在的时区
+    input_zone: String,
+    zones: Vec<String>,
+    conversion: Option<base::TimeConversion>,
+    error: String,
     current_time: String,
     input_state: Entity<InputState>,
+    input_zone_state: Entity<InputState>,
+    zone_state: Entity<InputState>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -30,16 +54,46 @@ impl TimestampConverter {
                 .placeholder("请输入时间戳或日期时间...")
                 .multi_line(false)
         });
-        let _subscriptions = vec![cx.subscribe_in(&input_state, window, {
-            let input_state = input_state.clone();
-            move |this, _, ev: &InputEvent, _, cx| {
-                if let InputEvent::Change = ev {
-                    this.input = input_state.read(cx).value().to_string();
-                    this.convert();
-                    cx.notify();
+        let input_zone_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("输入时区，如 Asia/Shanghai")
+                .default_value("UTC")
+        });
+        let zone_state = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("添加时区，如 Europe/Berlin，回车确认")
+        });
+        let _subscriptions = vec![
+            cx.subscribe_in(&input_state, window, {
+                let input_state = input_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.input = input_state.read(cx).value().to_string();
+                        this.convert();
+                        cx.notify();
+                    }
                 }
-            }
-        })];
+            }),
+            cx.subscribe_in(&input_zone_state, window, {
+                let input_zone_state = input_zone_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.input_zone = input_zone_state.read(cx).value().trim().to_string();
+                        this.convert();
+                        cx.notify();
+                    }
+                }
+            }),
+            cx.subscribe_in(&zone_state, window, {
+                let zone_state = zone_state.clone();
+                move |this, _, ev: &InputEvent, window, cx| {
+                    if let InputEvent::PressEnter { .. } = ev {
+                        let zone = zone_state.read(cx).value().trim().to_string();
+                        this.add_zone(zone, window, cx);
+                        cx.notify();
+                    }
+                }
+            }),
+        ];
 
         let current_time = format_now_timestamp();
         cx.spawn(async move |this: WeakEntity<Self>, cx| {
@@ -55,39 +109,68 @@ impl TimestampConverter {
 
         Self {
             input: String::new(),
-            timestamp: String::new(),
-            datetime: String::new(),
+            unit: None,
+            input_zone: "UTC".to_string(),
+            zones: DEFAULT_ZONES.iter().map(|z| z.to_string()).collect(),
+            conversion: None,
+            error: String::new(),
             current_time,
             input_state,
+            input_zone_state,
+            zone_state,
             _subscriptions,
         }
     }
 
     fn convert(&mut self) {
-        if self.input.is_empty() {
-            self.timestamp.clear();
-            self.datetime.clear();
+        self.error.clear();
+        if self.input.trim().is_empty() {
+            self.conversion = None;
+            return;
+        }
+
+        let zones = self.zones.iter().map(String::as_str).collect::<Vec<_>>();
+        let input_zone = (!self.input_zone.is_empty()).then_some(self.input_zone.as_str());
+        match base::convert_time(&self.input, self.unit, input_zone, &zones) {
+            Ok(conversion) => self.conversion = Some(conversion),
+            Err(e) => {
+                self.conversion = None;
+                self.error = e.to_string();
+            }
+        }
+    }
+
+    fn set_unit(&mut self, unit: Option<&'static str>) {
+        self.unit = unit;
+        self.convert();
+    }
+
+    fn add_zone(&mut self, zone: String, window: &mut Window, cx: &mut Context<Self>) {
+        if zone.is_empty() || self.zones.contains(&zone) {
+            return;
+        }
+        if !base::time_zones().contains(&zone.as_str()) {
+            self.error = format!("未知的时区: {zone}");
             return;
         }
+        self.zones.push(zone);
+        self.convert();
+        self.zone_state.update(cx, |state, cx| {
+            state.set_value(String::new(), window, cx);
+        });
+    }
 
-        if let Ok(_ts) = self.input.parse::<i64>() {
-            self.timestamp = self.input.clone();
-            self.datetime = base::timestamp(Some(&self.input))
-                .map(|m| m.get("format").unwrap_or(&String::new()).clone())
-                .unwrap_or_default();
-        } else if let Ok(result) = base::timestamp(Some(&self.input)) {
-            self.timestamp = result.get("format").unwrap_or(&String::new()).clone();
-            self.datetime = self.input.clone();
-        } else {
-            self.timestamp.clear();
-            self.datetime.clear();
+    fn remove_zone(&mut self, index: usize) {
+        if index < self.zones.len() {
+            self.zones.remove(index);
+            self.convert();
         }
     }
 
     fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.input.clear();
-        self.timestamp.clear();
-        self.datetime.clear();
+        self.conversion = None;
+        self.error.clear();
         self.input_state.update(cx, |state, cx| {
             state.set_value(String::new(), window, cx);
         });
@@ -104,18 +187,119 @@ impl TimestampConverter {
             }
         }
     }
+
+    fn readonly(value: String, cx: &Context<Self>) -> Div {
+        div()
+            .flex_1()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .px_2()
+            .py_1()
+            .text_sm()
+            .font_family("monospace")
+            .child(value)
+    }
+
+    fn copy_button(id: impl Into<ElementId>, value: String, cx: &Context<Self>) -> Button {
+        Button::new(id)
+            .icon(Icon::new(IconName::Copy))
+            .tooltip("复制")
+            .on_click(cx.listener(move |_, _, _, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(value.clone()));
+            }))
+    }
+
+    /// 秒、毫秒、微秒、纳秒时间戳
+    fn timestamps(&self, conversion: &base::TimeConversion, cx: &Context<Self>) -> Div {
+        let rows = [
+            ("秒", conversion.seconds.to_string()),
+            ("毫秒", conversion.milliseconds.to_string()),
+            ("微秒", conversion.microseconds.to_string()),
+            ("纳秒", conversion.nanoseconds.clone()),
+        ];
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .children(rows.into_iter().enumerate().map(|(i, (label, value))| {
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(120.0)).text_sm().child(label))
+                    .child(Self::readonly(value.clone(), cx))
+                    .child(Self::copy_button(("copy-timestamp", i), value, cx))
+            }))
+    }
+
+    /// 同一时刻在各个时区的时间
+    fn zone_table(&self, conversion: &base::TimeConversion, cx: &Context<Self>) -> Div {
+        let header = div()
+            .flex()
+            .gap_2()
+            .px_2()
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .child(div().w(px(160.0)).child("时区"))
+            .child(div().w(px(220.0)).child("时间"))
+            .child(div().w(px(110.0)).child("偏移"))
+            .child(div().flex_1().child("RFC 3339"));
+
+        div().flex().flex_col().gap_1().child(header).children(
+            conversion.zones.iter().enumerate().map(|(i, zone)| {
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .rounded_md()
+                    .text_sm()
+                    .child(div().w(px(160.0)).child(zone.zone.clone()))
+                    .child(
+                        div()
+                            .w(px(220.0))
+                            .font_family("monospace")
+                            .child(zone.datetime.clone()),
+                    )
+                    .child(
+                        div()
+                            .w(px(110.0))
+                            .font_family("monospace")
+                            .child(format!("{} {}", zone.offset, zone.abbreviation)),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .font_family("monospace")
+                            .child(zone.rfc3339.clone()),
+                    )
+                    .child(Self::copy_button(
+                        ("copy-zone", i),
+                        zone.datetime.clone(),
+                        cx,
+                    ))
+                    .child(
+                        Button::new(("remove-zone", i))
+                            .icon(Icon::new(IconName::Close))
+                            .tooltip("移除")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.remove_zone(i);
+                                cx.notify();
+                            })),
+                    )
+            }),
+        )
+    }
 }
 
 impl Render for TimestampConverter {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let datetime = self.datetime.clone();
         let current_time = self.current_time.clone();
-
-        let datetime_text = if datetime.is_empty() {
-            "-".to_string()
-        } else {
-            datetime.clone()
-        };
+        let unit = self.unit;
 
         div().child(
             div()
@@ -129,36 +313,16 @@ impl Render for TimestampConverter {
                         .items_center()
                         .gap_2()
                         .child(div().w(px(120.0)).text_sm().child("当前时间"))
-                        .child(
-                            div()
-                                .flex_1()
-                                .border_1()
-                                .border_color(cx.theme().border)
-                                .rounded_md()
-                                .px_2()
-                                .py_1()
-                                .text_sm()
-                                .font_family("monospace")
-                                .child(current_time),
-                        )
-                        .child(
-                            Button::new("copy-current")
-                                .icon(Icon::new(IconName::Copy))
-                                .tooltip("复制")
-                                .on_click(cx.listener(|this, _, _, cx| {
-                                    cx.write_to_clipboard(ClipboardItem::new_string(
-                                        this.current_time.clone(),
-                                    ));
-                                })),
-                        ),
+                        .child(Self::readonly(current_time.clone(), cx))
+                        .child(Self::copy_button("copy-current", current_time, cx)),
                 )
-                // Row: 时间戳 → Input + Paste
+                // Row: 时间戳或时间 → Input + Paste
                 .child(
                     div()
                         .flex()
                         .items_center()
                         .gap_2()
-                        .child(div().w(px(120.0)).text_sm().child("时间戳"))
+                        .child(div().w(px(120.0)).text_sm().child("时间戳 / 时间"))
                         .child(div().flex_1().child(Input::new(&self.input_state)))
                         .child(
                             Button::new("paste")
@@ -170,35 +334,63 @@ impl Render for TimestampConverter {
                                 })),
                         ),
                 )
-                // Row: 时间 → readonly + Copy
+                // Row: 时间戳单位与输入时区
                 .child(
                     div()
                         .flex()
                         .items_center()
                         .gap_2()
-                        .child(div().w(px(120.0)).text_sm().child("时间"))
-                        .child(
+                        .child(div().w(px(120.0)).text_sm().child("单位"))
+                        .child(UNITS.into_iter().fold(
+                            ButtonGroup::new("timestamp-unit"),
+                            |group, (value, label)| {
+                                group.child(
+                                    Button::new(label)
+                                        .label(label)
+                                        .selected(unit == value)
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.set_unit(value);
+                                            cx.notify();
+                                        })),
+                                )
+                            },
+                        ))
+                        .child(div().text_sm().child("输入时区"))
+                        .child(div().w(px(220.0)).child(Input::new(&self.input_zone_state))),
+                )
+                .when(!self.error.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().danger)
+                            .child(self.error.clone()),
+                    )
+                })
+                .when_some(self.conversion.as_ref(), |this, conversion| {
+                    this.when_some(conversion.unit, |this, unit| {
+                        let label = match unit {
+                            base::TimeUnit::Seconds => "秒",
+                            base::TimeUnit::Milliseconds => "毫秒",
+                            base::TimeUnit::Microseconds => "微秒",
+                            base::TimeUnit::Nanoseconds => "纳秒",
+                        };
+                        this.child(
                             div()
-                                .flex_1()
-                                .border_1()
-                                .border_color(cx.theme().border)
-                                .rounded_md()
-                                .px_2()
-                                .py_1()
                                 .text_sm()
-                                .font_family("monospace")
-                                .child(datetime_text),
+                                .text_color(cx.theme().muted_foreground)
+                                .child(format!("按{label}解析")),
                         )
-                        .child(
-                            Button::new("copy-datetime")
-                                .icon(Icon::new(IconName::Copy))
-                                .tooltip("复制")
-                                .on_click(cx.listener(|this, _, _, cx| {
-                                    cx.write_to_clipboard(ClipboardItem::new_string(
-                                        this.datetime.clone(),
-                                    ));
-                                })),
-                        ),
+                    })
+                    .child(self.timestamps(conversion, cx))
+                    .child(self.zone_table(conversion, cx))
+                })
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(div().w(px(120.0)).text_sm().child("添加时区"))
+                        .child(div().w(px(280.0)).child(Input::new(&self.zone_state))),
                 ),
         )
     }
@@ -1,6 +1,15 @@
-use gpui::*;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use gpui::{prelude::FluentBuilder as _, *};
 use gpui_component::{
     button::*,
+    checkbox::Checkbox,
     input::{Input, InputEvent, InputState},
     select::{Select, SelectEvent, SelectState},
     *,
@@ -13,23 +22,59 @@ pub struct TransformFiletype {
     from_format: String,
     to_format: String,
     indent: u8,
+    /// 输出样式：制表符缩进、键排序、TOML 内联表、YAML 流式
+    use_tabs: bool,
+    sort_keys: bool,
+    toml_inline: bool,
+    yaml_flow: bool,
     is_converting: bool,
     error: String,
+    /// 源格式为自动识别时的识别结果
+    detection: Option<base::Detection>,
     from_format_state: Entity<SelectState<Vec<String>>>,
     to_format_state: Entity<SelectState<Vec<String>>>,
     indent_state: Entity<SelectState<Vec<String>>>,
     input_state: Entity<InputState>,
     output_state: Entity<InputState>,
+    xml_attr_prefix_state: Entity<InputState>,
+    xml_text_key_state: Entity<InputState>,
+    xml_root_state: Entity<InputState>,
+    /// 大文件模式的源/目标格式
+    stream_from: String,
+    stream_to: String,
+    stream_from_state: Entity<SelectState<Vec<String>>>,
+    stream_to_state: Entity<SelectState<Vec<String>>>,
+    is_streaming: bool,
+    /// 源文件大小与已读取的字节数，用于显示进度
+    stream_total: u64,
+    stream_read: Arc<AtomicU64>,
+    stream_status: String,
     _subscriptions: Vec<Subscription>,
 }
 
 impl TransformFiletype {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let format_items = vec!["JSON".to_string(), "YAML".to_string(), "TOML".to_string()];
+        let format_items = vec![
+            "JSON".to_string(),
+            "YAML".to_string(),
+            "TOML".to_string(),
+            "XML".to_string(),
+            "JSONC".to_string(),
+            "JSON5".to_string(),
+            "HJSON".to_string(),
+            "INI".to_string(),
+            "ENV".to_string(),
+            "PROPERTIES".to_string(),
+            "CSV".to_string(),
+        ];
 
+        // 源格式多一个自动识别
+        let from_items = std::iter::once("Auto".to_string())
+            .chain(format_items.iter().cloned())
+            .collect::<Vec<_>>();
         let from_format_state = cx.new(|cx| {
-            let mut state = SelectState::new(format_items.clone(), None, window, cx);
-            state.set_selected_value(&"JSON".to_string(), window, cx);
+            let mut state = SelectState::new(from_items, None, window, cx);
+            state.set_selected_value(&"Auto".to_string(), window, cx);
             state
         });
         let to_format_state = cx.new(|cx| {
@@ -48,7 +93,36 @@ impl TransformFiletype {
                 .multi_line(true)
         });
 
-        let indent_items = vec!["2".to_string(), "4".to_string()];
+        let xml_options = base::XmlOptions::default();
+        let xml_attr_prefix_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value(xml_options.attr_prefix.clone())
+                .placeholder("属性前缀，如 @")
+        });
+        let xml_text_key_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value(xml_options.text_key.clone())
+                .placeholder("文本节点键，如 #text")
+        });
+        let xml_root_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value(xml_options.root.clone())
+                .placeholder("根元素名，留空则取顶层唯一的键")
+        });
+
+        let stream_items = vec!["JSON".to_string(), "YAML".to_string(), "JSONL".to_string()];
+        let stream_from_state = cx.new(|cx| {
+            let mut state = SelectState::new(stream_items.clone(), None, window, cx);
+            state.set_selected_value(&"JSON".to_string(), window, cx);
+            state
+        });
+        let stream_to_state = cx.new(|cx| {
+            let mut state = SelectState::new(stream_items, None, window, cx);
+            state.set_selected_value(&"YAML".to_string(), window, cx);
+            state
+        });
+
+        let indent_items = vec!["2".to_string(), "4".to_string(), "Tab".to_string()];
         let indent_state = cx.new(|cx| {
             let mut state = SelectState::new(indent_items, None, window, cx);
             state.set_selected_value(&"2".to_string(), window, cx);
@@ -81,11 +155,32 @@ impl TransformFiletype {
                 window,
                 move |this, _, ev: &SelectEvent<Vec<String>>, _, cx| {
                     if let SelectEvent::Confirm(Some(value)) = ev {
+                        this.use_tabs = value == "Tab";
                         this.indent = value.parse::<u8>().unwrap_or(2);
                         cx.notify();
                     }
                 },
             ),
+            cx.subscribe_in(
+                &stream_from_state,
+                window,
+                move |this, _, ev: &SelectEvent<Vec<String>>, _, cx| {
+                    if let SelectEvent::Confirm(Some(value)) = ev {
+                        this.stream_from = value.to_lowercase();
+                        cx.notify();
+                    }
+                },
+            ),
+            cx.subscribe_in(
+                &stream_to_state,
+                window,
+                move |this, _, ev: &SelectEvent<Vec<String>>, _, cx| {
+                    if let SelectEvent::Confirm(Some(value)) = ev {
+                        this.stream_to = value.to_lowercase();
+                        cx.notify();
+                    }
+                },
+            ),
             cx.subscribe_in(&input_state, window, {
                 let input_state = input_state.clone();
                 move |this, _, _ev: &InputEvent, _window, cx| {
@@ -108,16 +203,32 @@ impl TransformFiletype {
             file_path: String::new(),
             input: String::new(),
             output: String::new(),
-            from_format: "json".to_string(),
+            from_format: "auto".to_string(),
             to_format: "yaml".to_string(),
             indent: 2,
+            use_tabs: false,
+            sort_keys: false,
+            toml_inline: false,
+            yaml_flow: false,
             is_converting: false,
             error: String::new(),
+            detection: None,
             from_format_state,
             to_format_state,
             indent_state,
             input_state,
             output_state,
+            xml_attr_prefix_state,
+            xml_text_key_state,
+            xml_root_state,
+            stream_from: "json".to_string(),
+            stream_to: "yaml".to_string(),
+            stream_from_state,
+            stream_to_state,
+            is_streaming: false,
+            stream_total: 0,
+            stream_read: Arc::new(AtomicU64::new(0)),
+            stream_status: String::new(),
             _subscriptions,
         }
     }
@@ -126,7 +237,23 @@ impl TransformFiletype {
         let task = cx.background_executor().spawn(async move {
             rfd::AsyncFileDialog::new()
                 .set_title("选择文件")
-                .add_filter("配置文件", &["json", "yaml", "yml", "toml"])
+                .add_filter(
+                    "配置文件",
+                    &[
+                        "json",
+                        "yaml",
+                        "yml",
+                        "toml",
+                        "xml",
+                        "jsonc",
+                        "json5",
+                        "hjson",
+                        "ini",
+                        "env",
+                        "properties",
+                        "csv",
+                    ],
+                )
                 .pick_file()
                 .await
         });
@@ -147,6 +274,113 @@ impl TransformFiletype {
         .detach();
     }
 
+    /// 大文件模式：选择源文件和保存位置后在后台流式转换，不经过输入框
+    fn stream_convert(&mut self, cx: &mut Context<Self>) {
+        if self.is_streaming {
+            return;
+        }
+        let from = self.stream_from.clone();
+        let to = self.stream_to.clone();
+        let indent = self.indent;
+        let read = Arc::new(AtomicU64::new(0));
+        self.stream_read = read.clone();
+        // 选择文件期间就禁用按钮，避免重复打开对话框
+        self.is_streaming = true;
+        self.stream_total = 0;
+        self.stream_status = "正在选择文件...".to_string();
+        cx.notify();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            let cancel = |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+                let _ = this.update(cx, |this, cx| {
+                    this.is_streaming = false;
+                    this.stream_status.clear();
+                    cx.notify();
+                });
+            };
+            let input = cx
+                .background_executor()
+                .spawn(async move {
+                    rfd::AsyncFileDialog::new()
+                        .set_title("选择源文件")
+                        .add_filter("数据文件", &["json", "yaml", "yml", "jsonl", "ndjson"])
+                        .pick_file()
+                        .await
+                })
+                .await;
+            let Some(input) = input else {
+                cancel(this, cx);
+                return;
+            };
+
+            let file_name = format!("output.{}", if to == "yaml" { "yml" } else { &to });
+            let output = cx
+                .background_executor()
+                .spawn(async move {
+                    rfd::AsyncFileDialog::new()
+                        .set_title("保存到")
+                        .set_file_name(file_name)
+                        .save_file()
+                        .await
+                })
+                .await;
+            let Some(output) = output else {
+                cancel(this, cx);
+                return;
+            };
+
+            let input_path = input.path().to_string_lossy().to_string();
+            let output_path = output.path().to_string_lossy().to_string();
+            let total = std::fs::metadata(&input_path)
+                .map(|m| m.len())
+                .unwrap_or_default();
+            let _ = this.update(cx, |this, cx| {
+                this.stream_total = total;
+                this.stream_status = format!("{input_path} → {output_path}");
+                this.watch_stream_progress(cx);
+                cx.notify();
+            });
+
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    base::cffc_stream(indent, &from, &to, &input_path, &output_path, &read)
+                })
+                .await;
+
+            let _ = this.update(cx, |this, cx| {
+                this.is_streaming = false;
+                this.stream_status = match result {
+                    Ok(()) => format!("转换完成: {}", this.stream_status),
+                    Err(e) => format!("转换失败: {e}"),
+                };
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// 转换期间定时刷新，让进度条跟上已读取的字节数
+    fn watch_stream_progress(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this: WeakEntity<Self>, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(Duration::from_millis(200))
+                    .await;
+                let Ok(streaming) = this.update(cx, |this, cx| {
+                    cx.notify();
+                    this.is_streaming
+                }) else {
+                    break;
+                };
+                if !streaming {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
     fn convert(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.input.trim().is_empty() {
             self.error = "请输入内容".to_string();
@@ -161,13 +395,40 @@ impl TransformFiletype {
         let from = self.from_format.clone();
         let to = self.to_format.clone();
         let input = self.input.clone();
-        let indent = self.indent;
+        let style = base::FormatOptions {
+            indent: self.indent,
+            use_tabs: self.use_tabs,
+            sort_keys: self.sort_keys,
+            toml_tables: if self.toml_inline {
+                base::TomlTables::Inline
+            } else {
+                base::TomlTables::Table
+            },
+            yaml_style: if self.yaml_flow {
+                base::YamlStyle::Flow
+            } else {
+                base::YamlStyle::Block
+            },
+        };
+        let xml = base::XmlOptions {
+            attr_prefix: self.xml_attr_prefix_state.read(cx).value().to_string(),
+            text_key: self.xml_text_key_state.read(cx).value().to_string(),
+            root: self.xml_root_state.read(cx).value().trim().to_string(),
+        };
 
         cx.spawn_in(window, async move |this: WeakEntity<Self>, cx| {
-            let result = base::cffc(indent, &from, &to, &input);
+            let detection = (from == "auto").then(|| base::cffc_detect(&input));
+            let result = match &detection {
+                Some(d) => match d.format {
+                    Some(ft) => base::cffc_with_options(&ft.to_string(), &to, &input, style, xml),
+                    None => Err(anyhow::Error::msg(d.error_message())),
+                },
+                None => base::cffc_with_options(&from, &to, &input, style, xml),
+            };
 
             let _ = this.update_in(cx, |this, window, cx| {
                 this.is_converting = false;
+                this.detection = detection;
                 match result {
                     Ok(output) => {
                         this.output = output.clone();
@@ -235,6 +496,12 @@ impl TransformFiletype {
         }
         self.error.clear();
 
+        if !self.resolve_auto_source() {
+            self.error = "未识别出源格式，无法反向转换".to_string();
+            cx.notify();
+            return;
+        }
+
         // 把输出灌入输入
         self.input = output.clone();
         self.input_state.update(cx, |state, cx| {
@@ -262,7 +529,26 @@ impl TransformFiletype {
         }
     }
 
+    /// 源格式为自动识别时换成识别出的格式，之后才能与目标格式交换
+    fn resolve_auto_source(&mut self) -> bool {
+        if self.from_format != "auto" {
+            return true;
+        }
+        match self.detection.as_ref().and_then(|d| d.format) {
+            Some(ft) => {
+                self.from_format = ft.to_string().to_lowercase();
+                true
+            }
+            None => false,
+        }
+    }
+
     fn swap_formats(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.resolve_auto_source() {
+            self.error = "未识别出源格式，无法交换".to_string();
+            cx.notify();
+            return;
+        }
         std::mem::swap(&mut self.from_format, &mut self.to_format);
 
         let from_label = self.from_format.to_uppercase();
@@ -283,6 +569,27 @@ impl Render for TransformFiletype {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let error = self.error.clone();
         let is_converting = self.is_converting;
+        let uses_xml = self.from_format == "xml" || self.to_format == "xml";
+        let detected = self
+            .detection
+            .as_ref()
+            .filter(|_| self.from_format == "auto")
+            .and_then(|d| {
+                d.format.map(|ft| {
+                    format!(
+                        "识别为 {}（可信度：{}）",
+                        ft.to_string().to_uppercase(),
+                        d.confidence
+                    )
+                })
+            });
+        let is_streaming = self.is_streaming;
+        let stream_read = self.stream_read.load(Ordering::Relaxed);
+        let stream_percent = if self.stream_total > 0 {
+            (stream_read as f32 / self.stream_total as f32).min(1.0)
+        } else {
+            0.0
+        };
 
         div().child(
             div()
@@ -298,6 +605,68 @@ impl Render for TransformFiletype {
                         .child(div().w(px(100.0)).text_sm().child("缩进"))
                         .child(Select::new(&self.indent_state)),
                 )
+                // Row: 输出样式 → Checkbox
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(div().w(px(100.0)).text_sm().child("输出样式"))
+                        .child(
+                            div()
+                                .flex()
+                                .flex_wrap()
+                                .gap_4()
+                                .child(
+                                    Checkbox::new("opt-sort-keys")
+                                        .label("按键名排序")
+                                        .checked(self.sort_keys)
+                                        .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                            this.sort_keys = *v;
+                                            cx.notify();
+                                        })),
+                                )
+                                .child(
+                                    Checkbox::new("opt-toml-inline")
+                                        .label("TOML 内联表")
+                                        .checked(self.toml_inline)
+                                        .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                            this.toml_inline = *v;
+                                            cx.notify();
+                                        })),
+                                )
+                                .child(
+                                    Checkbox::new("opt-yaml-flow")
+                                        .label("YAML 流式")
+                                        .checked(self.yaml_flow)
+                                        .on_click(cx.listener(|this, v: &bool, _, cx| {
+                                            this.yaml_flow = *v;
+                                            cx.notify();
+                                        })),
+                                ),
+                        ),
+                )
+                // Row: XML 约定，仅在源或目标为 XML 时显示
+                .when(uses_xml, |this| {
+                    this.child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(div().w(px(100.0)).text_sm().child("XML"))
+                            .child(
+                                div()
+                                    .w(px(120.0))
+                                    .child(Input::new(&self.xml_attr_prefix_state)),
+                            )
+                            .child(
+                                div()
+                                    .w(px(120.0))
+                                    .child(Input::new(&self.xml_text_key_state)),
+                            )
+                            .child(div().flex_1().child(Input::new(&self.xml_root_state))),
+                    )
+                })
                 // Row: 输入文件类型 → Select
                 .child(
                     div()
@@ -305,7 +674,15 @@ impl Render for TransformFiletype {
                         .items_center()
                         .gap_2()
                         .child(div().w(px(100.0)).text_sm().child("输入文件类型"))
-                        .child(Select::new(&self.from_format_state)),
+                        .child(Select::new(&self.from_format_state))
+                        .when_some(detected, |this, detected| {
+                            this.child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(detected),
+                            )
+                        }),
                 )
                 // Row: 操作 → Paste+Copy+Close (input)
                 .child(
@@ -447,7 +824,88 @@ impl Render for TransformFiletype {
                         .gap_2()
                         .child(div().w(px(100.0)).text_sm().child("输出"))
                         .child(Input::new(&self.output_state).h(px(200.0))),
-                ),
+                )
+                // Row: 大文件模式 → 源格式 + 目标格式 + 选择文件
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(div().w(px(100.0)).text_sm().child("大文件模式"))
+                        .child(
+                            div()
+                                .w(px(120.0))
+                                .child(Select::new(&self.stream_from_state)),
+                        )
+                        .child(Icon::new(IconName::ArrowRight))
+                        .child(div().w(px(120.0)).child(Select::new(&self.stream_to_state)))
+                        .child(
+                            Button::new("stream-convert")
+                                .label("选择文件并转换")
+                                .disabled(is_streaming)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.stream_convert(cx);
+                                })),
+                        ),
+                )
+                // Row: 进度 → 进度条 + 已读取/总大小
+                .when(is_streaming || !self.stream_status.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(div().w(px(100.0)).text_sm().child("进度"))
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .h_2()
+                                    .rounded_md()
+                                    .bg(cx.theme().border)
+                                    .child(
+                                        div()
+                                            .h_full()
+                                            .w(relative(stream_percent))
+                                            .rounded_md()
+                                            .bg(cx.theme().accent),
+                                    ),
+                            )
+                            .child(div().text_sm().child(format!(
+                                "{} / {} ({:.0}%)",
+                                format_size(stream_read),
+                                format_size(self.stream_total),
+                                stream_percent * 100.0
+                            ))),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(div().w(px(100.0)))
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(self.stream_status.clone()),
+                            ),
+                    )
+                }),
         )
     }
 }
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
@@ -0,0 +1,296 @@
+use gpui::*;
+use gpui_component::{
+    button::*,
+    input::{Input, InputEvent, InputState},
+    select::{Select, SelectEvent, SelectState},
+    *,
+};
+
+/// 根据 JSON/YAML/TOML 样本生成类型定义
+pub struct TypeGenerator {
+    input: String,
+    output: String,
+    from_format: String,
+    language: String,
+    is_generating: bool,
+    error: String,
+    from_format_state: Entity<SelectState<Vec<String>>>,
+    language_state: Entity<SelectState<Vec<String>>>,
+    root_name_state: Entity<InputState>,
+    input_state: Entity<InputState>,
+    output_state: Entity<InputState>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TypeGenerator {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let format_items = vec![
+            "Auto".to_string(),
+            "JSON".to_string(),
+            "YAML".to_string(),
+            "TOML".to_string(),
+        ];
+        let from_format_state = cx.new(|cx| {
+            let mut state = SelectState::new(format_items, None, window, cx);
+            state.set_selected_value(&"Auto".to_string(), window, cx);
+            state
+        });
+        let language_items = vec![
+            "Rust".to_string(),
+            "TypeScript".to_string(),
+            "Java".to_string(),
+            "Go".to_string(),
+            "JSON Schema".to_string(),
+        ];
+        let language_state = cx.new(|cx| {
+            let mut state = SelectState::new(language_items, None, window, cx);
+            state.set_selected_value(&"Rust".to_string(), window, cx);
+            state
+        });
+        let root_name_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value("Root")
+                .placeholder("根类型名称")
+        });
+        let input_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("请输入样本数据，多个样本之间用单独一行的 --- 分隔...")
+                .multi_line(true)
+        });
+        let output_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("生成的类型定义将显示在这里...")
+                .multi_line(true)
+        });
+
+        let _subscriptions = vec![
+            cx.subscribe_in(
+                &from_format_state,
+                window,
+                move |this, _, ev: &SelectEvent<Vec<String>>, _, cx| {
+                    if let SelectEvent::Confirm(Some(value)) = ev {
+                        this.from_format = value.to_lowercase();
+                        cx.notify();
+                    }
+                },
+            ),
+            cx.subscribe_in(
+                &language_state,
+                window,
+                move |this, _, ev: &SelectEvent<Vec<String>>, _, cx| {
+                    if let SelectEvent::Confirm(Some(value)) = ev {
+                        this.language = value.replace(' ', "").to_lowercase();
+                        cx.notify();
+                    }
+                },
+            ),
+            cx.subscribe_in(&input_state, window, {
+                let input_state = input_state.clone();
+                move |this, _, _ev: &InputEvent, _window, cx| {
+                    let value = input_state.read(cx).value();
+                    this.input = value.to_string();
+                    cx.notify();
+                }
+            }),
+        ];
+
+        Self {
+            input: String::new(),
+            output: String::new(),
+            from_format: "auto".to_string(),
+            language: "rust".to_string(),
+            is_generating: false,
+            error: String::new(),
+            from_format_state,
+            language_state,
+            root_name_state,
+            input_state,
+            output_state,
+            _subscriptions,
+        }
+    }
+
+    fn generate(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.input.trim().is_empty() {
+            self.error = "请输入样本数据".to_string();
+            cx.notify();
+            return;
+        }
+
+        self.is_generating = true;
+        self.error.clear();
+        cx.notify();
+
+        let language = self.language.clone();
+        let from = self.from_format.clone();
+        let root_name = self.root_name_state.read(cx).value().to_string();
+        let input = self.input.clone();
+
+        cx.spawn_in(window, async move |this: WeakEntity<Self>, cx| {
+            let result = base::typegen(&language, &from, &root_name, &input);
+
+            let _ = this.update_in(cx, |this, window, cx| {
+                this.is_generating = false;
+                let output = match result {
+                    Ok(output) => output,
+                    Err(e) => {
+                        this.error = e.to_string();
+                        String::new()
+                    }
+                };
+                this.output = output.clone();
+                this.output_state.update(cx, |state, cx| {
+                    state.set_value(output, window, cx);
+                });
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.input.clear();
+        self.output.clear();
+        self.error.clear();
+        self.input_state.update(cx, |state, cx| {
+            state.set_value("".to_string(), window, cx);
+        });
+        self.output_state.update(cx, |state, cx| {
+            state.set_value("".to_string(), window, cx);
+        });
+    }
+
+    fn paste(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(item) = cx.read_from_clipboard() {
+            if let Some(text) = item.text() {
+                self.input = text.to_string();
+                self.input_state.update(cx, |state, cx| {
+                    state.set_value(text.to_string(), window, cx);
+                });
+            }
+        }
+    }
+
+    fn copy_output(&mut self, cx: &mut Context<Self>) {
+        if !self.output.is_empty() {
+            cx.write_to_clipboard(ClipboardItem::new_string(self.output.clone()));
+        }
+    }
+}
+
+impl Render for TypeGenerator {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let error = self.error.clone();
+        let is_generating = self.is_generating;
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            // Row: 样本格式 → Select
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("样本格式"))
+                    .child(Select::new(&self.from_format_state)),
+            )
+            // Row: 操作 → Paste + Close (input)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("操作"))
+                    .child(
+                        ButtonGroup::new("input-buttons")
+                            .child(
+                                Button::new("paste-input")
+                                    .icon(Icon::new(IconName::File))
+                                    .tooltip("粘贴")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.paste(window, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("clear-input")
+                                    .icon(Icon::new(IconName::Close))
+                                    .tooltip("清空")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.clear(window, cx);
+                                    })),
+                            ),
+                    ),
+            )
+            // Row: 样本 → textarea
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("样本"))
+                    .child(Input::new(&self.input_state).h(px(250.0))),
+            )
+            // Row: 目标语言 → Select + 根类型名称
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("目标语言"))
+                    .child(Select::new(&self.language_state))
+                    .child(div().text_sm().child("根类型"))
+                    .child(div().w(px(200.0)).child(Input::new(&self.root_name_state))),
+            )
+            // Row: 生成 → Button
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("生成"))
+                    .child(
+                        Button::new("generate")
+                            .primary()
+                            .icon(Icon::new(IconName::ArrowDown))
+                            .tooltip("生成类型定义")
+                            .disabled(is_generating)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.generate(window, cx);
+                            })),
+                    )
+                    .child(if !error.is_empty() {
+                        div().text_sm().text_color(rgb(0xff0000)).child(error)
+                    } else {
+                        div()
+                    }),
+            )
+            // Row: 操作 → Copy (output)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("操作"))
+                    .child(
+                        Button::new("copy-output")
+                            .icon(Icon::new(IconName::Copy))
+                            .tooltip("复制")
+                            .disabled(self.output.is_empty())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.copy_output(cx);
+                            })),
+                    ),
+            )
+            // Row: 类型定义 → textarea
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(100.0)).text_sm().child("类型定义"))
+                    .child(Input::new(&self.output_state).h(px(350.0))),
+            )
+    }
+}
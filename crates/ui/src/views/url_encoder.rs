@@ -1,15 +1,46 @@
-use gpui::*;
+use gpui::{prelude::FluentBuilder as _, *};
 use gpui_component::{
     button::*,
     input::{Input, InputEvent, InputState},
     *,
 };
 
+/// 可编辑的 URL 组成部分：(标签, 占位提示)，顺序与 `UrlEncoder::field_states` 一致
+const URL_FIELDS: [(&str, &str); 7] = [
+    ("协议", "相对引用"),
+    ("用户名", ""),
+    ("密码", ""),
+    ("主机", ""),
+    ("端口", ""),
+    ("路径", "/"),
+    ("片段", ""),
+];
+
+/// 查询参数表格中的一行
+struct ParamRow {
+    key_state: Entity<InputState>,
+    value_state: Entity<InputState>,
+    /// 解析时没有 `=`（如 `?x`），值仍为空时拼接为不带 `=` 的键
+    bare: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
 pub struct UrlEncoder {
     input: String,
     output: String,
     input_state: Entity<InputState>,
     output_state: Entity<InputState>,
+    /// 待解析的 URL
+    url_state: Entity<InputState>,
+    field_states: Vec<Entity<InputState>>,
+    params: Vec<ParamRow>,
+    /// 解析结果，用于展示 Punycode 与路径段
+    parts: Option<base::UrlParts>,
+    /// 由各部分重新拼出的 URL
+    rebuilt: String,
+    parse_error: String,
+    query_state: Entity<InputState>,
+    json_state: Entity<InputState>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -26,26 +57,276 @@ impl UrlEncoder {
                 .multi_line(true)
         });
 
-        let _subscriptions = vec![cx.subscribe_in(&input_state, window, {
-            let input_state = input_state.clone();
-            move |this, _, ev: &InputEvent, _, cx| {
-                if let InputEvent::Change = ev {
-                    let value = input_state.read(cx).value();
-                    this.input = value.to_string();
-                    cx.notify();
+        let url_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("https://user@example.com:8080/path?a=1&a=2#top")
+        });
+        let field_states = URL_FIELDS
+            .iter()
+            .map(|(_, placeholder)| {
+                cx.new(|cx| InputState::new(window, cx).placeholder(*placeholder))
+            })
+            .collect::<Vec<_>>();
+        let query_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("a[]=1&a[]=2&b[c]=x")
+                .multi_line(true)
+        });
+        let json_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("JSON 对象")
+                .multi_line(true)
+        });
+
+        let mut _subscriptions = vec![
+            cx.subscribe_in(&input_state, window, {
+                let input_state = input_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        let value = input_state.read(cx).value();
+                        this.input = value.to_string();
+                        cx.notify();
+                    }
                 }
-            }
-        })];
+            }),
+            cx.subscribe_in(
+                &url_state,
+                window,
+                |this, _, ev: &InputEvent, window, cx| {
+                    if let InputEvent::Change = ev {
+                        this.parse(window, cx);
+                    }
+                },
+            ),
+        ];
+        for state in &field_states {
+            _subscriptions.push(cx.subscribe_in(
+                state,
+                window,
+                |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.rebuild(cx);
+                    }
+                },
+            ));
+        }
 
         Self {
             input: String::new(),
             output: String::new(),
             input_state,
             output_state,
+            url_state,
+            field_states,
+            params: Vec::new(),
+            parts: None,
+            rebuilt: String::new(),
+            parse_error: String::new(),
+            query_state,
+            json_state,
+            _subscriptions,
+        }
+    }
+
+    /// 拆分 URL 并填入各部分的输入框与查询参数表格
+    fn parse(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let url = self.url_state.read(cx).value().to_string();
+        if url.trim().is_empty() {
+            self.parts = None;
+            self.params.clear();
+            self.rebuilt.clear();
+            self.parse_error.clear();
+            cx.notify();
+            return;
+        }
+        let parts = match base::parse_url(&url) {
+            Ok(parts) => parts,
+            Err(err) => {
+                self.parse_error = err.to_string();
+                cx.notify();
+                return;
+            }
+        };
+
+        let values = [
+            parts.scheme.clone().unwrap_or_default(),
+            parts.username.clone().unwrap_or_default(),
+            parts.password.clone().unwrap_or_default(),
+            parts.host.clone().unwrap_or_default(),
+            parts.port.map(|port| port.to_string()).unwrap_or_default(),
+            parts.path.clone(),
+            parts.fragment.clone().unwrap_or_default(),
+        ];
+        for (state, value) in self.field_states.iter().zip(values) {
+            state.update(cx, |state, cx| {
+                state.set_value(value, window, cx);
+            });
+        }
+        self.params = parts
+            .query
+            .iter()
+            .map(|param| Self::param_row(&param.key, param.value.as_deref(), window, cx))
+            .collect();
+        self.parts = Some(parts);
+        self.rebuild(cx);
+    }
+
+    fn param_row(
+        key: &str,
+        value: Option<&str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> ParamRow {
+        let key_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("参数名")
+                .default_value(key.to_string())
+        });
+        let value_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("值")
+                .default_value(value.unwrap_or_default().to_string())
+        });
+        let _subscriptions = [&key_state, &value_state]
+            .into_iter()
+            .map(|state| {
+                cx.subscribe_in(state, window, |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        this.rebuild(cx);
+                    }
+                })
+            })
+            .collect();
+        ParamRow {
+            key_state,
+            value_state,
+            bare: value.is_none(),
             _subscriptions,
         }
     }
 
+    /// 读取各输入框与参数表格，重新拼出 URL
+    fn rebuild(&mut self, cx: &mut Context<Self>) {
+        if self.parts.is_none() {
+            return;
+        }
+        let values = self
+            .field_states
+            .iter()
+            .map(|state| state.read(cx).value().to_string())
+            .collect::<Vec<_>>();
+        let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+
+        let port = match values[4].trim() {
+            "" => None,
+            port => match port.parse::<u16>() {
+                Ok(port) => Some(port),
+                Err(_) => {
+                    self.parse_error = format!("端口不合法: {port}");
+                    cx.notify();
+                    return;
+                }
+            },
+        };
+        // 原来有 authority 时，清空主机也保留 `//`，如 file:///path
+        let had_host = self
+            .parts
+            .as_ref()
+            .is_some_and(|parts| parts.host.is_some());
+        let parts = base::UrlParts {
+            scheme: optional(&values[0]),
+            username: optional(&values[1]),
+            password: optional(&values[2]),
+            host: optional(&values[3]).or_else(|| had_host.then(String::new)),
+            port,
+            path: values[5].clone(),
+            query: self
+                .params
+                .iter()
+                .map(|row| {
+                    let value = row.value_state.read(cx).value().to_string();
+                    base::QueryParam {
+                        key: row.key_state.read(cx).value().to_string(),
+                        value: (!row.bare || !value.is_empty()).then_some(value),
+                    }
+                })
+                .filter(|param| {
+                    !param.key.is_empty() || param.value.as_deref().is_some_and(|v| !v.is_empty())
+                })
+                .collect(),
+            fragment: optional(&values[6]),
+            ..Default::default()
+        };
+        match base::build_url(&parts) {
+            Ok(url) => {
+                self.rebuilt = url;
+                self.parse_error.clear();
+            }
+            Err(err) => self.parse_error = err.to_string(),
+        }
+        cx.notify();
+    }
+
+    fn add_param(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.params.push(Self::param_row("", Some(""), window, cx));
+        cx.notify();
+    }
+
+    fn remove_param(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.params.len() {
+            self.params.remove(index);
+            self.rebuild(cx);
+        }
+    }
+
+    /// 用拼好的 URL 重新解析，刷新 Punycode 与路径段
+    fn apply_rebuilt(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let url = self.rebuilt.clone();
+        self.url_state.update(cx, |state, cx| {
+            state.set_value(url, window, cx);
+        });
+        self.parse(window, cx);
+    }
+
+    fn paste_url(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(item) = cx.read_from_clipboard() {
+            if let Some(text) = item.text() {
+                self.url_state.update(cx, |state, cx| {
+                    state.set_value(text.to_string(), window, cx);
+                });
+                self.parse(window, cx);
+            }
+        }
+    }
+
+    fn params_to_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let query = self.query_state.read(cx).value().to_string();
+        match base::url_params_to_json(&query) {
+            Ok(json) => {
+                self.parse_error.clear();
+                self.json_state.update(cx, |state, cx| {
+                    state.set_value(json, window, cx);
+                });
+            }
+            Err(err) => self.parse_error = err.to_string(),
+        }
+        cx.notify();
+    }
+
+    fn json_to_params(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let json = self.json_state.read(cx).value().to_string();
+        match base::json_to_url_params(&json) {
+            Ok(query) => {
+                self.parse_error.clear();
+                self.query_state.update(cx, |state, cx| {
+                    state.set_value(query, window, cx);
+                });
+            }
+            Err(err) => self.parse_error = err.to_string(),
+        }
+        cx.notify();
+    }
+
     fn encode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.input.is_empty() {
             return;
@@ -115,6 +396,18 @@ impl UrlEncoder {
 
 impl Render for UrlEncoder {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let host_ascii = self.parts.as_ref().and_then(|parts| {
+            parts
+                .host_ascii
+                .clone()
+                .filter(|ascii| parts.host.as_ref() != Some(ascii))
+        });
+        let segments = self
+            .parts
+            .as_ref()
+            .map(|parts| parts.segments.clone())
+            .unwrap_or_default();
+
         div()
             .flex()
             .flex_col()
@@ -231,5 +524,179 @@ impl Render for UrlEncoder {
                     .child(div().text_sm().w(px(80.0)).child("输出"))
                     .child(Input::new(&self.output_state).h(px(250.0))),
             )
+            .child(
+                // Row 6: 解析 → URL + Paste
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().w(px(80.0)).child("解析"))
+                    .child(div().flex_1().child(Input::new(&self.url_state)))
+                    .child(
+                        Button::new("paste-url")
+                            .icon(Icon::new(IconName::File))
+                            .tooltip("粘贴")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.paste_url(window, cx);
+                            })),
+                    ),
+            )
+            .when(self.parts.is_some(), |this| {
+                this.children(URL_FIELDS.iter().zip(&self.field_states).map(
+                    |((label, _), state)| {
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(div().text_sm().w(px(80.0)).child(*label))
+                            .child(div().flex_1().child(Input::new(state)))
+                    },
+                ))
+                .when_some(host_ascii, |this, ascii| {
+                    this.child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .text_sm()
+                            .child(div().w(px(80.0)).child("Punycode"))
+                            .child(div().font_family("monospace").child(ascii)),
+                    )
+                })
+                .when(!segments.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .text_sm()
+                            .child(div().w(px(80.0)).child("路径段"))
+                            .child(div().flex().flex_wrap().gap_2().children(
+                                segments.into_iter().map(|segment| {
+                                    div()
+                                        .px_2()
+                                        .border_1()
+                                        .border_color(cx.theme().border)
+                                        .rounded_md()
+                                        .child(segment)
+                                }),
+                            )),
+                    )
+                })
+                .child(
+                    // 查询参数表格：参数名 + 值 + 删除
+                    div()
+                        .flex()
+                        .gap_2()
+                        .child(div().text_sm().w(px(80.0)).child("查询参数"))
+                        .child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .flex_col()
+                                .gap_1()
+                                .children(self.params.iter().enumerate().map(|(index, row)| {
+                                    div()
+                                        .flex()
+                                        .items_center()
+                                        .gap_2()
+                                        .child(div().w(px(200.0)).child(Input::new(&row.key_state)))
+                                        .child(div().flex_1().child(Input::new(&row.value_state)))
+                                        .child(
+                                            Button::new(SharedString::from(format!(
+                                                "remove-param-{index}"
+                                            )))
+                                            .icon(Icon::new(IconName::Delete))
+                                            .tooltip("删除")
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.remove_param(index, cx);
+                                            })),
+                                        )
+                                }))
+                                .child(
+                                    Button::new("add-param")
+                                        .icon(Icon::new(IconName::Plus))
+                                        .label("添加参数")
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.add_param(window, cx);
+                                        })),
+                                ),
+                        ),
+                )
+                .child(
+                    // 结果 → 拼好的 URL + Copy + 重新解析
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(div().text_sm().w(px(80.0)).child("结果"))
+                        .child(
+                            div()
+                                .flex_1()
+                                .text_sm()
+                                .font_family("monospace")
+                                .child(self.rebuilt.clone()),
+                        )
+                        .child(
+                            ButtonGroup::new("rebuilt-buttons")
+                                .child(
+                                    Button::new("copy-rebuilt")
+                                        .icon(Icon::new(IconName::Copy))
+                                        .tooltip("复制")
+                                        .on_click(cx.listener(|this, _, _, cx| {
+                                            cx.write_to_clipboard(ClipboardItem::new_string(
+                                                this.rebuilt.clone(),
+                                            ));
+                                        })),
+                                )
+                                .child(
+                                    Button::new("apply-rebuilt")
+                                        .icon(Icon::new(IconName::ArrowUp))
+                                        .tooltip("重新解析")
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.apply_rebuilt(window, cx);
+                                        })),
+                                ),
+                        ),
+                )
+            })
+            .child(
+                // Row 7: 参数/JSON → 查询字符串 + 转换按钮 + JSON
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_sm().w(px(80.0)).child("参数/JSON"))
+                    .child(Input::new(&self.query_state).h(px(120.0)))
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_2()
+                            .child(
+                                Button::new("params-to-json")
+                                    .icon(Icon::new(IconName::ArrowRight))
+                                    .tooltip("查询字符串转 JSON")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.params_to_json(window, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("json-to-params")
+                                    .icon(Icon::new(IconName::ArrowLeft))
+                                    .tooltip("JSON 转查询字符串")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.json_to_params(window, cx);
+                                    })),
+                            ),
+                    )
+                    .child(Input::new(&self.json_state).h(px(120.0))),
+            )
+            .when(!self.parse_error.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(self.parse_error.clone()),
+                )
+            })
     }
 }
@@ -8,16 +8,46 @@ use gpui_component::{
     *,
 };
 
+/// v3/v5 的预置命名空间，`None` 为自定义 UUID
+const NAMESPACES: [(Option<&str>, &str); 5] = [
+    (Some("dns"), "DNS"),
+    (Some("url"), "URL"),
+    (Some("oid"), "OID"),
+    (Some("x500"), "X.500"),
+    (None, "自定义"),
+];
+
+/// 可生成的 ID 种类
+const KINDS: [base::IdKind; 6] = [
+    base::IdKind::Uuid,
+    base::IdKind::Ulid,
+    base::IdKind::Ksuid,
+    base::IdKind::Snowflake,
+    base::IdKind::NanoId,
+    base::IdKind::Cuid2,
+];
+
 pub struct UuidGenerator {
+    /// 解析模式：识别输入的 UUID、ULID、KSUID、Snowflake、NanoID 或 CUID2
+    inspecting: bool,
+    kind: base::IdKind,
     uppercase: bool,
     remove_connector: bool,
     version: u32,
     number: u32,
     uuids: String,
+    namespace: Option<&'static str>,
+    error: String,
+    inspect_info: Option<base::uuid::IdInfo>,
     version_state: Entity<SelectState<Vec<String>>>,
     number_state: Entity<InputState>,
     namespace_state: Entity<InputState>,
     name_state: Entity<InputState>,
+    inspect_state: Entity<InputState>,
+    /// Snowflake 的起始时间、数据中心位数、机器位数、数据中心编号、机器编号
+    snowflake_states: [Entity<InputState>; 5],
+    alphabet_state: Entity<InputState>,
+    size_state: Entity<InputState>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -41,16 +71,50 @@ impl UuidGenerator {
         let number_state = cx.new(|cx| InputState::new(window, cx).default_value("5"));
         let namespace_state = cx.new(|cx| {
             InputState::new(window, cx)
-                .default_value("dns")
-                .placeholder("namespace")
+                .placeholder("命名空间 UUID，如 6ba7b810-9dad-11d1-80b4-00c04fd430c8")
         });
         let name_state = cx.new(|cx| {
             InputState::new(window, cx)
-                .default_value("name")
-                .placeholder("name")
+                .default_value("www.example.com")
+                .placeholder("名称")
+        });
+        let inspect_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("输入 UUID、ULID、KSUID、Snowflake、NanoID 或 CUID2")
+        });
+        let snowflake = base::SnowflakeConfig::default();
+        let snowflake_states = [
+            ("起始时间（毫秒）", snowflake.epoch.to_string()),
+            ("数据中心位数", snowflake.datacenter_bits.to_string()),
+            ("机器位数", snowflake.worker_bits.to_string()),
+            ("数据中心编号", snowflake.datacenter_id.to_string()),
+            ("机器编号", snowflake.worker_id.to_string()),
+        ]
+        .map(|(placeholder, value)| {
+            cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder(placeholder)
+                    .default_value(value)
+            })
         });
+        let alphabet_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("字母表")
+                .default_value(base::uuid::NANOID_ALPHABET)
+        });
+        let size_state = cx.new(|cx| InputState::new(window, cx).placeholder("默认长度"));
 
         let _subscriptions = vec![
+            cx.subscribe_in(&inspect_state, window, {
+                let inspect_state = inspect_state.clone();
+                move |this, _, ev: &InputEvent, _, cx| {
+                    if let InputEvent::Change = ev {
+                        let id = inspect_state.read(cx).value().to_string();
+                        this.inspect(&id, cx);
+                        cx.notify();
+                    }
+                }
+            }),
             cx.subscribe_in(
                 &version_state,
                 window,
@@ -105,15 +169,24 @@ impl UuidGenerator {
         ];
 
         Self {
+            inspecting: false,
+            kind: base::IdKind::Uuid,
             uppercase: false,
             remove_connector: false,
             version: 4,
             number: 5,
             uuids: String::new(),
+            namespace: Some("dns"),
+            error: String::new(),
+            inspect_info: None,
             version_state,
             number_state,
             namespace_state,
             name_state,
+            inspect_state,
+            snowflake_states,
+            alphabet_state,
+            size_state,
             _subscriptions,
         }
     }
@@ -126,33 +199,148 @@ impl UuidGenerator {
             .parse::<u32>()
             .unwrap_or(5);
 
-        let namespace = self.namespace_state.read(cx).value().to_string();
-        let name = self.name_state.read(cx).value().to_string();
-
-        let mut results = Vec::new();
-        for _ in 0..self.number {
-            let uuid_str = match self.version {
-                1 => base::uuid::uuid_v1().unwrap_or_default(),
-                3 => base::uuid::uuid_v3(&namespace, &name).unwrap_or_default(),
-                4 => base::uuid::uuid_v4().unwrap_or_default(),
-                5 => base::uuid::uuid_v5(&namespace, &name).unwrap_or_default(),
-                6 => base::uuid::uuid_v6().unwrap_or_default(),
-                7 => base::uuid::uuid_v7().unwrap_or_default(),
-                8 => base::uuid::uuid_v8().unwrap_or_default(),
-                _ => base::uuid::uuid_v4().unwrap_or_default(),
-            };
+        let namespace = match self.namespace {
+            Some(namespace) => namespace.to_string(),
+            None => self.namespace_state.read(cx).value().to_string(),
+        };
+        let size = self.size_state.read(cx).value().trim().to_string();
+        let options = base::IdOptions {
+            kind: self.kind,
+            version: self.version as u8,
+            namespace,
+            name: self.name_state.read(cx).value().to_string(),
+            snowflake: self.snowflake_config(cx),
+            alphabet: self.alphabet_state.read(cx).value().to_string(),
+            size: size.parse().ok(),
+        };
 
-            let mut uuid_str = uuid_str;
-            if self.uppercase {
-                uuid_str = uuid_str.to_uppercase();
-            }
-            if self.remove_connector {
-                uuid_str = uuid_str.replace("-", "");
+        self.error.clear();
+        match base::uuid(
+            None,
+            self.uppercase,
+            self.remove_connector,
+            self.number.min(u16::MAX as u32) as u16,
+            &options,
+        ) {
+            Ok(ids) => self.uuids = ids.join("\n"),
+            Err(e) => {
+                self.error = e.to_string();
+                self.uuids.clear();
             }
-            results.push(uuid_str);
         }
+    }
+
+    /// 读取 Snowflake 的位布局，无法解析的项使用默认值
+    fn snowflake_config(&self, cx: &App) -> base::SnowflakeConfig {
+        let default = base::SnowflakeConfig::default();
+        let [
+            epoch,
+            datacenter_bits,
+            worker_bits,
+            datacenter_id,
+            worker_id,
+        ] = self
+            .snowflake_states
+            .each_ref()
+            .map(|state| state.read(cx).value().trim().to_string());
+        base::SnowflakeConfig {
+            epoch: epoch.parse().unwrap_or(default.epoch),
+            datacenter_bits: datacenter_bits.parse().unwrap_or(default.datacenter_bits),
+            worker_bits: worker_bits.parse().unwrap_or(default.worker_bits),
+            datacenter_id: datacenter_id.parse().unwrap_or(default.datacenter_id),
+            worker_id: worker_id.parse().unwrap_or(default.worker_id),
+        }
+    }
+
+    fn inspect(&mut self, id: &str, cx: &App) {
+        self.error.clear();
+        self.inspect_info = None;
+        if id.trim().is_empty() {
+            return;
+        }
+        match base::inspect_id(id, None, &self.snowflake_config(cx)) {
+            Ok(info) => self.inspect_info = Some(info),
+            Err(e) => self.error = e.to_string(),
+        }
+    }
 
-        self.uuids = results.join("\n");
+    fn set_inspecting(&mut self, inspecting: bool) {
+        self.inspecting = inspecting;
+        self.error.clear();
+    }
+
+    fn set_kind(&mut self, kind: base::IdKind) {
+        self.kind = kind;
+        self.error.clear();
+    }
+
+    fn set_namespace(&mut self, namespace: Option<&'static str>) {
+        self.namespace = namespace;
+    }
+
+    /// Snowflake 的位布局，生成时还需要数据中心和机器编号
+    fn snowflake_fields(&self, with_ids: bool) -> Div {
+        let labels = [
+            "起始时间",
+            "数据中心位数",
+            "机器位数",
+            "数据中心编号",
+            "机器编号",
+        ];
+        let count = if with_ids { 5 } else { 3 };
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_2()
+            .child(div().w(px(85.0)).text_sm().child("Snowflake"))
+            .children(
+                labels
+                    .iter()
+                    .zip(&self.snowflake_states)
+                    .take(count)
+                    .enumerate()
+                    .map(|(i, (label, state))| {
+                        let width = if i == 0 { 160.0 } else { 60.0 };
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .child(div().text_sm().child(*label))
+                            .child(div().w(px(width)).child(Input::new(state)))
+                    }),
+            )
+    }
+
+    fn inspect_result(&self, info: &base::uuid::IdInfo) -> Div {
+        let row = |label: String, value: String| {
+            div()
+                .flex()
+                .items_center()
+                .gap_2()
+                .child(div().w(px(85.0)).text_sm().child(label))
+                .child(div().text_sm().font_family("monospace").child(value))
+        };
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(row("类型".to_string(), info.kind.clone()))
+            .child(row("规范形式".to_string(), info.canonical.clone()))
+            .when_some(info.version, |this, version| {
+                this.child(row("版本".to_string(), version.to_string()))
+            })
+            .when_some(info.variant.clone(), |this, variant| {
+                this.child(row("变体".to_string(), variant))
+            })
+            .when_some(info.timestamp.clone(), |this, timestamp| {
+                this.child(row("时间".to_string(), timestamp))
+            })
+            .children(
+                info.fields
+                    .iter()
+                    .map(|(label, value)| row(label.clone(), value.clone())),
+            )
     }
 
     fn clear(&mut self) {
@@ -185,18 +373,98 @@ impl Render for UuidGenerator {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let uppercase = self.uppercase;
         let remove_connector = self.remove_connector;
+        let inspecting = self.inspecting;
+        let namespace = self.namespace;
+        let kind = self.kind;
 
         let uuids_text = if self.uuids.is_empty() {
-            "点击生成按钮生成UUID...".to_string()
+            format!("点击生成按钮生成{}...", kind.name())
         } else {
             self.uuids.clone()
         };
 
+        let mode = div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .child(div().w(px(85.0)).text_sm().child("模式"))
+            .child(
+                ButtonGroup::new("uuid-mode")
+                    .child(
+                        Button::new("mode-generate")
+                            .label("生成")
+                            .selected(!inspecting)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.set_inspecting(false);
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("mode-inspect")
+                            .label("解析")
+                            .selected(inspecting)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.set_inspecting(true);
+                                cx.notify();
+                            })),
+                    ),
+            );
+        let error = div()
+            .text_sm()
+            .text_color(cx.theme().danger)
+            .child(self.error.clone());
+
+        if inspecting {
+            return div().child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(mode)
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(div().w(px(85.0)).text_sm().child("ID"))
+                            .child(div().flex_1().child(Input::new(&self.inspect_state))),
+                    )
+                    .child(self.snowflake_fields(false))
+                    .when(!self.error.is_empty(), |this| this.child(error))
+                    .when_some(self.inspect_info.clone(), |this, info| {
+                        this.child(self.inspect_result(&info))
+                    }),
+            );
+        }
+
         div().child(
             div()
                 .flex()
                 .flex_col()
                 .gap_2()
+                .child(mode)
+                // label "类型" → ButtonGroup
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(div().w(px(85.0)).text_sm().child("类型"))
+                        .child(KINDS.into_iter().fold(
+                            ButtonGroup::new("id-kind"),
+                            |group, value| {
+                                group.child(
+                                    Button::new(value.name())
+                                        .label(value.name())
+                                        .selected(kind == value)
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.set_kind(value);
+                                            cx.notify();
+                                        })),
+                                )
+                            },
+                        )),
+                )
                 // label "大写" → Switch
                 .child(
                     div()
@@ -230,32 +498,81 @@ impl Render for UuidGenerator {
                         ),
                 )
                 // label "UUID版本" → Select
-                .child(
-                    div()
-                        .flex()
-                        .items_center()
-                        .gap_2()
-                        .child(div().w(px(85.0)).text_sm().child("UUID版本"))
-                        .child(Select::new(&self.version_state)),
-                )
-                .when(self.version == 3 || self.version == 5, |this| {
+                .when(kind == base::IdKind::Uuid, |this| {
                     this.child(
                         div()
                             .flex()
                             .items_center()
                             .gap_2()
-                            .child(div().w(px(85.0)).text_sm().child("namespace"))
-                            .child(div().flex_1().child(Input::new(&self.namespace_state))),
+                            .child(div().w(px(85.0)).text_sm().child("UUID版本"))
+                            .child(Select::new(&self.version_state)),
                     )
-                    .child(
+                })
+                .when(
+                    kind == base::IdKind::Uuid && matches!(self.version, 3 | 5),
+                    |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .child(div().w(px(85.0)).text_sm().child("命名空间"))
+                                .child(NAMESPACES.into_iter().fold(
+                                    ButtonGroup::new("uuid-namespace"),
+                                    |group, (value, label)| {
+                                        group.child(
+                                            Button::new(label)
+                                                .label(label)
+                                                .selected(namespace == value)
+                                                .on_click(cx.listener(move |this, _, _, cx| {
+                                                    this.set_namespace(value);
+                                                    cx.notify();
+                                                })),
+                                        )
+                                    },
+                                ))
+                                .when(namespace.is_none(), |this| {
+                                    this.child(
+                                        div().flex_1().child(Input::new(&self.namespace_state)),
+                                    )
+                                }),
+                        )
+                        .child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .child(div().w(px(85.0)).text_sm().child("名称"))
+                                .child(div().flex_1().child(Input::new(&self.name_state))),
+                        )
+                    },
+                )
+                .when(kind == base::IdKind::Snowflake, |this| {
+                    this.child(self.snowflake_fields(true))
+                })
+                .when(kind == base::IdKind::NanoId, |this| {
+                    this.child(
                         div()
                             .flex()
                             .items_center()
                             .gap_2()
-                            .child(div().w(px(85.0)).text_sm().child("name"))
-                            .child(div().flex_1().child(Input::new(&self.name_state))),
+                            .child(div().w(px(85.0)).text_sm().child("字母表"))
+                            .child(div().flex_1().child(Input::new(&self.alphabet_state))),
                     )
                 })
+                .when(
+                    matches!(kind, base::IdKind::NanoId | base::IdKind::Cuid2),
+                    |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .child(div().w(px(85.0)).text_sm().child("长度"))
+                                .child(div().w(px(120.0)).child(Input::new(&self.size_state))),
+                        )
+                    },
+                )
                 // label "生成数量" → "UUID(s) x " + NumberInput + 生成 button
                 .child(
                     div()
@@ -268,7 +585,7 @@ impl Render for UuidGenerator {
                                 .flex()
                                 .items_center()
                                 .gap_2()
-                                .child("UUID(s) x ")
+                                .child(format!("{}(s) x ", kind.name()))
                                 .child(NumberInput::new(&self.number_state))
                                 .child(Button::new("generate").primary().label("生成").on_click(
                                     cx.listener(|this, _, _, cx| {
@@ -278,6 +595,7 @@ impl Render for UuidGenerator {
                                 )),
                         ),
                 )
+                .when(!self.error.is_empty(), |this| this.child(error))
                 // label "操作" → Copy button
                 .child(
                     div()
@@ -294,13 +612,19 @@ impl Render for UuidGenerator {
                                 })),
                         ),
                 )
-                // label "UUID(S)" → textarea
+                // label "ID(S)" → textarea
                 .child(
                     div()
                         .flex()
                         .items_start()
                         .gap_2()
-                        .child(div().w(px(85.0)).text_sm().mt_1().child("UUID(S)"))
+                        .child(
+                            div()
+                                .w(px(85.0))
+                                .text_sm()
+                                .mt_1()
+                                .child(format!("{}(S)", kind.name())),
+                        )
                         .child(
                             div()
                                 .flex_1()
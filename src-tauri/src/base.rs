@@ -5,6 +5,7 @@ use std::{
 
 use base::{Base, CharsetEncodeResult};
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
 
 type Result<T> = std::result::Result<T, String>;
 
@@ -15,10 +16,27 @@ pub async fn hash(
     hmac_mode: bool,
     secret: Option<&str>,
     input: Option<&str>,
+    input_type: Option<&str>,
+    algorithms: Option<Vec<String>>,
 ) -> Result<impl Serialize> {
-    base::hash(uppercase, output_type, hmac_mode, secret, input)
-        .await
-        .map_err(|e| e.to_string())
+    let algorithms = algorithms.unwrap_or_default();
+    let algorithms = algorithms.iter().map(String::as_str).collect::<Vec<_>>();
+    base::hash(
+        uppercase,
+        output_type,
+        hmac_mode,
+        secret,
+        input,
+        input_type,
+        &algorithms,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn hash_algorithms() -> Vec<base::hash::Algorithm> {
+    base::hash::algorithms()
 }
 
 #[tauri::command]
@@ -28,23 +46,94 @@ pub async fn checksum(r#type: &str, file_path: &str) -> Result<String> {
         .map_err(|e| e.to_string())
 }
 
+/// 生成或校验清单时每算完一个文件发出的事件
+const CHECKSUM_PROGRESS_EVENT: &str = "checksum-progress";
+
+/// 为目录生成校验清单，`output` 不为空时同时写入该文件
+#[tauri::command]
+pub async fn checksum_manifest(
+    app_handle: AppHandle,
+    dir: &str,
+    algorithm: &str,
+    style: Option<&str>,
+    output: Option<&str>,
+) -> Result<String> {
+    let manifest = base::checksum_manifest(dir, algorithm, style, |progress| {
+        let _ = app_handle.emit(CHECKSUM_PROGRESS_EVENT, progress);
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    if let Some(output) = output {
+        std::fs::write(output, &manifest).map_err(|e| e.to_string())?;
+    }
+    Ok(manifest)
+}
+
+#[tauri::command]
+pub fn checksum_manifest_name(algorithm: &str) -> String {
+    base::checksum_manifest_name(algorithm)
+}
+
+#[tauri::command]
+pub async fn checksum_verify(
+    app_handle: AppHandle,
+    manifest_path: &str,
+    dir: Option<&str>,
+    algorithm: Option<&str>,
+) -> Result<base::VerifyReport> {
+    base::checksum_verify(manifest_path, dir, algorithm, |progress| {
+        let _ = app_handle.emit(CHECKSUM_PROGRESS_EVENT, progress);
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn hash_password(password: String, options: base::PasswordOptions) -> Result<String> {
+    // 代价较高的算法可能耗时数百毫秒，放到阻塞线程池中计算
+    tauri::async_runtime::spawn_blocking(move || base::hash_password(&password, &options))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn verify_password(password: String, hash: String) -> Result<base::PasswordVerification> {
+    tauri::async_runtime::spawn_blocking(move || base::verify_password(&password, &hash))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn uuid(
     hyphens: Option<&str>,
     uppercase: bool,
     remove_connector: bool,
-    version: u8,
     number: u16,
+    options: base::IdOptions,
 ) -> Result<Vec<String>> {
-    base::uuid(hyphens, uppercase, remove_connector, version, number).map_err(|e| e.to_string())
+    base::uuid(hyphens, uppercase, remove_connector, number, &options).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn encode_base64_text(input: Option<&str>) -> Result<String> {
+pub fn inspect_id(
+    id: &str,
+    kind: Option<&str>,
+    snowflake: Option<base::SnowflakeConfig>,
+) -> Result<impl Serialize> {
+    base::inspect_id(id, kind, &snowflake.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn encode_base64_text(
+    input: Option<&str>,
+    options: Option<base::Base64Options>,
+) -> Result<String> {
     let Some(data) = input else {
         return Err("input empty".to_string());
     };
-    base::encode_base64_text(data).map_err(|e| e.to_string())
+    base::encode_base64_text(data, &options.unwrap_or_default()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -55,6 +144,73 @@ pub fn decode_base64_text(input: Option<&str>) -> Result<String> {
     base::decode_base64_text(data).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn encode_base64_img(
+    file_path: &str,
+    options: base::Base64Options,
+    data_uri: bool,
+) -> Result<String> {
+    let image = std::fs::read(file_path).map_err(|e| e.to_string())?;
+    base::encode_base64_img(&image, &options, data_uri).map_err(|e| e.to_string())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Base64Image {
+    #[serde(flatten)]
+    info: base::ImageInfo,
+    /// 供前端 `<img>` 预览的 data URI，统一为带填充的标准 Base64
+    data_uri: String,
+}
+
+#[tauri::command]
+pub fn decode_base64_img(input: &str) -> Result<Base64Image> {
+    let (info, image) = base::decode_base64_img(input).map_err(|e| e.to_string())?;
+    let options = base::Base64Options {
+        padding: true,
+        ..Default::default()
+    };
+    let data_uri = format!(
+        "data:{};base64,{}",
+        info.mime,
+        base::encode_base64(&image, &options)
+    );
+    Ok(Base64Image { info, data_uri })
+}
+
+#[tauri::command]
+pub fn save_base64_img(input: &str, file_path: &str) -> Result<()> {
+    let (_, image) = base::decode_base64_img(input).map_err(|e| e.to_string())?;
+    std::fs::write(file_path, image).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn encode_binary(
+    input: &str,
+    input_type: Option<&str>,
+    hrp: Option<&str>,
+) -> Result<Vec<base::Encoded>> {
+    let hrp = hrp.unwrap_or(base::encoding::DEFAULT_HRP);
+    base::encode_binary(input, input_type, hrp).map_err(|e| e.to_string())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedBinary {
+    /// 不是合法的 UTF-8 时为 `None`
+    text: Option<String>,
+    hex: String,
+}
+
+#[tauri::command]
+pub fn decode_binary(encoding: base::Encoding, input: &str) -> Result<DecodedBinary> {
+    let bytes = base::decode_binary(encoding, input).map_err(|e| e.to_string())?;
+    Ok(DecodedBinary {
+        hex: base::hash::encode_output(&bytes, Some("hex"), false),
+        text: String::from_utf8(bytes).ok(),
+    })
+}
+
 #[tauri::command]
 pub fn encode_url(input: Option<&str>) -> Result<String> {
     let Some(data) = input else {
@@ -71,6 +227,26 @@ pub fn decode_url(input: Option<&str>) -> Result<String> {
     base::decode_url(data).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn parse_url(input: &str) -> Result<base::UrlParts> {
+    base::parse_url(input).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn build_url(parts: base::UrlParts) -> Result<String> {
+    base::build_url(&parts).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn url_params_to_json(input: &str) -> Result<String> {
+    base::url_params_to_json(input).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn json_to_url_params(input: &str) -> Result<String> {
+    base::json_to_url_params(input).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn decode_jwt(input: Option<&str>) -> Result<String> {
     let Some(data) = input else {
@@ -146,6 +322,72 @@ pub fn charset_encode(
     .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn cipher_encrypt(input: &str, options: base::CipherOptions) -> Result<String> {
+    base::cipher_encrypt(input, &options).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cipher_decrypt(input: &str, options: base::CipherOptions) -> Result<String> {
+    base::cipher_decrypt(input, &options).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cipher_random(len: usize, output_type: &str) -> Result<String> {
+    base::cipher_random(len, output_type).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn key_generate(algorithm: base::KeyAlgorithm, rsa_bits: usize) -> Result<base::KeyPair> {
+    // RSA 4096 的素数搜索可能需要数秒
+    tauri::async_runtime::spawn_blocking(move || base::key_generate(algorithm, rsa_bits))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn key_inspect(input: &str) -> Result<base::KeyInfo> {
+    base::key_inspect(input).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn key_convert(input: &str, options: base::KeyConvertOptions) -> Result<String> {
+    base::key_convert(input, &options).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn key_sign(key: &str, message: &str) -> Result<String> {
+    base::key_sign(key, message).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn key_verify(key: &str, message: &str, signature: &str) -> Result<bool> {
+    base::key_verify(key, message, signature).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cert_decode(input: &str, password: &str) -> Result<base::CertBundle> {
+    base::cert_decode(input.as_bytes(), password).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cert_decode_file(file_path: &str, password: &str) -> Result<base::CertBundle> {
+    // PKCS#12 和 DER 是二进制格式，只能按文件读取
+    let input = std::fs::read(file_path).map_err(|e| e.to_string())?;
+    base::cert_decode(&input, password).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cert_self_signed(
+    options: base::SelfSignedOptions,
+) -> Result<base::SelfSignedCertificate> {
+    tauri::async_runtime::spawn_blocking(move || base::cert_self_signed(&options))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn auto_detect_charset(input: &str) -> Result<String> {
     base::auto_detect_charset(input).map_err(|e| e.to_string())
@@ -660,19 +660,52 @@ pub async fn preview_text(config: Value) -> Result<String> {
 
 #[tauri::command]
 pub async fn preview_uuid(config: Value) -> Result<String> {
-    let uuid = Faker::new().uuid();
-    let mut value = match config
-        .get("uuidVersion")
-        .and_then(Value::as_u64)
-        .unwrap_or(4)
-    {
-        1 => uuid.uuid_v1(),
-        3 => uuid.uuid_v3(),
-        5 => uuid.uuid_v5(),
-        6 => uuid.uuid_v6(),
-        7 => uuid.uuid_v7(),
-        8 => uuid.uuid_v8(),
-        _ => uuid.uuid_v4(),
+    let kind = config
+        .get("idKind")
+        .and_then(Value::as_str)
+        .unwrap_or("uuid");
+    let kind = base::IdKind::try_from(kind).map_err(|e| e.to_string())?;
+    let mut value = if kind == base::IdKind::Uuid {
+        let uuid = Faker::new().uuid();
+        match config
+            .get("uuidVersion")
+            .and_then(Value::as_u64)
+            .unwrap_or(4)
+        {
+            1 => uuid.uuid_v1(),
+            3 => uuid.uuid_v3(),
+            5 => uuid.uuid_v5(),
+            6 => uuid.uuid_v6(),
+            7 => uuid.uuid_v7(),
+            8 => uuid.uuid_v8(),
+            _ => uuid.uuid_v4(),
+        }
+    } else {
+        // ULID、Snowflake 等由 base 生成，同一进程内保持递增、不重复
+        let snowflake = match config.get("snowflake") {
+            Some(snowflake) => {
+                serde_json::from_value(snowflake.clone()).map_err(|e| e.to_string())?
+            }
+            None => base::SnowflakeConfig::default(),
+        };
+        let options = base::IdOptions {
+            kind,
+            snowflake,
+            alphabet: config
+                .get("alphabet")
+                .and_then(Value::as_str)
+                .filter(|alphabet| !alphabet.is_empty())
+                .unwrap_or(base::uuid::NANOID_ALPHABET)
+                .to_string(),
+            size: config
+                .get("size")
+                .and_then(Value::as_u64)
+                .map(|size| size as usize),
+            ..Default::default()
+        };
+        base::uuid::IdGenerator::new(options)
+            .and_then(|mut generator| generator.next_id())
+            .map_err(|e| e.to_string())?
     };
     if config
         .get("uppercase")
@@ -691,6 +724,34 @@ pub async fn preview_uuid(config: Value) -> Result<String> {
     Ok(value)
 }
 
+/// 随机强密码，或固定的明文；配置了 `hashAlgorithm` 时输出哈希后的值
+#[tauri::command]
+pub async fn preview_password(config: Value) -> Result<String> {
+    let password = config
+        .get("plaintext")
+        .and_then(Value::as_str)
+        .filter(|plaintext| !plaintext.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| Faker::new().person().strong_password());
+    let algorithm = match config.get("hashAlgorithm").and_then(Value::as_str) {
+        None | Some("" | "none") => return Ok(password),
+        Some(algorithm) => {
+            base::PasswordAlgorithm::try_from(algorithm).map_err(|e| e.to_string())?
+        }
+    };
+    let mut options = match config.get("passwordOptions") {
+        Some(options) => serde_json::from_value::<base::PasswordOptions>(options.clone())
+            .map_err(|e| e.to_string())?,
+        // 批量生成与 gpui 版共用低代价参数
+        None => base::PasswordOptions::bulk(algorithm),
+    };
+    options.algorithm = algorithm;
+    tauri::async_runtime::spawn_blocking(move || base::hash_password(&password, &options))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn preview_website(_config: Value) -> Result<String> {
     Ok(Faker::new().internet().static_url())
@@ -712,6 +773,7 @@ pub async fn preview_generator(generator: String, config: Value) -> Result<Strin
         "mac" => return preview_mac(config).await,
         "name" => return preview_name(config).await,
         "number" => return preview_number(config).await,
+        "password" => return preview_password(config).await,
         "text" => return preview_text(config).await,
         "uuid" => return preview_uuid(config).await,
         "website" => return preview_website(config).await,
@@ -729,7 +791,6 @@ pub async fn preview_generator(generator: String, config: Value) -> Result<Strin
         "id_card" => person.id_card(),
         "gender" => person.gender(),
         "username" => internet.username(),
-        "password" => person.strong_password(),
         "qq" => person.qq(),
         "nickname" => person.qq_nick_name(),
         "money" => format!("{:.2}", random_i64(1, 100000) as f64 + random_fraction()),
@@ -1839,6 +1900,7 @@ async fn generate_value(
         "mac" => preview_mac(config.clone()).await?,
         "name" => preview_name(config.clone()).await?,
         "number" => preview_number(config.clone()).await?,
+        "password" => preview_password(config.clone()).await?,
         "regex" => {
             let pattern = config
                 .get("pattern")
@@ -52,12 +52,28 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
             base::hash,
+            base::hash_algorithms,
             base::checksum,
+            base::checksum_manifest,
+            base::checksum_manifest_name,
+            base::checksum_verify,
+            base::hash_password,
+            base::verify_password,
             base::uuid,
+            base::inspect_id,
             base::encode_base64_text,
             base::decode_base64_text,
+            base::encode_base64_img,
+            base::decode_base64_img,
+            base::save_base64_img,
+            base::encode_binary,
+            base::decode_binary,
             base::encode_url,
             base::decode_url,
+            base::parse_url,
+            base::build_url,
+            base::url_params_to_json,
+            base::json_to_url_params,
             base::decode_jwt,
             base::cffc,
             base::timestamp,
@@ -66,6 +82,17 @@ pub fn run() {
             base::check_ip,
             base::ip_to_number,
             base::charset_encode,
+            base::cipher_encrypt,
+            base::cipher_decrypt,
+            base::cipher_random,
+            base::key_generate,
+            base::key_inspect,
+            base::key_convert,
+            base::key_sign,
+            base::key_verify,
+            base::cert_decode,
+            base::cert_decode_file,
+            base::cert_self_signed,
             base::auto_detect_charset,
             base::recover_garbled_code,
             database::database_ping,
@@ -95,6 +122,7 @@ pub fn run() {
             datafaker::preview_mac,
             datafaker::preview_name,
             datafaker::preview_number,
+            datafaker::preview_password,
             datafaker::preview_sequence,
             datafaker::preview_text,
             datafaker::preview_time,
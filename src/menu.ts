@@ -23,7 +23,7 @@ import {
   QrCode,
   Settings,
 } from "@vicons/carbon";
-import { Binary, File, Hash, Markdown } from "@vicons/tabler";
+import { Binary, Certificate, File, Hash, Key, Lock, Markdown } from "@vicons/tabler";
 import { TransformFilled } from "@vicons/material";
 
 import TodoIcon from '@/assets/todo.svg';
@@ -97,6 +97,11 @@ export const menus = [
         key: "/encodedecode/base64text",
         icon: renderMenuIcon(TextUnderline),
       },
+      {
+        label: "Base32/58/85",
+        key: "/encodedecode/binarytext",
+        icon: renderMenuIcon(Binary),
+      },
       {
         label: "URL",
         key: "/encodedecode/url",
@@ -112,6 +117,21 @@ export const menus = [
         key: "/encodedecode/charset",
         icon: renderMenuIcon(TextUnderline),
       },
+      {
+        label: "对称加密",
+        key: "/encodedecode/cipher",
+        icon: renderMenuIcon(Lock),
+      },
+      {
+        label: "非对称密钥",
+        key: "/encodedecode/keypair",
+        icon: renderMenuIcon(Key),
+      },
+      {
+        label: "证书",
+        key: "/encodedecode/certificate",
+        icon: renderMenuIcon(Certificate),
+      },
       {
         label: "乱码恢复",
         key: "/encodedecode/messycode",
@@ -156,6 +176,11 @@ export const menus = [
         key: "/generator/hash",
         icon: renderMenuIcon(Hash),
       },
+      {
+        label: "密码哈希",
+        key: "/generator/password",
+        icon: renderMenuIcon(Lock),
+      },
       {
         label: "文件校验",
         key: "/generator/checksum",
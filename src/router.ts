@@ -32,6 +32,10 @@ const routes = [
     path: "/encodedecode/base64text",
     component: () => import("@/views/encodedecode/Base64Text.vue"),
   },
+  {
+    path: "/encodedecode/binarytext",
+    component: () => import("@/views/encodedecode/BinaryText.vue"),
+  },
   {
     path: "/encodedecode/jwt",
     component: () => import("@/views/encodedecode/JWT.vue"),
@@ -40,6 +44,18 @@ const routes = [
     path: "/encodedecode/charset",
     component: () => import("@/views/encodedecode/CharsetEncoder.vue"),
   },
+  {
+    path: "/encodedecode/cipher",
+    component: () => import("@/views/encodedecode/Cipher.vue"),
+  },
+  {
+    path: "/encodedecode/keypair",
+    component: () => import("@/views/encodedecode/KeyPair.vue"),
+  },
+  {
+    path: "/encodedecode/certificate",
+    component: () => import("@/views/encodedecode/Certificate.vue"),
+  },
   {
     path: "/encodedecode/messycode",
     component: () => import("@/views/encodedecode/MessyCodeRecover.vue"),
@@ -66,6 +82,10 @@ const routes = [
     path: "/generator/uuid",
     component: () => import("@/views/generator/UUID.vue"),
   },
+  {
+    path: "/generator/password",
+    component: () => import("@/views/generator/Password.vue"),
+  },
   {
     path: "/generator/checksum",
     component: () => import("@/views/generator/Checksum.vue"),